@@ -1,6 +1,7 @@
 mod cache;
 mod danmaku_map;
 mod image_fetch;
+pub mod lyrics;
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -0,0 +1,3 @@
+//! 歌词相关的应用层编排。
+
+pub mod sync;
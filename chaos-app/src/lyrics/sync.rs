@@ -0,0 +1,365 @@
+//! 歌词时间轴同步：根据结构化歌词与播放时钟（位置 / 倍速 / 暂停）推送
+//! `lyrics.lineChanged` / `lyrics.wordProgress`，各窗口（Dock / Float）消费同一份事件，不再各自轮询插值。
+//!
+//! 两次快照之间按倍速外推位置；新快照与外推位置偏差超过 [`DRIFT_TOLERANCE_MS`] 时重新对齐，
+//! 否则保持外推，避免快照抖动导致高亮来回跳。
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use chaos_core::lyrics::parse::Lyrics;
+use chaos_core::now_playing::{NowPlayingSession, NowPlayingSnapshot};
+use chaos_proto::{
+    LyricsLineChangedNotif, LyricsWordProgressNotif, NOTIF_LYRICS_LINE_CHANGED,
+    NOTIF_LYRICS_WORD_PROGRESS,
+};
+use tokio::sync::mpsc;
+
+pub const DEFAULT_TICK_INTERVAL: Duration = Duration::from_millis(50);
+/// 快照位置与外推位置的允许偏差，超过则认为发生了 seek / 卡顿并重新对齐。
+pub const DRIFT_TOLERANCE_MS: u64 = 150;
+/// 快照自身的延迟补偿上限（`retrievedAtUnixMs` 距今）。
+const MAX_SNAPSHOT_AGE_MS: u64 = 2_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackClock {
+    pub position_ms: u64,
+    pub rate: f64,
+    pub paused: bool,
+}
+
+impl PlaybackClock {
+    /// Now Playing 会话 -> 时钟；没有位置信息时返回 `None`。系统媒体会话不提供倍速，按 1.0 处理。
+    pub fn from_session(s: &NowPlayingSession) -> Option<Self> {
+        Some(Self {
+            position_ms: s.position_ms?,
+            rate: 1.0,
+            paused: !s.playback_status.eq_ignore_ascii_case("Playing"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LyricsSyncEvent {
+    LineChanged(LyricsLineChangedNotif),
+    WordProgress(LyricsWordProgressNotif),
+}
+
+impl LyricsSyncEvent {
+    /// 对应的通知方法名。
+    pub fn method(&self) -> &'static str {
+        match self {
+            Self::LineChanged(_) => NOTIF_LYRICS_LINE_CHANGED,
+            Self::WordProgress(_) => NOTIF_LYRICS_WORD_PROGRESS,
+        }
+    }
+
+    /// 标上订阅 id（daemon 的 `lyrics.sync.start` 返回值）。
+    pub fn with_sync_id(mut self, sync_id: &str) -> Self {
+        match &mut self {
+            Self::LineChanged(n) => n.sync_id = sync_id.to_string(),
+            Self::WordProgress(n) => n.sync_id = sync_id.to_string(),
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Anchor {
+    clock: PlaybackClock,
+    at: Instant,
+}
+
+/// 纯状态机：不读系统时间，由调用方传入 `now`，便于测试与复用。
+#[derive(Debug, Default)]
+pub struct LyricsSyncEngine {
+    lyrics: Option<Lyrics>,
+    anchor: Option<Anchor>,
+    /// 外层 `None` 表示尚未推送过（换歌词后需要重新推送当前行）。
+    current_line: Option<Option<usize>>,
+    last_word: Option<(usize, usize, f64)>,
+}
+
+impl LyricsSyncEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn lyrics(&self) -> Option<&Lyrics> {
+        self.lyrics.as_ref()
+    }
+
+    /// 换歌词（或清空）；下一次 [`Self::tick`] 会重新推送当前行。
+    pub fn set_lyrics(&mut self, lyrics: Option<Lyrics>) {
+        self.lyrics = lyrics;
+        self.current_line = None;
+        self.last_word = None;
+    }
+
+    /// 外推出的播放位置（毫秒）；还没有时钟时返回 `None`。
+    pub fn position_at(&self, now: Instant) -> Option<u64> {
+        let a = self.anchor?;
+        if a.clock.paused {
+            return Some(a.clock.position_ms);
+        }
+        let elapsed = now.saturating_duration_since(a.at).as_millis() as f64;
+        Some(a.clock.position_ms + (elapsed * a.clock.rate.max(0.0)) as u64)
+    }
+
+    /// 用新的播放时钟校正；`None` 表示播放器不可用，停止推送。
+    pub fn update_clock(&mut self, clock: Option<PlaybackClock>, now: Instant) {
+        let Some(clock) = clock else {
+            self.anchor = None;
+            return;
+        };
+        if let Some(a) = self.anchor
+            && !a.clock.paused
+            && !clock.paused
+            && a.clock.rate == clock.rate
+            && let Some(predicted) = self.position_at(now)
+            && predicted.abs_diff(clock.position_ms) <= DRIFT_TOLERANCE_MS
+        {
+            return;
+        }
+        self.anchor = Some(Anchor { clock, at: now });
+    }
+
+    /// 用 Now Playing 快照校正，并按快照获取时间补偿其延迟。
+    pub fn update_snapshot(&mut self, snap: &NowPlayingSnapshot, now: Instant) {
+        let clock = snap
+            .now_playing
+            .as_ref()
+            .and_then(PlaybackClock::from_session)
+            .map(|mut c| {
+                if !c.paused {
+                    let now_ms = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map(|d| d.as_millis() as u64)
+                        .unwrap_or(0);
+                    let age = now_ms
+                        .saturating_sub(snap.retrieved_at_unix_ms)
+                        .min(MAX_SNAPSHOT_AGE_MS);
+                    c.position_ms += (age as f64 * c.rate.max(0.0)) as u64;
+                }
+                c
+            });
+        self.update_clock(clock, now);
+    }
+
+    /// 计算 `now` 时刻需要推送的事件：当前行变化时推 `LineChanged`，逐字行内进度变化时推 `WordProgress`。
+    pub fn tick(&mut self, now: Instant) -> Vec<LyricsSyncEvent> {
+        let mut out = Vec::new();
+        let (Some(lyrics), Some(position_ms)) = (self.lyrics.as_ref(), self.position_at(now))
+        else {
+            return out;
+        };
+
+        let index = lyrics.line_index_at(position_ms);
+        if self.current_line != Some(index) {
+            self.current_line = Some(index);
+            self.last_word = None;
+            let line = index.and_then(|i| lyrics.lines.get(i));
+            out.push(LyricsSyncEvent::LineChanged(LyricsLineChangedNotif {
+                sync_id: String::new(),
+                line_index: index.map(|i| i as u32),
+                position_ms,
+                start_ms: line.map(|l| l.start_ms),
+                end_ms: line.map(|l| l.end_ms),
+                text: line.map(|l| l.text.clone()),
+                translation: line.and_then(|l| l.translation.clone()),
+            }));
+        }
+
+        let Some((line_index, line)) = index.and_then(|i| Some((i, lyrics.lines.get(i)?))) else {
+            return out;
+        };
+        // 逐字时间与行时间同一时间轴，同样需要计入 `[offset:]`。
+        let t = position_ms.saturating_add_signed(lyrics.metadata.offset_ms);
+        let Some(word_index) = line
+            .words
+            .partition_point(|w| w.start_ms <= t)
+            .checked_sub(1)
+        else {
+            return out;
+        };
+        let w = &line.words[word_index];
+        let progress = if w.end_ms > w.start_ms {
+            ((t - w.start_ms) as f64 / (w.end_ms - w.start_ms) as f64).min(1.0)
+        } else {
+            1.0
+        };
+        if self.last_word != Some((line_index, word_index, progress)) {
+            self.last_word = Some((line_index, word_index, progress));
+            out.push(LyricsSyncEvent::WordProgress(LyricsWordProgressNotif {
+                sync_id: String::new(),
+                line_index: line_index as u32,
+                word_index: word_index as u32,
+                position_ms,
+                progress,
+            }));
+        }
+        out
+    }
+}
+
+enum Command {
+    Lyrics(Option<Lyrics>),
+    Clock(Option<PlaybackClock>),
+    Snapshot(Box<NowPlayingSnapshot>),
+}
+
+/// [`LyricsSyncEngine`] 的后台驱动：按固定间隔 tick，把事件发到返回的 receiver。
+///
+/// daemon 为每个 `lyrics.sync.start` 订阅启动一个，并把事件作为通知转发给订阅的连接。
+pub struct LyricsSync {
+    tx: mpsc::UnboundedSender<Command>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl LyricsSync {
+    pub fn spawn(interval: Duration) -> (Self, mpsc::UnboundedReceiver<LyricsSyncEvent>) {
+        let (tx, mut cmd_rx) = mpsc::unbounded_channel::<Command>();
+        let (ev_tx, ev_rx) = mpsc::unbounded_channel::<LyricsSyncEvent>();
+        let task = tokio::spawn(async move {
+            let mut engine = LyricsSyncEngine::new();
+            let mut ticker = tokio::time::interval(interval.max(Duration::from_millis(10)));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    cmd = cmd_rx.recv() => {
+                        let now = Instant::now();
+                        match cmd {
+                            None => break,
+                            Some(Command::Lyrics(l)) => engine.set_lyrics(l),
+                            Some(Command::Clock(c)) => engine.update_clock(c, now),
+                            Some(Command::Snapshot(s)) => engine.update_snapshot(&s, now),
+                        }
+                    }
+                    _ = ticker.tick() => {}
+                }
+                for ev in engine.tick(Instant::now()) {
+                    if ev_tx.send(ev).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        (Self { tx, task }, ev_rx)
+    }
+
+    pub fn set_lyrics(&self, lyrics: Option<Lyrics>) {
+        let _ = self.tx.send(Command::Lyrics(lyrics));
+    }
+
+    pub fn update_clock(&self, clock: Option<PlaybackClock>) {
+        let _ = self.tx.send(Command::Clock(clock));
+    }
+
+    pub fn update_snapshot(&self, snap: NowPlayingSnapshot) {
+        let _ = self.tx.send(Command::Snapshot(Box::new(snap)));
+    }
+
+    pub async fn stop(self) {
+        drop(self.tx);
+        let _ = self.task.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chaos_core::lyrics::parse;
+
+    fn clock(position_ms: u64, paused: bool) -> Option<PlaybackClock> {
+        Some(PlaybackClock {
+            position_ms,
+            rate: 1.0,
+            paused,
+        })
+    }
+
+    fn line_index(ev: &LyricsSyncEvent) -> Option<u32> {
+        match ev {
+            LyricsSyncEvent::LineChanged(n) => n.line_index,
+            LyricsSyncEvent::WordProgress(_) => panic!("expected line change"),
+        }
+    }
+
+    #[test]
+    fn line_changes_follow_extrapolated_clock_and_seek() {
+        let t0 = Instant::now();
+        let mut engine = LyricsSyncEngine::new();
+        engine.set_lyrics(Some(parse::parse("[00:01.00]a\n[00:02.00]b\n[00:03.00]c")));
+        assert!(engine.tick(t0).is_empty());
+
+        engine.update_clock(clock(500, false), t0);
+        let ev = engine.tick(t0);
+        assert_eq!(ev.len(), 1);
+        assert_eq!(line_index(&ev[0]), None);
+
+        let ev = engine.tick(t0 + Duration::from_millis(600));
+        assert_eq!(line_index(&ev[0]), Some(0));
+        assert!(engine.tick(t0 + Duration::from_millis(700)).is_empty());
+
+        // 小偏差不重新对齐；大偏差（seek 回退）立即推送。
+        engine.update_clock(clock(1_800, false), t0 + Duration::from_millis(1_400));
+        assert_eq!(
+            engine.position_at(t0 + Duration::from_millis(1_400)),
+            Some(1_900)
+        );
+        engine.update_clock(clock(200, false), t0 + Duration::from_millis(1_500));
+        let ev = engine.tick(t0 + Duration::from_millis(1_500));
+        assert_eq!(line_index(&ev[0]), None);
+
+        engine.update_clock(clock(2_500, true), t0 + Duration::from_millis(1_600));
+        let ev = engine.tick(t0 + Duration::from_secs(60));
+        assert_eq!(line_index(&ev[0]), Some(1));
+    }
+
+    #[test]
+    fn word_progress_respects_offset_and_rate() {
+        let t0 = Instant::now();
+        let mut engine = LyricsSyncEngine::new();
+        engine.set_lyrics(Some(parse::parse(
+            "[offset:500]\n[00:01.00]<00:01.00>Hel<00:02.00>lo<00:03.00>",
+        )));
+        engine.update_clock(
+            Some(PlaybackClock {
+                position_ms: 500,
+                rate: 2.0,
+                paused: false,
+            }),
+            t0,
+        );
+        let ev = engine.tick(t0 + Duration::from_millis(500));
+        assert_eq!(ev.len(), 2);
+        assert_eq!(line_index(&ev[0]), Some(0));
+        let LyricsSyncEvent::WordProgress(w) = &ev[1] else {
+            panic!("expected word progress");
+        };
+        assert_eq!((w.word_index, w.position_ms), (1, 1_500));
+        assert_eq!(w.progress, 0.0);
+
+        // 暂停后进度不变，不重复推送。
+        engine.update_clock(clock(1_750, true), t0 + Duration::from_millis(600));
+        assert_eq!(engine.tick(t0 + Duration::from_millis(600)).len(), 1);
+        assert!(engine.tick(t0 + Duration::from_secs(5)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn background_driver_pushes_events() {
+        let (sync, mut rx) = LyricsSync::spawn(Duration::from_millis(10));
+        sync.set_lyrics(Some(parse::parse("[00:00.00]a")));
+        sync.update_clock(clock(100, false));
+        let ev = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("event")
+            .expect("open");
+        assert_eq!(ev.method(), NOTIF_LYRICS_LINE_CHANGED);
+        assert_eq!(line_index(&ev), Some(0));
+        let LyricsSyncEvent::LineChanged(n) = ev.with_sync_id("sync1") else {
+            panic!("expected line change");
+        };
+        assert_eq!(n.sync_id, "sync1");
+        sync.stop().await;
+    }
+}
@@ -0,0 +1,421 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, atomic::AtomicBool};
+
+use serde_json::Value;
+
+use super::download::{DownloadCtx, DownloadOptions, ProgressCb, download_to_file_single};
+use super::parse::{ViewInfo, ViewPage};
+use super::{BiliClient, BiliError, bili_check_code, header_map_with_cookie};
+use crate::download_scheduler::DownloadTask;
+use crate::music::util::{build_track_path_by_template, sanitize_component};
+
+/// 音频区（au 号）歌曲信息。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioSongInfo {
+    pub sid: String,
+    pub title: String,
+    /// 歌手（`author`），为空时回退到上传者昵称。
+    pub author: String,
+    pub up_name: Option<String>,
+    pub up_mid: Option<String>,
+    pub cover: Option<String>,
+    pub intro: Option<String>,
+    pub lyric_url: Option<String>,
+    pub duration_s: Option<u32>,
+    pub pub_time_unix_s: Option<i64>,
+    pub bvid: Option<String>,
+}
+
+impl AudioSongInfo {
+    /// 以单 P 稿件的形式展示：cid 为 sid，aid 为空（若稿件关联了视频则 bvid 为关联 BV）。
+    pub fn into_view_info(self) -> ViewInfo {
+        let owner_name = Some(self.author).filter(|s| !s.is_empty()).or(self.up_name);
+        ViewInfo {
+            aid: String::new(),
+            bvid: self.bvid.unwrap_or_default(),
+            title: self.title.clone(),
+            desc: self.intro,
+            pic: self.cover,
+            owner_name,
+            owner_mid: self.up_mid,
+            pub_time_unix_s: self.pub_time_unix_s,
+            pages: vec![ViewPage {
+                page_number: 1,
+                cid: self.sid,
+                page_title: self.title,
+                duration_s: self.duration_s,
+                dimension: None,
+            }],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioQuality {
+    /// 0=128K, 1=192K, 2=320K, 3=FLAC
+    pub quality_type: i64,
+    pub desc: String,
+    pub size: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioStream {
+    pub quality_type: i64,
+    pub urls: Vec<String>,
+    pub size: Option<u64>,
+    pub qualities: Vec<AudioQuality>,
+}
+
+impl AudioStream {
+    /// 根据 url 后缀推断扩展名（`m4a`/`mp3`/`flac`），否则按音质类型回退。
+    pub fn ext(&self) -> &'static str {
+        let path = self
+            .urls
+            .first()
+            .map(|u| {
+                u.split(['?', '#'])
+                    .next()
+                    .unwrap_or("")
+                    .to_ascii_lowercase()
+            })
+            .unwrap_or_default();
+        if path.ends_with(".flac") {
+            "flac"
+        } else if path.ends_with(".mp3") {
+            "mp3"
+        } else if path.ends_with(".m4a") {
+            "m4a"
+        } else if self.quality_type == 3 {
+            "flac"
+        } else {
+            "m4a"
+        }
+    }
+}
+
+fn json_str(v: &Value, key: &str) -> Option<String> {
+    v.get(key)
+        .and_then(|x| x.as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn json_id(v: &Value, key: &str) -> Option<String> {
+    match v.get(key) {
+        Some(Value::Number(n)) => Some(n.to_string()).filter(|s| s != "0"),
+        Some(Value::String(s)) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        _ => None,
+    }
+}
+
+fn normalize_sid(sid: &str) -> Result<String, BiliError> {
+    let s = sid.trim();
+    let s = s
+        .strip_prefix("au")
+        .or_else(|| s.strip_prefix("AU"))
+        .unwrap_or(s);
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+        return Err(BiliError::InvalidInput(format!("invalid audio sid: {sid}")));
+    }
+    Ok(s.to_string())
+}
+
+async fn get_json(
+    client: &BiliClient,
+    url: &str,
+    cookie: Option<&str>,
+) -> Result<Value, BiliError> {
+    let headers = header_map_with_cookie(cookie);
+    let json: Value = client
+        .http
+        .get(url)
+        .headers(headers)
+        .send()
+        .await?
+        .json()
+        .await?;
+    bili_check_code(&json)?;
+    Ok(json)
+}
+
+pub async fn fetch_song_info(
+    client: &BiliClient,
+    sid: &str,
+    cookie: Option<&str>,
+) -> Result<AudioSongInfo, BiliError> {
+    let sid = normalize_sid(sid)?;
+    let url = format!(
+        "{}/audio/music-service-c/web/song/info?sid={}",
+        client.endpoints.www_base.trim_end_matches('/'),
+        urlencoding::encode(&sid)
+    );
+    let json = get_json(client, &url, cookie).await?;
+    let data = json
+        .get("data")
+        .filter(|v| v.is_object())
+        .ok_or_else(|| BiliError::Parse("missing data".to_string()))?;
+
+    let title = json_str(data, "title")
+        .ok_or_else(|| BiliError::Parse("missing audio title".to_string()))?;
+    let up_name = json_str(data, "uname");
+    let author = json_str(data, "author")
+        .or_else(|| up_name.clone())
+        .unwrap_or_default();
+
+    Ok(AudioSongInfo {
+        sid,
+        title,
+        author,
+        up_name,
+        up_mid: json_id(data, "uid"),
+        cover: json_str(data, "cover"),
+        intro: json_str(data, "intro"),
+        lyric_url: json_str(data, "lyric"),
+        duration_s: data
+            .get("duration")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32),
+        pub_time_unix_s: data
+            .get("passtime")
+            .and_then(|v| v.as_i64())
+            .filter(|v| *v > 0),
+        bvid: json_str(data, "bvid"),
+    })
+}
+
+/// 下载 LRC 歌词文本（`song/info` 的 `lyric` 字段为 LRC 文件地址）。
+pub async fn fetch_lyrics_lrc(
+    client: &BiliClient,
+    lyric_url: &str,
+    cookie: Option<&str>,
+) -> Result<String, BiliError> {
+    let u = lyric_url.trim();
+    if u.is_empty() {
+        return Err(BiliError::InvalidInput("empty lyric url".to_string()));
+    }
+    let u = if u.starts_with("//") {
+        format!("https:{u}")
+    } else {
+        u.to_string()
+    };
+    let headers = header_map_with_cookie(cookie);
+    let resp = client.http.get(u).headers(headers).send().await?;
+    if !resp.status().is_success() {
+        return Err(BiliError::Http(format!("lyric http {}", resp.status())));
+    }
+    let text = resp.text().await?;
+    let text = text.trim_start_matches('\u{feff}').to_string();
+    if text.trim().is_empty() {
+        return Err(BiliError::Parse("empty lyric".to_string()));
+    }
+    Ok(text)
+}
+
+async fn fetch_audio_stream_with_quality(
+    client: &BiliClient,
+    sid: &str,
+    quality: i64,
+    cookie: Option<&str>,
+) -> Result<AudioStream, BiliError> {
+    let url = format!(
+        "{}/audio/music-service-c/url?songid={}&quality={quality}&privilege=2&mid=0&platform=android",
+        client.endpoints.api_base.trim_end_matches('/'),
+        urlencoding::encode(sid)
+    );
+    let json = get_json(client, &url, cookie).await?;
+    let data = json
+        .get("data")
+        .filter(|v| v.is_object())
+        .ok_or_else(|| BiliError::Parse("missing data".to_string()))?;
+
+    let urls = data
+        .get("cdns")
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|x| x.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if urls.is_empty() {
+        return Err(BiliError::Parse("audio stream has no cdn url".to_string()));
+    }
+
+    let qualities = data
+        .get("qualities")
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|q| {
+                    Some(AudioQuality {
+                        quality_type: q.get("type").and_then(|v| v.as_i64())?,
+                        desc: json_str(q, "desc").unwrap_or_default(),
+                        size: q.get("size").and_then(|v| v.as_u64()).filter(|v| *v > 0),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    Ok(AudioStream {
+        quality_type: data.get("type").and_then(|v| v.as_i64()).unwrap_or(quality),
+        urls,
+        size: data.get("size").and_then(|v| v.as_u64()).filter(|v| *v > 0),
+        qualities,
+    })
+}
+
+/// 获取最高可用音质的音频流：先按 320K 请求拿到音质列表，若有更高音质再尝试一次（失败则回退）。
+pub async fn fetch_audio_stream(
+    client: &BiliClient,
+    sid: &str,
+    cookie: Option<&str>,
+) -> Result<AudioStream, BiliError> {
+    let sid = normalize_sid(sid)?;
+    let base = fetch_audio_stream_with_quality(client, &sid, 2, cookie).await?;
+    let best = base
+        .qualities
+        .iter()
+        .map(|q| q.quality_type)
+        .max()
+        .unwrap_or(base.quality_type);
+    if best > base.quality_type
+        && let Ok(s) = fetch_audio_stream_with_quality(client, &sid, best, cookie).await
+        && s.quality_type >= base.quality_type
+    {
+        return Ok(s);
+    }
+    Ok(base)
+}
+
+pub fn build_audio_path(
+    out_dir: &Path,
+    path_template: Option<&str>,
+    info: &AudioSongInfo,
+    ext: &str,
+) -> PathBuf {
+    // 标题/歌手中的 `/` 不应被模板当作目录分隔符。
+    let artist = sanitize_component(&info.author);
+    build_track_path_by_template(
+        out_dir,
+        path_template.unwrap_or(""),
+        std::slice::from_ref(&artist),
+        None,
+        None,
+        &sanitize_component(&info.title),
+        ext,
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioDownloadOptions {
+    pub out_dir: PathBuf,
+    /// 与音乐下载一致的路径模板（`{{artist}}/{{title}}.{{ext}}`）；`None` 为默认布局。
+    pub path_template: Option<String>,
+    pub overwrite: bool,
+    pub retries: u32,
+    /// 同时写出同名 `.lrc`。
+    pub download_lyrics: bool,
+    /// 所属的下载调度任务；`None` 时不受全局并发/限速约束。
+    pub task: Option<DownloadTask>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioDownloadResult {
+    pub info: AudioSongInfo,
+    pub path: PathBuf,
+    pub lyrics_path: Option<PathBuf>,
+    pub bytes: u64,
+    /// 目标已存在且未开启 overwrite。
+    pub skipped: bool,
+}
+
+pub async fn download_audio(
+    client: &BiliClient,
+    sid: &str,
+    cookie: Option<&str>,
+    opt: &AudioDownloadOptions,
+    cancel: Option<&Arc<AtomicBool>>,
+    progress: Option<ProgressCb>,
+) -> Result<AudioDownloadResult, BiliError> {
+    let info = fetch_song_info(client, sid, cookie).await?;
+    let stream = fetch_audio_stream(client, &info.sid, cookie).await?;
+    let path = build_audio_path(
+        &opt.out_dir,
+        opt.path_template.as_deref(),
+        &info,
+        stream.ext(),
+    );
+
+    if path.exists() && !opt.overwrite {
+        return Ok(AudioDownloadResult {
+            info,
+            path,
+            lyrics_path: None,
+            bytes: 0,
+            skipped: true,
+        });
+    }
+
+    let headers = header_map_with_cookie(cookie);
+    let mut last_err: Option<BiliError> = None;
+    let mut bytes: Option<u64> = None;
+    for url in &stream.urls {
+        match download_to_file_single(
+            &client.http,
+            url,
+            &headers,
+            &path,
+            DownloadOptions {
+                concurrency: 1,
+                retries: opt.retries,
+                overwrite: opt.overwrite,
+            },
+            DownloadCtx {
+                cancel: cancel.cloned(),
+                progress: progress.clone(),
+                task: opt.task.clone(),
+            },
+        )
+        .await
+        {
+            Ok(n) => {
+                bytes = Some(n);
+                break;
+            }
+            Err(e) => {
+                let canceled = cancel
+                    .map(|c| c.load(std::sync::atomic::Ordering::Relaxed))
+                    .unwrap_or(false);
+                last_err = Some(e);
+                if canceled {
+                    break;
+                }
+            }
+        }
+    }
+    let Some(bytes) = bytes else {
+        return Err(last_err.unwrap_or_else(|| BiliError::Io("download failed".to_string())));
+    };
+
+    let mut lyrics_path = None;
+    if opt.download_lyrics
+        && let Some(u) = info.lyric_url.as_deref()
+        && let Ok(text) = fetch_lyrics_lrc(client, u, cookie).await
+    {
+        let p = path.with_extension("lrc");
+        if tokio::fs::write(&p, text).await.is_ok() {
+            lyrics_path = Some(p);
+        }
+    }
+
+    Ok(AudioDownloadResult {
+        info,
+        path,
+        lyrics_path,
+        bytes,
+        skipped: false,
+    })
+}
@@ -12,9 +12,40 @@ use reqwest::header::HeaderMap;
 use tokio::io::AsyncWriteExt;
 
 use super::BiliError;
+use crate::download_scheduler::{ConnectionPermit, DownloadTask};
 
 pub type ProgressCb = Arc<dyn Fn(u64, Option<u64>) + Send + Sync + 'static>;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadOptions {
+    /// 分段并发数（1..=16）；单连接下载忽略。
+    pub concurrency: u32,
+    pub retries: u32,
+    pub overwrite: bool,
+}
+
+/// 一次下载的控制句柄：取消标记、进度回调与所属调度任务（并发/限速/暂停）。
+#[derive(Clone, Default)]
+pub struct DownloadCtx {
+    pub cancel: Option<Arc<AtomicBool>>,
+    pub progress: Option<ProgressCb>,
+    pub task: Option<DownloadTask>,
+}
+
+impl DownloadCtx {
+    fn canceled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::Relaxed))
+    }
+
+    fn report(&self, downloaded: u64, total: Option<u64>) {
+        if let Some(cb) = self.progress.as_ref() {
+            cb(downloaded, total);
+        }
+    }
+}
+
 async fn fetch_with_range(
     http: &Client,
     url: &str,
@@ -38,6 +69,34 @@ fn parse_total_from_content_range(v: &str) -> Option<u64> {
     total.trim().parse::<u64>().ok()
 }
 
+/// 向调度器申请连接名额（未接入调度器时直接放行）。
+async fn acquire_connection(
+    task: Option<&DownloadTask>,
+    cancel: Option<&Arc<AtomicBool>>,
+) -> Result<Option<ConnectionPermit>, BiliError> {
+    match task {
+        Some(t) => t
+            .acquire(cancel)
+            .await
+            .map(Some)
+            .ok_or_else(|| BiliError::Io("canceled".to_string())),
+        None => Ok(None),
+    }
+}
+
+async fn throttle(
+    task: Option<&DownloadTask>,
+    bytes: usize,
+    cancel: Option<&Arc<AtomicBool>>,
+) -> Result<(), BiliError> {
+    if let Some(t) = task
+        && !t.throttle(bytes as u64, cancel).await
+    {
+        return Err(BiliError::Io("canceled".to_string()));
+    }
+    Ok(())
+}
+
 pub async fn probe_size(
     http: &Client,
     url: &str,
@@ -72,27 +131,25 @@ pub async fn download_to_file_single(
     url: &str,
     headers: &HeaderMap,
     out_path: &Path,
-    retries: u32,
-    overwrite: bool,
-    cancel: Option<&Arc<AtomicBool>>,
-    progress: Option<ProgressCb>,
+    opt: DownloadOptions,
+    ctx: DownloadCtx,
 ) -> Result<u64, BiliError> {
-    if out_path.exists() && !overwrite {
+    if out_path.exists() && !opt.overwrite {
         return Err(BiliError::Io("target exists".to_string()));
     }
     if let Some(parent) = out_path.parent() {
         tokio::fs::create_dir_all(parent).await?;
     }
 
+    let cancel = ctx.cancel.as_ref();
+    let _permit = acquire_connection(ctx.task.as_ref(), cancel).await?;
     let tmp = out_path.with_extension("part");
     let _ = tokio::fs::remove_file(&tmp).await;
 
     let mut last_err: Option<String> = None;
-    for attempt in 0..=retries {
-        if let Some(c) = cancel {
-            if c.load(Ordering::Relaxed) {
-                return Err(BiliError::Io("canceled".to_string()));
-            }
+    for attempt in 0..=opt.retries {
+        if ctx.canceled() {
+            return Err(BiliError::Io("canceled".to_string()));
         }
         if attempt > 0 {
             tokio::time::sleep(Duration::from_millis(300 * attempt as u64)).await;
@@ -107,24 +164,19 @@ pub async fn download_to_file_single(
             }
         };
         let total = resp.content_length();
-        if let Some(cb) = progress.as_ref() {
-            cb(0, total);
-        }
+        ctx.report(0, total);
         let mut stream = resp.bytes_stream();
         let mut f = tokio::fs::File::create(&tmp).await?;
         let mut downloaded: u64 = 0;
         while let Some(chunk) = stream.next().await {
-            if let Some(c) = cancel {
-                if c.load(Ordering::Relaxed) {
-                    return Err(BiliError::Io("canceled".to_string()));
-                }
+            if ctx.canceled() {
+                return Err(BiliError::Io("canceled".to_string()));
             }
             let chunk: Bytes = chunk.map_err(|e| BiliError::Http(e.to_string()))?;
+            throttle(ctx.task.as_ref(), chunk.len(), cancel).await?;
             downloaded = downloaded.saturating_add(chunk.len() as u64);
             f.write_all(&chunk).await?;
-            if let Some(cb) = progress.as_ref() {
-                cb(downloaded, total);
-            }
+            ctx.report(downloaded, total);
         }
         f.flush().await?;
         drop(f);
@@ -141,33 +193,46 @@ pub async fn download_to_file_single(
     ))
 }
 
-async fn download_range_part(
-    http: &Client,
-    url: &str,
+/// 分段下载共享的参数；`downloaded` 为所有分段累计的字节数。
+struct RangedShared {
+    http: Client,
+    url: String,
     headers: HeaderMap,
+    retries: u32,
+    total: u64,
+    downloaded: AtomicU64,
+    ctx: DownloadCtx,
+}
+
+async fn download_range_part(
+    shared: Arc<RangedShared>,
     start: u64,
     end: u64,
     part_path: PathBuf,
-    retries: u32,
-    cancel: Option<Arc<AtomicBool>>,
-    progress_total: Arc<AtomicU64>,
-    total_opt: Option<u64>,
-    progress: Option<ProgressCb>,
 ) -> Result<(), BiliError> {
+    let RangedShared {
+        http,
+        url,
+        headers,
+        retries,
+        total,
+        downloaded,
+        ctx,
+    } = &*shared;
+    let cancel = ctx.cancel.as_ref();
+    let _permit = acquire_connection(ctx.task.as_ref(), cancel).await?;
     let _ = tokio::fs::remove_file(&part_path).await;
     let mut last_err: Option<String> = None;
-    for attempt in 0..=retries {
-        if let Some(c) = cancel.as_ref() {
-            if c.load(Ordering::Relaxed) {
-                return Err(BiliError::Io("canceled".to_string()));
-            }
+    for attempt in 0..=*retries {
+        if ctx.canceled() {
+            return Err(BiliError::Io("canceled".to_string()));
         }
         if attempt > 0 {
             tokio::time::sleep(Duration::from_millis(200 * attempt as u64)).await;
         }
         let _ = tokio::fs::remove_file(&part_path).await;
 
-        let resp = match fetch_with_range(http, url, &headers, Some((start, end))).await {
+        let resp = match fetch_with_range(http, url, headers, Some((start, end))).await {
             Ok(r) => r,
             Err(e) => {
                 last_err = Some(e.to_string());
@@ -177,19 +242,16 @@ async fn download_range_part(
         let mut stream = resp.bytes_stream();
         let mut f = tokio::fs::File::create(&part_path).await?;
         while let Some(chunk) = stream.next().await {
-            if let Some(c) = cancel.as_ref() {
-                if c.load(Ordering::Relaxed) {
-                    return Err(BiliError::Io("canceled".to_string()));
-                }
+            if ctx.canceled() {
+                return Err(BiliError::Io("canceled".to_string()));
             }
             let chunk: Bytes = chunk.map_err(|e| BiliError::Http(e.to_string()))?;
+            throttle(ctx.task.as_ref(), chunk.len(), cancel).await?;
             f.write_all(&chunk).await?;
-            let total_now = progress_total
+            let total_now = downloaded
                 .fetch_add(chunk.len() as u64, Ordering::Relaxed)
                 .saturating_add(chunk.len() as u64);
-            if let Some(cb) = progress.as_ref() {
-                cb(total_now, total_opt);
-            }
+            ctx.report(total_now, Some(*total));
         }
         f.flush().await?;
         drop(f);
@@ -205,13 +267,10 @@ pub async fn download_to_file_ranged(
     url: &str,
     headers: &HeaderMap,
     out_path: &Path,
-    concurrency: u32,
-    retries: u32,
-    overwrite: bool,
-    cancel: Option<&Arc<AtomicBool>>,
-    progress: Option<ProgressCb>,
+    opt: DownloadOptions,
+    ctx: DownloadCtx,
 ) -> Result<u64, BiliError> {
-    if out_path.exists() && !overwrite {
+    if out_path.exists() && !opt.overwrite {
         return Err(BiliError::Io("target exists".to_string()));
     }
     if let Some(parent) = out_path.parent() {
@@ -219,15 +278,10 @@ pub async fn download_to_file_ranged(
     }
 
     let (total_opt, can_range) = probe_size(http, url, headers).await?;
-    if let Some(cb) = progress.as_ref() {
-        cb(0, total_opt);
-    }
-    let conc = concurrency.clamp(1, 16);
+    ctx.report(0, total_opt);
+    let conc = opt.concurrency.clamp(1, 16);
     if !can_range || total_opt.unwrap_or(0) < 2 * 1024 * 1024 || conc <= 1 {
-        return download_to_file_single(
-            http, url, headers, out_path, retries, overwrite, cancel, progress,
-        )
-        .await;
+        return download_to_file_single(http, url, headers, out_path, opt, ctx).await;
     }
     let total = total_opt.unwrap();
 
@@ -243,8 +297,15 @@ pub async fn download_to_file_ranged(
     let mut part_paths: Vec<PathBuf> = Vec::new();
 
     let chunk = (total / conc as u64).max(1024 * 512);
-    let downloaded = Arc::new(AtomicU64::new(0));
-    let progress_cb = progress.clone();
+    let shared = Arc::new(RangedShared {
+        http: http.clone(),
+        url: url.to_string(),
+        headers: headers.clone(),
+        retries: opt.retries,
+        total,
+        downloaded: AtomicU64::new(0),
+        ctx,
+    });
 
     let mut tasks = Vec::new();
     for i in 0..conc {
@@ -260,29 +321,12 @@ pub async fn download_to_file_ranged(
         let part_path = part_dir.join(format!("{base}.part{i:02}"));
         part_paths.push(part_path.clone());
 
-        let http = http.clone();
-        let url = url.to_string();
-        let headers = headers.clone();
-        let cancel = cancel.cloned();
-        let downloaded = downloaded.clone();
-        let progress_cb = progress_cb.clone();
-
-        tasks.push(tokio::spawn(async move {
-            download_range_part(
-                &http,
-                &url,
-                headers,
-                start,
-                end,
-                part_path,
-                retries,
-                cancel,
-                downloaded,
-                Some(total),
-                progress_cb,
-            )
-            .await
-        }));
+        tasks.push(tokio::spawn(download_range_part(
+            shared.clone(),
+            start,
+            end,
+            part_path,
+        )));
     }
 
     for t in tasks {
@@ -301,5 +345,5 @@ pub async fn download_to_file_ranged(
     }
 
     tokio::fs::rename(&tmp, out_path).await?;
-    Ok(downloaded.load(Ordering::Relaxed))
+    Ok(shared.downloaded.load(Ordering::Relaxed))
 }
@@ -0,0 +1,450 @@
+use std::path::{Path, PathBuf};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+use serde_json::Value;
+
+use super::download::{DownloadCtx, DownloadOptions, ProgressCb, download_to_file_ranged};
+use super::mux::{concat_ffmpeg, remux_url_ffmpeg};
+use super::parse::{ViewInfo, ViewPage};
+use super::template::{TemplateVars, build_output_path};
+use super::{BiliClient, BiliError, bili_check_code, header_map_with_cookie};
+use crate::download_scheduler::DownloadTask;
+
+const LIVE_REFERER: &str = "https://live.bilibili.com/";
+
+/// 直播回放（录播）条目。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveReplay {
+    pub rid: String,
+    pub room_id: String,
+    pub title: String,
+    pub cover: Option<String>,
+    pub start_unix_s: Option<i64>,
+    pub end_unix_s: Option<i64>,
+    pub duration_ms: Option<u64>,
+    pub owner_name: Option<String>,
+    pub owner_mid: Option<String>,
+}
+
+impl LiveReplay {
+    /// 以单 P 稿件的形式展示：cid 为回放 rid。
+    pub fn into_view_info(self) -> ViewInfo {
+        ViewInfo {
+            aid: String::new(),
+            bvid: String::new(),
+            title: self.title.clone(),
+            desc: None,
+            pic: self.cover,
+            owner_name: self.owner_name,
+            owner_mid: self.owner_mid,
+            pub_time_unix_s: self.start_unix_s,
+            pages: vec![ViewPage {
+                page_number: 1,
+                cid: self.rid,
+                page_title: self.title,
+                duration_s: self.duration_ms.map(|ms| (ms / 1000) as u32),
+                dimension: None,
+            }],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveReplayPage {
+    pub items: Vec<LiveReplay>,
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveReplayPart {
+    pub url: String,
+    pub size: Option<u64>,
+    pub length_ms: Option<u64>,
+}
+
+fn get_str(v: &Value, key: &str) -> Option<String> {
+    match v.get(key) {
+        Some(Value::String(s)) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Some(Value::Number(n)) => Some(n.to_string()).filter(|s| s != "0"),
+        _ => None,
+    }
+}
+
+fn get_i64(v: &Value, key: &str) -> Option<i64> {
+    v.get(key).and_then(|x| x.as_i64()).filter(|x| *x > 0)
+}
+
+fn parse_replay(v: &Value, room_id: &str) -> Option<LiveReplay> {
+    let rid = get_str(v, "rid")?;
+    let start = get_i64(v, "start_timestamp");
+    let end = get_i64(v, "end_timestamp");
+    let duration_ms = v
+        .get("length")
+        .and_then(|x| x.as_u64())
+        .filter(|x| *x > 0)
+        .or_else(|| match (start, end) {
+            (Some(s), Some(e)) if e > s => Some(((e - s) as u64) * 1000),
+            _ => None,
+        });
+    Some(LiveReplay {
+        rid,
+        room_id: get_str(v, "room_id").unwrap_or_else(|| room_id.to_string()),
+        title: get_str(v, "title").unwrap_or_default(),
+        cover: get_str(v, "cover"),
+        start_unix_s: start,
+        end_unix_s: end,
+        duration_ms,
+        owner_name: get_str(v, "uname"),
+        owner_mid: get_str(v, "uid"),
+    })
+}
+
+async fn get_json(
+    client: &BiliClient,
+    url: &str,
+    cookie: Option<&str>,
+) -> Result<Value, BiliError> {
+    let headers = header_map_with_cookie(cookie);
+    let json: Value = client
+        .http
+        .get(url)
+        .headers(headers)
+        .send()
+        .await?
+        .json()
+        .await?;
+    bili_check_code(&json)?;
+    Ok(json)
+}
+
+/// 短号 -> 真实房间号。
+pub async fn resolve_room_id(
+    client: &BiliClient,
+    room_id: &str,
+    cookie: Option<&str>,
+) -> Result<String, BiliError> {
+    let r = room_id.trim();
+    if r.is_empty() || !r.chars().all(|c| c.is_ascii_digit()) {
+        return Err(BiliError::InvalidInput(format!(
+            "invalid room_id: {room_id}"
+        )));
+    }
+    let url = format!(
+        "{}/room/v1/Room/room_init?id={r}",
+        client.endpoints.live_api_base.trim_end_matches('/')
+    );
+    let json = get_json(client, &url, cookie).await?;
+    json.get("data")
+        .and_then(|d| get_str(d, "room_id"))
+        .ok_or_else(|| BiliError::Parse("missing data.room_id".to_string()))
+}
+
+/// 按房间分页列出回放（`page` 从 1 开始）。
+pub async fn list_replays(
+    client: &BiliClient,
+    room_id: &str,
+    page: u32,
+    page_size: u32,
+    cookie: Option<&str>,
+) -> Result<LiveReplayPage, BiliError> {
+    let rid = resolve_room_id(client, room_id, cookie).await?;
+    let url = format!(
+        "{}/xlive/web-room/v1/record/getList?room_id={rid}&page={}&page_size={}",
+        client.endpoints.live_api_base.trim_end_matches('/'),
+        page.max(1),
+        page_size.clamp(1, 50)
+    );
+    let json = get_json(client, &url, cookie).await?;
+    let data = json.get("data").cloned().unwrap_or(Value::Null);
+    let items = data
+        .get("list")
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| parse_replay(v, &rid))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let total = data
+        .get("count")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u32)
+        .unwrap_or(items.len() as u32);
+    Ok(LiveReplayPage { items, total })
+}
+
+pub async fn fetch_replay_info(
+    client: &BiliClient,
+    rid: &str,
+    cookie: Option<&str>,
+) -> Result<LiveReplay, BiliError> {
+    let r = rid.trim();
+    if r.is_empty() {
+        return Err(BiliError::InvalidInput("empty replay rid".to_string()));
+    }
+    let url = format!(
+        "{}/xlive/web-room/v1/record/getInfoByLiveRecord?rid={}",
+        client.endpoints.live_api_base.trim_end_matches('/'),
+        urlencoding::encode(r)
+    );
+    let json = get_json(client, &url, cookie).await?;
+    let info = json
+        .pointer("/data/live_record_info")
+        .ok_or_else(|| BiliError::Parse("missing data.live_record_info".to_string()))?;
+    let mut out =
+        parse_replay(info, "").ok_or_else(|| BiliError::Parse("missing replay rid".to_string()))?;
+    if out.owner_name.is_none()
+        && let Some(anchor) = json.pointer("/data/anchor_info/base_info")
+    {
+        out.owner_name = get_str(anchor, "uname");
+    }
+    Ok(out)
+}
+
+/// 获取回放流地址：通常为多段 FLV，也可能是单个 HLS(m3u8)。
+pub async fn fetch_replay_parts(
+    client: &BiliClient,
+    rid: &str,
+    cookie: Option<&str>,
+) -> Result<Vec<LiveReplayPart>, BiliError> {
+    let r = rid.trim();
+    if r.is_empty() {
+        return Err(BiliError::InvalidInput("empty replay rid".to_string()));
+    }
+    let url = format!(
+        "{}/xlive/web-room/v1/record/getLiveRecordUrl?rid={}&platform=html5",
+        client.endpoints.live_api_base.trim_end_matches('/'),
+        urlencoding::encode(r)
+    );
+    let json = get_json(client, &url, cookie).await?;
+    let parts = json
+        .pointer("/data/list")
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|p| {
+                    let url = get_str(p, "url")?;
+                    Some(LiveReplayPart {
+                        url,
+                        size: p.get("size").and_then(|v| v.as_u64()).filter(|v| *v > 0),
+                        length_ms: p.get("length").and_then(|v| v.as_u64()).filter(|v| *v > 0),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if parts.is_empty() {
+        return Err(BiliError::Parse(
+            "replay has no stream (maybe still transcoding)".to_string(),
+        ));
+    }
+    Ok(parts)
+}
+
+fn is_hls(url: &str) -> bool {
+    url.split(['?', '#'])
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase()
+        .ends_with(".m3u8")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayDownloadOptions {
+    pub out_dir: PathBuf,
+    /// 文件名模板（同视频下载的 `filePattern`，cid 为 rid）。
+    pub file_pattern: String,
+    pub multi_file_pattern: String,
+    pub ffmpeg_path: String,
+    pub concurrency: u32,
+    pub retries: u32,
+    pub overwrite: bool,
+    /// 不合并 FLV 分段（无需 ffmpeg），保留为 `.partNNN.flv`；HLS 回放仍需 ffmpeg 拉流。
+    pub skip_mux: bool,
+    pub task: Option<DownloadTask>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayDownloadResult {
+    pub info: LiveReplay,
+    /// 合并后的 mp4；`skip_mux` 时为第一段 FLV。
+    pub path: PathBuf,
+    /// `skip_mux` 时保留的全部分段。
+    pub parts: Vec<PathBuf>,
+    pub bytes: u64,
+    /// 目标已存在且未开启 overwrite。
+    pub skipped: bool,
+}
+
+fn part_path(out_path: &Path, index: usize) -> PathBuf {
+    out_path.with_extension(format!("part{:03}.flv", index + 1))
+}
+
+/// 回放输出路径：按 `file_pattern` 渲染（标题为回放标题，cid 为 rid）。
+pub fn build_replay_path(info: &LiveReplay, opt: &ReplayDownloadOptions) -> PathBuf {
+    let vars = TemplateVars {
+        video_title: info.title.clone(),
+        page_number: 1,
+        page_title: info.title.clone(),
+        bvid: String::new(),
+        aid: String::new(),
+        cid: info.rid.clone(),
+        dfn: String::new(),
+        res: String::new(),
+        fps: String::new(),
+        video_codecs: String::new(),
+        audio_codecs: String::new(),
+        owner_name: info.owner_name.clone().unwrap_or_default(),
+        owner_mid: info.owner_mid.clone().unwrap_or_default(),
+    };
+    build_output_path(
+        &opt.out_dir,
+        &opt.file_pattern,
+        &opt.multi_file_pattern,
+        1,
+        &vars,
+        "mp4",
+    )
+}
+
+/// 下载回放：FLV 分段先下载再 concat 为 mp4，HLS 直接交给 ffmpeg 拉流封装。
+///
+/// 目标已存在且未开启 overwrite 时跳过；需要 ffmpeg 时在下载前检查 `ffmpeg_path`。
+/// 进度回调按所有分段累计字节数上报；HLS 模式下无字节进度。
+pub async fn download_replay(
+    client: &BiliClient,
+    rid: &str,
+    cookie: Option<&str>,
+    opt: &ReplayDownloadOptions,
+    cancel: Option<&Arc<AtomicBool>>,
+    progress: Option<ProgressCb>,
+) -> Result<ReplayDownloadResult, BiliError> {
+    let info = fetch_replay_info(client, rid, cookie).await?;
+    let out_path = build_replay_path(&info, opt);
+    let target = if opt.skip_mux {
+        part_path(&out_path, 0)
+    } else {
+        out_path.clone()
+    };
+    if target.exists() && !opt.overwrite {
+        return Ok(ReplayDownloadResult {
+            info,
+            path: target,
+            parts: Vec::new(),
+            bytes: 0,
+            skipped: true,
+        });
+    }
+    let parts = fetch_replay_parts(client, &info.rid, cookie).await?;
+    let hls = parts.len() == 1 && is_hls(&parts[0].url);
+    if (hls || !opt.skip_mux) && opt.ffmpeg_path.trim().is_empty() {
+        return Err(BiliError::InvalidInput(if hls {
+            "ffmpegPath is required for HLS replay".to_string()
+        } else {
+            "ffmpegPath is empty".to_string()
+        }));
+    }
+
+    if hls {
+        remux_url_ffmpeg(
+            &opt.ffmpeg_path,
+            &parts[0].url,
+            LIVE_REFERER,
+            &out_path,
+            opt.overwrite,
+            cancel,
+        )
+        .await?;
+        let bytes = tokio::fs::metadata(&out_path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        return Ok(ReplayDownloadResult {
+            info,
+            path: out_path,
+            parts: Vec::new(),
+            bytes,
+            skipped: false,
+        });
+    }
+
+    let mut headers = header_map_with_cookie(cookie);
+    headers.insert(
+        reqwest::header::REFERER,
+        reqwest::header::HeaderValue::from_static(LIVE_REFERER),
+    );
+
+    let known_total = parts.iter().map(|p| p.size).sum::<Option<u64>>();
+    let done_before = Arc::new(AtomicU64::new(0));
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut result: Result<(), BiliError> = Ok(());
+    for (i, p) in parts.iter().enumerate() {
+        if cancel.map(|c| c.load(Ordering::Relaxed)).unwrap_or(false) {
+            result = Err(BiliError::Io("canceled".to_string()));
+            break;
+        }
+        let part_path = part_path(&out_path, i);
+        let cb: Option<ProgressCb> = progress.as_ref().map(|cb| {
+            let cb = cb.clone();
+            let done_before = done_before.clone();
+            Arc::new(move |d: u64, _t: Option<u64>| {
+                cb(done_before.load(Ordering::Relaxed) + d, known_total);
+            }) as ProgressCb
+        });
+        match download_to_file_ranged(
+            &client.http,
+            &p.url,
+            &headers,
+            &part_path,
+            DownloadOptions {
+                concurrency: opt.concurrency,
+                retries: opt.retries,
+                overwrite: true,
+            },
+            DownloadCtx {
+                cancel: cancel.cloned(),
+                progress: cb,
+                task: opt.task.clone(),
+            },
+        )
+        .await
+        {
+            Ok(n) => {
+                done_before.fetch_add(n, Ordering::Relaxed);
+                files.push(part_path);
+            }
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&part_path).await;
+                result = Err(e);
+                break;
+            }
+        }
+    }
+
+    let bytes = done_before.load(Ordering::Relaxed);
+    if opt.skip_mux && result.is_ok() {
+        return Ok(ReplayDownloadResult {
+            info,
+            path: files.first().cloned().unwrap_or(out_path),
+            parts: files,
+            bytes,
+            skipped: false,
+        });
+    }
+    if result.is_ok() {
+        result = concat_ffmpeg(&opt.ffmpeg_path, &files, &out_path, opt.overwrite, cancel).await;
+    }
+    for f in &files {
+        let _ = tokio::fs::remove_file(f).await;
+    }
+    result.map(|_| ReplayDownloadResult {
+        info,
+        path: out_path,
+        parts: Vec::new(),
+        bytes,
+        skipped: false,
+    })
+}
@@ -6,8 +6,10 @@ use reqwest::Url;
 use reqwest::cookie::{CookieStore, Jar};
 use reqwest::header::{HeaderMap, HeaderValue};
 
+pub mod audio;
 pub mod auth;
 pub mod download;
+pub mod live_replay;
 pub mod mux;
 pub mod parse;
 pub mod pgc;
@@ -56,11 +58,21 @@ impl From<std::io::Error> for BiliError {
     }
 }
 
+impl From<crate::ffmpeg::FfmpegError> for BiliError {
+    fn from(e: crate::ffmpeg::FfmpegError) -> Self {
+        match e {
+            crate::ffmpeg::FfmpegError::Canceled => Self::Io("canceled".to_string()),
+            e => Self::Mux(e.to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BiliEndpoints {
     pub api_base: String,      // https://api.bilibili.com
     pub passport_base: String, // https://passport.bilibili.com
     pub www_base: String,      // https://www.bilibili.com
+    pub live_api_base: String, // https://api.live.bilibili.com
 }
 
 impl Default for BiliEndpoints {
@@ -69,6 +81,7 @@ impl Default for BiliEndpoints {
             api_base: "https://api.bilibili.com".to_string(),
             passport_base: "https://passport.bilibili.com".to_string(),
             www_base: "https://www.bilibili.com".to_string(),
+            live_api_base: "https://api.live.bilibili.com".to_string(),
         }
     }
 }
@@ -1,22 +1,230 @@
 use std::path::{Path, PathBuf};
-use std::sync::{
-    Arc,
-    atomic::{AtomicBool, Ordering},
-};
+use std::sync::{Arc, atomic::AtomicBool};
 
 use tokio::process::Command;
 
-use super::BiliError;
+use super::parse::{ViewInfo, ViewPage, ViewPoint, fetch_view_points, fetch_view_tags};
+use super::{BiliClient, BiliError, header_map_with_cookie};
+
+/// 封装时写入 MP4 的元数据：标题/UP 主/BV 号/发布日期/简介/标签、章节与封面。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MuxMetadata {
+    pub title: String,
+    pub artist: String,
+    pub bvid: String,
+    /// YYYY-MM-DD
+    pub date: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub chapters: Vec<ViewPoint>,
+    pub cover_path: Option<PathBuf>,
+}
+
+/// 要嵌入的元数据（对应下载选项的 `embedMetadata` / `embedChapters` / `embedCover`）。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbedOptions {
+    pub metadata: bool,
+    pub chapters: bool,
+    pub cover: bool,
+}
+
+impl EmbedOptions {
+    pub fn any(self) -> bool {
+        self.metadata || self.chapters || self.cover
+    }
+}
+
+/// 组装 [`MuxMetadata`] 所需的视频信息：来自 view 接口，或恢复任务时保存的字段。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetadataSource<'a> {
+    pub title: &'a str,
+    /// 多 P 视频的分 P 标题（拼到标题后面）；单 P 为 `None`。
+    pub page_title: Option<&'a str>,
+    pub owner_name: &'a str,
+    pub bvid: &'a str,
+    pub cid: &'a str,
+    pub description: &'a str,
+    pub pub_time_unix_s: Option<i64>,
+    pub cover_url: Option<&'a str>,
+}
+
+impl<'a> MetadataSource<'a> {
+    pub fn from_view(view: &'a ViewInfo, page: &'a ViewPage) -> Self {
+        Self {
+            title: &view.title,
+            page_title: (view.pages.len() > 1).then_some(page.page_title.as_str()),
+            owner_name: view.owner_name.as_deref().unwrap_or_default(),
+            bvid: &view.bvid,
+            cid: &page.cid,
+            description: view.desc.as_deref().unwrap_or_default(),
+            pub_time_unix_s: view.pub_time_unix_s,
+            cover_url: view.pic.as_deref(),
+        }
+    }
+}
+
+/// 按 `embed` 拉取标签/章节/封面并组装 [`MuxMetadata`]；什么都不嵌入时返回 `None`。
+///
+/// 封面下载到 `cover_path`（由调用方在 mux 后清理）；标签、章节、封面失败时忽略。
+pub async fn build_metadata(
+    client: &BiliClient,
+    src: MetadataSource<'_>,
+    embed: EmbedOptions,
+    cover_path: &Path,
+    cookie: Option<&str>,
+) -> Option<MuxMetadata> {
+    if !embed.any() {
+        return None;
+    }
+    let mut meta = MuxMetadata::default();
+    if embed.metadata {
+        meta.title = match src.page_title {
+            Some(p) => format!("{} - {p}", src.title),
+            None => src.title.to_string(),
+        };
+        meta.artist = src.owner_name.to_string();
+        meta.bvid = src.bvid.to_string();
+        meta.date = src.pub_time_unix_s.map(format_pub_date).unwrap_or_default();
+        meta.description = src.description.to_string();
+        meta.tags = fetch_view_tags(client, src.bvid, cookie)
+            .await
+            .unwrap_or_default();
+    }
+    if embed.chapters {
+        meta.chapters = fetch_view_points(client, src.bvid, src.cid, cookie)
+            .await
+            .unwrap_or_default();
+    }
+    if embed.cover
+        && let Some(pic) = src.cover_url
+        && download_cover(client, pic, cover_path, cookie)
+            .await
+            .is_ok()
+    {
+        meta.cover_path = Some(cover_path.to_path_buf());
+    }
+    (embed.metadata || embed.chapters || meta.cover_path.is_some()).then_some(meta)
+}
+
+/// 发布时间（unix 秒）转为 `YYYY-MM-DD`（按 UTC+8，与 B 站页面展示一致）。
+pub fn format_pub_date(unix_s: i64) -> String {
+    let days = (unix_s + 8 * 3600).div_euclid(86_400);
+    // civil_from_days (Howard Hinnant)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+fn escape_ffmetadata(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '=' | ';' | '#' | '\\' | '\n' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '\r' => {}
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// 生成 ffmpeg `FFMETADATA1` 文本（全局元数据 + 章节），供 `-map_metadata/-map_chapters` 使用。
+pub fn render_ffmetadata(meta: &MuxMetadata) -> String {
+    let mut out = String::from(";FFMETADATA1\n");
+    let mut kv = |k: &str, v: &str| {
+        let v = v.trim();
+        if !v.is_empty() {
+            out.push_str(k);
+            out.push('=');
+            out.push_str(&escape_ffmetadata(v));
+            out.push('\n');
+        }
+    };
+    kv("title", &meta.title);
+    kv("artist", &meta.artist);
+    kv("date", &meta.date);
+    kv("description", &meta.description);
+    if !meta.bvid.trim().is_empty() {
+        kv(
+            "comment",
+            &format!("https://www.bilibili.com/video/{}", meta.bvid.trim()),
+        );
+    }
+    kv("keywords", &meta.tags.join(","));
+
+    for c in &meta.chapters {
+        out.push_str("\n[CHAPTER]\nTIMEBASE=1/1000\n");
+        out.push_str(&format!("START={}\nEND={}\n", c.start_ms, c.end_ms));
+        out.push_str(&format!("title={}\n", escape_ffmetadata(c.title.trim())));
+    }
+    out
+}
+
+/// 下载封面图到 `out_path`（失败时由调用方决定是否忽略）。
+pub async fn download_cover(
+    client: &BiliClient,
+    url: &str,
+    out_path: &Path,
+    cookie: Option<&str>,
+) -> Result<(), BiliError> {
+    let u = url.trim();
+    if u.is_empty() {
+        return Err(BiliError::InvalidInput("empty cover url".to_string()));
+    }
+    let u = if let Some(rest) = u.strip_prefix("//") {
+        format!("https://{rest}")
+    } else if let Some(rest) = u.strip_prefix("http://") {
+        format!("https://{rest}")
+    } else {
+        u.to_string()
+    };
+    let headers = header_map_with_cookie(cookie);
+    let resp = client.http.get(u).headers(headers).send().await?;
+    if !resp.status().is_success() {
+        return Err(BiliError::Http(format!("cover http {}", resp.status())));
+    }
+    let bytes = resp.bytes().await?;
+    if bytes.is_empty() {
+        return Err(BiliError::Parse("empty cover".to_string()));
+    }
+    if let Some(parent) = out_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(out_path, &bytes).await?;
+    Ok(())
+}
+
+/// [`mux_ffmpeg`] 的输入：视频/音频轨、外挂字幕与可选元数据。
+#[derive(Debug, Clone, Copy)]
+pub struct MuxInputs<'a> {
+    pub video: &'a Path,
+    pub audio: &'a Path,
+    pub subtitles: &'a [PathBuf],
+    pub metadata: Option<&'a MuxMetadata>,
+}
 
 pub async fn mux_ffmpeg(
     ffmpeg_path: &str,
-    video_path: &Path,
-    audio_path: &Path,
-    subtitles: &[PathBuf],
+    inputs: MuxInputs<'_>,
     out_path: &Path,
     overwrite: bool,
     cancel: Option<&Arc<AtomicBool>>,
 ) -> Result<(), BiliError> {
+    let MuxInputs {
+        video: video_path,
+        audio: audio_path,
+        subtitles,
+        metadata,
+    } = inputs;
     let bin = ffmpeg_path.trim();
     if bin.is_empty() {
         return Err(BiliError::InvalidInput("ffmpegPath is empty".to_string()));
@@ -34,6 +242,18 @@ pub async fn mux_ffmpeg(
         tokio::fs::create_dir_all(parent).await?;
     }
 
+    let cover_path = metadata
+        .and_then(|m| m.cover_path.as_deref())
+        .filter(|p| p.exists());
+    let ffmeta_path = match metadata {
+        Some(m) => {
+            let p = out_path.with_extension("ffmeta.txt");
+            tokio::fs::write(&p, render_ffmetadata(m)).await?;
+            Some(p)
+        }
+        None => None,
+    };
+
     let mut cmd = Command::new(bin);
     cmd.arg("-hide_banner").arg("-loglevel").arg("error");
     cmd.arg(if overwrite { "-y" } else { "-n" });
@@ -42,45 +262,132 @@ pub async fn mux_ffmpeg(
     for s in subtitles {
         cmd.arg("-i").arg(s);
     }
+    let mut next_input = subtitles.len() + 2;
+    let cover_input = cover_path.map(|p| {
+        cmd.arg("-i").arg(p);
+        next_input += 1;
+        next_input - 1
+    });
+    let meta_input = ffmeta_path.as_ref().map(|p| {
+        cmd.arg("-f").arg("ffmetadata").arg("-i").arg(p);
+        next_input += 1;
+        next_input - 1
+    });
+
     cmd.arg("-map").arg("0:v:0");
     cmd.arg("-map").arg("1:a:0");
     for i in 0..subtitles.len() {
         cmd.arg("-map").arg(format!("{}:0", i + 2));
     }
+    if let Some(i) = cover_input {
+        cmd.arg("-map").arg(format!("{i}:0"));
+    }
+    if let Some(i) = meta_input {
+        cmd.arg("-map_metadata").arg(i.to_string());
+        cmd.arg("-map_chapters").arg(i.to_string());
+    }
 
     cmd.arg("-c").arg("copy");
     if !subtitles.is_empty() {
         cmd.arg("-c:s").arg("mov_text");
     }
+    if cover_input.is_some() {
+        cmd.arg("-disposition:v:1").arg("attached_pic");
+    }
     cmd.arg(out_path);
     cmd.kill_on_drop(true);
 
-    let mut child = cmd.spawn().map_err(|e| BiliError::Mux(e.to_string()))?;
+    let res = crate::ffmpeg::run(cmd, cancel).await;
+    if let Some(p) = ffmeta_path {
+        let _ = tokio::fs::remove_file(p).await;
+    }
+    res?;
+    Ok(())
+}
 
-    if let Some(c) = cancel {
-        tokio::select! {
-            r = child.wait() => {
-                let st = r.map_err(|e| BiliError::Mux(e.to_string()))?;
-                if st.success() { Ok(()) } else { Err(BiliError::Mux(format!("ffmpeg exit code: {st}"))) }
-            }
-            _ = async {
-                while !c.load(Ordering::Relaxed) {
-                    tokio::time::sleep(std::time::Duration::from_millis(120)).await;
-                }
-            } => {
-                let _ = child.kill().await;
-                Err(BiliError::Io("canceled".to_string()))
-            }
-        }
-    } else {
-        let st = child
-            .wait()
-            .await
-            .map_err(|e| BiliError::Mux(e.to_string()))?;
-        if st.success() {
-            Ok(())
-        } else {
-            Err(BiliError::Mux(format!("ffmpeg exit code: {st}")))
-        }
+/// 用 concat demuxer 无损合并分段（如直播回放的多段 FLV）。
+pub async fn concat_ffmpeg(
+    ffmpeg_path: &str,
+    parts: &[PathBuf],
+    out_path: &Path,
+    overwrite: bool,
+    cancel: Option<&Arc<AtomicBool>>,
+) -> Result<(), BiliError> {
+    let bin = ffmpeg_path.trim();
+    if bin.is_empty() {
+        return Err(BiliError::InvalidInput("ffmpegPath is empty".to_string()));
     }
+    if parts.is_empty() || out_path.as_os_str().is_empty() {
+        return Err(BiliError::InvalidInput(
+            "empty input/output path".to_string(),
+        ));
+    }
+    if let Some(parent) = out_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let mut list = String::new();
+    for p in parts {
+        let abs = std::path::absolute(p).unwrap_or_else(|_| p.clone());
+        let s = abs
+            .to_string_lossy()
+            .replace('\\', "/")
+            .replace('\'', "'\\''");
+        list.push_str(&format!("file '{s}'\n"));
+    }
+    let list_path = out_path.with_extension("concat.txt");
+    tokio::fs::write(&list_path, list).await?;
+
+    let mut cmd = Command::new(bin);
+    cmd.arg("-hide_banner").arg("-loglevel").arg("error");
+    cmd.arg(if overwrite { "-y" } else { "-n" });
+    cmd.arg("-f").arg("concat").arg("-safe").arg("0");
+    cmd.arg("-i").arg(&list_path);
+    cmd.arg("-c").arg("copy");
+    cmd.arg(out_path);
+    cmd.kill_on_drop(true);
+
+    let res = crate::ffmpeg::run(cmd, cancel).await;
+    let _ = tokio::fs::remove_file(&list_path).await;
+    res?;
+    Ok(())
+}
+
+/// 直接用 ffmpeg 拉取远端流（HLS m3u8 等）并封装到 `out_path`。
+pub async fn remux_url_ffmpeg(
+    ffmpeg_path: &str,
+    url: &str,
+    referer: &str,
+    out_path: &Path,
+    overwrite: bool,
+    cancel: Option<&Arc<AtomicBool>>,
+) -> Result<(), BiliError> {
+    let bin = ffmpeg_path.trim();
+    if bin.is_empty() {
+        return Err(BiliError::InvalidInput("ffmpegPath is empty".to_string()));
+    }
+    if url.trim().is_empty() || out_path.as_os_str().is_empty() {
+        return Err(BiliError::InvalidInput(
+            "empty input/output path".to_string(),
+        ));
+    }
+    if let Some(parent) = out_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let mut cmd = Command::new(bin);
+    cmd.arg("-hide_banner").arg("-loglevel").arg("error");
+    cmd.arg(if overwrite { "-y" } else { "-n" });
+    cmd.arg("-user_agent").arg(super::default_user_agent());
+    if !referer.trim().is_empty() {
+        cmd.arg("-headers")
+            .arg(format!("Referer: {}\r\n", referer.trim()));
+    }
+    cmd.arg("-i").arg(url.trim());
+    cmd.arg("-c").arg("copy");
+    cmd.arg(out_path);
+    cmd.kill_on_drop(true);
+
+    crate::ffmpeg::run(cmd, cancel).await?;
+    Ok(())
 }
@@ -47,6 +47,16 @@ fn re_ep_id() -> &'static Regex {
     RE.get_or_init(|| Regex::new(r"(?i)\bep(\d{1,20})\b").unwrap())
 }
 
+fn re_audio_sid() -> &'static Regex {
+    static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)\bau(\d{1,20})\b").unwrap())
+}
+
+fn re_live_record() -> &'static Regex {
+    static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)live\.bilibili\.com/record/(R[0-9A-Za-z]{4,64})").unwrap())
+}
+
 fn re_season_id() -> &'static Regex {
     static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
     RE.get_or_init(|| Regex::new(r"(?i)\bss(\d{1,20})\b").unwrap())
@@ -79,11 +89,29 @@ pub fn parse_video_id(input: &str) -> Result<VideoId, BiliError> {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParsedInput {
     Video(VideoId),
-    BangumiEpisode { ep_id: String },
-    BangumiSeason { season_id: String },
+    BangumiEpisode {
+        ep_id: String,
+    },
+    BangumiSeason {
+        season_id: String,
+    },
+    /// 音频区（`au<sid>` / `www.bilibili.com/audio/au<sid>`）
+    Audio {
+        sid: String,
+    },
+    /// 直播回放（`live.bilibili.com/record/R...`）
+    LiveReplay {
+        rid: String,
+    },
 }
 
 fn try_parse_input_sync(raw: &str) -> Option<ParsedInput> {
+    // 回放 rid 中可能含有形如 `BV...` 的片段，需先于 BV 匹配。
+    if let Some(m) = re_live_record().captures(raw).and_then(|c| c.get(1)) {
+        return Some(ParsedInput::LiveReplay {
+            rid: m.as_str().to_string(),
+        });
+    }
     if let Some(m) = re_bvid().captures(raw).and_then(|c| c.get(1)) {
         return Some(ParsedInput::Video(VideoId {
             aid: None,
@@ -106,6 +134,11 @@ fn try_parse_input_sync(raw: &str) -> Option<ParsedInput> {
             season_id: m.as_str().to_string(),
         });
     }
+    if let Some(m) = re_audio_sid().captures(raw).and_then(|c| c.get(1)) {
+        return Some(ParsedInput::Audio {
+            sid: m.as_str().to_string(),
+        });
+    }
     None
 }
 
@@ -148,12 +181,12 @@ pub async fn parse_input(client: &BiliClient, input: &str) -> Result<ParsedInput
             return Ok(p);
         }
         return Err(BiliError::InvalidInput(format!(
-            "unsupported url (no BV/av/ep/ss found): {final_url}"
+            "unsupported url (no BV/av/ep/ss/au/record found): {final_url}"
         )));
     }
 
     Err(BiliError::InvalidInput(
-        "unsupported input (expect BV/av/ep/ss/url)".to_string(),
+        "unsupported input (expect BV/av/ep/ss/au/url)".to_string(),
     ))
 }
 
@@ -292,3 +325,105 @@ pub async fn fetch_view_info(
         pages,
     })
 }
+
+/// 视频看点（章节），时间单位毫秒。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewPoint {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub title: String,
+}
+
+pub async fn fetch_view_tags(
+    client: &BiliClient,
+    bvid: &str,
+    cookie: Option<&str>,
+) -> Result<Vec<String>, BiliError> {
+    let bv = bvid.trim();
+    if bv.is_empty() {
+        return Ok(vec![]);
+    }
+    let url = format!(
+        "{}/x/web-interface/view/detail/tag?bvid={}",
+        client.endpoints.api_base.trim_end_matches('/'),
+        urlencoding::encode(bv)
+    );
+    let headers = header_map_with_cookie(cookie);
+    let json: Value = client
+        .http
+        .get(url)
+        .headers(headers)
+        .send()
+        .await?
+        .json()
+        .await?;
+    bili_check_code(&json)?;
+
+    let mut out: Vec<String> = Vec::new();
+    if let Some(arr) = json.get("data").and_then(|v| v.as_array()) {
+        for t in arr {
+            let name = t
+                .get("tag_name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .trim();
+            if !name.is_empty() && !out.iter().any(|x| x == name) {
+                out.push(name.to_string());
+            }
+        }
+    }
+    Ok(out)
+}
+
+pub async fn fetch_view_points(
+    client: &BiliClient,
+    bvid: &str,
+    cid: &str,
+    cookie: Option<&str>,
+) -> Result<Vec<ViewPoint>, BiliError> {
+    let bv = bvid.trim();
+    let c = cid.trim();
+    if bv.is_empty() || c.is_empty() {
+        return Ok(vec![]);
+    }
+    let url = format!(
+        "{}/x/player/v2?bvid={}&cid={}",
+        client.endpoints.api_base.trim_end_matches('/'),
+        urlencoding::encode(bv),
+        urlencoding::encode(c)
+    );
+    let headers = header_map_with_cookie(cookie);
+    let json: Value = client
+        .http
+        .get(url)
+        .headers(headers)
+        .send()
+        .await?
+        .json()
+        .await?;
+    bili_check_code(&json)?;
+
+    let mut out: Vec<ViewPoint> = Vec::new();
+    if let Some(arr) = json.pointer("/data/view_points").and_then(|v| v.as_array()) {
+        for p in arr {
+            let from = p.get("from").and_then(|v| v.as_u64()).unwrap_or(0);
+            let to = p.get("to").and_then(|v| v.as_u64()).unwrap_or(0);
+            let title = p
+                .get("content")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .trim()
+                .to_string();
+            if title.is_empty() || to <= from {
+                continue;
+            }
+            out.push(ViewPoint {
+                start_ms: from * 1000,
+                end_ms: to * 1000,
+                title,
+            });
+        }
+    }
+    out.sort_by_key(|p| p.start_ms);
+    Ok(out)
+}
@@ -1,43 +1,119 @@
+use std::path::{Path, PathBuf};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
 use serde_json::Value;
 
 use super::{BiliClient, BiliError, bili_check_code, header_map_with_cookie};
+use crate::music::util::sanitize_component;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SubtitleTrack {
     pub lang: String,
     pub lang_doc: String,
     pub url: String,
+    /// AI 生成字幕（`ai-zh` 等）。
+    pub ai: bool,
 }
 
-pub async fn fetch_subtitles(
-    client: &BiliClient,
-    bvid: &str,
-    cid: &str,
-    cookie: Option<&str>,
-) -> Result<Vec<SubtitleTrack>, BiliError> {
-    let bv = bvid.trim();
-    let c = cid.trim();
-    if bv.is_empty() || c.is_empty() {
-        return Ok(vec![]);
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    #[default]
+    Srt,
+    Ass,
+    Vtt,
+    /// 原始 BCC JSON（不参与 mux）。
+    Json,
+}
+
+impl SubtitleFormat {
+    pub fn ext(self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Ass => "ass",
+            Self::Vtt => "vtt",
+            Self::Json => "json",
+        }
     }
 
-    let url = format!(
-        "{}/x/player/v2?bvid={}&cid={}",
-        client.endpoints.api_base.trim_end_matches('/'),
-        urlencoding::encode(bv),
-        urlencoding::encode(c)
-    );
-    let headers = header_map_with_cookie(cookie);
-    let json: Value = client
-        .http
-        .get(url)
-        .headers(headers)
-        .send()
-        .await?
-        .json()
-        .await?;
-    bili_check_code(&json)?;
+    pub fn can_mux(self) -> bool {
+        !matches!(self, Self::Json)
+    }
+}
 
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubtitleOptions {
+    pub format: SubtitleFormat,
+    /// 语言过滤（如 `zh-CN`、`en`、`ai-zh`）；空表示全部。
+    pub langs: Vec<String>,
+    pub include_ai: bool,
+    /// 双语合并：(主字幕, 副字幕) 语言，额外输出一个 ASS。
+    pub bilingual: Option<(String, String)>,
+}
+
+impl SubtitleOptions {
+    /// 由下载选项的原始字符串构造：`langs` / `bilingual` 为逗号分隔的语言列表，
+    /// `bilingual` 取前两项，不足两项时忽略。
+    pub fn from_lists(
+        format: SubtitleFormat,
+        langs: &str,
+        include_ai: bool,
+        bilingual: Option<&str>,
+    ) -> Self {
+        let bilingual = bilingual
+            .map(parse_lang_list)
+            .and_then(|v| match v.as_slice() {
+                [a, b, ..] => Some((a.clone(), b.clone())),
+                _ => None,
+            });
+        Self {
+            format,
+            langs: parse_lang_list(langs),
+            include_ai,
+            bilingual,
+        }
+    }
+}
+
+/// 字幕所属的视频分 P。
+#[derive(Debug, Clone, Copy)]
+pub struct SubtitleVideo<'a> {
+    pub aid: &'a str,
+    pub bvid: &'a str,
+    pub cid: &'a str,
+}
+
+/// 解析逗号/分号分隔的语言列表（`"zh-CN, en-US"`）。
+pub fn parse_lang_list(s: &str) -> Vec<String> {
+    s.split([',', ';', '，'])
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .map(|x| x.to_string())
+        .collect()
+}
+
+/// `want = "zh"` 可匹配 `zh-CN`/`zh-Hans`；大小写不敏感。
+pub fn lang_matches(track_lang: &str, want: &str) -> bool {
+    let t = track_lang.trim().to_ascii_lowercase();
+    let w = want.trim().to_ascii_lowercase();
+    !w.is_empty() && (t == w || t.starts_with(&format!("{w}-")))
+}
+
+pub fn select_subtitle_tracks(
+    tracks: &[SubtitleTrack],
+    opt: &SubtitleOptions,
+) -> Vec<SubtitleTrack> {
+    tracks
+        .iter()
+        .filter(|t| opt.include_ai || !t.ai)
+        .filter(|t| opt.langs.is_empty() || opt.langs.iter().any(|w| lang_matches(&t.lang, w)))
+        .cloned()
+        .collect()
+}
+
+fn parse_subtitle_list(json: &Value) -> Vec<SubtitleTrack> {
     let subs = json
         .pointer("/data/subtitle/subtitles")
         .and_then(|v| v.as_array())
@@ -51,9 +127,6 @@ pub async fn fetch_subtitles(
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .trim();
-        if url.is_empty() {
-            continue;
-        }
         let url = if url.starts_with("//") {
             format!("https:{url}")
         } else {
@@ -74,33 +147,138 @@ pub async fn fetch_subtitles(
         if lang.is_empty() {
             continue;
         }
+        let ai = lang.starts_with("ai-") || s.get("type").and_then(|v| v.as_i64()) == Some(1);
         out.push(SubtitleTrack {
             lang,
             lang_doc,
             url,
+            ai,
         });
     }
+    out
+}
+
+pub async fn fetch_subtitles(
+    client: &BiliClient,
+    bvid: &str,
+    cid: &str,
+    cookie: Option<&str>,
+) -> Result<Vec<SubtitleTrack>, BiliError> {
+    let bv = bvid.trim();
+    let c = cid.trim();
+    if bv.is_empty() || c.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let url = format!(
+        "{}/x/player/v2?bvid={}&cid={}",
+        client.endpoints.api_base.trim_end_matches('/'),
+        urlencoding::encode(bv),
+        urlencoding::encode(c)
+    );
+    let headers = header_map_with_cookie(cookie);
+    let json: Value = client
+        .http
+        .get(url)
+        .headers(headers)
+        .send()
+        .await?
+        .json()
+        .await?;
+    bili_check_code(&json)?;
+
+    Ok(parse_subtitle_list(&json)
+        .into_iter()
+        .filter(|t| !t.url.is_empty())
+        .collect())
+}
+
+/// AI 字幕在 `x/player/v2` 中通常没有 `subtitle_url`，需要登录后从 `x/v2/dm/view` 单独获取。
+pub async fn fetch_ai_subtitles(
+    client: &BiliClient,
+    aid: &str,
+    cid: &str,
+    cookie: Option<&str>,
+) -> Result<Vec<SubtitleTrack>, BiliError> {
+    let c = cid.trim();
+    if c.is_empty() || cookie.map(|s| s.trim().is_empty()).unwrap_or(true) {
+        return Ok(vec![]);
+    }
+    let mut url = format!(
+        "{}/x/v2/dm/view?type=1&oid={}",
+        client.endpoints.api_base.trim_end_matches('/'),
+        urlencoding::encode(c)
+    );
+    if !aid.trim().is_empty() {
+        url.push_str(&format!("&pid={}", urlencoding::encode(aid.trim())));
+    }
+    let headers = header_map_with_cookie(cookie);
+    let json: Value = client
+        .http
+        .get(url)
+        .headers(headers)
+        .send()
+        .await?
+        .json()
+        .await?;
+    bili_check_code(&json)?;
+
+    Ok(parse_subtitle_list(&json)
+        .into_iter()
+        .filter(|t| t.ai && !t.url.is_empty())
+        .collect())
+}
+
+/// 普通字幕 + AI 字幕（去重，AI 字幕获取失败时忽略）。
+pub async fn fetch_subtitles_with_ai(
+    client: &BiliClient,
+    aid: &str,
+    bvid: &str,
+    cid: &str,
+    cookie: Option<&str>,
+) -> Result<Vec<SubtitleTrack>, BiliError> {
+    let mut out = fetch_subtitles(client, bvid, cid, cookie).await?;
+    if let Ok(ai) = fetch_ai_subtitles(client, aid, cid, cookie).await {
+        for t in ai {
+            if !out.iter().any(|x| x.lang == t.lang) {
+                out.push(t);
+            }
+        }
+    }
     Ok(out)
 }
 
-fn format_srt_time(sec: f64) -> String {
+fn split_ms(sec: f64) -> (u64, u64, u64, u64) {
     let ms_total = (sec.max(0.0) * 1000.0).round() as u64;
     let ms = ms_total % 1000;
     let s_total = ms_total / 1000;
     let s = s_total % 60;
     let m_total = s_total / 60;
-    let m = m_total % 60;
-    let h = m_total / 60;
+    (m_total / 60, m_total % 60, s, ms)
+}
+
+fn format_srt_time(sec: f64) -> String {
+    let (h, m, s, ms) = split_ms(sec);
     format!("{:02}:{:02}:{:02},{:03}", h, m, s, ms)
 }
 
-pub fn bcc_json_to_srt(json: &Value) -> Result<String, BiliError> {
+fn format_vtt_time(sec: f64) -> String {
+    let (h, m, s, ms) = split_ms(sec);
+    format!("{:02}:{:02}:{:02}.{:03}", h, m, s, ms)
+}
+
+fn format_ass_time(sec: f64) -> String {
+    let (h, m, s, ms) = split_ms(sec);
+    format!("{}:{:02}:{:02}.{:02}", h, m, s, ms / 10)
+}
+
+/// BCC `body` -> (from, to, content)，跳过空行。
+fn bcc_cues(json: &Value) -> Result<Vec<(f64, f64, String)>, BiliError> {
     let body = json
         .get("body")
         .and_then(|v| v.as_array())
         .ok_or_else(|| BiliError::Parse("bcc missing body".to_string()))?;
-    let mut out = String::new();
-    let mut idx: u32 = 1;
+    let mut out = Vec::new();
     for item in body {
         let from = item.get("from").and_then(|v| v.as_f64()).unwrap_or(0.0);
         let to = item.get("to").and_then(|v| v.as_f64()).unwrap_or(0.0);
@@ -114,25 +292,89 @@ pub fn bcc_json_to_srt(json: &Value) -> Result<String, BiliError> {
         if content.is_empty() {
             continue;
         }
-        out.push_str(&format!("{idx}\n"));
+        out.push((from, to.max(from), content));
+    }
+    Ok(out)
+}
+
+pub fn bcc_json_to_srt(json: &Value) -> Result<String, BiliError> {
+    let mut out = String::new();
+    for (i, (from, to, content)) in bcc_cues(json)?.into_iter().enumerate() {
+        out.push_str(&format!("{}\n", i + 1));
         out.push_str(&format!(
             "{} --> {}\n",
             format_srt_time(from),
-            format_srt_time(to.max(from))
+            format_srt_time(to)
         ));
         out.push_str(&content);
         out.push('\n');
         out.push('\n');
-        idx += 1;
     }
     Ok(out)
 }
 
-pub async fn download_subtitle_srt(
+pub fn bcc_json_to_vtt(json: &Value) -> Result<String, BiliError> {
+    let mut out = String::from("WEBVTT\n\n");
+    for (from, to, content) in bcc_cues(json)? {
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_vtt_time(from),
+            format_vtt_time(to)
+        ));
+        out.push_str(&content.replace("-->", "->"));
+        out.push('\n');
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+const ASS_HEADER: &str = "[Script Info]\nScriptType: v4.00+\nPlayResX: 1920\nPlayResY: 1080\nWrapStyle: 0\nScaledBorderAndShadow: yes\n\n[V4+ Styles]\nFormat: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\nStyle: Default,Microsoft YaHei,64,&H00FFFFFF,&H000000FF,&H00000000,&H64000000,0,0,0,0,100,100,0,0,1,3,1,2,60,60,50,1\nStyle: Secondary,Microsoft YaHei,48,&H00E0E0E0,&H000000FF,&H00000000,&H64000000,0,0,0,0,100,100,0,0,1,2,1,2,60,60,50,1\n\n[Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n";
+
+fn ass_escape(s: &str) -> String {
+    s.replace('{', "｛").replace('}', "｝").replace('\n', "\\N")
+}
+
+fn push_ass_events(out: &mut String, cues: &[(f64, f64, String)], style: &str) {
+    for (from, to, content) in cues {
+        out.push_str(&format!(
+            "Dialogue: 0,{},{},{style},,0,0,0,,{}\n",
+            format_ass_time(*from),
+            format_ass_time(*to),
+            ass_escape(content)
+        ));
+    }
+}
+
+pub fn bcc_json_to_ass(json: &Value) -> Result<String, BiliError> {
+    let mut out = ASS_HEADER.to_string();
+    push_ass_events(&mut out, &bcc_cues(json)?, "Default");
+    Ok(out)
+}
+
+/// 双语 ASS：主字幕使用 `Default`，副字幕使用较小的 `Secondary` 样式（libass 会自动堆叠同时显示的行）。
+pub fn bcc_json_to_bilingual_ass(primary: &Value, secondary: &Value) -> Result<String, BiliError> {
+    let mut out = ASS_HEADER.to_string();
+    push_ass_events(&mut out, &bcc_cues(secondary)?, "Secondary");
+    push_ass_events(&mut out, &bcc_cues(primary)?, "Default");
+    Ok(out)
+}
+
+pub fn render_bcc(json: &Value, format: SubtitleFormat) -> Result<String, BiliError> {
+    match format {
+        SubtitleFormat::Srt => bcc_json_to_srt(json),
+        SubtitleFormat::Ass => bcc_json_to_ass(json),
+        SubtitleFormat::Vtt => bcc_json_to_vtt(json),
+        SubtitleFormat::Json => {
+            serde_json::to_string_pretty(json).map_err(|e| BiliError::Parse(e.to_string()))
+        }
+    }
+}
+
+pub async fn download_subtitle_bcc(
     client: &BiliClient,
     url: &str,
     cookie: Option<&str>,
-) -> Result<String, BiliError> {
+) -> Result<Value, BiliError> {
     let u = url.trim();
     if u.is_empty() {
         return Err(BiliError::InvalidInput("empty subtitle url".to_string()));
@@ -146,5 +388,89 @@ pub async fn download_subtitle_srt(
         .await?
         .json()
         .await?;
+    Ok(json)
+}
+
+pub async fn download_subtitle_srt(
+    client: &BiliClient,
+    url: &str,
+    cookie: Option<&str>,
+) -> Result<String, BiliError> {
+    let json = download_subtitle_bcc(client, url, cookie).await?;
     bcc_json_to_srt(&json)
 }
+
+/// 按选项下载字幕，写到 `out_path` 同名的 `.{lang}.{ext}`（双语为 `.{a}+{b}.ass`）。
+///
+/// 返回可用于 mux 的字幕路径；单条字幕失败会被忽略，取消时返回已写出的部分。
+pub async fn save_subtitles(
+    client: &BiliClient,
+    video: SubtitleVideo<'_>,
+    cookie: Option<&str>,
+    opt: &SubtitleOptions,
+    out_path: &Path,
+    cancel: Option<&Arc<AtomicBool>>,
+) -> Result<Vec<PathBuf>, BiliError> {
+    let SubtitleVideo { aid, bvid, cid } = video;
+    let cancelled = || cancel.is_some_and(|c| c.load(Ordering::Relaxed));
+    let tracks = if opt.include_ai {
+        fetch_subtitles_with_ai(client, aid, bvid, cid, cookie).await?
+    } else {
+        fetch_subtitles(client, bvid, cid, cookie).await?
+    };
+
+    let mut cache: Vec<(String, Value)> = Vec::new();
+    let mut out: Vec<PathBuf> = Vec::new();
+    for t in select_subtitle_tracks(&tracks, opt) {
+        if cancelled() {
+            return Ok(out);
+        }
+        let Ok(json) = download_subtitle_bcc(client, &t.url, cookie).await else {
+            continue;
+        };
+        let Ok(text) = render_bcc(&json, opt.format) else {
+            continue;
+        };
+        let lang = sanitize_component(&t.lang);
+        let path = out_path.with_extension(format!("{lang}.{}", opt.format.ext()));
+        if tokio::fs::write(&path, text).await.is_ok() && opt.format.can_mux() {
+            out.push(path);
+        }
+        cache.push((t.lang.clone(), json));
+    }
+
+    if let Some((a, b)) = opt.bilingual.as_ref()
+        && !cancelled()
+    {
+        // 双语轨道可能被语言过滤排除，因此在全量轨道中查找（显式指定的 AI 字幕也允许）。
+        let find = |want: &str| tracks.iter().find(|t| lang_matches(&t.lang, want)).cloned();
+        if let (Some(ta), Some(tb)) = (find(a), find(b)) {
+            let mut load = Vec::new();
+            for t in [&ta, &tb] {
+                let cached = cache
+                    .iter()
+                    .find(|(l, _)| *l == t.lang)
+                    .map(|(_, v)| v.clone());
+                let json = match cached {
+                    Some(v) => Some(v),
+                    None => download_subtitle_bcc(client, &t.url, cookie).await.ok(),
+                };
+                load.push(json);
+            }
+            if let [Some(ja), Some(jb)] = load.as_slice()
+                && let Ok(text) = bcc_json_to_bilingual_ass(ja, jb)
+            {
+                let name = format!(
+                    "{}+{}.ass",
+                    sanitize_component(&ta.lang),
+                    sanitize_component(&tb.lang)
+                );
+                let path = out_path.with_extension(name);
+                if tokio::fs::write(&path, text).await.is_ok() {
+                    out.push(path);
+                }
+            }
+        }
+    }
+    Ok(out)
+}
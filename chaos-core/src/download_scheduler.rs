@@ -0,0 +1,460 @@
+//! 全局下载调度器：music 与 bili 下载共用。
+//!
+//! - 全局并发连接上限（运行时可调）
+//! - 按字节/秒的令牌桶限速（运行时可调）
+//! - 任务优先级：优先级高者先拿到连接；同优先级下，当前占用连接更少的任务优先，
+//!   避免整季番剧这类大任务把单曲下载饿死
+//! - 全局/单任务的暂停与恢复（暂停时不再分配新连接，已建立的连接在下一块数据前挂起）
+
+use std::collections::HashMap;
+use std::sync::{
+    Arc, Mutex, OnceLock,
+    atomic::{AtomicBool, Ordering},
+};
+use std::time::{Duration, Instant};
+
+use tokio::sync::Notify;
+
+/// 等待期间轮询取消标记的间隔。
+const CANCEL_POLL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchedulerConfig {
+    /// 全局并发连接数上限（1..=64）。
+    pub max_connections: u32,
+    /// 全局限速（字节/秒）；`None` 为不限速。
+    pub rate_limit_bps: Option<u64>,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 16,
+            rate_limit_bps: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchedulerStats {
+    pub max_connections: u32,
+    pub rate_limit_bps: Option<u64>,
+    pub paused: bool,
+    /// 当前占用的连接数。
+    pub active: u32,
+    /// 正在排队等待连接的请求数。
+    pub queued: u32,
+    /// 已注册（未释放）的任务数。
+    pub tasks: u32,
+}
+
+#[derive(Debug)]
+struct TaskState {
+    priority: i32,
+    active: u32,
+    paused: bool,
+}
+
+#[derive(Debug)]
+struct Waiter {
+    seq: u64,
+    task_id: u64,
+}
+
+#[derive(Debug)]
+struct State {
+    max_connections: u32,
+    rate_limit_bps: Option<u64>,
+    paused: bool,
+    active: u32,
+    next_seq: u64,
+    next_task_id: u64,
+    waiters: Vec<Waiter>,
+    tasks: HashMap<u64, TaskState>,
+}
+
+impl State {
+    fn task_runnable(&self, task_id: u64) -> bool {
+        !self.paused && self.tasks.get(&task_id).map(|t| !t.paused).unwrap_or(true)
+    }
+
+    /// 当前应获得下一个连接的等待者。
+    fn best_waiter(&self) -> Option<u64> {
+        self.waiters
+            .iter()
+            .filter(|w| self.task_runnable(w.task_id))
+            .min_by_key(|w| {
+                let t = self.tasks.get(&w.task_id);
+                (
+                    std::cmp::Reverse(t.map(|t| t.priority).unwrap_or(0)),
+                    t.map(|t| t.active).unwrap_or(0),
+                    w.seq,
+                )
+            })
+            .map(|w| w.seq)
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last: Instant,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: Mutex<State>,
+    notify: Notify,
+    bucket: Mutex<Bucket>,
+}
+
+/// 下载调度器句柄（可廉价 clone，clone 之间共享状态）。
+#[derive(Debug, Clone)]
+pub struct DownloadScheduler {
+    inner: Arc<Inner>,
+}
+
+fn normalize_max_connections(n: u32) -> u32 {
+    n.clamp(1, 64)
+}
+
+fn normalize_rate_limit(bps: Option<u64>) -> Option<u64> {
+    bps.filter(|v| *v > 0)
+}
+
+fn is_canceled(cancel: Option<&Arc<AtomicBool>>) -> bool {
+    cancel.map(|c| c.load(Ordering::Relaxed)).unwrap_or(false)
+}
+
+impl Default for DownloadScheduler {
+    fn default() -> Self {
+        Self::new(SchedulerConfig::default())
+    }
+}
+
+impl DownloadScheduler {
+    pub fn new(cfg: SchedulerConfig) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State {
+                    max_connections: normalize_max_connections(cfg.max_connections),
+                    rate_limit_bps: normalize_rate_limit(cfg.rate_limit_bps),
+                    paused: false,
+                    active: 0,
+                    next_seq: 0,
+                    next_task_id: 0,
+                    waiters: Vec::new(),
+                    tasks: HashMap::new(),
+                }),
+                notify: Notify::new(),
+                bucket: Mutex::new(Bucket {
+                    tokens: 0.0,
+                    last: Instant::now(),
+                }),
+            }),
+        }
+    }
+
+    /// 进程级共享调度器（daemon / FFI 的 music 与 bili 下载均使用它）。
+    pub fn global() -> &'static DownloadScheduler {
+        static GLOBAL: OnceLock<DownloadScheduler> = OnceLock::new();
+        GLOBAL.get_or_init(DownloadScheduler::default)
+    }
+
+    fn with_state<R>(&self, f: impl FnOnce(&mut State) -> R) -> R {
+        let mut st = self.inner.state.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut st)
+    }
+
+    fn wake_all(&self) {
+        self.inner.notify.notify_waiters();
+    }
+
+    pub fn set_max_connections(&self, n: u32) {
+        self.with_state(|st| st.max_connections = normalize_max_connections(n));
+        self.wake_all();
+    }
+
+    /// 设置全局限速；`None` 或 `Some(0)` 表示不限速。
+    pub fn set_rate_limit(&self, bps: Option<u64>) {
+        self.with_state(|st| st.rate_limit_bps = normalize_rate_limit(bps));
+        self.wake_all();
+    }
+
+    pub fn pause(&self) {
+        self.with_state(|st| st.paused = true);
+    }
+
+    pub fn resume(&self) {
+        self.with_state(|st| st.paused = false);
+        self.wake_all();
+    }
+
+    pub fn stats(&self) -> SchedulerStats {
+        self.with_state(|st| SchedulerStats {
+            max_connections: st.max_connections,
+            rate_limit_bps: st.rate_limit_bps,
+            paused: st.paused,
+            active: st.active,
+            queued: st.waiters.len() as u32,
+            tasks: st.tasks.len() as u32,
+        })
+    }
+
+    /// 注册一个下载任务（一次 music 下载会话 / 一个 bili 任务）。任务的所有 clone 释放后自动注销。
+    pub fn task(&self, priority: i32) -> DownloadTask {
+        let id = self.with_state(|st| {
+            let id = st.next_task_id;
+            st.next_task_id += 1;
+            st.tasks.insert(
+                id,
+                TaskState {
+                    priority,
+                    active: 0,
+                    paused: false,
+                },
+            );
+            id
+        });
+        DownloadTask {
+            inner: Arc::new(TaskInner {
+                sched: self.clone(),
+                id,
+            }),
+        }
+    }
+
+    async fn wait_runnable(&self, task_id: u64, cancel: Option<&Arc<AtomicBool>>) -> bool {
+        loop {
+            let notified = self.inner.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if is_canceled(cancel) {
+                return false;
+            }
+            if self.with_state(|st| st.task_runnable(task_id)) {
+                return true;
+            }
+            tokio::select! {
+                _ = &mut notified => {}
+                _ = tokio::time::sleep(CANCEL_POLL) => {}
+            }
+        }
+    }
+
+    /// 按令牌桶等待 `bytes` 字节的额度；等待中被取消或暂停时退还未用完的令牌并返回 `false`。
+    async fn consume_tokens(
+        &self,
+        task_id: u64,
+        bytes: u64,
+        cancel: Option<&Arc<AtomicBool>>,
+    ) -> bool {
+        let Some(bps) = self.with_state(|st| st.rate_limit_bps) else {
+            return true;
+        };
+        let rate = bps as f64;
+        // 先预支令牌再在锁外等待：欠账累积在桶里，后到的请求等得更久，因此仍按到达顺序放行。
+        let wait = {
+            let mut b = self.inner.bucket.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            b.tokens = (b.tokens + now.duration_since(b.last).as_secs_f64() * rate).min(rate);
+            b.last = now;
+            b.tokens -= bytes as f64;
+            (b.tokens < 0.0).then(|| Duration::from_secs_f64(-b.tokens / rate))
+        };
+        let Some(wait) = wait else {
+            return true;
+        };
+        let deadline = Instant::now() + wait;
+        loop {
+            let notified = self.inner.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            let now = Instant::now();
+            if now >= deadline || self.with_state(|st| st.rate_limit_bps.is_none()) {
+                return true;
+            }
+            if is_canceled(cancel) || !self.with_state(|st| st.task_runnable(task_id)) {
+                // 还没等到的那部分额度还给桶，不让其它任务替它还账。
+                let unused = ((deadline - now).as_secs_f64() * rate).min(bytes as f64);
+                let mut b = self.inner.bucket.lock().unwrap_or_else(|e| e.into_inner());
+                b.tokens = (b.tokens + unused).min(rate);
+                return false;
+            }
+            tokio::select! {
+                _ = &mut notified => {}
+                _ = tokio::time::sleep_until((now + CANCEL_POLL).min(deadline).into()) => {}
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TaskInner {
+    sched: DownloadScheduler,
+    id: u64,
+}
+
+impl Drop for TaskInner {
+    fn drop(&mut self) {
+        self.sched.with_state(|st| {
+            st.tasks.remove(&self.id);
+        });
+        self.sched.wake_all();
+    }
+}
+
+/// 调度器中的一个任务：用于申请连接、限速与暂停控制。
+#[derive(Debug, Clone)]
+pub struct DownloadTask {
+    inner: Arc<TaskInner>,
+}
+
+impl PartialEq for DownloadTask {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for DownloadTask {}
+
+impl DownloadTask {
+    pub fn scheduler(&self) -> &DownloadScheduler {
+        &self.inner.sched
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.inner.sched.with_state(|st| {
+            st.tasks
+                .get(&self.inner.id)
+                .map(|t| t.priority)
+                .unwrap_or(0)
+        })
+    }
+
+    pub fn set_priority(&self, priority: i32) {
+        self.inner.sched.with_state(|st| {
+            if let Some(t) = st.tasks.get_mut(&self.inner.id) {
+                t.priority = priority;
+            }
+        });
+        self.inner.sched.wake_all();
+    }
+
+    pub fn pause(&self) {
+        self.inner.sched.with_state(|st| {
+            if let Some(t) = st.tasks.get_mut(&self.inner.id) {
+                t.paused = true;
+            }
+        });
+    }
+
+    pub fn resume(&self) {
+        self.inner.sched.with_state(|st| {
+            if let Some(t) = st.tasks.get_mut(&self.inner.id) {
+                t.paused = false;
+            }
+        });
+        self.inner.sched.wake_all();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.inner
+            .sched
+            .with_state(|st| !st.task_runnable(self.inner.id))
+    }
+
+    /// 申请一个连接名额；被取消时返回 `None`。
+    ///
+    /// 返回的 permit 在 drop 时归还名额。
+    pub async fn acquire(&self, cancel: Option<&Arc<AtomicBool>>) -> Option<ConnectionPermit> {
+        let sched = &self.inner.sched;
+        let task_id = self.inner.id;
+        let seq = sched.with_state(|st| {
+            let seq = st.next_seq;
+            st.next_seq += 1;
+            st.waiters.push(Waiter { seq, task_id });
+            seq
+        });
+        // 等待中途被 drop（或取消）时移出队列，并唤醒其它等待者重新竞争。
+        let guard = WaiterGuard { sched, seq };
+
+        loop {
+            let notified = sched.inner.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if is_canceled(cancel) {
+                return None;
+            }
+            let granted = sched.with_state(|st| {
+                if st.active >= st.max_connections || st.best_waiter() != Some(seq) {
+                    return false;
+                }
+                st.waiters.retain(|w| w.seq != seq);
+                st.active += 1;
+                if let Some(t) = st.tasks.get_mut(&task_id) {
+                    t.active += 1;
+                }
+                true
+            });
+            if granted {
+                std::mem::forget(guard);
+                // 其它等待者可能也能拿到名额（例如刚调大了上限）。
+                sched.wake_all();
+                return Some(ConnectionPermit { task: self.clone() });
+            }
+            tokio::select! {
+                _ = &mut notified => {}
+                _ = tokio::time::sleep(CANCEL_POLL) => {}
+            }
+        }
+    }
+
+    /// 在写入 `bytes` 字节前调用：暂停时挂起，限速时按令牌桶等待；被取消时返回 `false`。
+    ///
+    /// 限速等待中被暂停时退还额度，恢复后重新排队。
+    pub async fn throttle(&self, bytes: u64, cancel: Option<&Arc<AtomicBool>>) -> bool {
+        let sched = &self.inner.sched;
+        loop {
+            if !sched.wait_runnable(self.inner.id, cancel).await {
+                return false;
+            }
+            if sched.consume_tokens(self.inner.id, bytes, cancel).await {
+                return !is_canceled(cancel);
+            }
+        }
+    }
+}
+
+struct WaiterGuard<'a> {
+    sched: &'a DownloadScheduler,
+    seq: u64,
+}
+
+impl Drop for WaiterGuard<'_> {
+    fn drop(&mut self) {
+        let seq = self.seq;
+        self.sched
+            .with_state(|st| st.waiters.retain(|w| w.seq != seq));
+        self.sched.wake_all();
+    }
+}
+
+/// 一个已分配的连接名额。
+#[derive(Debug)]
+pub struct ConnectionPermit {
+    task: DownloadTask,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        let id = self.task.inner.id;
+        self.task.inner.sched.with_state(|st| {
+            st.active = st.active.saturating_sub(1);
+            if let Some(t) = st.tasks.get_mut(&id) {
+                t.active = t.active.saturating_sub(1);
+            }
+        });
+        self.task.inner.sched.wake_all();
+    }
+}
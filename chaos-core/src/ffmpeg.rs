@@ -0,0 +1,82 @@
+//! ffmpeg 子进程运行（bili mux 与音乐后处理共用）。
+
+use std::process::Stdio;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use thiserror::Error;
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+
+#[derive(Debug, Error)]
+pub enum FfmpegError {
+    #[error("ffmpeg: {0}")]
+    Spawn(std::io::Error),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("canceled")]
+    Canceled,
+    /// 非零退出；`last_line` 为 stderr 最后一行非空输出。
+    #[error("ffmpeg exit code: {status}: {last_line}")]
+    Exit {
+        status: std::process::ExitStatus,
+        last_line: String,
+    },
+}
+
+/// 运行 ffmpeg 并返回 stderr 日志；`cancel` 置位时杀掉进程。
+pub async fn run(
+    mut cmd: Command,
+    cancel: Option<&Arc<AtomicBool>>,
+) -> Result<String, FfmpegError> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    cmd.kill_on_drop(true);
+    let mut child = cmd.spawn().map_err(FfmpegError::Spawn)?;
+
+    // 边运行边读 stderr，避免管道写满阻塞 ffmpeg。
+    let stderr = child.stderr.take();
+    let reader = tokio::spawn(async move {
+        let mut buf = Vec::new();
+        if let Some(mut s) = stderr {
+            let _ = s.read_to_end(&mut buf).await;
+        }
+        String::from_utf8_lossy(&buf).into_owned()
+    });
+
+    let st = if let Some(c) = cancel {
+        tokio::select! {
+            r = child.wait() => r?,
+            _ = async {
+                while !c.load(Ordering::Relaxed) {
+                    tokio::time::sleep(std::time::Duration::from_millis(120)).await;
+                }
+            } => {
+                let _ = child.kill().await;
+                return Err(FfmpegError::Canceled);
+            }
+        }
+    } else {
+        child.wait().await?
+    };
+
+    let log = reader.await.unwrap_or_default();
+    if st.success() {
+        Ok(log)
+    } else {
+        let last_line = log
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("")
+            .trim()
+            .to_string();
+        Err(FfmpegError::Exit {
+            status: st,
+            last_line,
+        })
+    }
+}
@@ -1,5 +1,7 @@
 pub mod bili_video;
 pub mod danmaku;
+pub mod download_scheduler;
+pub mod ffmpeg;
 pub mod live_directory;
 pub mod livestream;
 pub mod llm;
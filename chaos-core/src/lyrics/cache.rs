@@ -0,0 +1,524 @@
+//! 歌词持久化缓存：按归一化的 标题 / 歌手 / 时长（秒）缓存搜索结果，
+//! 并保存用户固定的歌词（"这首歌总是用这个网易云结果"）与手动时间偏移。
+//!
+//! - 搜索结果按歌词源分别记录、分别过期：只请求了 QQ 的条目不会让只要网易云的搜索误命中
+//! - 搜索结果有 TTL；用户固定与偏移不过期，也不会因容量上限被淘汰
+//! - 超过条目上限时按最近使用时间淘汰
+//! - 整个缓存是一个 JSON 文件，每次修改后先写临时文件再改名；
+//!   [`LyricsCache::store`] 在 tokio 运行时内把写盘放到阻塞线程池
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::lyrics::core::sort_results;
+use crate::lyrics::error::LyricsError;
+use crate::lyrics::match_score::fingerprint;
+use crate::lyrics::model::{
+    LyricsSearchOptions, LyricsSearchRequest, LyricsSearchResult, LyricsSearchTerm, LyricsService,
+};
+
+pub const DEFAULT_TTL: Duration = Duration::from_secs(30 * 24 * 3600);
+pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+/// 查找时允许的时长误差（秒）。
+const DURATION_TOLERANCE_SECS: u64 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LyricsCacheEntry {
+    pub title: String,
+    pub artist: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// 各歌词源最近一次搜索的结果（只含在线结果）。
+    #[serde(default, deserialize_with = "de_known")]
+    pub results: Vec<LyricsSearchResult>,
+    /// `results` 覆盖的歌词源；不在其中（或已过期）的源需要重新查询。
+    #[serde(default, deserialize_with = "de_known")]
+    pub providers: Vec<LyricsCachedProvider>,
+    /// 用户固定的歌词，总是排在第一位。
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "de_known_opt"
+    )]
+    pub pinned: Option<LyricsSearchResult>,
+    /// 用户手动调整的时间偏移。
+    #[serde(default)]
+    pub offset_ms: i64,
+    #[serde(default)]
+    pub last_used_unix_ms: u64,
+}
+
+impl LyricsCacheEntry {
+    fn is_user_data(&self) -> bool {
+        self.pinned.is_some() || self.offset_ms != 0
+    }
+
+    fn result_count(&self, service: LyricsService) -> usize {
+        self.results.iter().filter(|r| r.service == service).count()
+    }
+}
+
+/// 条目中某个歌词源的缓存记录。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LyricsCachedProvider {
+    pub service: LyricsService,
+    pub fetched_at_unix_ms: u64,
+    /// 产生结果时的 `limit`，请求更多时需要重新搜索。
+    pub limit: usize,
+    /// 结果来自精确查找；只有这样的记录能满足 `exact_only` 搜索。
+    #[serde(default)]
+    pub exact: bool,
+}
+
+/// [`LyricsCache::lookup`] 的命中结果。
+#[derive(Debug, Clone, Default)]
+pub struct LyricsCacheHit {
+    /// 缓存中的在线结果：固定的歌词排第一，其余按匹配度排序，并带上用户偏移。
+    pub results: Vec<LyricsSearchResult>,
+    /// 请求的在线歌词源中没有可用缓存（未缓存、已过期或不满足 `exact_only`）的，需要重新查询。
+    pub missing: Vec<LyricsService>,
+}
+
+/// 跳过无法解析的元素（通常是本进程没有注册的自定义歌词源），不让整个缓存文件作废。
+fn de_known<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let items = Vec::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(items
+        .into_iter()
+        .filter_map(|v| serde_json::from_value(v).ok())
+        .collect())
+}
+
+fn de_known_opt<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let v = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(v.and_then(|v| serde_json::from_value(v).ok()))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    #[serde(default)]
+    entries: HashMap<String, LyricsCacheEntry>,
+}
+
+#[derive(Debug)]
+pub struct LyricsCache {
+    path: Option<PathBuf>,
+    ttl: Duration,
+    max_entries: usize,
+    state: Mutex<CacheFile>,
+    /// 每次落盘快照的序号；后台写盘时跳过比已写入更旧的快照。
+    generation: AtomicU64,
+    written: Arc<Mutex<u64>>,
+}
+
+fn now_unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// `(标题, 歌手, 时长秒)`；关键词搜索时关键词作为标题。标题为空时不缓存。
+fn key_parts(req: &LyricsSearchRequest) -> Option<(String, String, Option<u64>)> {
+    let (title, artist) = match &req.term {
+        LyricsSearchTerm::Info { title, artist, .. } => (fingerprint(title), fingerprint(artist)),
+        LyricsSearchTerm::Keyword { .. } | LyricsSearchTerm::File { .. } => {
+            (fingerprint(&req.term.description()), String::new())
+        }
+    };
+    if title.is_empty() {
+        return None;
+    }
+    let secs = req.duration_ms.map(|ms| (ms + 500) / 1000);
+    Some((title, artist, secs))
+}
+
+fn key_string(title: &str, artist: &str, secs: Option<u64>) -> String {
+    match secs {
+        Some(s) => format!("{title}\u{1f}{artist}\u{1f}{s}"),
+        None => format!("{title}\u{1f}{artist}\u{1f}"),
+    }
+}
+
+impl LyricsCache {
+    /// 打开（或新建）缓存文件；文件损坏时从空缓存开始。
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let state = std::fs::read(&path)
+            .ok()
+            .and_then(|b| serde_json::from_slice::<CacheFile>(&b).ok())
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            ttl: DEFAULT_TTL,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            state: Mutex::new(state),
+            generation: AtomicU64::new(0),
+            written: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// 不落盘的缓存（找不到缓存目录时使用）。
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            ttl: DEFAULT_TTL,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            state: Mutex::new(CacheFile::default()),
+            generation: AtomicU64::new(0),
+            written: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// `<cache_dir>/ChaosSeed/lyrics_cache.json`
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|d| d.join("ChaosSeed").join("lyrics_cache.json"))
+    }
+
+    /// 进程级共享缓存（`search` / `search_with_http` / `search_stream` 使用它）。
+    pub fn global() -> &'static LyricsCache {
+        static GLOBAL: OnceLock<LyricsCache> = OnceLock::new();
+        GLOBAL.get_or_init(|| match Self::default_path() {
+            Some(p) => Self::open(p),
+            None => Self::in_memory(),
+        })
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.with_state(|st| st.entries.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn with_state<R>(&self, f: impl FnOnce(&mut CacheFile) -> R) -> R {
+        let mut st = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut st)
+    }
+
+    /// 查找条目的 key：优先精确时长，其次 ±[`DURATION_TOLERANCE_SECS`] 内最近的。
+    fn find_key(st: &CacheFile, req: &LyricsSearchRequest) -> Option<String> {
+        let (title, artist, secs) = key_parts(req)?;
+        let Some(secs) = secs else {
+            let k = key_string(&title, &artist, None);
+            return st.entries.contains_key(&k).then_some(k);
+        };
+        (0..=DURATION_TOLERANCE_SECS)
+            .flat_map(|d| [secs + d, secs.saturating_sub(d)])
+            .map(|s| key_string(&title, &artist, Some(s)))
+            .find(|k| st.entries.contains_key(k))
+    }
+
+    fn entry_mut<'a>(
+        st: &'a mut CacheFile,
+        req: &LyricsSearchRequest,
+    ) -> Option<&'a mut LyricsCacheEntry> {
+        let key = match Self::find_key(st, req) {
+            Some(k) => k,
+            None => {
+                let (title, artist, secs) = key_parts(req)?;
+                key_string(&title, &artist, secs)
+            }
+        };
+        let (title, artist) = match &req.term {
+            LyricsSearchTerm::Info { title, artist, .. } => (title.clone(), artist.clone()),
+            LyricsSearchTerm::Keyword { .. } | LyricsSearchTerm::File { .. } => {
+                (req.term.description(), String::new())
+            }
+        };
+        Some(st.entries.entry(key).or_insert_with(|| LyricsCacheEntry {
+            title,
+            artist,
+            duration_ms: req.duration_ms,
+            results: Vec::new(),
+            providers: Vec::new(),
+            pinned: None,
+            offset_ms: 0,
+            last_used_unix_ms: now_unix_ms(),
+        }))
+    }
+
+    pub fn entry(&self, req: &LyricsSearchRequest) -> Option<LyricsCacheEntry> {
+        self.with_state(|st| {
+            let k = Self::find_key(st, req)?;
+            st.entries.get(&k).cloned()
+        })
+    }
+
+    /// 按 `opt` 查找：`opt.services` 中的在线歌词源逐个判断缓存是否可用（未过期、
+    /// 缓存的结果数满足 `req.limit`、`exact_only` 时结果来自精确查找），不可用的列入
+    /// [`LyricsCacheHit::missing`]。没有任何可用结果、也没有固定的歌词时返回 `None`。
+    pub fn lookup(
+        &self,
+        req: &LyricsSearchRequest,
+        opt: &LyricsSearchOptions,
+    ) -> Option<LyricsCacheHit> {
+        let now = now_unix_ms();
+        let ttl_ms = self.ttl.as_millis() as u64;
+        self.with_state(|st| {
+            let k = Self::find_key(st, req)?;
+            let e = st.entries.get_mut(&k)?;
+            let limit = req.limit.max(1);
+            let (fresh, missing): (Vec<LyricsService>, Vec<LyricsService>) = opt
+                .services
+                .iter()
+                .copied()
+                .filter(|s| *s != LyricsService::Local)
+                .partition(|s| {
+                    e.providers.iter().any(|p| {
+                        p.service == *s
+                            && now.saturating_sub(p.fetched_at_unix_ms) < ttl_ms
+                            && (p.limit >= limit || e.result_count(p.service) < p.limit)
+                            && (p.exact || !opt.exact_only)
+                    })
+                });
+            if fresh.is_empty() && e.pinned.is_none() {
+                return None;
+            }
+            e.last_used_unix_ms = now;
+
+            let cached = e
+                .results
+                .iter()
+                .filter(|r| fresh.contains(&r.service))
+                .filter(|r| !opt.strict_match || r.matched)
+                .filter(|r| {
+                    !e.pinned.as_ref().is_some_and(|p| {
+                        p.service == r.service && p.service_token == r.service_token
+                    })
+                })
+                .cloned()
+                .collect();
+            let mut results: Vec<LyricsSearchResult> = e.pinned.iter().cloned().collect();
+            results.extend(sort_results(cached, &opt.services));
+            results.truncate(limit);
+            for r in &mut results {
+                r.offset_ms = e.offset_ms;
+            }
+            Some(LyricsCacheHit { results, missing })
+        })
+    }
+
+    /// 保存一次搜索的在线结果：`results` 中出现的每个歌词源替换该源原有的缓存，
+    /// `exact` 为其中结果来自精确查找的源。没有结果的源不缓存（下次重新查询）。
+    ///
+    /// 尽力落盘，写失败只影响下次启动；在 tokio 运行时内不阻塞当前任务。
+    pub fn store(
+        &self,
+        req: &LyricsSearchRequest,
+        results: &[LyricsSearchResult],
+        exact: &[LyricsService],
+    ) {
+        let mut services: Vec<LyricsService> = Vec::new();
+        for r in results {
+            if r.service != LyricsService::Local && !services.contains(&r.service) {
+                services.push(r.service);
+            }
+        }
+        if services.is_empty() {
+            return;
+        }
+        let changed = self.with_state(|st| {
+            let Some(e) = Self::entry_mut(st, req) else {
+                return false;
+            };
+            let now = now_unix_ms();
+            e.results.retain(|r| !services.contains(&r.service));
+            e.results.extend(
+                results
+                    .iter()
+                    .filter(|r| services.contains(&r.service))
+                    .cloned()
+                    .map(|mut r| {
+                        r.offset_ms = 0;
+                        r
+                    }),
+            );
+            e.providers.retain(|p| !services.contains(&p.service));
+            e.providers
+                .extend(services.iter().map(|s| LyricsCachedProvider {
+                    service: *s,
+                    fetched_at_unix_ms: now,
+                    limit: req.limit.max(1),
+                    exact: exact.contains(s),
+                }));
+            e.last_used_unix_ms = now;
+            true
+        });
+        if changed {
+            self.save_in_background();
+        }
+    }
+
+    /// 用户固定这首歌的歌词：之后的搜索总是把它放在第一位（即使其它结果已过期）。
+    pub fn pin(
+        &self,
+        req: &LyricsSearchRequest,
+        mut result: LyricsSearchResult,
+    ) -> Result<(), LyricsError> {
+        result.offset_ms = 0;
+        self.update(req, |e| e.pinned = Some(result))
+    }
+
+    pub fn unpin(&self, req: &LyricsSearchRequest) -> Result<(), LyricsError> {
+        self.update(req, |e| e.pinned = None)
+    }
+
+    /// 设置这首歌的手动时间偏移（毫秒，正数提前）；`0` 表示清除。
+    pub fn set_offset(&self, req: &LyricsSearchRequest, offset_ms: i64) -> Result<(), LyricsError> {
+        self.update(req, |e| e.offset_ms = offset_ms)
+    }
+
+    pub fn offset_ms(&self, req: &LyricsSearchRequest) -> i64 {
+        self.entry(req).map_or(0, |e| e.offset_ms)
+    }
+
+    pub fn remove(&self, req: &LyricsSearchRequest) -> Result<(), LyricsError> {
+        self.with_state(|st| {
+            if let Some(k) = Self::find_key(st, req) {
+                st.entries.remove(&k);
+            }
+        });
+        self.save()
+    }
+
+    pub fn clear(&self) -> Result<(), LyricsError> {
+        self.with_state(|st| st.entries.clear());
+        self.save()
+    }
+
+    fn update(
+        &self,
+        req: &LyricsSearchRequest,
+        f: impl FnOnce(&mut LyricsCacheEntry),
+    ) -> Result<(), LyricsError> {
+        self.with_state(|st| {
+            let e = Self::entry_mut(st, req).ok_or_else(|| {
+                LyricsError::Parse("lyrics cache: empty title/keyword".to_string())
+            })?;
+            f(e);
+            e.last_used_unix_ms = now_unix_ms();
+            Ok::<_, LyricsError>(())
+        })?;
+        self.save()
+    }
+
+    /// 清理过期结果并按容量淘汰，然后写盘。
+    fn save(&self) -> Result<(), LyricsError> {
+        match self.snapshot()? {
+            Some((path, generation, bytes)) => {
+                write_snapshot(&path, generation, &bytes, &self.written)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// 同 [`Self::save`]，但在 tokio 运行时内用 `spawn_blocking` 写盘。
+    fn save_in_background(&self) {
+        let Ok(Some((path, generation, bytes))) = self.snapshot() else {
+            return;
+        };
+        let written = Arc::clone(&self.written);
+        match tokio::runtime::Handle::try_current() {
+            Ok(rt) => {
+                rt.spawn_blocking(move || {
+                    let _ = write_snapshot(&path, generation, &bytes, &written);
+                });
+            }
+            Err(_) => {
+                let _ = write_snapshot(&path, generation, &bytes, &written);
+            }
+        }
+    }
+
+    /// 清理过期结果并按容量淘汰；有缓存文件时返回 `(路径, 序号, 序列化内容)`。
+    fn snapshot(&self) -> Result<Option<(PathBuf, u64, Vec<u8>)>, LyricsError> {
+        let now = now_unix_ms();
+        let ttl_ms = self.ttl.as_millis() as u64;
+        let bytes = self.with_state(|st| {
+            for e in st.entries.values_mut() {
+                e.providers
+                    .retain(|p| now.saturating_sub(p.fetched_at_unix_ms) < ttl_ms);
+                let live = &e.providers;
+                e.results
+                    .retain(|r| live.iter().any(|p| p.service == r.service));
+            }
+            st.entries
+                .retain(|_, e| !e.results.is_empty() || e.is_user_data());
+
+            if st.entries.len() > self.max_entries {
+                let mut evictable = st
+                    .entries
+                    .iter()
+                    .filter(|(_, e)| !e.is_user_data())
+                    .map(|(k, e)| (e.last_used_unix_ms, k.clone()))
+                    .collect::<Vec<_>>();
+                evictable.sort();
+                let excess = st.entries.len() - self.max_entries;
+                for (_, k) in evictable.into_iter().take(excess) {
+                    st.entries.remove(&k);
+                }
+            }
+            if self.path.is_some() {
+                // 在锁内取序号，保证序号与快照内容的先后一致。
+                let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+                serde_json::to_vec(&*st).map(|b| Some((generation, b)))
+            } else {
+                Ok(None)
+            }
+        })?;
+        Ok(self
+            .path
+            .clone()
+            .zip(bytes)
+            .map(|(path, (generation, bytes))| (path, generation, bytes)))
+    }
+}
+
+/// 写入一份快照；已有更新的快照写入时跳过。
+fn write_snapshot(
+    path: &Path,
+    generation: u64,
+    bytes: &[u8],
+    written: &Mutex<u64>,
+) -> Result<(), LyricsError> {
+    let mut last = written.lock().unwrap_or_else(|e| e.into_inner());
+    if *last >= generation {
+        return Ok(());
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path)?;
+    *last = generation;
+    Ok(())
+}
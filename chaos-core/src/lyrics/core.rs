@@ -1,14 +1,20 @@
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
 use futures_util::stream::{FuturesUnordered, StreamExt};
 
+use crate::lyrics::cache::{LyricsCache, LyricsCacheHit};
 use crate::lyrics::error::LyricsError;
 use crate::lyrics::match_score;
 use crate::lyrics::model::{
-    LyricsSearchOptions, LyricsSearchRequest, LyricsSearchResult, LyricsService,
+    LyricsProviderReport, LyricsProviderStatus, LyricsSearchOptions, LyricsSearchReport,
+    LyricsSearchRequest, LyricsSearchResult, LyricsSearchTerm, LyricsService,
 };
-use crate::lyrics::providers::Provider;
+use crate::lyrics::providers::{LyricsProvider, resolve_file_request};
 use crate::lyrics::quality;
+use crate::lyrics::registry::LyricsProviderRegistry;
 
 pub async fn search(
     req: &LyricsSearchRequest,
@@ -21,46 +27,361 @@ pub async fn search(
     search_with_http(&http, req, opt).await
 }
 
+/// 先查进程级歌词缓存（[`LyricsCache::global`]），只请求没有可用缓存的歌词源；
+/// `opt.bypass_cache` 时全部重新请求。
 pub async fn search_with_http(
     http: &reqwest::Client,
     req: &LyricsSearchRequest,
     opt: LyricsSearchOptions,
 ) -> Result<Vec<LyricsSearchResult>, LyricsError> {
-    let timeout = Duration::from_millis(opt.timeout_ms.max(1));
+    search_with_cache(http, req, opt, Some(LyricsCache::global())).await
+}
 
-    let providers = build_providers(&opt.services);
-    let mut provider_tasks = FuturesUnordered::new();
+pub async fn search_with_cache(
+    http: &reqwest::Client,
+    req: &LyricsSearchRequest,
+    opt: LyricsSearchOptions,
+    cache: Option<&LyricsCache>,
+) -> Result<Vec<LyricsSearchResult>, LyricsError> {
+    search_with_registry(http, req, opt, LyricsProviderRegistry::global(), cache).await
+}
 
-    for p in providers {
-        let http = http.clone();
-        let req = req.clone();
-        let timeout = timeout;
-        provider_tasks.push(async move { search_one_provider(&http, p, &req, timeout).await });
+/// 指定 provider 注册表与缓存的搜索；`opt.services` 中未注册的服务被跳过。
+///
+/// `File` 搜索词先按文件标签/文件名解析成普通请求（保留 `file_path` 供本地歌词使用）。
+/// 本地歌词总排在最前，且不写入缓存（文件可能随时被修改）。
+pub async fn search_with_registry(
+    http: &reqwest::Client,
+    req: &LyricsSearchRequest,
+    opt: LyricsSearchOptions,
+    registry: &LyricsProviderRegistry,
+    cache: Option<&LyricsCache>,
+) -> Result<Vec<LyricsSearchResult>, LyricsError> {
+    search_with_registry_report(http, req, opt, registry, cache)
+        .await
+        .map(|(results, _)| results)
+}
+
+/// 同 [`search`]，额外返回每个歌词源的状态、耗时与候选数（排查"为什么没有歌词"）。
+pub async fn search_with_report(
+    req: &LyricsSearchRequest,
+    opt: LyricsSearchOptions,
+) -> Result<(Vec<LyricsSearchResult>, LyricsSearchReport), LyricsError> {
+    crate::tls::ensure_rustls_provider();
+    let http = reqwest::Client::builder()
+        .user_agent("chaos-seed/0.1")
+        .build()?;
+    search_with_registry_report(
+        &http,
+        req,
+        opt,
+        LyricsProviderRegistry::global(),
+        Some(LyricsCache::global()),
+    )
+    .await
+}
+
+pub async fn search_with_registry_report(
+    http: &reqwest::Client,
+    req: &LyricsSearchRequest,
+    opt: LyricsSearchOptions,
+    registry: &LyricsProviderRegistry,
+    cache: Option<&LyricsCache>,
+) -> Result<(Vec<LyricsSearchResult>, LyricsSearchReport), LyricsError> {
+    let mut events = search_stream_with_registry(http.clone(), req.clone(), opt, registry, cache);
+    while let Some(ev) = events.next().await {
+        if let LyricsSearchEvent::Done { results, report } = ev {
+            return Ok((results, report));
+        }
     }
+    Ok((Vec::new(), LyricsSearchReport::default()))
+}
 
-    let mut results: Vec<LyricsSearchResult> = Vec::new();
-    while let Some(r) = provider_tasks.next().await {
-        match r {
-            Ok(mut items) => results.append(&mut items),
-            Err(_) => {
-                // Provider failure is tolerated: other providers may still succeed.
-            }
+/// 流式搜索的事件；最后一个事件总是 `Done`。
+#[derive(Debug, Clone)]
+pub enum LyricsSearchEvent {
+    /// 命中缓存的在线结果（已按缓存顺序排列）。
+    Cached { results: Vec<LyricsSearchResult> },
+    /// 某个歌词源完成：已打分（`strict_match` 已过滤）但未与其它源合并排序的结果。
+    Provider {
+        report: LyricsProviderReport,
+        results: Vec<LyricsSearchResult>,
+    },
+    /// 最终结果：与 [`search_with_registry_report`] 的返回值相同。
+    Done {
+        results: Vec<LyricsSearchResult>,
+        report: LyricsSearchReport,
+    },
+}
+
+/// 同 [`search`]，但每个歌词源完成时就产出结果，不必等最慢的源。
+pub fn search_stream(
+    req: &LyricsSearchRequest,
+    opt: LyricsSearchOptions,
+) -> Result<BoxStream<'static, LyricsSearchEvent>, LyricsError> {
+    crate::tls::ensure_rustls_provider();
+    let http = reqwest::Client::builder()
+        .user_agent("chaos-seed/0.1")
+        .build()?;
+    Ok(search_stream_with_registry(
+        http,
+        req.clone(),
+        opt,
+        LyricsProviderRegistry::global(),
+        Some(LyricsCache::global()),
+    ))
+}
+
+pub fn search_stream_with_registry<'a>(
+    http: reqwest::Client,
+    req: LyricsSearchRequest,
+    opt: LyricsSearchOptions,
+    registry: &'a LyricsProviderRegistry,
+    cache: Option<&'a LyricsCache>,
+) -> BoxStream<'a, LyricsSearchEvent> {
+    let start = StreamState::Start {
+        http,
+        req,
+        opt,
+        registry,
+        cache,
+    };
+    futures::stream::unfold(start, stream_step).boxed()
+}
+
+enum StreamState<'a> {
+    Start {
+        http: reqwest::Client,
+        req: LyricsSearchRequest,
+        opt: LyricsSearchOptions,
+        registry: &'a LyricsProviderRegistry,
+        cache: Option<&'a LyricsCache>,
+    },
+    Running(Box<RunningSearch<'a>>),
+    Finished,
+}
+
+async fn stream_step(state: StreamState<'_>) -> Option<(LyricsSearchEvent, StreamState<'_>)> {
+    let mut run = match state {
+        StreamState::Start {
+            http,
+            req,
+            opt,
+            registry,
+            cache,
+        } => Box::new(RunningSearch::start(&http, req, opt, registry, cache).await),
+        StreamState::Running(run) => run,
+        StreamState::Finished => return None,
+    };
+
+    if !run.cached_sent
+        && let Some(hit) = &run.cached
+    {
+        run.cached_sent = true;
+        let results = hit.results.clone();
+        return Some((
+            LyricsSearchEvent::Cached { results },
+            StreamState::Running(run),
+        ));
+    }
+    if let Some((results, report)) = run.search.next().await {
+        run.results.extend(results.iter().cloned());
+        return Some((
+            LyricsSearchEvent::Provider { report, results },
+            StreamState::Running(run),
+        ));
+    }
+    let (results, report) = run.finish();
+    Some((
+        LyricsSearchEvent::Done { results, report },
+        StreamState::Finished,
+    ))
+}
+
+/// 一次搜索的状态：解析后的请求、缓存命中的结果与已完成歌词源的结果。
+struct RunningSearch<'a> {
+    req: LyricsSearchRequest,
+    opt: LyricsSearchOptions,
+    cache: Option<&'a LyricsCache>,
+    started: Instant,
+    search: ProviderSearch,
+    /// 命中缓存时的在线结果；此时只查询本地歌词源与缓存缺失的歌词源。
+    cached: Option<LyricsCacheHit>,
+    cached_sent: bool,
+    results: Vec<LyricsSearchResult>,
+}
+
+impl<'a> RunningSearch<'a> {
+    async fn start(
+        http: &reqwest::Client,
+        req: LyricsSearchRequest,
+        opt: LyricsSearchOptions,
+        registry: &LyricsProviderRegistry,
+        cache: Option<&'a LyricsCache>,
+    ) -> Self {
+        let started = Instant::now();
+        let req = if matches!(req.term, LyricsSearchTerm::File { .. }) {
+            let r = req.clone();
+            // 读取标签失败（panic）时按原请求继续，本地源仍可用文件路径。
+            tokio::task::spawn_blocking(move || resolve_file_request(&r))
+                .await
+                .unwrap_or(req)
+        } else {
+            req
+        };
+
+        let cached = cache
+            .filter(|_| !opt.bypass_cache)
+            .and_then(|c| c.lookup(&req, &opt));
+        let search_opt = match &cached {
+            // 缓存只有在线结果，本地歌词每次重新读取。
+            Some(hit) => LyricsSearchOptions {
+                services: opt
+                    .services
+                    .iter()
+                    .copied()
+                    .filter(|s| {
+                        (*s == LyricsService::Local && req.file_path.is_some())
+                            || hit.missing.contains(s)
+                    })
+                    .collect(),
+                ..opt.clone()
+            },
+            None => opt.clone(),
+        };
+        let search = ProviderSearch::start(http, &req, &search_opt, registry);
+        Self {
+            req,
+            opt,
+            cache,
+            started,
+            search,
+            cached,
+            cached_sent: false,
+            results: Vec::new(),
         }
     }
 
-    // Compute match + quality.
-    for item in &mut results {
-        item.matched = quality::is_matched(item, req);
-        item.match_percentage = match_score::compute_match_percentage(req, item);
-        item.quality = quality::compute_quality(item, req);
+    fn finish(self) -> (Vec<LyricsSearchResult>, LyricsSearchReport) {
+        let limit = self.req.limit.max(1);
+        let (mut results, remote): (Vec<_>, Vec<_>) =
+            sort_results(self.results, &self.opt.services)
+                .into_iter()
+                .partition(|r| r.service == LyricsService::Local);
+        results.truncate(limit);
+
+        let cache_hit = self.cached.is_some();
+        let remote = match self.cache {
+            Some(cache) => {
+                let exact: Vec<LyricsService> = self
+                    .search
+                    .reports
+                    .iter()
+                    .filter(|r| r.exact)
+                    .map(|r| r.service)
+                    .collect();
+                cache.store(&self.req, &remote, &exact);
+                // 与缓存中其它歌词源的结果合并，并带上用户固定的歌词与偏移。
+                cache
+                    .lookup(&self.req, &self.opt)
+                    .map_or(remote, |hit| hit.results)
+            }
+            None => remote,
+        };
+        results.extend(remote);
+        results.truncate(limit);
+
+        let mut providers = self.search.reports;
+        let order = service_order_index(&self.opt.services);
+        providers.sort_by_key(|r| order.get(&r.service).copied().unwrap_or(usize::MAX));
+        let report = LyricsSearchReport {
+            cache_hit,
+            elapsed_ms: millis(self.started.elapsed()),
+            returned: results.len(),
+            providers,
+        };
+        (results, report)
     }
+}
 
-    if opt.strict_match {
-        results.retain(|r| r.matched);
+type ProviderTask = BoxFuture<'static, (Vec<LyricsSearchResult>, LyricsProviderReport)>;
+
+/// 并发查询各歌词源；`next` 按完成顺序返回打好分的结果。
+/// Provider failures are tolerated (other providers may still succeed); they only show up in the reports.
+struct ProviderSearch {
+    req: LyricsSearchRequest,
+    strict_match: bool,
+    tasks: FuturesUnordered<ProviderTask>,
+    reports: Vec<LyricsProviderReport>,
+}
+
+impl ProviderSearch {
+    fn start(
+        http: &reqwest::Client,
+        req: &LyricsSearchRequest,
+        opt: &LyricsSearchOptions,
+        registry: &LyricsProviderRegistry,
+    ) -> Self {
+        let timeout = Duration::from_millis(opt.timeout_ms.max(1));
+        let mut reports = Vec::new();
+        let tasks = FuturesUnordered::new();
+        for service in &opt.services {
+            let Some(p) = registry.get(*service) else {
+                let mut report = ProviderRun::new(*service).report;
+                report.status = LyricsProviderStatus::NotRegistered;
+                reports.push(report);
+                continue;
+            };
+            let http = http.clone();
+            let req = req.clone();
+            let exact_only = opt.exact_only;
+            let task: ProviderTask =
+                Box::pin(
+                    async move { search_one_provider(&http, p, &req, timeout, exact_only).await },
+                );
+            tasks.push(task);
+        }
+        Self {
+            req: req.clone(),
+            strict_match: opt.strict_match,
+            tasks,
+            reports,
+        }
     }
 
-    let order = service_order_index(&opt.services);
+    async fn next(&mut self) -> Option<(Vec<LyricsSearchResult>, LyricsProviderReport)> {
+        let (mut results, mut report) = self.tasks.next().await?;
+
+        // Compute match + quality.
+        for item in &mut results {
+            item.matched = quality::is_matched(item, &self.req);
+            item.match_percentage = match_score::compute_match_percentage(&self.req, item);
+            item.quality = quality::compute_quality(item, &self.req);
+        }
+        report.matched = results.iter().filter(|r| r.matched).count();
+        if self.strict_match {
+            let before = results.len();
+            results.retain(|r| r.matched || r.service == LyricsService::Local);
+            report.filtered = before - results.len();
+        }
+
+        self.reports.push(report.clone());
+        Some((results, report))
+    }
+}
+
+pub(crate) fn sort_results(
+    mut results: Vec<LyricsSearchResult>,
+    services: &[LyricsService],
+) -> Vec<LyricsSearchResult> {
+    let order = service_order_index(services);
     results.sort_by(|a, b| {
+        // 本地歌词优先，且保持本地 provider 给出的顺序（同名 .lrc > 内嵌 > 模糊匹配）。
+        let is_local = |r: &LyricsSearchResult| r.service == LyricsService::Local;
+        let by_local = is_local(b).cmp(&is_local(a));
+        if by_local != std::cmp::Ordering::Equal || is_local(a) {
+            return by_local;
+        }
         let by_match = b.match_percentage.cmp(&a.match_percentage);
         if by_match != std::cmp::Ordering::Equal {
             return by_match;
@@ -75,24 +396,7 @@ pub async fn search_with_http(
         let ib = order.get(&b.service).copied().unwrap_or(usize::MAX);
         ia.cmp(&ib)
     });
-
-    results.truncate(req.limit.max(1));
-    Ok(results)
-}
-
-fn build_providers(services: &[LyricsService]) -> Vec<Provider> {
-    services
-        .iter()
-        .copied()
-        .map(|s| match s {
-            LyricsService::Netease => Provider::Netease(Default::default()),
-            LyricsService::QQMusic => Provider::QQ(Default::default()),
-            LyricsService::Kugou => Provider::Kugou(Default::default()),
-            LyricsService::LrcLib => Provider::LrcLib(Default::default()),
-            LyricsService::Gecimi => Provider::Gecimi(Default::default()),
-            LyricsService::Syair => Provider::Syair(Default::default()),
-        })
-        .collect()
+    results
 }
 
 fn service_order_index(
@@ -105,37 +409,148 @@ fn service_order_index(
     m
 }
 
+fn millis(d: Duration) -> u64 {
+    u64::try_from(d.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// 单个 provider 的一次搜索：记录第一个错误，结束时生成报告。
+struct ProviderRun {
+    report: LyricsProviderReport,
+    started: Instant,
+}
+
+impl ProviderRun {
+    fn new(service: LyricsService) -> Self {
+        Self {
+            report: LyricsProviderReport {
+                service,
+                status: LyricsProviderStatus::Ok,
+                http_status: None,
+                error: None,
+                exact: false,
+                latency_ms: 0,
+                candidates: 0,
+                fetched: 0,
+                fetch_failed: 0,
+                matched: 0,
+                filtered: 0,
+            },
+            started: Instant::now(),
+        }
+    }
+
+    fn fail(&mut self, status: LyricsProviderStatus, http_status: Option<u16>, error: String) {
+        if self.report.error.is_none() {
+            self.report.status = status;
+            self.report.http_status = http_status;
+            self.report.error = Some(error);
+        }
+    }
+
+    fn fail_with(&mut self, e: &LyricsError) {
+        let (status, http_status) = match e {
+            LyricsError::Http(e) if e.is_timeout() => (LyricsProviderStatus::Timeout, None),
+            LyricsError::Http(e) => (LyricsProviderStatus::Http, e.status().map(|s| s.as_u16())),
+            LyricsError::Json(_) | LyricsError::Parse(_) => (LyricsProviderStatus::Parse, None),
+            LyricsError::UnsupportedTerm => (LyricsProviderStatus::Unsupported, None),
+            LyricsError::Io(_) | LyricsError::Llm(_) => (LyricsProviderStatus::Error, None),
+        };
+        self.fail(status, http_status, e.to_string());
+    }
+
+    fn finish(mut self) -> LyricsProviderReport {
+        // 有候选取到歌词就算成功；只记录错误信息。
+        if self.report.fetched > 0 {
+            self.report.status = LyricsProviderStatus::Ok;
+            self.report.http_status = None;
+        }
+        self.report.latency_ms = millis(self.started.elapsed());
+        self.report
+    }
+}
+
 async fn search_one_provider(
     http: &reqwest::Client,
-    provider: Provider,
+    provider: Arc<dyn LyricsProvider>,
     req: &LyricsSearchRequest,
     timeout: Duration,
-) -> Result<Vec<LyricsSearchResult>, LyricsError> {
-    let tokens = match tokio::time::timeout(timeout, provider.search(http, req, timeout)).await {
+    exact_only: bool,
+) -> (Vec<LyricsSearchResult>, LyricsProviderReport) {
+    let mut run = ProviderRun::new(provider.service());
+    // 先精确查找；没有命中（或出错）时再模糊搜索，除非只要精确结果。
+    let exact = match tokio::time::timeout(timeout, provider.lookup(http, req, timeout)).await {
         Ok(Ok(v)) => v,
-        Ok(Err(e)) => return Err(e),
-        Err(_) => return Err(LyricsError::Parse("provider search timeout".to_string())),
+        Ok(Err(e)) if exact_only => {
+            run.fail_with(&e);
+            return (Vec::new(), run.finish());
+        }
+        Err(_) if exact_only => {
+            run.fail(
+                LyricsProviderStatus::Timeout,
+                None,
+                "provider lookup timeout".to_string(),
+            );
+            return (Vec::new(), run.finish());
+        }
+        Ok(Err(_)) | Err(_) => Vec::new(),
     };
+    run.report.exact = !exact.is_empty();
+    let tokens = if run.report.exact || exact_only {
+        exact
+    } else {
+        match tokio::time::timeout(timeout, provider.search(http, req, timeout)).await {
+            Ok(Ok(v)) => v,
+            Ok(Err(e)) => {
+                run.fail_with(&e);
+                return (Vec::new(), run.finish());
+            }
+            Err(_) => {
+                run.fail(
+                    LyricsProviderStatus::Timeout,
+                    None,
+                    "provider search timeout".to_string(),
+                );
+                return (Vec::new(), run.finish());
+            }
+        }
+    };
+    run.report.candidates = tokens.len();
 
     let mut fetch_tasks = FuturesUnordered::new();
-    for token in tokens.into_iter().take(req.limit.max(1)) {
+    for (i, token) in tokens.into_iter().take(req.limit.max(1)).enumerate() {
         let http = http.clone();
-        let provider = provider.clone();
+        let provider = Arc::clone(&provider);
         let req = req.clone();
         fetch_tasks.push(async move {
-            match tokio::time::timeout(timeout, provider.fetch(&http, token, &req, timeout)).await {
-                Ok(Ok(v)) => Ok(v),
-                Ok(Err(e)) => Err(e),
-                Err(_) => Err(LyricsError::Parse("provider fetch timeout".to_string())),
-            }
+            let r =
+                tokio::time::timeout(timeout, provider.fetch(&http, token, &req, timeout)).await;
+            (i, r)
         });
     }
 
+    // 按 provider 返回候选的顺序输出（排序时同分结果保持该顺序）。
     let mut out = Vec::new();
-    while let Some(r) = fetch_tasks.next().await {
-        if let Ok(item) = r {
-            out.push(item);
+    while let Some((i, r)) = fetch_tasks.next().await {
+        match r {
+            Ok(Ok(item)) => out.push((i, item)),
+            Ok(Err(e)) => {
+                run.report.fetch_failed += 1;
+                run.fail_with(&e);
+            }
+            Err(_) => {
+                run.report.fetch_failed += 1;
+                run.fail(
+                    LyricsProviderStatus::Timeout,
+                    None,
+                    "provider fetch timeout".to_string(),
+                );
+            }
         }
     }
-    Ok(out)
+    run.report.fetched = out.len();
+    out.sort_by_key(|(i, _)| *i);
+    (
+        out.into_iter().map(|(_, item)| item).collect(),
+        run.finish(),
+    )
 }
@@ -4,8 +4,12 @@ use thiserror::Error;
 pub enum LyricsError {
     #[error("http error: {0}")]
     Http(#[from] reqwest::Error),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("llm error: {0}")]
+    Llm(#[from] crate::llm::LlmError),
     #[error("parse error: {0}")]
     Parse(String),
     #[error("unsupported search term for this provider")]
@@ -26,7 +26,9 @@ pub fn compute_match_percentage(req: &LyricsSearchRequest, result: &LyricsSearch
                 album.as_deref().map(|s| s.trim()),
             )
         }
-        LyricsSearchTerm::Keyword { .. } => (false, None, None, None),
+        LyricsSearchTerm::Keyword { .. } | LyricsSearchTerm::File { .. } => {
+            (false, None, None, None)
+        }
     };
 
     let remote_title = result
@@ -98,7 +100,7 @@ pub fn compute_match_percentage(req: &LyricsSearchRequest, result: &LyricsSearch
     (score.clamp(0.0, 1.0) * 100.0).round().clamp(0.0, 100.0) as u8
 }
 
-fn string_similarity(a: &str, b: &str) -> f64 {
+pub(crate) fn string_similarity(a: &str, b: &str) -> f64 {
     let a = a.trim().to_lowercase();
     let b = b.trim().to_lowercase();
 
@@ -111,7 +113,7 @@ fn string_similarity(a: &str, b: &str) -> f64 {
     jaro_winkler(&a, &b).clamp(0.0, 1.0)
 }
 
-fn duration_similarity_ms(local: Option<u64>, remote: Option<u64>) -> f64 {
+pub(crate) fn duration_similarity_ms(local: Option<u64>, remote: Option<u64>) -> f64 {
     let (Some(a), Some(b)) = (local, remote) else {
         return 0.0;
     };
@@ -130,7 +132,7 @@ fn duration_similarity_ms(local: Option<u64>, remote: Option<u64>) -> f64 {
     1.0 - ((diff - PERFECT_TOL_MS) as f64) / ((MAX_TOL_MS - PERFECT_TOL_MS) as f64)
 }
 
-fn fingerprint(input: &str) -> String {
+pub(crate) fn fingerprint(input: &str) -> String {
     static RE: OnceLock<Regex> = OnceLock::new();
     let re = RE.get_or_init(|| Regex::new(r"[\p{P}\p{S}]").expect("fingerprint regex"));
 
@@ -181,6 +183,7 @@ mod tests {
             has_inline_timetags: false,
             lyrics_original: "x".to_string(),
             lyrics_translation: None,
+            offset_ms: 0,
             debug: None,
         }
     }
@@ -1,7 +1,13 @@
+pub mod cache;
 pub mod core;
 pub mod error;
 pub mod match_score;
 pub mod model;
+pub mod offset;
+pub mod parse;
 pub mod providers;
 pub mod quality;
+pub mod registry;
+pub mod romanize;
+pub mod translate;
 pub mod util;
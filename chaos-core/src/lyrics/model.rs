@@ -1,25 +1,41 @@
+use std::collections::HashSet;
 use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LyricsService {
-    #[serde(rename = "netease")]
     Netease,
-    #[serde(rename = "qq")]
     QQMusic,
-    #[serde(rename = "kugou")]
     Kugou,
-    #[serde(rename = "lrclib")]
     LrcLib,
-    #[serde(rename = "gecimi")]
     Gecimi,
-    #[serde(rename = "syair")]
     Syair,
+    /// Sidecar `.lrc` files and embedded tag lyrics of a local audio file.
+    Local,
+    /// Provider registered at runtime (see `lyrics::registry`); the name is interned.
+    Custom(&'static str),
+}
+
+fn custom_names() -> &'static Mutex<HashSet<&'static str>> {
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    NAMES.get_or_init(|| Mutex::new(HashSet::new()))
 }
 
 impl LyricsService {
+    pub const BUILTIN: [Self; 7] = [
+        Self::Netease,
+        Self::QQMusic,
+        Self::Kugou,
+        Self::LrcLib,
+        Self::Gecimi,
+        Self::Syair,
+        Self::Local,
+    ];
+
     pub const fn as_str(self) -> &'static str {
         match self {
             Self::Netease => "netease",
@@ -28,8 +44,46 @@ impl LyricsService {
             Self::LrcLib => "lrclib",
             Self::Gecimi => "gecimi",
             Self::Syair => "syair",
+            Self::Local => "local",
+            Self::Custom(name) => name,
+        }
+    }
+
+    fn builtin_from_str(s: &str) -> Option<Self> {
+        match s {
+            "netease" | "163" | "ne" => Some(Self::Netease),
+            "qq" | "qqmusic" | "qq_music" => Some(Self::QQMusic),
+            "kugou" | "kg" => Some(Self::Kugou),
+            "lrclib" | "lrc" | "lrc_lib" => Some(Self::LrcLib),
+            "gecimi" | "gc" => Some(Self::Gecimi),
+            "syair" | "sy" => Some(Self::Syair),
+            "local" | "file" => Some(Self::Local),
+            _ => None,
         }
     }
+
+    /// Service id for a custom provider. Built-in names map to the built-in variants;
+    /// other names are normalized (trim + lowercase) and interned.
+    pub fn custom(name: &str) -> Self {
+        let v = name.trim().to_ascii_lowercase();
+        if let Some(s) = Self::builtin_from_str(&v) {
+            return s;
+        }
+        let mut names = custom_names().lock().unwrap_or_else(|e| e.into_inner());
+        let interned = match names.get(v.as_str()) {
+            Some(n) => *n,
+            None => {
+                let n: &'static str = Box::leak(v.into_boxed_str());
+                names.insert(n);
+                n
+            }
+        };
+        Self::Custom(interned)
+    }
+
+    pub fn is_builtin(self) -> bool {
+        !matches!(self, Self::Custom(_))
+    }
 }
 
 impl fmt::Display for LyricsService {
@@ -41,17 +95,40 @@ impl fmt::Display for LyricsService {
 impl FromStr for LyricsService {
     type Err = String;
 
+    /// Accepts built-in names/aliases and names of custom services seen so far.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let v = s.trim().to_ascii_lowercase();
-        match v.as_str() {
-            "netease" | "163" | "ne" => Ok(Self::Netease),
-            "qq" | "qqmusic" | "qq_music" => Ok(Self::QQMusic),
-            "kugou" | "kg" => Ok(Self::Kugou),
-            "lrclib" | "lrc" | "lrc_lib" => Ok(Self::LrcLib),
-            "gecimi" | "gc" => Ok(Self::Gecimi),
-            "syair" | "sy" => Ok(Self::Syair),
-            _ => Err(format!("unknown lyrics service: {s}")),
+        if let Some(svc) = Self::builtin_from_str(&v) {
+            return Ok(svc);
         }
+        let names = custom_names().lock().unwrap_or_else(|e| e.into_inner());
+        names
+            .get(v.as_str())
+            .map(|n| Self::Custom(n))
+            .ok_or_else(|| format!("unknown lyrics service: {s}"))
+    }
+}
+
+impl Serialize for LyricsService {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for LyricsService {
+    /// Accepts only canonical names ([`LyricsService::as_str`]) of built-in services and of
+    /// custom services already created in this process; aliases are `FromStr`-only.
+    /// Unknown names are rejected instead of interned, so untrusted JSON cannot grow the name table.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        if let Some(svc) = Self::BUILTIN.into_iter().find(|b| b.as_str() == s) {
+            return Ok(svc);
+        }
+        let names = custom_names().lock().unwrap_or_else(|e| e.into_inner());
+        names
+            .get(s.as_str())
+            .map(|n| Self::Custom(n))
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown lyrics service: {s}")))
     }
 }
 
@@ -67,12 +144,21 @@ pub enum LyricsSearchTerm {
     Keyword {
         keyword: String,
     },
+    /// Lyrics for a local audio file: sidecar `.lrc` / embedded tags, and the file's
+    /// tags (or file name) for the online services.
+    File {
+        path: PathBuf,
+    },
 }
 
 impl LyricsSearchTerm {
     pub fn description(&self) -> String {
         match self {
             Self::Keyword { keyword } => keyword.clone(),
+            Self::File { path } => path
+                .file_stem()
+                .map(|s| s.to_string_lossy().trim().to_string())
+                .unwrap_or_default(),
             Self::Info { title, artist, .. } => {
                 let t = title.trim();
                 let a = artist.trim();
@@ -89,7 +175,7 @@ impl LyricsSearchTerm {
     pub fn title_artist(&self) -> (Option<&str>, Option<&str>) {
         match self {
             Self::Info { title, artist, .. } => (Some(title.as_str()), Some(artist.as_str())),
-            Self::Keyword { .. } => (None, None),
+            Self::Keyword { .. } | Self::File { .. } => (None, None),
         }
     }
 }
@@ -100,6 +186,12 @@ pub struct LyricsSearchRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration_ms: Option<u64>,
     pub limit: usize,
+    /// Local audio file the lyrics are for (set when a `File` term is resolved); used by the local provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<PathBuf>,
+    /// 录音 ISRC；支持按 ISRC 精确查找的歌词源在 `lookup` 中优先使用。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isrc: Option<String>,
 }
 
 impl LyricsSearchRequest {
@@ -108,6 +200,8 @@ impl LyricsSearchRequest {
             term,
             duration_ms: None,
             limit: 6,
+            file_path: None,
+            isrc: None,
         }
     }
 }
@@ -117,6 +211,12 @@ pub struct LyricsSearchOptions {
     pub timeout_ms: u64,
     pub strict_match: bool,
     pub services: Vec<LyricsService>,
+    /// 跳过歌词缓存读取，总是请求各歌词源（结果仍写回缓存）。
+    #[serde(default)]
+    pub bypass_cache: bool,
+    /// 只用各歌词源的精确查找（ISRC / 标题+歌手+专辑+时长），不回退到模糊搜索。
+    #[serde(default)]
+    pub exact_only: bool,
 }
 
 impl Default for LyricsSearchOptions {
@@ -125,10 +225,13 @@ impl Default for LyricsSearchOptions {
             timeout_ms: 10_000,
             strict_match: false,
             services: vec![
+                LyricsService::Local,
                 LyricsService::QQMusic,
                 LyricsService::Netease,
                 LyricsService::LrcLib,
             ],
+            bypass_cache: false,
+            exact_only: false,
         }
     }
 }
@@ -157,7 +260,81 @@ pub struct LyricsSearchResult {
     pub lyrics_original: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lyrics_translation: Option<String>,
+    /// User-adjusted timing offset for this song (same sign as LRC `[offset:]`), from the lyrics cache.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub offset_ms: i64,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub debug: Option<serde_json::Value>,
 }
+
+fn is_zero(v: &i64) -> bool {
+    *v == 0
+}
+
+/// Outcome of one provider in a search (see [`LyricsSearchReport`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LyricsProviderStatus {
+    Ok,
+    /// Search or every fetch exceeded `timeout_ms`.
+    Timeout,
+    /// Transport error or non-2xx response (`http_status` when known).
+    Http,
+    /// The response could not be parsed (API changed, unexpected payload).
+    Parse,
+    /// The provider does not support this search term.
+    Unsupported,
+    /// Requested in `services` but no provider is registered for it.
+    NotRegistered,
+    Error,
+}
+
+impl LyricsProviderStatus {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Timeout => "timeout",
+            Self::Http => "http",
+            Self::Parse => "parse",
+            Self::Unsupported => "unsupported",
+            Self::NotRegistered => "not_registered",
+            Self::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LyricsProviderReport {
+    pub service: LyricsService,
+    pub status: LyricsProviderStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    /// Search error, or the first fetch error when no candidate could be fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Candidates came from the provider's exact lookup rather than fuzzy search.
+    #[serde(default)]
+    pub exact: bool,
+    /// Wall time of search + fetches.
+    pub latency_ms: u64,
+    /// Candidates returned by the provider's search (before `limit`).
+    pub candidates: usize,
+    pub fetched: usize,
+    pub fetch_failed: usize,
+    /// Fetched results with `matched = true`.
+    pub matched: usize,
+    /// Results dropped by `strict_match`.
+    pub filtered: usize,
+}
+
+/// Per-search diagnostics returned by [`crate::lyrics::core::search_with_report`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LyricsSearchReport {
+    /// Remote results came from the lyrics cache (providers in `providers` were still queried).
+    pub cache_hit: bool,
+    pub elapsed_ms: u64,
+    /// Number of results returned after sorting / `limit`.
+    pub returned: usize,
+    pub providers: Vec<LyricsProviderReport>,
+}
@@ -0,0 +1,312 @@
+//! 歌词整体偏移估计：解码音频 → 重采样到 8 kHz（`rubato`）→ 高通去掉低频伴奏 →
+//! 能量 VAD（[`EnergyVad`]，阈值按整首歌的帧能量分布自适应）取人声起点 →
+//! 与歌词行起始时间做互相关，得到全局偏移。
+//!
+//! 结果与 `[offset:]` 同义（正数表示歌词提前显示）：可以直接写进 [`Lyrics::metadata`]
+//! 后 [`Lyrics::to_lrc`]，或用 [`OffsetEstimate::cache_offset_ms`] 存进歌词缓存。
+//! 这是启发式估计，伴奏很响或人声很少的歌可能不准，请结合 [`OffsetEstimate::confidence`] 使用。
+
+use std::path::Path;
+
+use rubato::{FftFixedIn, Resampler};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error as DecodeError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::lyrics::error::LyricsError;
+use crate::lyrics::parse::Lyrics;
+use crate::tts::vad::{EnergyVad, VadConfig, VadEngine};
+use crate::tts::wav::f32_to_pcm16_mono;
+
+/// 分析用采样率；重采样同时起到 4 kHz 低通的作用。
+pub const ANALYSIS_SAMPLE_RATE: u32 = 8_000;
+/// 高通截止频率：压掉底鼓/贝斯的能量，保留人声基频以上的部分。
+const HIGH_PASS_HZ: f32 = 200.0;
+/// 少于这么多行有时间的歌词不做估计。
+const MIN_TIMED_LINES: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct OffsetEstimateOptions {
+    /// 搜索范围 `[-max, max]`。
+    pub max_offset_ms: u64,
+    pub step_ms: u64,
+    /// 行起点与人声起点相差多少以内算对齐（三角核的半宽）。
+    pub tolerance_ms: u64,
+}
+
+impl Default for OffsetEstimateOptions {
+    fn default() -> Self {
+        Self {
+            max_offset_ms: 5_000,
+            step_ms: 10,
+            tolerance_ms: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OffsetEstimate {
+    /// 建议的 `[offset:]`（毫秒）。
+    pub offset_ms: i64,
+    /// 0..=1：最佳偏移的得分相对平均得分的突出程度。
+    pub confidence: f32,
+    /// 最佳偏移下落在容差内的行数。
+    pub matched_lines: usize,
+    pub timed_lines: usize,
+    pub onsets: usize,
+}
+
+impl OffsetEstimate {
+    /// 写入 `[offset:]`（替换原有值）。
+    pub fn apply(&self, lyrics: &mut Lyrics) {
+        lyrics.metadata.offset_ms = self.offset_ms;
+    }
+
+    /// 歌词缓存里的用户偏移叠加在歌词自带的 `[offset:]` 上，这里返回需要存的差值；
+    /// `lyrics` 应是未叠加用户偏移的原文解析结果。
+    pub fn cache_offset_ms(&self, lyrics: &Lyrics) -> i64 {
+        self.offset_ms - lyrics.metadata.offset_ms
+    }
+}
+
+/// 解码音频文件并估计偏移；歌词行太少或检测不到人声时返回 `None`。
+///
+/// 这是阻塞调用（解码整首歌）。
+pub fn estimate_offset_for_file(
+    lyrics: &Lyrics,
+    path: &Path,
+    opt: &OffsetEstimateOptions,
+) -> Result<Option<OffsetEstimate>, LyricsError> {
+    let (pcm, sample_rate) = decode_mono(path)?;
+    estimate_offset(lyrics, &pcm, sample_rate, opt)
+}
+
+/// 对单声道 PCM 估计偏移。
+pub fn estimate_offset(
+    lyrics: &Lyrics,
+    pcm: &[f32],
+    sample_rate: u32,
+    opt: &OffsetEstimateOptions,
+) -> Result<Option<OffsetEstimate>, LyricsError> {
+    let starts: Vec<u64> = lyrics
+        .lines
+        .iter()
+        .filter(|l| !l.text.trim().is_empty())
+        .map(|l| l.start_ms)
+        .collect();
+    if starts.len() < MIN_TIMED_LINES {
+        return Ok(None);
+    }
+    let onsets = vocal_onsets_ms(pcm, sample_rate)?;
+    if onsets.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(correlate(&starts, &onsets, opt)))
+}
+
+/// 人声段起点（毫秒，升序）。
+pub fn vocal_onsets_ms(pcm: &[f32], sample_rate: u32) -> Result<Vec<u64>, LyricsError> {
+    if sample_rate == 0 {
+        return Err(LyricsError::Parse("sample_rate must be > 0".to_string()));
+    }
+    let mut x = resample(pcm, sample_rate, ANALYSIS_SAMPLE_RATE)?;
+    high_pass(&mut x, ANALYSIS_SAMPLE_RATE, HIGH_PASS_HZ);
+
+    let peak = x.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+    if peak <= f32::EPSILON {
+        return Ok(Vec::new());
+    }
+    x.iter_mut().for_each(|s| *s /= peak);
+    let pcm16 = f32_to_pcm16_mono(&x);
+
+    let mut cfg = VadConfig {
+        min_speech_ms: 300,
+        min_silence_ms: 200,
+        ..VadConfig::default()
+    };
+    cfg.threshold = adaptive_threshold(&x, ANALYSIS_SAMPLE_RATE, cfg.frame_ms);
+    let segs = EnergyVad
+        .detect_segments(&pcm16, ANALYSIS_SAMPLE_RATE, &cfg)
+        .map_err(|e| LyricsError::Parse(e.to_string()))?;
+    Ok(segs
+        .iter()
+        .map(|s| s.start_sample as u64 * 1000 / u64::from(ANALYSIS_SAMPLE_RATE))
+        .collect())
+}
+
+/// 帧 RMS 的 20% 与 80% 分位数的中点：安静段（前奏/间奏）落在阈值以下，人声段在上。
+fn adaptive_threshold(x: &[f32], sample_rate: u32, frame_ms: u32) -> f32 {
+    let frame = (sample_rate as usize * frame_ms as usize / 1000).max(1);
+    let mut rms: Vec<f32> = x
+        .chunks(frame)
+        .map(|c| (c.iter().map(|s| s * s).sum::<f32>() / c.len() as f32).sqrt())
+        .collect();
+    rms.sort_by(f32::total_cmp);
+    let at = |q: f32| rms[((rms.len() - 1) as f32 * q) as usize];
+    (at(0.2) + at(0.8)) / 2.0
+}
+
+/// 候选偏移 `o` 下，行起点 `L` 对应的人声起点应在 `L - o`；按到最近起点的距离计三角核得分。
+fn correlate(starts: &[u64], onsets: &[u64], opt: &OffsetEstimateOptions) -> OffsetEstimate {
+    let tol = opt.tolerance_ms.max(1) as f64;
+    let max = opt.max_offset_ms as i64;
+    let step = opt.step_ms.max(1) as usize;
+    let nearest = |t: i64| -> u64 {
+        let t = t.max(0) as u64;
+        let i = onsets.partition_point(|&o| o < t);
+        [i.checked_sub(1), Some(i)]
+            .into_iter()
+            .flatten()
+            .filter_map(|i| onsets.get(i))
+            .map(|&o| o.abs_diff(t))
+            .min()
+            .unwrap_or(u64::MAX)
+    };
+    let score = |o: i64| -> (f64, usize) {
+        starts.iter().fold((0.0, 0), |(s, n), &l| {
+            let d = nearest(l as i64 - o) as f64;
+            if d <= tol {
+                (s + 1.0 - d / tol, n + 1)
+            } else {
+                (s, n)
+            }
+        })
+    };
+
+    let mut best = (0i64, 0.0f64, 0usize);
+    let mut total = 0.0;
+    let mut count = 0usize;
+    for o in (-max..=max).step_by(step) {
+        let (s, n) = score(o);
+        total += s;
+        count += 1;
+        // 得分相同取绝对值更小的偏移。
+        if s > best.1 || (s == best.1 && o.abs() < best.0.abs()) {
+            best = (o, s, n);
+        }
+    }
+    let mean = total / count.max(1) as f64;
+    let confidence = ((best.1 - mean) / starts.len() as f64).clamp(0.0, 1.0) as f32;
+    OffsetEstimate {
+        offset_ms: best.0,
+        confidence,
+        matched_lines: best.2,
+        timed_lines: starts.len(),
+        onsets: onsets.len(),
+    }
+}
+
+fn high_pass(x: &mut [f32], sample_rate: u32, cutoff_hz: f32) {
+    let rc = 1.0 / (2.0 * std::f32::consts::PI * cutoff_hz);
+    let dt = 1.0 / sample_rate as f32;
+    let a = rc / (rc + dt);
+    let (mut prev_x, mut prev_y) = (0.0f32, 0.0f32);
+    for s in x.iter_mut() {
+        let y = a * (prev_y + *s - prev_x);
+        prev_x = *s;
+        prev_y = y;
+        *s = y;
+    }
+}
+
+fn resample(pcm: &[f32], from: u32, to: u32) -> Result<Vec<f32>, LyricsError> {
+    if from == to || pcm.is_empty() {
+        return Ok(pcm.to_vec());
+    }
+    let err = |e: &dyn std::fmt::Display| LyricsError::Parse(format!("resample: {e}"));
+    let mut r =
+        FftFixedIn::<f32>::new(from as usize, to as usize, 1024, 2, 1).map_err(|e| err(&e))?;
+    let delay = r.output_delay();
+    let mut out = Vec::with_capacity(pcm.len() * to as usize / from as usize + 1024);
+    let mut pos = 0;
+    while pcm.len() - pos >= r.input_frames_next() {
+        let n = r.input_frames_next();
+        let chunk = r
+            .process(&[&pcm[pos..pos + n]], None)
+            .map_err(|e| err(&e))?;
+        out.extend_from_slice(&chunk[0]);
+        pos += n;
+    }
+    let tail = r
+        .process_partial(Some(&[&pcm[pos..]]), None)
+        .map_err(|e| err(&e))?;
+    out.extend_from_slice(&tail[0]);
+    // 冲出滤波器延迟里剩下的样本。
+    let flush = r
+        .process_partial::<&[f32]>(None, None)
+        .map_err(|e| err(&e))?;
+    out.extend_from_slice(&flush[0]);
+    let expected = (pcm.len() as u64 * u64::from(to) / u64::from(from)) as usize;
+    Ok(out.into_iter().skip(delay).take(expected).collect())
+}
+
+/// 解码为单声道 f32（多声道取平均），返回 `(pcm, sample_rate)`。
+fn decode_mono(path: &Path) -> Result<(Vec<f32>, u32), LyricsError> {
+    let err = |e: DecodeError| LyricsError::Parse(format!("audio decode: {e}"));
+    let file = std::fs::File::open(path)?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(err)?;
+    let mut format = probed.format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| LyricsError::Parse("audio decode: no audio track".to_string()))?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(err)?;
+
+    let mut mono = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(p) => p,
+            Err(DecodeError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break;
+            }
+            Err(DecodeError::ResetRequired) => break,
+            Err(e) => return Err(err(e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(d) => d,
+            // 个别坏帧跳过。
+            Err(DecodeError::DecodeError(_)) => continue,
+            Err(e) => return Err(err(e)),
+        };
+        let spec = *decoded.spec();
+        sample_rate = spec.rate;
+        let channels = spec.channels.count().max(1);
+        let mut buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buf.copy_interleaved_ref(decoded);
+        mono.extend(
+            buf.samples()
+                .chunks(channels)
+                .map(|f| f.iter().sum::<f32>() / channels as f32),
+        );
+    }
+    if sample_rate == 0 {
+        return Err(LyricsError::Parse(
+            "audio decode: unknown sample rate".to_string(),
+        ));
+    }
+    Ok((mono, sample_rate))
+}
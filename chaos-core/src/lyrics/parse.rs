@@ -0,0 +1,617 @@
+//! 结构化歌词：解析 LRC / 增强 LRC（`<mm:ss.xx>`）/ 网易云 YRC / QQ QRC / 酷狗 KRC，
+//! 按时间戳对齐翻译，并序列化回 LRC / 增强 LRC / SRT。
+//!
+//! 时间均为毫秒；`[offset:]` 保存在 [`LyricsMetadata::offset_ms`] 中，不改写行时间，
+//! 只在 [`Lyrics::line_index_at`] 与 [`Lyrics::to_srt`] 中生效。
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::lyrics::model::LyricsSearchResult;
+
+/// LRC 最后一行没有结束时间，按此时长补齐。
+pub const LAST_LINE_DURATION_MS: u64 = 5_000;
+/// 翻译行与原文行起始时间的最大允许偏差。
+pub const TRANSLATION_TOLERANCE_MS: u64 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LyricsFormat {
+    Lrc,
+    /// 行内 `<mm:ss.xx>` 逐字时间。
+    EnhancedLrc,
+    /// 网易云逐字：`[start,dur](start,dur,0)字`；旧版 klyric 的字时间为相对行首偏移。
+    Yrc,
+    /// QQ 逐字：`[start,dur]字(start,dur)`。
+    Qrc,
+    /// 酷狗逐字（已解密）：`[start,dur]<offset,dur,0>字`。
+    Krc,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LyricsMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub by: Option<String>,
+    /// `[offset:]`：正数表示歌词整体提前显示。
+    #[serde(default)]
+    pub offset_ms: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LyricsWord {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LyricsLine {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<LyricsWord>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lyrics {
+    pub metadata: LyricsMetadata,
+    pub lines: Vec<LyricsLine>,
+}
+
+fn lrc_tag_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^\[([^\[\]]*)\]").expect("lrc tag regex"))
+}
+
+fn enhanced_tag_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"<(\d+:\d+(?:[.:]\d+)?)>").expect("enhanced tag regex"))
+}
+
+fn word_line_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^\[(\d+),(\d+)\]").expect("word line regex"))
+}
+
+fn krc_word_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"<(\d+),(\d+),\d+>").expect("krc word regex"))
+}
+
+fn paren_word_re() -> &'static Regex {
+    // YRC `(start,dur,0)`、klyric `(offset,dur)`、QRC `(start,dur)`。
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\((\d+),(\d+)(?:,\d+)?\)").expect("paren word regex"))
+}
+
+/// `mm:ss.xx` / `mm:ss:xx` / `mm:ss` -> 毫秒。
+fn parse_time_tag(tag: &str) -> Option<u64> {
+    let (mm, rest) = tag.trim().split_once(':')?;
+    let mm: u64 = mm.parse().ok()?;
+    let (ss, frac) = rest.split_once(['.', ':']).unwrap_or((rest, ""));
+    let ss: u64 = ss.parse().ok()?;
+    if !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let frac_ms = match frac.len() {
+        0 => 0,
+        1 => frac.parse::<u64>().ok()? * 100,
+        2 => frac.parse::<u64>().ok()? * 10,
+        _ => frac.get(..3)?.parse::<u64>().ok()?,
+    };
+    Some(mm * 60_000 + ss * 1000 + frac_ms)
+}
+
+/// `[mm:ss.xx]` 中的时间部分。
+pub(crate) fn format_lrc_time(ms: u64) -> String {
+    format!(
+        "{:02}:{:02}.{:02}",
+        ms / 60_000,
+        ms / 1000 % 60,
+        ms % 1000 / 10
+    )
+}
+
+fn format_srt_time(ms: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+/// 识别为元数据标签时写入 `meta` 并返回 `true`。
+fn apply_metadata(meta: &mut LyricsMetadata, tag: &str) -> bool {
+    let Some((key, value)) = tag.split_once(':') else {
+        return false;
+    };
+    let value = value.trim();
+    let text = || (!value.is_empty()).then(|| value.to_string());
+    match key.trim().to_ascii_lowercase().as_str() {
+        "ti" => meta.title = text(),
+        "ar" => meta.artist = text(),
+        "al" => meta.album = text(),
+        "by" => meta.by = text(),
+        "offset" => meta.offset_ms = value.trim_start_matches('+').parse().unwrap_or(0),
+        _ => return false,
+    }
+    true
+}
+
+/// 猜测歌词格式；无法识别逐字格式时按 LRC 处理。
+pub fn detect_format(text: &str) -> LyricsFormat {
+    let mut word_lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| word_line_re().is_match(l))
+        .peekable();
+    if word_lines.peek().is_some() {
+        let bodies = word_lines
+            .map(|l| &l[word_line_re().find(l).map_or(0, |m| m.end())..])
+            .collect::<Vec<_>>();
+        if bodies.iter().any(|b| krc_word_re().is_match(b)) {
+            return LyricsFormat::Krc;
+        }
+        // QRC 的字时间在字后，YRC / klyric 在字前。
+        let tag_first = bodies
+            .iter()
+            .filter(|b| !b.is_empty())
+            .all(|b| paren_word_re().find(b).is_some_and(|m| m.start() == 0));
+        return if tag_first {
+            LyricsFormat::Yrc
+        } else {
+            LyricsFormat::Qrc
+        };
+    }
+    if text
+        .lines()
+        .any(|l| lrc_tag_re().is_match(l.trim()) && enhanced_tag_re().is_match(l))
+    {
+        return LyricsFormat::EnhancedLrc;
+    }
+    LyricsFormat::Lrc
+}
+
+/// 自动识别格式并解析。
+pub fn parse(text: &str) -> Lyrics {
+    parse_as(text, detect_format(text))
+}
+
+/// 原文 + 可选翻译（通常是各服务返回的独立翻译 LRC）。
+pub fn parse_with_translation(original: &str, translation: Option<&str>) -> Lyrics {
+    let mut lyrics = parse(original);
+    if let Some(t) = translation.filter(|t| !t.trim().is_empty()) {
+        lyrics.merge_translation(&parse(t));
+    }
+    lyrics
+}
+
+pub fn parse_as(text: &str, format: LyricsFormat) -> Lyrics {
+    // 部分接口带 BOM / `\r\n`。
+    let text = text.trim_start_matches('\u{feff}');
+    let mut lyrics = match format {
+        LyricsFormat::Lrc | LyricsFormat::EnhancedLrc => parse_lrc(text),
+        LyricsFormat::Yrc | LyricsFormat::Qrc | LyricsFormat::Krc => parse_word_lines(text, format),
+    };
+    if format == LyricsFormat::Krc {
+        // KRC 的 `[language:]` 翻译按时间行顺序对齐。
+        if let Some(rows) = krc_language_rows(text)
+            .into_iter()
+            .find_map(|(kind, rows)| (kind == 1).then_some(rows))
+        {
+            for (line, row) in lyrics.lines.iter_mut().zip(rows) {
+                let row = row.trim();
+                if !row.is_empty() {
+                    line.translation = Some(row.to_string());
+                }
+            }
+        }
+    }
+    lyrics
+}
+
+fn parse_lrc(text: &str) -> Lyrics {
+    let mut meta = LyricsMetadata::default();
+    let mut entries: Vec<(u64, String, Vec<LyricsWord>)> = Vec::new();
+    for raw in text.lines() {
+        let mut rest = raw.trim();
+        let mut times = Vec::new();
+        while let Some(caps) = lrc_tag_re().captures(rest) {
+            let tag = caps.get(1).map_or("", |m| m.as_str());
+            match parse_time_tag(tag) {
+                Some(t) => times.push(t),
+                None if times.is_empty() && apply_metadata(&mut meta, tag) => {}
+                None => break,
+            }
+            rest = &rest[caps[0].len()..];
+        }
+        for start in times {
+            let (text, words) = parse_enhanced_words(rest, start);
+            entries.push((start, text, words));
+        }
+    }
+    // 稳定排序：同一时间戳下先出现的为原文，之后的视为翻译。
+    entries.sort_by_key(|(t, _, _)| *t);
+
+    let mut lines: Vec<LyricsLine> = Vec::with_capacity(entries.len());
+    for (start, text, words) in entries {
+        if let Some(prev) = lines.last_mut()
+            && prev.start_ms == start
+            && !prev.text.is_empty()
+            && !text.is_empty()
+        {
+            if prev.translation.is_none() && text != prev.text {
+                prev.translation = Some(text);
+            }
+            continue;
+        }
+        lines.push(LyricsLine {
+            start_ms: start,
+            end_ms: 0,
+            text,
+            words,
+            translation: None,
+        });
+    }
+    fill_line_ends(&mut lines);
+    Lyrics {
+        metadata: meta,
+        lines,
+    }
+}
+
+/// 增强 LRC 行内容：`<mm:ss.xx>字<mm:ss.xx>字<mm:ss.xx>`；返回 `(整行文本, 逐字)`。
+fn parse_enhanced_words(body: &str, line_start: u64) -> (String, Vec<LyricsWord>) {
+    let tags = enhanced_tag_re()
+        .captures_iter(body)
+        .filter_map(|c| {
+            let m = c.get(0)?;
+            Some((m.start(), m.end(), parse_time_tag(&c[1])?))
+        })
+        .collect::<Vec<_>>();
+    if tags.is_empty() {
+        return (body.trim().to_string(), Vec::new());
+    }
+
+    let mut words: Vec<LyricsWord> = Vec::new();
+    // 第一个标签前的文字从行首开始。
+    let lead = &body[..tags[0].0];
+    if !lead.trim().is_empty() {
+        words.push(LyricsWord {
+            start_ms: line_start,
+            end_ms: tags[0].2,
+            text: lead.to_string(),
+        });
+    }
+    for (i, &(_, end, start)) in tags.iter().enumerate() {
+        let next = tags.get(i + 1);
+        let text = &body[end..next.map_or(body.len(), |n| n.0)];
+        if let Some(w) = words.last_mut()
+            && w.end_ms == 0
+        {
+            w.end_ms = start;
+        }
+        if text.is_empty() {
+            continue;
+        }
+        words.push(LyricsWord {
+            start_ms: start,
+            end_ms: next.map_or(0, |n| n.2),
+            text: text.to_string(),
+        });
+    }
+    let text = words.iter().map(|w| w.text.as_str()).collect::<String>();
+    (text.trim().to_string(), words)
+}
+
+fn parse_word_lines(text: &str, format: LyricsFormat) -> Lyrics {
+    let mut meta = LyricsMetadata::default();
+    let mut lines = Vec::new();
+    for raw in text.lines() {
+        let line = raw.trim();
+        let Some(caps) = word_line_re().captures(line) else {
+            // 元数据行；YRC 开头的 JSON 行（作词/作曲信息）忽略。
+            if let Some(c) = lrc_tag_re().captures(line) {
+                apply_metadata(&mut meta, &c[1]);
+            }
+            continue;
+        };
+        let start: u64 = caps[1].parse().unwrap_or(0);
+        let dur: u64 = caps[2].parse().unwrap_or(0);
+        let body = &line[caps[0].len()..];
+        let words = match format {
+            LyricsFormat::Krc => tag_first_words(body, krc_word_re(), |t| start + t),
+            LyricsFormat::Qrc => qrc_words(body),
+            _ => {
+                // klyric 的字时间是相对行首的偏移。
+                let relative = paren_word_re()
+                    .captures(body)
+                    .and_then(|c| c[1].parse::<u64>().ok())
+                    .is_some_and(|t| t < start);
+                tag_first_words(
+                    body,
+                    paren_word_re(),
+                    |t| if relative { start + t } else { t },
+                )
+            }
+        };
+        let text = if words.is_empty() {
+            body.trim().to_string()
+        } else {
+            words
+                .iter()
+                .map(|w| w.text.as_str())
+                .collect::<String>()
+                .trim()
+                .to_string()
+        };
+        lines.push(LyricsLine {
+            start_ms: start,
+            end_ms: start + dur,
+            text,
+            words,
+            translation: None,
+        });
+    }
+    lines.sort_by_key(|l| l.start_ms);
+    Lyrics {
+        metadata: meta,
+        lines,
+    }
+}
+
+/// 时间标签在字前（KRC / YRC / klyric）。
+fn tag_first_words(body: &str, re: &Regex, abs: impl Fn(u64) -> u64) -> Vec<LyricsWord> {
+    let tags = re
+        .captures_iter(body)
+        .filter_map(|c| {
+            let m = c.get(0)?;
+            Some((m.start(), m.end(), c[1].parse().ok()?, c[2].parse().ok()?))
+        })
+        .collect::<Vec<(usize, usize, u64, u64)>>();
+    tags.iter()
+        .enumerate()
+        .filter_map(|(i, &(_, end, t, dur))| {
+            let text = &body[end..tags.get(i + 1).map_or(body.len(), |n| n.0)];
+            (!text.is_empty()).then(|| LyricsWord {
+                start_ms: abs(t),
+                end_ms: abs(t) + dur,
+                text: text.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 时间标签在字后（QRC），时间为绝对毫秒。
+fn qrc_words(body: &str) -> Vec<LyricsWord> {
+    let mut words = Vec::new();
+    let mut prev = 0;
+    for c in paren_word_re().captures_iter(body) {
+        let Some(m) = c.get(0) else {
+            continue;
+        };
+        let text = &body[prev..m.start()];
+        prev = m.end();
+        let (Ok(t), Ok(dur)) = (c[1].parse::<u64>(), c[2].parse::<u64>()) else {
+            continue;
+        };
+        if !text.is_empty() {
+            words.push(LyricsWord {
+                start_ms: t,
+                end_ms: t + dur,
+                text: text.to_string(),
+            });
+        }
+    }
+    words
+}
+
+/// LRC 行的结束时间取下一行开始；逐字行取最后一个字的结束。
+fn fill_line_ends(lines: &mut [LyricsLine]) {
+    let starts = lines.iter().map(|l| l.start_ms).collect::<Vec<_>>();
+    for (i, line) in lines.iter_mut().enumerate() {
+        let next = starts.get(i + 1).copied();
+        let fallback = next.unwrap_or(line.start_ms + LAST_LINE_DURATION_MS);
+        if let Some(w) = line.words.last_mut()
+            && w.end_ms == 0
+        {
+            w.end_ms = fallback.max(w.start_ms);
+        }
+        line.end_ms = line
+            .words
+            .last()
+            .map_or(fallback, |w| w.end_ms)
+            .max(line.start_ms);
+    }
+}
+
+/// KRC `[language:base64]` 中的 `(type, 每行文本)`：type 0 = 音译（逐字拼接），1 = 翻译。
+pub(crate) fn krc_language_rows(krc: &str) -> Vec<(i64, Vec<String>)> {
+    use base64::Engine as _;
+
+    let Some(raw) = krc.lines().find_map(|l| {
+        l.trim()
+            .strip_prefix("[language:")
+            .and_then(|s| s.strip_suffix(']'))
+    }) else {
+        return Vec::new();
+    };
+    let Some(json) = base64::engine::general_purpose::STANDARD
+        .decode(raw.trim())
+        .ok()
+        .and_then(|b| serde_json::from_slice::<Value>(&b).ok())
+    else {
+        return Vec::new();
+    };
+    json.get("content")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let kind = item.get("type").and_then(|v| v.as_i64())?;
+            let rows = item
+                .get("lyricContent")
+                .and_then(|v| v.as_array())?
+                .iter()
+                .map(|row| {
+                    row.as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|w| w.as_str())
+                        .collect::<String>()
+                })
+                .collect();
+            Some((kind, rows))
+        })
+        .collect()
+}
+
+impl Lyrics {
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|l| l.text.trim().is_empty())
+    }
+
+    /// 是否有逐字时间。
+    pub fn has_word_timing(&self) -> bool {
+        self.lines.iter().any(|l| !l.words.is_empty())
+    }
+
+    pub fn has_translation(&self) -> bool {
+        self.lines.iter().any(|l| l.translation.is_some())
+    }
+
+    /// 把另一份歌词（翻译）按起始时间对齐到本歌词：取 [`TRANSLATION_TOLERANCE_MS`] 内最近的行，
+    /// 已有翻译的行不覆盖。
+    pub fn merge_translation(&mut self, translation: &Lyrics) {
+        let offset = translation.metadata.offset_ms - self.metadata.offset_ms;
+        for t in &translation.lines {
+            let text = t.text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            let start = t.start_ms.saturating_add_signed(-offset);
+            let best = self
+                .lines
+                .iter_mut()
+                .filter(|l| !l.text.trim().is_empty() && l.translation.is_none())
+                .map(|l| (l.start_ms.abs_diff(start), l))
+                .filter(|(d, _)| *d <= TRANSLATION_TOLERANCE_MS)
+                .min_by_key(|(d, _)| *d);
+            if let Some((_, line)) = best {
+                line.translation = Some(text.to_string());
+            }
+        }
+    }
+
+    /// 播放到 `position_ms` 时应高亮的行（已计入 `[offset:]`）；第一行之前返回 `None`。
+    pub fn line_index_at(&self, position_ms: u64) -> Option<usize> {
+        let t = position_ms.saturating_add_signed(self.metadata.offset_ms);
+        self.lines
+            .partition_point(|l| l.start_ms <= t)
+            .checked_sub(1)
+    }
+
+    fn metadata_tags(&self) -> String {
+        let m = &self.metadata;
+        let mut out = String::new();
+        for (k, v) in [
+            ("ti", &m.title),
+            ("ar", &m.artist),
+            ("al", &m.album),
+            ("by", &m.by),
+        ] {
+            if let Some(v) = v {
+                out.push_str(&format!("[{k}:{v}]\n"));
+            }
+        }
+        if m.offset_ms != 0 {
+            out.push_str(&format!("[offset:{}]\n", m.offset_ms));
+        }
+        out
+    }
+
+    /// 行级 LRC；有翻译时在原文后追加同时间戳的译文行。
+    pub fn to_lrc(&self) -> String {
+        let mut out = self.metadata_tags();
+        for l in &self.lines {
+            let t = format_lrc_time(l.start_ms);
+            out.push_str(&format!("[{t}]{}\n", l.text));
+            if let Some(tr) = &l.translation {
+                out.push_str(&format!("[{t}]{tr}\n"));
+            }
+        }
+        out
+    }
+
+    /// 增强 LRC：逐字行写成 `[行]<字>字<字>字<结束>`，没有逐字时间的行同 [`Self::to_lrc`]。
+    pub fn to_enhanced_lrc(&self) -> String {
+        let mut out = self.metadata_tags();
+        for l in &self.lines {
+            let t = format_lrc_time(l.start_ms);
+            out.push_str(&format!("[{t}]"));
+            if l.words.is_empty() {
+                out.push_str(&l.text);
+            } else {
+                for w in &l.words {
+                    out.push_str(&format!("<{}>{}", format_lrc_time(w.start_ms), w.text));
+                }
+                if let Some(w) = l.words.last() {
+                    out.push_str(&format!("<{}>", format_lrc_time(w.end_ms)));
+                }
+            }
+            out.push('\n');
+            if let Some(tr) = &l.translation {
+                out.push_str(&format!("[{t}]{tr}\n"));
+            }
+        }
+        out
+    }
+
+    /// SRT 字幕（已计入 `[offset:]`）；空行跳过，翻译作为第二行。
+    pub fn to_srt(&self) -> String {
+        let shift = |ms: u64| ms.saturating_add_signed(-self.metadata.offset_ms);
+        let mut out = String::new();
+        let mut n = 0;
+        for l in self.lines.iter().filter(|l| !l.text.trim().is_empty()) {
+            n += 1;
+            let start = shift(l.start_ms);
+            let end = shift(l.end_ms).max(start);
+            out.push_str(&format!(
+                "{n}\n{} --> {}\n{}\n",
+                format_srt_time(start),
+                format_srt_time(end),
+                l.text
+            ));
+            if let Some(tr) = &l.translation {
+                out.push_str(tr);
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl LyricsSearchResult {
+    /// 把 `lyrics_original` / `lyrics_translation` 解析为结构化歌词；用户偏移叠加到 `[offset:]` 上。
+    pub fn parsed_lyrics(&self) -> Lyrics {
+        let mut lyrics =
+            parse_with_translation(&self.lyrics_original, self.lyrics_translation.as_deref());
+        lyrics.metadata.offset_ms += self.offset_ms;
+        lyrics
+    }
+}
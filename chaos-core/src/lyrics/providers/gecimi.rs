@@ -94,6 +94,7 @@ impl GecimiProvider {
             has_inline_timetags: false,
             lyrics_original: s,
             lyrics_translation: None,
+            offset_ms: 0,
             debug: None,
         })
     }
@@ -1,7 +1,7 @@
 use std::io::Read;
 use std::time::Duration;
 
-use flate2::read::GzDecoder;
+use flate2::read::{GzDecoder, ZlibDecoder};
 use reqwest::Client;
 use serde::Deserialize;
 
@@ -131,6 +131,7 @@ impl KugouProvider {
             has_inline_timetags: data.fmt == "krc",
             lyrics_original: lyric_text,
             lyrics_translation: None,
+            offset_ms: 0,
             debug: None,
         })
     }
@@ -170,7 +171,8 @@ fn decode_b64(s: &str) -> Result<Vec<u8>, base64::DecodeError> {
     base64::engine::general_purpose::STANDARD.decode(s.trim())
 }
 
-fn decrypt_kugou_krc(data: &[u8]) -> Option<String> {
+/// `krc1` 头 + 异或 + 压缩（zlib，部分接口为 gzip）。
+pub(crate) fn decrypt_kugou_krc(data: &[u8]) -> Option<String> {
     const FLAG: &[u8] = b"krc1";
     const KEY: [u8; 16] = [
         64, 71, 97, 119, 94, 50, 116, 71, 81, 54, 49, 45, 206, 210, 110, 105,
@@ -182,8 +184,14 @@ fn decrypt_kugou_krc(data: &[u8]) -> Option<String> {
     for (i, b) in data[4..].iter().enumerate() {
         xored.push(b ^ KEY[i & 0x0f]);
     }
-    let mut gz = GzDecoder::new(&xored[..]);
     let mut out = String::new();
-    gz.read_to_string(&mut out).ok()?;
+    if ZlibDecoder::new(&xored[..])
+        .read_to_string(&mut out)
+        .is_ok()
+    {
+        return Some(out);
+    }
+    out.clear();
+    GzDecoder::new(&xored[..]).read_to_string(&mut out).ok()?;
     Some(out)
 }
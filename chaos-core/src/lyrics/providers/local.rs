@@ -0,0 +1,304 @@
+//! 本地歌词：音频文件旁的 `.lrc`（同名优先，其次模糊匹配文件名）与文件内嵌歌词标签
+//! （ID3v2 SYLT/USLT、Vorbis `LYRICS`/`UNSYNCEDLYRICS`、MP4 `©lyr`）。
+//!
+//! 只处理带 `file_path` 的请求（[`LyricsSearchTerm::File`] 在搜索前会被解析成带路径的请求）。
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use lofty::config::ParseOptions;
+use lofty::file::{AudioFile, TaggedFileExt};
+use lofty::id3::v2::{Frame, SynchronizedTextFrame, TimestampFormat};
+use lofty::mpeg::MpegFile;
+use lofty::tag::{Accessor, ItemKey};
+use reqwest::Client;
+
+use crate::lyrics::error::LyricsError;
+use crate::lyrics::match_score::fingerprint;
+use crate::lyrics::model::{
+    LyricsSearchRequest, LyricsSearchResult, LyricsSearchTerm, LyricsService,
+};
+use crate::lyrics::parse;
+use crate::lyrics::providers::{LyricsProvider, LyricsToken};
+
+/// 文件名模糊匹配的最低相似度（`strsim::normalized_levenshtein`，按 fingerprint 比较）。
+const FUZZY_SIDECAR_THRESHOLD: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalProvider;
+
+/// 找到的一份本地歌词；`source` 为 sidecar 路径、`embedded:SYLT` 或 `embedded:lyrics`。
+#[derive(Debug, Clone)]
+struct LocalLyrics {
+    source: String,
+    content: String,
+}
+
+impl LyricsProvider for LocalProvider {
+    fn service(&self) -> LyricsService {
+        LyricsService::Local
+    }
+
+    fn search<'a>(
+        &'a self,
+        _http: &'a Client,
+        req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>> {
+        Box::pin(async move {
+            let Some(path) = audio_path(req) else {
+                return Ok(Vec::new());
+            };
+            let (title, artist) = req.term.title_artist();
+            let hint = match (title, artist) {
+                (Some(t), Some(a)) => Some(format!("{a} - {t}")),
+                (Some(t), None) => Some(t.to_string()),
+                _ => None,
+            };
+            let found = tokio::task::spawn_blocking(move || find_local_lyrics(&path, hint))
+                .await
+                .map_err(|e| LyricsError::Parse(format!("local lyrics task failed: {e}")))?;
+            Ok(found.into_iter().map(LyricsToken::new).collect())
+        })
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        _http: &'a Client,
+        token: LyricsToken,
+        req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<LyricsSearchResult, LyricsError>> {
+        Box::pin(async move {
+            let found = token.downcast::<LocalLyrics>()?;
+            let parsed = parse::parse(&found.content);
+            let (title, artist) = req.term.title_artist();
+            let album = match &req.term {
+                LyricsSearchTerm::Info { album, .. } => album.clone(),
+                _ => None,
+            };
+            Ok(LyricsSearchResult {
+                service: LyricsService::Local,
+                service_token: found.source,
+                title: title
+                    .map(str::to_string)
+                    .or_else(|| Some(req.term.description()).filter(|s| !s.is_empty())),
+                artist: artist.map(str::to_string),
+                album,
+                duration_ms: req.duration_ms,
+                match_percentage: 0,
+                quality: 0.0,
+                matched: false,
+                has_translation: parsed.has_translation(),
+                has_inline_timetags: parsed.has_word_timing(),
+                lyrics_original: found.content,
+                lyrics_translation: None,
+                offset_ms: 0,
+                debug: None,
+            })
+        })
+    }
+}
+
+fn audio_path(req: &LyricsSearchRequest) -> Option<PathBuf> {
+    match (&req.file_path, &req.term) {
+        (Some(p), _) => Some(p.clone()),
+        (None, LyricsSearchTerm::File { path }) => Some(path.clone()),
+        _ => None,
+    }
+}
+
+/// 按优先级返回：同名 `.lrc`、SYLT、其它内嵌歌词、模糊匹配的 `.lrc`（相似度降序）。
+fn find_local_lyrics(path: &Path, hint: Option<String>) -> Vec<LocalLyrics> {
+    let mut exact = Vec::new();
+    let mut fuzzy: Vec<(f64, LocalLyrics)> = Vec::new();
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let targets: Vec<String> = std::iter::once(stem.as_str())
+        .chain(hint.as_deref())
+        .map(fingerprint)
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty());
+    let entries = std::fs::read_dir(dir.unwrap_or(Path::new(".")));
+    for entry in entries.into_iter().flatten().flatten() {
+        let p = entry.path();
+        let is_lrc = p
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("lrc"));
+        if !is_lrc || !p.is_file() {
+            continue;
+        }
+        let lrc_stem = p
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let is_exact = lrc_stem.to_lowercase() == stem.to_lowercase();
+        let score = if is_exact {
+            1.0
+        } else {
+            let fp = fingerprint(&lrc_stem);
+            targets
+                .iter()
+                .map(|t| strsim::normalized_levenshtein(&fp, t))
+                .fold(0.0, f64::max)
+        };
+        if score < FUZZY_SIDECAR_THRESHOLD {
+            continue;
+        }
+        let Some(content) = read_text(&p) else {
+            continue;
+        };
+        let found = LocalLyrics {
+            source: p.to_string_lossy().to_string(),
+            content,
+        };
+        if is_exact {
+            exact.push(found);
+        } else {
+            fuzzy.push((score, found));
+        }
+    }
+
+    let mut out = exact;
+    out.extend(embedded_lyrics(path));
+    fuzzy.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    out.extend(fuzzy.into_iter().map(|(_, f)| f));
+    out
+}
+
+/// UTF-8（容错）读取并去掉 BOM；空文件返回 `None`。
+fn read_text(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let text = String::from_utf8_lossy(&bytes);
+    let text = text.trim_start_matches('\u{feff}').trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn embedded_lyrics(path: &Path) -> Vec<LocalLyrics> {
+    let mut out = Vec::new();
+    if let Some(lrc) = sylt_lyrics(path) {
+        out.push(LocalLyrics {
+            source: "embedded:SYLT".to_string(),
+            content: lrc,
+        });
+    }
+    let Ok(file) = lofty::read_from_path(path) else {
+        return out;
+    };
+    let Some(tag) = file.primary_tag().or_else(|| file.first_tag()) else {
+        return out;
+    };
+    let unsynced = tag
+        .get_string(&ItemKey::Lyrics)
+        .or_else(|| tag.get_string(&ItemKey::Unknown("UNSYNCEDLYRICS".to_string())));
+    if let Some(text) = unsynced.map(str::trim).filter(|s| !s.is_empty()) {
+        out.push(LocalLyrics {
+            source: "embedded:lyrics".to_string(),
+            content: text.to_string(),
+        });
+    }
+    out
+}
+
+/// mp3 的 SYLT 帧（毫秒时间戳）转成 LRC。
+fn sylt_lyrics(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    if ext != "mp3" {
+        return None;
+    }
+    let mut f = File::open(path).ok()?;
+    let file = MpegFile::read_from(&mut f, ParseOptions::new().read_properties(false)).ok()?;
+    let tag = file.id3v2()?;
+    tag.into_iter().find_map(|frame| {
+        let Frame::Binary(bin) = frame else {
+            return None;
+        };
+        if bin.id().as_str() != "SYLT" {
+            return None;
+        }
+        let sylt = SynchronizedTextFrame::parse(&bin.data, bin.flags()).ok()?;
+        if sylt.timestamp_format != TimestampFormat::MS || sylt.content.is_empty() {
+            return None;
+        }
+        let mut lines = sylt.content;
+        lines.sort_by_key(|(t, _)| *t);
+        Some(
+            lines
+                .iter()
+                .map(|(t, text)| format!("[{}]{}", parse::format_lrc_time(u64::from(*t)), text))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    })
+}
+
+/// 把 [`LyricsSearchTerm::File`] 解析成在线服务可用的请求：优先文件标签（标题/歌手/专辑/时长/ISRC），
+/// 其次 `歌手 - 标题` 形式的文件名，最后用文件名做关键词。
+///
+/// 这是阻塞 IO。
+pub fn resolve_file_request(req: &LyricsSearchRequest) -> LyricsSearchRequest {
+    let mut out = req.clone();
+    let LyricsSearchTerm::File { path } = &req.term else {
+        return out;
+    };
+    out.file_path = Some(path.clone());
+
+    let mut title = String::new();
+    let mut artist = String::new();
+    let mut album = None;
+    if let Ok(file) = lofty::read_from_path(path) {
+        let duration = u64::try_from(file.properties().duration().as_millis()).unwrap_or(0);
+        if out.duration_ms.is_none() && duration > 0 {
+            out.duration_ms = Some(duration);
+        }
+        if let Some(tag) = file.primary_tag().or_else(|| file.first_tag()) {
+            title = tag
+                .title()
+                .map(|s| s.trim().to_string())
+                .unwrap_or_default();
+            artist = tag
+                .artist()
+                .map(|s| s.split('\0').next().unwrap_or("").trim().to_string())
+                .unwrap_or_default();
+            album = tag
+                .album()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty());
+            if out.isrc.is_none() {
+                out.isrc = tag
+                    .get_string(&ItemKey::Isrc)
+                    .map(|s| s.trim().to_uppercase())
+                    .filter(|s| !s.is_empty());
+            }
+        }
+    }
+
+    let stem = req.term.description();
+    if title.is_empty()
+        && let Some((a, t)) = stem.split_once(" - ")
+        && !a.trim().is_empty()
+        && !t.trim().is_empty()
+    {
+        title = t.trim().to_string();
+        if artist.is_empty() {
+            artist = a.trim().to_string();
+        }
+    }
+    out.term = match (title.is_empty(), artist.is_empty()) {
+        (false, false) => LyricsSearchTerm::Info {
+            title,
+            artist,
+            album,
+        },
+        (false, true) => LyricsSearchTerm::Keyword { keyword: title },
+        _ => LyricsSearchTerm::Keyword { keyword: stem },
+    };
+    out
+}
@@ -11,23 +11,73 @@ use crate::lyrics::model::{LyricsSearchRequest, LyricsSearchResult, LyricsServic
 #[derive(Debug, Clone)]
 pub struct LrcLibProvider {
     search_url: String,
+    get_url: String,
 }
 
 impl Default for LrcLibProvider {
     fn default() -> Self {
-        Self {
-            search_url: "https://lrclib.net/api/search".to_string(),
-        }
+        Self::with_base_url("https://lrclib.net/api/search")
     }
 }
 
 impl LrcLibProvider {
+    /// `search_url` 为 `.../api/search`；精确查找用同目录下的 `.../api/get`。
     pub fn with_base_url(search_url: &str) -> Self {
+        let search_url = search_url.trim_end_matches('/').to_string();
+        let api = search_url.strip_suffix("/search").unwrap_or(&search_url);
         Self {
-            search_url: search_url.trim_end_matches('/').to_string(),
+            get_url: format!("{api}/get"),
+            search_url,
         }
     }
 
+    /// `/api/get` 精确查找：需要标题、歌手与时长（LRCLIB 按 ±2 秒匹配时长），专辑可选。
+    /// 没有记录（404）或只有纯文本歌词时返回空列表。
+    pub async fn lookup(
+        &self,
+        http: &Client,
+        req: &LyricsSearchRequest,
+        timeout: Duration,
+    ) -> Result<Vec<LrcLibToken>, LyricsError> {
+        let crate::lyrics::model::LyricsSearchTerm::Info {
+            title,
+            artist,
+            album,
+        } = &req.term
+        else {
+            return Ok(vec![]);
+        };
+        let (title, artist) = (title.trim(), artist.trim());
+        let Some(duration_ms) = req.duration_ms.filter(|v| *v > 0) else {
+            return Ok(vec![]);
+        };
+        if title.is_empty() || artist.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut q: Vec<(&str, String)> = vec![
+            ("track_name", title.to_string()),
+            ("artist_name", artist.to_string()),
+            ("duration", ((duration_ms + 500) / 1000).to_string()),
+        ];
+        if let Some(album) = album.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            q.push(("album_name", album.to_string()));
+        }
+
+        let resp = http
+            .get(&self.get_url)
+            .query(&q)
+            .timeout(timeout)
+            .send()
+            .await?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(vec![]);
+        }
+        let body = resp.error_for_status()?.bytes().await?;
+        let it: LrcLibSearchItem = serde_json::from_slice(&body)?;
+        Ok(it.into_token(0).into_iter().collect())
+    }
+
     pub async fn search(
         &self,
         http: &Client,
@@ -45,6 +95,7 @@ impl LrcLibProvider {
                 album.as_deref().unwrap_or("").trim(),
             ),
             crate::lyrics::model::LyricsSearchTerm::Keyword { keyword } => (keyword.trim(), "", ""),
+            crate::lyrics::model::LyricsSearchTerm::File { .. } => return Ok(vec![]),
         };
 
         if title.is_empty() {
@@ -72,26 +123,12 @@ impl LrcLibProvider {
         let body = resp.bytes().await?;
         let arr: Vec<LrcLibSearchItem> = serde_json::from_slice(&body)?;
 
-        let mut out = Vec::new();
-        for (idx, it) in arr.into_iter().enumerate() {
-            if idx >= 20 {
-                break;
-            }
-            let synced = it.synced_lyrics.unwrap_or_default();
-            if synced.trim().is_empty() {
-                continue;
-            }
-            out.push(LrcLibToken {
-                id: it
-                    .id
-                    .unwrap_or_else(|| format!("lrclib:{idx}:{}", fastrand::u64(..))),
-                track_name: it.track_name.unwrap_or_default(),
-                artist_name: it.artist_name.unwrap_or_default(),
-                album_name: it.album_name.unwrap_or_default(),
-                duration_ms: it.duration.map(|s| (s * 1000.0).round() as u64),
-                synced_lyrics: synced,
-            });
-        }
+        let out = arr
+            .into_iter()
+            .take(20)
+            .enumerate()
+            .filter_map(|(idx, it)| it.into_token(idx))
+            .collect();
 
         Ok(out)
     }
@@ -118,6 +155,7 @@ impl LrcLibProvider {
             has_inline_timetags,
             lyrics_original: token.synced_lyrics,
             lyrics_translation: None,
+            offset_ms: 0,
             debug: None,
         })
     }
@@ -135,7 +173,8 @@ pub struct LrcLibToken {
 
 #[derive(Debug, Deserialize)]
 struct LrcLibSearchItem {
-    #[serde(default)]
+    /// LRCLIB 返回数字 id；兼容字符串。
+    #[serde(default, deserialize_with = "de_id")]
     id: Option<String>,
     #[serde(rename = "trackName")]
     #[serde(default)]
@@ -154,6 +193,37 @@ struct LrcLibSearchItem {
     synced_lyrics: Option<String>,
 }
 
+impl LrcLibSearchItem {
+    /// 没有同步歌词的条目（纯文本/纯音乐）返回 `None`。
+    fn into_token(self, idx: usize) -> Option<LrcLibToken> {
+        let synced = self.synced_lyrics.unwrap_or_default();
+        if synced.trim().is_empty() {
+            return None;
+        }
+        Some(LrcLibToken {
+            id: self
+                .id
+                .unwrap_or_else(|| format!("lrclib:{idx}:{}", fastrand::u64(..))),
+            track_name: self.track_name.unwrap_or_default(),
+            artist_name: self.artist_name.unwrap_or_default(),
+            album_name: self.album_name.unwrap_or_default(),
+            duration_ms: self.duration.map(|s| (s * 1000.0).round() as u64),
+            synced_lyrics: synced,
+        })
+    }
+}
+
+fn de_id<'de, D>(d: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(match Option::<serde_json::Value>::deserialize(d)? {
+        Some(serde_json::Value::String(s)) => Some(s),
+        Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
 fn looks_like_lrc(s: &str) -> bool {
     // Quick heuristic to drive UI/quality bonuses.
     static RE: OnceLock<Regex> = OnceLock::new();
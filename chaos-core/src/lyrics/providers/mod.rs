@@ -1,12 +1,17 @@
 mod gecimi;
-mod kugou;
+pub(crate) mod kugou;
+mod local;
 mod lrclib;
 mod netease;
 mod qq;
 mod syair;
 
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
+use futures::future::BoxFuture;
 use reqwest::Client;
 
 use crate::lyrics::error::LyricsError;
@@ -14,11 +19,81 @@ use crate::lyrics::model::{LyricsSearchRequest, LyricsSearchResult, LyricsServic
 
 pub use gecimi::GecimiProvider;
 pub use kugou::KugouProvider;
+pub use local::{LocalProvider, resolve_file_request};
 pub use lrclib::LrcLibProvider;
 pub use netease::NeteaseProvider;
 pub use qq::QqMusicProvider;
 pub use syair::SyairProvider;
 
+/// 歌词源：`search` 返回候选 token，`fetch` 按 token 拉取歌词。
+///
+/// 内置的各服务通过 [`Provider`] 实现；调用方可以实现该 trait（内部歌词服务器、本地目录等）
+/// 并注册到 [`crate::lyrics::registry::LyricsProviderRegistry`]，无需修改 chaos-core。
+pub trait LyricsProvider: Send + Sync {
+    /// 结果中的 `service`；自定义源用 [`LyricsService::custom`] 生成。
+    fn service(&self) -> LyricsService;
+
+    /// 精确查找（ISRC，或标题 + 歌手 + 专辑 + 时长）；在 `search` 之前调用，有结果时不再模糊搜索。
+    /// 不支持或信息不足时返回空列表（默认实现）。
+    fn lookup<'a>(
+        &'a self,
+        _http: &'a Client,
+        _req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    fn search<'a>(
+        &'a self,
+        http: &'a Client,
+        req: &'a LyricsSearchRequest,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>>;
+
+    fn fetch<'a>(
+        &'a self,
+        http: &'a Client,
+        token: LyricsToken,
+        req: &'a LyricsSearchRequest,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<LyricsSearchResult, LyricsError>>;
+}
+
+/// `search` 与 `fetch` 之间传递的不透明 token，由各 provider 自行定义内容。
+pub struct LyricsToken(Box<dyn Any + Send + Sync>);
+
+impl LyricsToken {
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        Self(Box::new(value))
+    }
+
+    pub fn downcast<T: Any>(self) -> Result<T, LyricsError> {
+        self.0
+            .downcast::<T>()
+            .map(|b| *b)
+            .map_err(|_| LyricsError::Parse("provider token does not match provider".to_string()))
+    }
+
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref::<T>()
+    }
+}
+
+impl fmt::Debug for LyricsToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LyricsToken(..)")
+    }
+}
+
+/// 内置服务的默认 provider（含本地歌词）；自定义服务返回 `None`。
+pub fn builtin_provider(service: LyricsService) -> Option<Arc<dyn LyricsProvider>> {
+    match service {
+        LyricsService::Local => Some(Arc::new(LocalProvider)),
+        s => Provider::builtin(s).map(|p| Arc::new(p) as Arc<dyn LyricsProvider>),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ProviderToken {
     Netease(netease::NeteaseToken),
@@ -40,6 +115,19 @@ pub enum Provider {
 }
 
 impl Provider {
+    /// 内置在线服务的默认实现；`Local` 与自定义服务返回 `None`（见 [`builtin_provider`]）。
+    pub fn builtin(service: LyricsService) -> Option<Self> {
+        Some(match service {
+            LyricsService::Netease => Self::Netease(Default::default()),
+            LyricsService::QQMusic => Self::QQ(Default::default()),
+            LyricsService::Kugou => Self::Kugou(Default::default()),
+            LyricsService::LrcLib => Self::LrcLib(Default::default()),
+            LyricsService::Gecimi => Self::Gecimi(Default::default()),
+            LyricsService::Syair => Self::Syair(Default::default()),
+            LyricsService::Local | LyricsService::Custom(_) => return None,
+        })
+    }
+
     pub fn service(&self) -> LyricsService {
         match self {
             Self::Netease(_) => LyricsService::Netease,
@@ -85,6 +173,22 @@ impl Provider {
         }
     }
 
+    /// 精确查找；目前只有 LRCLIB（`/api/get`）支持，其它服务返回空列表。
+    pub async fn lookup(
+        &self,
+        http: &Client,
+        req: &LyricsSearchRequest,
+        timeout: Duration,
+    ) -> Result<Vec<ProviderToken>, LyricsError> {
+        match self {
+            Self::LrcLib(p) => p
+                .lookup(http, req, timeout)
+                .await
+                .map(|v| v.into_iter().map(ProviderToken::LrcLib).collect()),
+            _ => Ok(Vec::new()),
+        }
+    }
+
     pub async fn fetch(
         &self,
         http: &Client,
@@ -105,3 +209,48 @@ impl Provider {
         }
     }
 }
+
+impl LyricsProvider for Provider {
+    fn service(&self) -> LyricsService {
+        Provider::service(self)
+    }
+
+    fn lookup<'a>(
+        &'a self,
+        http: &'a Client,
+        req: &'a LyricsSearchRequest,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>> {
+        Box::pin(async move {
+            Provider::lookup(self, http, req, timeout)
+                .await
+                .map(|v| v.into_iter().map(LyricsToken::new).collect())
+        })
+    }
+
+    fn search<'a>(
+        &'a self,
+        http: &'a Client,
+        req: &'a LyricsSearchRequest,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>> {
+        Box::pin(async move {
+            Provider::search(self, http, req, timeout)
+                .await
+                .map(|v| v.into_iter().map(LyricsToken::new).collect())
+        })
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        http: &'a Client,
+        token: LyricsToken,
+        req: &'a LyricsSearchRequest,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<LyricsSearchResult, LyricsError>> {
+        Box::pin(async move {
+            let token = token.downcast::<ProviderToken>()?;
+            Provider::fetch(self, http, token, req, timeout).await
+        })
+    }
+}
@@ -181,6 +181,7 @@ impl NeteaseProvider {
             has_inline_timetags,
             lyrics_original,
             lyrics_translation,
+            offset_ms: 0,
             debug: None,
         })
     }
@@ -134,6 +134,7 @@ impl QqMusicProvider {
             has_inline_timetags: false,
             lyrics_original: lyric_str,
             lyrics_translation: trans_str,
+            offset_ms: 0,
             debug: None,
         })
     }
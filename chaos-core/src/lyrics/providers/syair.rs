@@ -48,6 +48,7 @@ impl SyairProvider {
             LyricsSearchTerm::Keyword { keyword } => {
                 params.push(("q", keyword.clone()));
             }
+            LyricsSearchTerm::File { .. } => params.push(("q", req.term.description())),
         }
 
         let resp = http
@@ -125,6 +126,7 @@ impl SyairProvider {
             has_inline_timetags: false,
             lyrics_original: text,
             lyrics_translation: None,
+            offset_ms: 0,
             debug: None,
         })
     }
@@ -23,9 +23,10 @@ pub fn is_matched(result: &LyricsSearchResult, req: &LyricsSearchRequest) -> boo
             artist: sa,
             ..
         } => is_case_insensitive_similar(title, st) && is_case_insensitive_similar(artist, sa),
-        LyricsSearchTerm::Keyword { keyword } => {
-            is_case_insensitive_similar(title, keyword)
-                && is_case_insensitive_similar(artist, keyword)
+        LyricsSearchTerm::Keyword { .. } | LyricsSearchTerm::File { .. } => {
+            let keyword = req.term.description();
+            is_case_insensitive_similar(title, &keyword)
+                && is_case_insensitive_similar(artist, &keyword)
         }
     }
 }
@@ -67,11 +68,12 @@ fn artist_quality(result: &LyricsSearchResult, req: &LyricsSearchRequest) -> f64
             }
             similarity(artist, sa)
         }
-        LyricsSearchTerm::Keyword { keyword } => {
+        LyricsSearchTerm::Keyword { .. } | LyricsSearchTerm::File { .. } => {
+            let keyword = req.term.description();
             if keyword.contains(artist) {
                 return MATCHED_ARTIST_FACTOR;
             }
-            similarity_in(artist, keyword)
+            similarity_in(artist, &keyword)
         }
     }
 }
@@ -87,11 +89,12 @@ fn title_quality(result: &LyricsSearchResult, req: &LyricsSearchRequest) -> f64
             }
             similarity(title, st)
         }
-        LyricsSearchTerm::Keyword { keyword } => {
+        LyricsSearchTerm::Keyword { .. } | LyricsSearchTerm::File { .. } => {
+            let keyword = req.term.description();
             if keyword.contains(title) {
                 return MATCHED_TITLE_FACTOR;
             }
-            similarity_in(title, keyword)
+            similarity_in(title, &keyword)
         }
     }
 }
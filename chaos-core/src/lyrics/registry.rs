@@ -0,0 +1,84 @@
+//! 歌词源注册表：`search_with_http` 按 `LyricsSearchOptions::services` 的顺序从这里取 provider。
+//!
+//! 默认包含全部内置服务；调用方可以在运行时注册自定义 provider（同一 `service` 后注册的覆盖先注册的）。
+
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::lyrics::model::LyricsService;
+use crate::lyrics::providers::{LyricsProvider, builtin_provider};
+
+pub struct LyricsProviderRegistry {
+    providers: RwLock<Vec<Arc<dyn LyricsProvider>>>,
+}
+
+impl std::fmt::Debug for LyricsProviderRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LyricsProviderRegistry")
+            .field("services", &self.services())
+            .finish()
+    }
+}
+
+impl Default for LyricsProviderRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+impl LyricsProviderRegistry {
+    pub fn empty() -> Self {
+        Self {
+            providers: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// 全部内置服务（在线服务与本地歌词）。
+    pub fn with_builtins() -> Self {
+        let reg = Self::empty();
+        for s in LyricsService::BUILTIN {
+            if let Some(p) = builtin_provider(s) {
+                reg.register(p);
+            }
+        }
+        reg
+    }
+
+    /// 进程级注册表（`search_with_http` 默认使用它）。
+    pub fn global() -> &'static LyricsProviderRegistry {
+        static GLOBAL: OnceLock<LyricsProviderRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(Self::with_builtins)
+    }
+
+    /// 注册 provider；已有同一 `service` 的 provider 时替换它。
+    pub fn register(&self, provider: Arc<dyn LyricsProvider>) {
+        let service = provider.service();
+        let mut list = self.providers.write().unwrap_or_else(|e| e.into_inner());
+        match list.iter_mut().find(|p| p.service() == service) {
+            Some(slot) => *slot = provider,
+            None => list.push(provider),
+        }
+    }
+
+    pub fn unregister(&self, service: LyricsService) -> bool {
+        let mut list = self.providers.write().unwrap_or_else(|e| e.into_inner());
+        let before = list.len();
+        list.retain(|p| p.service() != service);
+        list.len() != before
+    }
+
+    pub fn get(&self, service: LyricsService) -> Option<Arc<dyn LyricsProvider>> {
+        let list = self.providers.read().unwrap_or_else(|e| e.into_inner());
+        list.iter().find(|p| p.service() == service).cloned()
+    }
+
+    /// 已注册的服务（注册顺序）。
+    pub fn services(&self) -> Vec<LyricsService> {
+        let list = self.providers.read().unwrap_or_else(|e| e.into_inner());
+        list.iter().map(|p| p.service()).collect()
+    }
+
+    /// 按 `services` 顺序取出已注册的 provider，未注册的服务被跳过。
+    pub fn resolve(&self, services: &[LyricsService]) -> Vec<Arc<dyn LyricsProvider>> {
+        services.iter().filter_map(|s| self.get(*s)).collect()
+    }
+}
@@ -0,0 +1,441 @@
+//! 歌词罗马音：假名 → 平文式罗马字（Hepburn），谚文 → 韩文罗马字（Revised Romanization），
+//! 汉字 → 拼音。
+//!
+//! 按行判断语种：含假名的行视为日语（汉字原样保留，没有读音词典），否则含谚文视为韩语，
+//! 否则含汉字视为中文；其它行（拉丁字母等）不生成罗马音。韩语只处理连音与流音化，
+//! 不做鼻音化/紧音化等其它音变。
+
+use pinyin::ToPinyin;
+
+use crate::lyrics::parse::{Lyrics, LyricsLine};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RomanizeOptions {
+    /// 拼音带声调符号（`nǐ hǎo`）；关闭时输出 `ni hao`。
+    pub pinyin_tone_marks: bool,
+}
+
+impl Default for RomanizeOptions {
+    fn default() -> Self {
+        Self {
+            pinyin_tone_marks: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    Japanese,
+    Korean,
+    Chinese,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Kana,
+    Hangul,
+    Han,
+    Other,
+}
+
+fn classify(c: char) -> CharClass {
+    match c {
+        '\u{3041}'..='\u{3096}' | '\u{30A1}'..='\u{30FA}' | 'ー' => CharClass::Kana,
+        '\u{AC00}'..='\u{D7A3}' => CharClass::Hangul,
+        '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '\u{F900}'..='\u{FAFF}' | '〆' => {
+            CharClass::Han
+        }
+        _ => CharClass::Other,
+    }
+}
+
+/// 判断一行歌词需要哪种罗马音；没有可转写的字符时返回 `None`。
+pub fn detect_script(text: &str) -> Option<Script> {
+    let mut hangul = false;
+    let mut han = false;
+    for c in text.chars() {
+        match classify(c) {
+            CharClass::Kana => return Some(Script::Japanese),
+            CharClass::Hangul => hangul = true,
+            CharClass::Han => han = true,
+            CharClass::Other => {}
+        }
+    }
+    if hangul {
+        Some(Script::Korean)
+    } else if han {
+        Some(Script::Chinese)
+    } else {
+        None
+    }
+}
+
+/// 转写一行文本；不需要转写时返回 `None`。
+pub fn romanize_text(text: &str, opt: RomanizeOptions) -> Option<String> {
+    let script = detect_script(text)?;
+    let mut segments: Vec<String> = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let class = classify(c);
+        let mut run = String::from(c);
+        while let Some(&n) = chars.peek() {
+            if classify(n) != class {
+                break;
+            }
+            run.push(n);
+            chars.next();
+        }
+        let seg = match (script, class) {
+            (Script::Japanese, CharClass::Kana) => kana_to_romaji(&run),
+            (Script::Korean, CharClass::Hangul) => hangul_to_rr(&run),
+            (Script::Chinese, CharClass::Han) => han_to_pinyin(&run, opt.pinyin_tone_marks),
+            _ => run,
+        };
+        if !seg.is_empty() {
+            segments.push(seg);
+        }
+    }
+    let mut out = String::new();
+    for seg in segments {
+        let glue = out.chars().next_back().is_some_and(char::is_alphanumeric)
+            && seg.chars().next().is_some_and(char::is_alphanumeric);
+        if glue {
+            out.push(' ');
+        }
+        out.push_str(&seg);
+    }
+    Some(out.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// 生成与原歌词同一时间轴的罗马音歌词：每行文本替换为罗马音（不需要转写的行保留原文），
+/// 逐字时间按字分别转写；不带翻译。可直接 [`Lyrics::to_lrc`] 输出。
+pub fn romanize_lyrics(lyrics: &Lyrics, opt: RomanizeOptions) -> Lyrics {
+    let lines = lyrics
+        .lines
+        .iter()
+        .map(|l| {
+            let last = l.words.len().saturating_sub(1);
+            let words = l
+                .words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| {
+                    let mut w = w.clone();
+                    if let Some(r) = romanize_text(&w.text, opt) {
+                        // 逐字拼接时罗马音之间需要空格分隔。
+                        let pad = if i < last { " " } else { "" };
+                        w.text = format!("{r}{pad}");
+                    }
+                    w
+                })
+                .collect();
+            LyricsLine {
+                start_ms: l.start_ms,
+                end_ms: l.end_ms,
+                text: romanize_text(&l.text, opt).unwrap_or_else(|| l.text.clone()),
+                words,
+                translation: None,
+            }
+        })
+        .collect();
+    Lyrics {
+        metadata: lyrics.metadata.clone(),
+        lines,
+    }
+}
+
+/// 是否有任何一行需要罗马音。
+pub fn needs_romanization(lyrics: &Lyrics) -> bool {
+    lyrics
+        .lines
+        .iter()
+        .any(|l| detect_script(&l.text).is_some())
+}
+
+fn han_to_pinyin(run: &str, tone_marks: bool) -> String {
+    run.chars()
+        .map(|c| match c.to_pinyin() {
+            Some(p) if tone_marks => p.with_tone().to_string(),
+            Some(p) => p.plain().to_string(),
+            None => c.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ---- 假名 ----
+
+fn to_katakana(c: char) -> char {
+    match c {
+        '\u{3041}'..='\u{3096}' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+fn kana_romaji(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ア' | 'ァ' => "a",
+        'イ' | 'ィ' => "i",
+        'ウ' | 'ゥ' => "u",
+        'エ' | 'ェ' => "e",
+        'オ' | 'ォ' => "o",
+        'カ' | 'ヵ' => "ka",
+        'キ' => "ki",
+        'ク' => "ku",
+        'ケ' | 'ヶ' => "ke",
+        'コ' => "ko",
+        'ガ' => "ga",
+        'ギ' => "gi",
+        'グ' => "gu",
+        'ゲ' => "ge",
+        'ゴ' => "go",
+        'サ' => "sa",
+        'シ' => "shi",
+        'ス' => "su",
+        'セ' => "se",
+        'ソ' => "so",
+        'ザ' => "za",
+        'ジ' | 'ヂ' => "ji",
+        'ズ' | 'ヅ' => "zu",
+        'ゼ' => "ze",
+        'ゾ' => "zo",
+        'タ' => "ta",
+        'チ' => "chi",
+        'ツ' => "tsu",
+        'テ' => "te",
+        'ト' => "to",
+        'ダ' => "da",
+        'デ' => "de",
+        'ド' => "do",
+        'ナ' => "na",
+        'ニ' => "ni",
+        'ヌ' => "nu",
+        'ネ' => "ne",
+        'ノ' => "no",
+        'ハ' => "ha",
+        'ヒ' => "hi",
+        'フ' => "fu",
+        'ヘ' => "he",
+        'ホ' => "ho",
+        'バ' => "ba",
+        'ビ' => "bi",
+        'ブ' => "bu",
+        'ベ' => "be",
+        'ボ' => "bo",
+        'パ' => "pa",
+        'ピ' => "pi",
+        'プ' => "pu",
+        'ペ' => "pe",
+        'ポ' => "po",
+        'マ' => "ma",
+        'ミ' => "mi",
+        'ム' => "mu",
+        'メ' => "me",
+        'モ' => "mo",
+        'ヤ' | 'ャ' => "ya",
+        'ユ' | 'ュ' => "yu",
+        'ヨ' | 'ョ' => "yo",
+        'ラ' => "ra",
+        'リ' => "ri",
+        'ル' => "ru",
+        'レ' => "re",
+        'ロ' => "ro",
+        'ワ' | 'ヮ' => "wa",
+        'ヰ' => "i",
+        'ヱ' => "e",
+        'ヲ' => "o",
+        'ン' => "n",
+        'ヴ' => "vu",
+        'ヷ' => "va",
+        'ヸ' => "vi",
+        'ヹ' => "ve",
+        'ヺ' => "vo",
+        _ => return None,
+    })
+}
+
+fn small_y_vowel(c: char) -> Option<char> {
+    match c {
+        'ャ' => Some('a'),
+        'ュ' => Some('u'),
+        'ョ' => Some('o'),
+        _ => None,
+    }
+}
+
+fn small_vowel(c: char) -> Option<char> {
+    match c {
+        'ァ' => Some('a'),
+        'ィ' => Some('i'),
+        'ゥ' => Some('u'),
+        'ェ' => Some('e'),
+        'ォ' => Some('o'),
+        _ => None,
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'i' | 'u' | 'e' | 'o')
+}
+
+/// 假名串转平文式罗马字：拗音（きゃ → kya）、外来语小写元音（ティ → ti）、
+/// 促音双写辅音（っち → tchi）、`ん` 在元音/y 前写作 `n'`，长音符 `ー` 重复前一个元音。
+fn kana_to_romaji(run: &str) -> String {
+    let kana: Vec<char> = run.chars().map(to_katakana).collect();
+    let mut out = String::new();
+    let mut geminate = false;
+    let mut i = 0;
+    while i < kana.len() {
+        let c = kana[i];
+        i += 1;
+        if c == 'ッ' {
+            geminate = true;
+            continue;
+        }
+        if c == 'ー' {
+            if let Some(v) = out.chars().rev().find(|c| is_vowel(*c)) {
+                out.push(v);
+            }
+            continue;
+        }
+        let Some(base) = kana_romaji(c) else {
+            continue;
+        };
+        let next = kana.get(i).copied();
+        let mut syl = base.to_string();
+        if c == 'ン' {
+            let before_vowel = next
+                .and_then(kana_romaji)
+                .and_then(|r| r.chars().next())
+                .is_some_and(|f| is_vowel(f) || f == 'y');
+            if before_vowel {
+                syl.push('\'');
+            }
+        } else if let Some(v) = next.and_then(small_y_vowel)
+            && base.len() > 1
+            && base.ends_with('i')
+        {
+            let stem = &base[..base.len() - 1];
+            syl = if matches!(stem, "sh" | "ch" | "j") {
+                format!("{stem}{v}")
+            } else {
+                format!("{stem}y{v}")
+            };
+            i += 1;
+        } else if let Some(v) = next.and_then(small_vowel) {
+            let stem = base.trim_end_matches(is_vowel);
+            let stem = if stem.is_empty() && base == "u" {
+                "w"
+            } else {
+                stem
+            };
+            if !stem.is_empty() {
+                syl = format!("{stem}{v}");
+                i += 1;
+            }
+        }
+        if geminate {
+            geminate = false;
+            if syl.starts_with("ch") {
+                out.push('t');
+            } else if let Some(f) = syl.chars().next().filter(|f| !is_vowel(*f) && *f != 'n') {
+                out.push(f);
+            }
+        }
+        out.push_str(&syl);
+    }
+    out
+}
+
+// ---- 谚文 ----
+
+const HANGUL_INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p",
+    "h",
+];
+
+const HANGUL_MEDIALS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we",
+    "wi", "yu", "eu", "ui", "i",
+];
+
+/// 收音在词尾/辅音前的读法。
+const HANGUL_FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p",
+    "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+
+/// 收音后接 `ㅇ` 声母时的连音：(留在本音节的部分, 移到下一音节的声母)。
+const HANGUL_LIAISON: [(&str, &str); 28] = [
+    ("", ""),
+    ("", "g"),
+    ("", "kk"),
+    ("k", "s"),
+    ("", "n"),
+    ("n", "j"),
+    ("n", ""),
+    ("", "d"),
+    ("", "r"),
+    ("l", "g"),
+    ("l", "m"),
+    ("l", "b"),
+    ("l", "s"),
+    ("l", "t"),
+    ("l", "p"),
+    ("l", ""),
+    ("", "m"),
+    ("", "b"),
+    ("p", "s"),
+    ("", "s"),
+    ("", "ss"),
+    ("ng", ""),
+    ("", "j"),
+    ("", "ch"),
+    ("", "k"),
+    ("", "t"),
+    ("", "p"),
+    ("", ""),
+];
+
+const IEUNG_INITIAL: usize = 11;
+const NIEUN_INITIAL: usize = 2;
+const RIEUL_INITIAL: usize = 5;
+const NIEUN_FINAL: usize = 4;
+const RIEUL_FINAL: usize = 8;
+
+fn hangul_jamo(c: char) -> Option<(usize, usize, usize)> {
+    let s = (c as u32).checked_sub(0xAC00)? as usize;
+    (s < 11172).then_some((s / 588, (s % 588) / 28, s % 28))
+}
+
+/// 谚文音节串转 Revised Romanization。
+fn hangul_to_rr(run: &str) -> String {
+    let syllables: Vec<(usize, usize, usize)> = run.chars().filter_map(hangul_jamo).collect();
+    let mut out = String::new();
+    // 上一音节连音移过来的声母。
+    let mut carried: Option<&str> = None;
+    for (idx, &(l, v, t)) in syllables.iter().enumerate() {
+        let initial = carried.take().unwrap_or(HANGUL_INITIALS[l]);
+        out.push_str(initial);
+        out.push_str(HANGUL_MEDIALS[v]);
+        let next_initial = syllables.get(idx + 1).map(|s| s.0);
+        match next_initial {
+            Some(IEUNG_INITIAL) => {
+                let (keep, moved) = HANGUL_LIAISON[t];
+                out.push_str(keep);
+                carried = Some(moved);
+            }
+            // 流音化：ㄹㄹ / ㄹㄴ / ㄴㄹ 都读作 ll。
+            Some(RIEUL_INITIAL | NIEUN_INITIAL) if t == RIEUL_FINAL => {
+                out.push('l');
+                carried = Some("l");
+            }
+            Some(RIEUL_INITIAL) if t == NIEUN_FINAL => {
+                out.push('l');
+                carried = Some("l");
+            }
+            _ => out.push_str(HANGUL_FINALS[t]),
+        }
+    }
+    out
+}
@@ -0,0 +1,121 @@
+//! 用 [`LlmClient`] 补全缺失的歌词翻译。
+//!
+//! 只翻译原文非空且没有翻译的行；按行号发给模型、按行号写回，时间轴不变。
+//! 模型漏掉的行保持无翻译。
+
+use std::collections::HashMap;
+
+use crate::llm::{ChatMessage, ChatRequest, LlmClient, ReasoningMode};
+use crate::lyrics::error::LyricsError;
+use crate::lyrics::model::LyricsSearchResult;
+use crate::lyrics::parse::{self, Lyrics};
+
+/// 每次请求最多发送的行数。
+pub const TRANSLATE_BATCH_LINES: usize = 40;
+
+const SYSTEM_PROMPT: &str = "You translate song lyrics. The user sends a JSON object mapping \
+line numbers to lyric lines. Reply with ONLY a JSON object mapping the same line numbers to \
+their translations. Keep every number, translate each line on its own, do not merge or split \
+lines, and do not add commentary.";
+
+/// 为缺少翻译的行补上 `target_language`（如 `"简体中文"`、`"English"`）译文，返回补上的行数。
+pub async fn translate_missing_lines(
+    client: &LlmClient,
+    lyrics: &mut Lyrics,
+    target_language: &str,
+) -> Result<usize, LyricsError> {
+    let pending: Vec<usize> = lyrics
+        .lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.translation.is_none() && !l.text.trim().is_empty())
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut filled = 0;
+    for batch in pending.chunks(TRANSLATE_BATCH_LINES) {
+        let input: serde_json::Map<String, serde_json::Value> = batch
+            .iter()
+            .map(|&i| (i.to_string(), lyrics.lines[i].text.trim().into()))
+            .collect();
+        let req = ChatRequest {
+            system: Some(SYSTEM_PROMPT.to_string()),
+            messages: vec![ChatMessage {
+                role: "user".to_string(),
+                content: format!(
+                    "Target language: {target_language}\n{}",
+                    serde_json::Value::Object(input)
+                ),
+            }],
+            reasoning_mode: ReasoningMode::Normal,
+            temperature: Some(0.2),
+            max_tokens: None,
+        };
+        let resp = client.chat(req).await?;
+        for (i, text) in parse_numbered_reply(&resp.text)? {
+            if !batch.contains(&i) {
+                continue;
+            }
+            let text = text.trim();
+            if !text.is_empty() {
+                lyrics.lines[i].translation = Some(text.to_string());
+                filled += 1;
+            }
+        }
+    }
+    Ok(filled)
+}
+
+/// 在搜索结果上补全翻译：成功补上至少一行时把 `lyrics_translation` 重写为与原文同时间戳的 LRC。
+pub async fn translate_result(
+    client: &LlmClient,
+    result: &mut LyricsSearchResult,
+    target_language: &str,
+) -> Result<usize, LyricsError> {
+    let mut lyrics = parse::parse_with_translation(
+        &result.lyrics_original,
+        result.lyrics_translation.as_deref(),
+    );
+    if lyrics.lines.is_empty() {
+        return Ok(0);
+    }
+    let filled = translate_missing_lines(client, &mut lyrics, target_language).await?;
+    if filled > 0 {
+        result.lyrics_translation = Some(translation_lrc(&lyrics));
+        result.has_translation = true;
+    }
+    Ok(filled)
+}
+
+/// 只含译文行的 LRC（时间戳取原文行，`[offset:]` 与原文一致，便于再次按时间对齐）。
+pub fn translation_lrc(lyrics: &Lyrics) -> String {
+    let mut out = String::new();
+    if lyrics.metadata.offset_ms != 0 {
+        out.push_str(&format!("[offset:{}]\n", lyrics.metadata.offset_ms));
+    }
+    for l in &lyrics.lines {
+        if let Some(tr) = &l.translation {
+            out.push_str(&format!("[{}]{tr}\n", parse::format_lrc_time(l.start_ms)));
+        }
+    }
+    out
+}
+
+/// 解析模型回复里的 `{"行号": "译文"}`；容忍 ```json 代码块等包裹。
+fn parse_numbered_reply(text: &str) -> Result<HashMap<usize, String>, LyricsError> {
+    let span = text.find('{').zip(text.rfind('}')).filter(|(s, e)| s < e);
+    let Some((start, end)) = span else {
+        return Err(LyricsError::Parse(
+            "llm reply contains no JSON object".to_string(),
+        ));
+    };
+    let map: HashMap<String, serde_json::Value> = serde_json::from_str(&text[start..=end])?;
+    Ok(map
+        .into_iter()
+        .filter_map(|(k, v)| {
+            let i = k.trim().parse().ok()?;
+            let s = v.as_str()?.to_string();
+            Some((i, s))
+        })
+        .collect())
+}
@@ -47,8 +47,10 @@ pub fn decode_xml_entities(input: &str) -> Cow<'_, str> {
     let bytes = input.as_bytes();
     while i < bytes.len() {
         if bytes[i] != b'&' {
-            out.push(bytes[i] as char);
-            i += 1;
+            // 按整段拷贝，避免把多字节 UTF-8 拆成单字节字符。
+            let next = input[i..].find('&').map_or(bytes.len(), |off| i + off);
+            out.push_str(&input[i..next]);
+            i = next;
             continue;
         }
         // Find ';'
@@ -0,0 +1,793 @@
+//! 音乐批量下载引擎：展开下载目标（单曲/专辑/歌手全部专辑）-> 并发下载 -> 转码/歌词/标签/响度后处理。
+//!
+//! daemon / FFI / CLI 共用同一套逻辑：通过 [`DownloadJob::status`] 获取 `MusicDownloadStatus`
+//! 快照，通过 [`DownloadJob::subscribe`] 订阅进度事件，[`DownloadJob::cancel`] 取消。
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use tokio::sync::{broadcast, watch};
+
+use super::client::MusicClient;
+use super::error::MusicError;
+use super::library::{LibraryIndex, LibraryMatch};
+use super::matching::{self, MatchOptions};
+use super::model::{
+    AuthState, MusicDownloadJobResult, MusicDownloadOptions, MusicDownloadStatus,
+    MusicDownloadTarget, MusicDownloadTotals, MusicJobState, MusicPostProcessOptions, MusicService,
+    MusicTrack,
+};
+use super::{download, postprocess, providers, tagging, util};
+use crate::download_scheduler::{DownloadScheduler, DownloadTask};
+use crate::lyrics;
+
+const SKIPPED_EXISTS: &str = "skipped: target exists";
+const SKIPPED_OWNED: &str = "skipped: already in library";
+
+/// 展开后的单个下载项；`track_no` 为专辑内序号（从 1 开始，单曲为 `None`）。
+#[derive(Debug, Clone)]
+pub struct BatchItem {
+    pub track: MusicTrack,
+    pub track_no: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadJobOptions {
+    pub download: MusicDownloadOptions,
+    /// 下载成功后 best-effort 写出同名 `.lrc`。
+    pub fetch_lyrics: bool,
+    /// 下载成功后 best-effort 写入音频标签（封面/歌词）。
+    pub write_tags: bool,
+    /// 共享调度器中的任务；`None` 时按 `download.priority` 在全局调度器中创建。
+    pub task: Option<DownloadTask>,
+}
+
+impl DownloadJobOptions {
+    pub fn new(download: MusicDownloadOptions) -> Self {
+        Self {
+            download,
+            fetch_lyrics: true,
+            write_tags: true,
+            task: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadEvent {
+    /// 某个 job 状态变化（开始运行 / 完成 / 跳过 / 失败）。
+    Job(MusicDownloadJobResult),
+    /// 全部结束（含取消），之后不会再有事件。
+    Finished(MusicDownloadTotals),
+}
+
+/// 目标所属的服务。
+pub fn target_service(target: &MusicDownloadTarget) -> MusicService {
+    match target {
+        MusicDownloadTarget::Track { track } => track.service,
+        MusicDownloadTarget::Album { service, .. } => *service,
+        MusicDownloadTarget::ArtistAll { service, .. } => *service,
+        MusicDownloadTarget::Playlist { service, .. } => *service,
+    }
+}
+
+/// 按请求的音质选择该曲目实际可用的音质 id（不可用时按 `quality_fallback_order` 降级）。
+pub fn choose_quality_id(track: &MusicTrack, requested: &str) -> Option<String> {
+    let req = requested.trim();
+    if req.is_empty() {
+        return None;
+    }
+    if track.qualities.iter().any(|q| q.id == req) {
+        return Some(req.to_string());
+    }
+    for q in util::quality_fallback_order() {
+        if track.qualities.iter().any(|x| x.id == q) {
+            return Some(q.to_string());
+        }
+    }
+    None
+}
+
+/// `current` 之后（更低）且该曲目可用的下一个音质；用于账号无权获取当前音质时降级。
+fn lower_quality_id(track: &MusicTrack, current: &str) -> Option<String> {
+    util::quality_fallback_order()
+        .into_iter()
+        .skip_while(|q| *q != current)
+        .skip(1)
+        .find(|q| track.qualities.iter().any(|x| x.id == *q))
+        .map(|q| q.to_string())
+}
+
+/// 展开下载目标；歌手全部专辑按曲目 id 去重，单个专辑拉取失败时跳过该专辑。
+///
+/// 歌单曲目同样按 id 去重，且不写入音轨号（歌单顺序不是专辑曲序）；`auth` 用于私密歌单。
+pub async fn resolve_target(
+    client: &MusicClient,
+    target: MusicDownloadTarget,
+    auth: &AuthState,
+) -> Result<Vec<BatchItem>, MusicError> {
+    let mut items = Vec::new();
+    match target {
+        MusicDownloadTarget::Track { track } => items.push(BatchItem {
+            track: *track,
+            track_no: None,
+        }),
+        MusicDownloadTarget::Album { service, album_id } => {
+            let tracks = client.album_tracks(service, &album_id).await?;
+            for (idx, track) in tracks.into_iter().enumerate() {
+                items.push(BatchItem {
+                    track,
+                    track_no: Some((idx as u32) + 1),
+                });
+            }
+        }
+        MusicDownloadTarget::ArtistAll { service, artist_id } => {
+            let albums = client.artist_albums(service, &artist_id).await?;
+            let mut seen: HashSet<String> = HashSet::new();
+            for alb in albums {
+                let tracks = client
+                    .album_tracks(service, &alb.id)
+                    .await
+                    .unwrap_or_default();
+                for (idx, mut track) in tracks.into_iter().enumerate() {
+                    if !seen.insert(track.id.clone()) {
+                        continue;
+                    }
+                    if track.album.is_none() {
+                        track.album = Some(alb.title.clone());
+                    }
+                    items.push(BatchItem {
+                        track,
+                        track_no: Some((idx as u32) + 1),
+                    });
+                }
+            }
+        }
+        MusicDownloadTarget::Playlist {
+            service,
+            playlist_id,
+        } => {
+            let tracks = client.playlist_tracks(service, &playlist_id, auth).await?;
+            let mut seen: HashSet<String> = HashSet::new();
+            for track in tracks {
+                if seen.insert(track.id.clone()) {
+                    items.push(BatchItem {
+                        track,
+                        track_no: None,
+                    });
+                }
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// 写出同名 `.lrc`（已存在且不覆盖时保留原文件），返回原文歌词用于写入标签。
+///
+/// 优先按 `source`（实际下载的曲目）的 id 从其服务精确获取歌词，失败时再按 `track` 的
+/// 标题/歌手模糊搜索。Best-effort：歌词失败不影响音频下载结果。
+pub async fn save_lyrics_for_track(
+    client: &MusicClient,
+    track: &MusicTrack,
+    source: &MusicTrack,
+    audio_path: &Path,
+    overwrite: bool,
+) -> Option<String> {
+    let (content, original) = match client.track_lyrics(source.service, &source.id).await {
+        Ok(found) => (super::lyrics::lrc_file_content(&found), found.original),
+        Err(_) => search_lyrics(&client.http, track).await?,
+    };
+    let lrc_path = audio_path.with_extension("lrc");
+    if overwrite || !lrc_path.exists() {
+        let _ = tokio::fs::write(&lrc_path, content).await;
+    }
+    Some(original)
+}
+
+/// 歌词搜索兜底：返回 `(.lrc 内容, 原文)`。
+async fn search_lyrics(http: &reqwest::Client, track: &MusicTrack) -> Option<(String, String)> {
+    let title = track.title.trim();
+    if title.is_empty() {
+        return None;
+    }
+
+    let artist = track
+        .artists
+        .iter()
+        .filter(|s| !s.trim().is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" / ");
+
+    let term = if artist.trim().is_empty() {
+        lyrics::model::LyricsSearchTerm::Keyword {
+            keyword: title.to_string(),
+        }
+    } else {
+        lyrics::model::LyricsSearchTerm::Info {
+            title: title.to_string(),
+            artist,
+            album: track.album.clone().filter(|s| !s.trim().is_empty()),
+        }
+    };
+
+    let mut req = lyrics::model::LyricsSearchRequest::new(term);
+    req.duration_ms = track.duration_ms;
+    req.isrc = track.isrc.clone();
+    req.limit = 1;
+
+    let opt = lyrics::model::LyricsSearchOptions {
+        timeout_ms: 8000,
+        strict_match: false,
+        services: vec![
+            lyrics::model::LyricsService::QQMusic,
+            lyrics::model::LyricsService::Netease,
+            lyrics::model::LyricsService::LrcLib,
+        ],
+        bypass_cache: false,
+        exact_only: false,
+    };
+
+    let best = lyrics::core::search_with_http(http, &req, opt)
+        .await
+        .ok()?
+        .pop()?;
+    if best.lyrics_original.trim().is_empty() {
+        return None;
+    }
+
+    let mut content = best.lyrics_original.clone();
+    if let Some(t) = best.lyrics_translation
+        && !t.trim().is_empty()
+    {
+        content.push_str("\n\n");
+        content.push_str(&t);
+    }
+    Some((content, best.lyrics_original))
+}
+
+#[derive(Debug)]
+struct Shared {
+    status: Mutex<MusicDownloadStatus>,
+    cancel: Arc<AtomicBool>,
+    task: DownloadTask,
+    events: broadcast::Sender<DownloadEvent>,
+    done: watch::Sender<bool>,
+    handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+}
+
+impl Shared {
+    fn update_job(
+        &self,
+        index: usize,
+        f: impl FnOnce(&mut MusicDownloadJobResult, &mut MusicDownloadTotals),
+    ) {
+        let job = {
+            let mut guard = self.status.lock().unwrap_or_else(|e| e.into_inner());
+            let s = &mut *guard;
+            if s.done {
+                return;
+            }
+            let Some(job) = s.jobs.get_mut(index) else {
+                return;
+            };
+            f(job, &mut s.totals);
+            job.clone()
+        };
+        let _ = self.events.send(DownloadEvent::Job(job));
+    }
+
+    fn finish(&self, canceled: bool) {
+        let totals = {
+            let mut s = self.status.lock().unwrap_or_else(|e| e.into_inner());
+            if s.done {
+                return;
+            }
+            if canceled {
+                let mut n: u32 = 0;
+                for job in s.jobs.iter_mut() {
+                    if matches!(job.state, MusicJobState::Pending | MusicJobState::Running) {
+                        job.state = MusicJobState::Canceled;
+                        n = n.saturating_add(1);
+                    }
+                }
+                s.totals.canceled = s.totals.canceled.saturating_add(n);
+            }
+            s.done = true;
+            s.totals.clone()
+        };
+        let _ = self.events.send(DownloadEvent::Finished(totals));
+        self.done.send_replace(true);
+    }
+}
+
+#[derive(Debug)]
+struct Worker {
+    client: MusicClient,
+    auth: AuthState,
+    items: Vec<BatchItem>,
+    next: AtomicUsize,
+    out_dir: PathBuf,
+    quality_id: String,
+    path_template: Option<String>,
+    overwrite: bool,
+    retries: u32,
+    fetch_lyrics: bool,
+    write_tags: bool,
+    fallback: Option<MatchOptions>,
+    /// `skip_owned` 时在开始下载前扫描 `out_dir` 填充。
+    library: OnceLock<LibraryIndex>,
+    skip_owned: bool,
+    post_process: Option<MusicPostProcessOptions>,
+    cancel: Arc<AtomicBool>,
+    task: DownloadTask,
+}
+
+enum Outcome {
+    Done {
+        path: PathBuf,
+        bytes: u64,
+        /// 回退源（服务, 曲目 id）。
+        source: Option<(MusicService, String)>,
+    },
+    Skipped {
+        path: PathBuf,
+        reason: &'static str,
+    },
+}
+
+impl Worker {
+    async fn run(&self, shared: &Shared) {
+        loop {
+            if shared.cancel.load(Ordering::Relaxed) {
+                return;
+            }
+            let index = self.next.fetch_add(1, Ordering::Relaxed);
+            let Some(item) = self.items.get(index) else {
+                return;
+            };
+
+            let found = self.library_match(item);
+            let upgraded_from = match &found {
+                LibraryMatch::UpgradeAvailable { entry, .. } => {
+                    Some(entry.path.to_string_lossy().to_string())
+                }
+                _ => None,
+            };
+            shared.update_job(index, |job, _| {
+                job.state = MusicJobState::Running;
+                job.upgraded_from = upgraded_from;
+            });
+            let res = self.download_one(item, found).await;
+            if shared.cancel.load(Ordering::Relaxed) {
+                return;
+            }
+            shared.update_job(index, |job, totals| match res {
+                Ok(Outcome::Done {
+                    path,
+                    bytes,
+                    source,
+                }) => {
+                    job.state = MusicJobState::Done;
+                    job.path = Some(path.to_string_lossy().to_string());
+                    job.bytes = Some(bytes);
+                    if let Some((svc, id)) = source {
+                        job.source_service = Some(svc);
+                        job.source_track_id = Some(id);
+                    }
+                    totals.done = totals.done.saturating_add(1);
+                }
+                Ok(Outcome::Skipped { path, reason }) => {
+                    job.state = MusicJobState::Skipped;
+                    job.path = Some(path.to_string_lossy().to_string());
+                    job.error = Some(reason.to_string());
+                    totals.skipped = totals.skipped.saturating_add(1);
+                }
+                Err(e) => {
+                    job.state = MusicJobState::Failed;
+                    job.error = Some(e.to_string());
+                    totals.failed = totals.failed.saturating_add(1);
+                }
+            });
+        }
+    }
+
+    fn library_match(&self, item: &BatchItem) -> LibraryMatch<'_> {
+        let Some(lib) = self.library.get() else {
+            return LibraryMatch::Missing;
+        };
+        let quality = choose_quality_id(&item.track, &self.quality_id);
+        lib.check(&item.track, quality.as_deref())
+    }
+
+    async fn download_one(
+        &self,
+        item: &BatchItem,
+        found: LibraryMatch<'_>,
+    ) -> Result<Outcome, MusicError> {
+        let old = match found {
+            LibraryMatch::Owned(entry) => {
+                return Ok(Outcome::Skipped {
+                    path: entry.path.clone(),
+                    reason: SKIPPED_OWNED,
+                });
+            }
+            LibraryMatch::UpgradeAvailable { entry, .. } => Some(entry.path.as_path()),
+            LibraryMatch::Missing => None,
+        };
+        let out = self.download_with_fallback(item, old).await?;
+        // 升级成功：删除旧文件（同路径时已被覆盖）。
+        if let (Some(old), Outcome::Done { path, .. }) = (old, &out)
+            && old != path.as_path()
+        {
+            let _ = tokio::fs::remove_file(old).await;
+        }
+        Ok(out)
+    }
+
+    async fn download_with_fallback(
+        &self,
+        item: &BatchItem,
+        replace: Option<&Path>,
+    ) -> Result<Outcome, MusicError> {
+        let err = match self.download_from(item, &item.track, replace).await {
+            Ok(o) => return Ok(o),
+            Err(e) => e,
+        };
+        let Some(opt) = self.fallback.as_ref() else {
+            return Err(err);
+        };
+        for alt in matching::find_alternatives(&self.client, &item.track, opt).await {
+            if let Ok(o) = self.download_from(item, &alt.track, replace).await {
+                return Ok(match o {
+                    Outcome::Done { path, bytes, .. } => Outcome::Done {
+                        path,
+                        bytes,
+                        source: Some((alt.track.service, alt.track.id)),
+                    },
+                    skipped => skipped,
+                });
+            }
+        }
+        Err(err)
+    }
+
+    /// 从 `source`（源曲目或回退匹配到的曲目）下载；文件名、标签始终使用 `item` 的元数据。
+    ///
+    /// `replace` 为要升级替换的曲库文件，目标路径与之相同时直接覆盖。
+    async fn download_from(
+        &self,
+        item: &BatchItem,
+        source: &MusicTrack,
+        replace: Option<&Path>,
+    ) -> Result<Outcome, MusicError> {
+        let track = &item.track;
+        let mut quality =
+            choose_quality_id(source, &self.quality_id).unwrap_or_else(|| self.quality_id.clone());
+        let (url, ext) = loop {
+            match self
+                .client
+                .track_download_url(source.service, &source.id, &quality, &self.auth)
+                .await
+            {
+                Err(MusicError::NotEntitled(msg)) => match lower_quality_id(source, &quality) {
+                    Some(lower) => quality = lower,
+                    None => return Err(MusicError::NotEntitled(msg)),
+                },
+                res => break res?,
+            }
+        };
+        let path = match self.path_template.as_deref() {
+            Some(tpl) => util::build_track_path_by_template(
+                &self.out_dir,
+                tpl,
+                &track.artists,
+                track.album.as_deref(),
+                item.track_no,
+                &track.title,
+                &ext,
+            ),
+            None => util::build_track_path(
+                &self.out_dir,
+                &track.artists,
+                track.album.as_deref(),
+                item.track_no,
+                &track.title,
+                &ext,
+            ),
+        };
+        let transcode = self.post_process.as_ref().and_then(|pp| {
+            let opt = pp.transcode.as_ref()?;
+            let out = postprocess::transcode_target(&path, opt)?;
+            Some((pp.ffmpeg_path.as_str(), opt, out))
+        });
+        let final_path = transcode.as_ref().map_or(&path, |(_, _, out)| out);
+        let overwrite = self.overwrite || replace == Some(final_path.as_path());
+        if final_path.exists() && !overwrite {
+            return Ok(Outcome::Skipped {
+                path: final_path.clone(),
+                reason: SKIPPED_EXISTS,
+            });
+        }
+
+        let http = &self.client.http;
+        let mut bytes = download::download_url_to_file(
+            http,
+            &url,
+            &path,
+            download::DownloadOptions {
+                timeout: self.client.timeout,
+                retries: self.retries,
+                overwrite,
+            },
+            Some(&self.task),
+            Some(&self.cancel),
+        )
+        .await?;
+        let path = match transcode {
+            Some((ffmpeg, opt, out)) => {
+                let res = postprocess::transcode_ffmpeg(
+                    ffmpeg,
+                    &path,
+                    opt,
+                    &out,
+                    overwrite,
+                    Some(&self.cancel),
+                )
+                .await;
+                // 不保留源文件时，转码失败也删除，重试时重新下载。
+                if !opt.keep_source {
+                    let _ = tokio::fs::remove_file(&path).await;
+                }
+                res?;
+                bytes = tokio::fs::metadata(&out).await?.len();
+                out
+            }
+            None => path,
+        };
+
+        let lyrics = if self.fetch_lyrics {
+            save_lyrics_for_track(&self.client, track, source, &path, self.overwrite).await
+        } else {
+            None
+        };
+        if self.write_tags {
+            let tags = tagging::TrackTags {
+                title: track.title.clone(),
+                artists: track.artists.clone(),
+                album: track.album.clone(),
+                track_no: item.track_no,
+                lyrics,
+            };
+            // Best-effort：标签写入失败不影响 job 结果。
+            let cover = track.cover_url.as_deref().or(source.cover_url.as_deref());
+            let _ = tagging::tag_file(http, &path, &tags, cover, self.client.timeout).await;
+        }
+        if let Some(pp) = self.post_process.as_ref().filter(|pp| pp.replay_gain) {
+            // 同标签：响度扫描/写入失败不影响 job 结果。
+            let _ =
+                postprocess::apply_replay_gain(&pp.ffmpeg_path, &path, Some(&self.cancel)).await;
+        }
+        Ok(Outcome::Done {
+            path,
+            bytes,
+            source: None,
+        })
+    }
+}
+
+/// 一次批量下载。克隆后共享同一个任务。
+#[derive(Debug, Clone)]
+pub struct DownloadJob {
+    shared: Arc<Shared>,
+}
+
+impl DownloadJob {
+    /// 校验参数、展开目标并开始下载（需在 tokio runtime 内调用）。
+    ///
+    /// 目标或回退源包含网易云且未提供 cookie 时，会先尝试获取匿名 cookie。
+    pub async fn start(
+        client: MusicClient,
+        target: MusicDownloadTarget,
+        mut auth: AuthState,
+        options: DownloadJobOptions,
+    ) -> Result<Self, MusicError> {
+        if options.download.out_dir.trim().is_empty() {
+            return Err(MusicError::InvalidInput(
+                "options.outDir is empty".to_string(),
+            ));
+        }
+        if options.download.quality_id.trim().is_empty() {
+            return Err(MusicError::InvalidInput(
+                "options.qualityId is empty".to_string(),
+            ));
+        }
+        if let Some(pp) = options.download.post_process.as_ref()
+            && pp.ffmpeg_path.trim().is_empty()
+        {
+            return Err(MusicError::InvalidInput(
+                "options.postProcess.ffmpegPath is empty".to_string(),
+            ));
+        }
+
+        let needs_netease = target_service(&target) == MusicService::Netease
+            || options
+                .download
+                .fallback_services
+                .contains(&MusicService::Netease);
+        if needs_netease
+            && auth.netease_cookie.is_none()
+            && let Ok(c) = providers::netease::fetch_anonymous_cookie(
+                &client.http,
+                &client.cfg,
+                client.timeout,
+            )
+            .await
+        {
+            auth.netease_cookie = Some(c);
+        }
+
+        let items = resolve_target(&client, target, &auth).await?;
+        Ok(Self::spawn(client, auth, items, options))
+    }
+
+    /// 对已展开的下载项开始下载（需在 tokio runtime 内调用）。
+    pub fn spawn(
+        client: MusicClient,
+        auth: AuthState,
+        items: Vec<BatchItem>,
+        options: DownloadJobOptions,
+    ) -> Self {
+        let total = u32::try_from(items.len()).unwrap_or(u32::MAX);
+        let status = MusicDownloadStatus {
+            done: false,
+            totals: MusicDownloadTotals {
+                total,
+                done: 0,
+                failed: 0,
+                skipped: 0,
+                canceled: 0,
+            },
+            jobs: items
+                .iter()
+                .enumerate()
+                .map(|(i, it)| MusicDownloadJobResult {
+                    index: i as u32,
+                    track_id: Some(it.track.id.clone()),
+                    state: MusicJobState::Pending,
+                    path: None,
+                    bytes: None,
+                    error: None,
+                    source_service: None,
+                    source_track_id: None,
+                    upgraded_from: None,
+                })
+                .collect(),
+        };
+        let (events, _) = broadcast::channel(256);
+        let (done, _) = watch::channel(false);
+        let opts = options.download;
+        let concurrency = opts.concurrency.clamp(1, 16) as usize;
+        let task = options
+            .task
+            .unwrap_or_else(|| DownloadScheduler::global().task(opts.priority));
+        let shared = Arc::new(Shared {
+            status: Mutex::new(status),
+            cancel: Arc::new(AtomicBool::new(false)),
+            task: task.clone(),
+            events,
+            done,
+            handle: Mutex::new(None),
+        });
+        let worker = Arc::new(Worker {
+            client,
+            auth,
+            items,
+            next: AtomicUsize::new(0),
+            out_dir: PathBuf::from(opts.out_dir.trim()),
+            quality_id: opts.quality_id.trim().to_string(),
+            path_template: opts
+                .path_template
+                .as_deref()
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string()),
+            overwrite: opts.overwrite,
+            retries: opts.retries.min(10),
+            fetch_lyrics: options.fetch_lyrics,
+            write_tags: options.write_tags,
+            fallback: (!opts.fallback_services.is_empty()).then(|| MatchOptions {
+                services: opts.fallback_services.clone(),
+                min_score: opts
+                    .fallback_min_score
+                    .unwrap_or(matching::DEFAULT_MIN_SCORE)
+                    .min(100),
+                ..MatchOptions::default()
+            }),
+            library: OnceLock::new(),
+            skip_owned: opts.skip_owned,
+            post_process: opts.post_process.clone(),
+            cancel: Arc::clone(&shared.cancel),
+            task,
+        });
+
+        let st = Arc::clone(&shared);
+        let handle = tokio::spawn(async move {
+            if worker.skip_owned {
+                // Best-effort：扫描失败时按未拥有处理。
+                let dir = worker.out_dir.clone();
+                if let Ok(Ok(lib)) =
+                    tokio::task::spawn_blocking(move || LibraryIndex::scan(&dir)).await
+                {
+                    let _ = worker.library.set(lib);
+                }
+            }
+            let mut joinset = tokio::task::JoinSet::new();
+            for _ in 0..concurrency.min(worker.items.len()) {
+                let worker = Arc::clone(&worker);
+                let st = Arc::clone(&st);
+                joinset.spawn(async move { worker.run(&st).await });
+            }
+            while joinset.join_next().await.is_some() {}
+            let canceled = st.cancel.load(Ordering::Relaxed);
+            st.finish(canceled);
+        });
+        *shared.handle.lock().unwrap_or_else(|e| e.into_inner()) = Some(handle);
+
+        Self { shared }
+    }
+
+    pub fn status(&self) -> MusicDownloadStatus {
+        self.shared
+            .status
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub fn is_done(&self) -> bool {
+        *self.shared.done.borrow()
+    }
+
+    /// 订阅后续事件；订阅前已发生的变化请以 [`Self::status`] 快照为准。
+    pub fn subscribe(&self) -> broadcast::Receiver<DownloadEvent> {
+        self.shared.events.subscribe()
+    }
+
+    /// 暂停：正在进行的下载在下一个分块处等待，不再申请新的连接名额。
+    pub fn pause(&self) {
+        self.shared.task.pause();
+    }
+
+    pub fn resume(&self) {
+        self.shared.task.resume();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.shared.task.is_paused()
+    }
+
+    /// 取消：未完成的 job 标记为 `canceled`，正在进行的下载会被中止。
+    pub fn cancel(&self) {
+        self.shared.cancel.store(true, Ordering::Relaxed);
+        if let Some(h) = self
+            .shared
+            .handle
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+        {
+            h.abort();
+        }
+        self.shared.finish(true);
+    }
+
+    /// 等待结束并返回最终快照。
+    pub async fn wait(&self) -> MusicDownloadStatus {
+        let mut rx = self.shared.done.subscribe();
+        let _ = rx.wait_for(|d| *d).await;
+        self.status()
+    }
+}
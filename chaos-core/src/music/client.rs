@@ -3,7 +3,10 @@ use std::time::Duration;
 use reqwest::Client;
 
 use super::error::MusicError;
-use super::model::{AuthState, MusicAlbum, MusicArtist, MusicService, MusicTrack, ProviderConfig};
+use super::model::{
+    AuthState, MusicAlbum, MusicArtist, MusicChart, MusicLyrics, MusicPlaylist, MusicService,
+    MusicTrack, ProviderConfig,
+};
 use super::providers::{kugou, kuwo, netease, qq};
 
 #[derive(Debug, Clone)]
@@ -148,6 +151,38 @@ impl MusicClient {
         }
     }
 
+    /// 按 id 获取单曲详情（含可用音质）。
+    pub async fn track_detail(
+        &self,
+        service: MusicService,
+        track_id: &str,
+    ) -> Result<MusicTrack, MusicError> {
+        match service {
+            MusicService::Qq => qq::track_detail(&self.http, track_id, self.timeout).await,
+            MusicService::Kuwo => kuwo::track_detail(&self.http, track_id, self.timeout).await,
+            MusicService::Kugou => kugou::track_detail(&self.http, track_id, self.timeout).await,
+            MusicService::Netease => {
+                netease::track_detail(&self.http, &self.cfg, track_id, self.timeout).await
+            }
+        }
+    }
+
+    /// 按 id 获取该服务自己的歌词（原文/翻译/音译；QQ、酷狗、网易云附带逐字歌词）。
+    pub async fn track_lyrics(
+        &self,
+        service: MusicService,
+        track_id: &str,
+    ) -> Result<MusicLyrics, MusicError> {
+        match service {
+            MusicService::Qq => qq::track_lyrics(&self.http, track_id, self.timeout).await,
+            MusicService::Kuwo => kuwo::track_lyrics(&self.http, track_id, self.timeout).await,
+            MusicService::Kugou => kugou::track_lyrics(&self.http, track_id, self.timeout).await,
+            MusicService::Netease => {
+                netease::track_lyrics(&self.http, &self.cfg, track_id, self.timeout).await
+            }
+        }
+    }
+
     pub async fn album_tracks(
         &self,
         service: MusicService,
@@ -182,6 +217,130 @@ impl MusicClient {
         }
     }
 
+    pub async fn search_playlists(
+        &self,
+        service: MusicService,
+        keyword: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Vec<MusicPlaylist>, MusicError> {
+        match service {
+            MusicService::Qq => {
+                qq::search_playlists(&self.http, keyword, page, page_size, self.timeout).await
+            }
+            MusicService::Kuwo => {
+                kuwo::search_playlists(&self.http, keyword, page, page_size, self.timeout).await
+            }
+            MusicService::Kugou => {
+                kugou::search_playlists(
+                    &self.http,
+                    &self.cfg,
+                    keyword,
+                    page,
+                    page_size,
+                    self.timeout,
+                )
+                .await
+            }
+            MusicService::Netease => {
+                netease::search_playlists(
+                    &self.http,
+                    &self.cfg,
+                    keyword,
+                    page,
+                    page_size,
+                    self.timeout,
+                )
+                .await
+            }
+        }
+    }
+
+    /// 歌单全部曲目（各服务内部分页拉取）；`auth` 用于私密歌单。
+    pub async fn playlist_tracks(
+        &self,
+        service: MusicService,
+        playlist_id: &str,
+        auth: &AuthState,
+    ) -> Result<Vec<MusicTrack>, MusicError> {
+        match service {
+            MusicService::Qq => qq::playlist_tracks(&self.http, playlist_id, self.timeout).await,
+            MusicService::Kuwo => {
+                kuwo::playlist_tracks(&self.http, playlist_id, self.timeout).await
+            }
+            MusicService::Kugou => {
+                kugou::playlist_tracks(&self.http, &self.cfg, playlist_id, auth, self.timeout).await
+            }
+            MusicService::Netease => {
+                netease::playlist_tracks(&self.http, &self.cfg, playlist_id, auth, self.timeout)
+                    .await
+            }
+        }
+    }
+
+    /// 已登录用户自己的歌单。
+    pub async fn user_playlists(
+        &self,
+        service: MusicService,
+        auth: &AuthState,
+    ) -> Result<Vec<MusicPlaylist>, MusicError> {
+        match service {
+            MusicService::Qq => qq::user_playlists(&self.http, auth, self.timeout).await,
+            MusicService::Kuwo => Err(MusicError::UnsupportedService(
+                "kuwo: user playlists are not supported".to_string(),
+            )),
+            MusicService::Kugou => {
+                kugou::user_playlists(&self.http, &self.cfg, auth, self.timeout).await
+            }
+            MusicService::Netease => {
+                netease::user_playlists(&self.http, &self.cfg, auth, self.timeout).await
+            }
+        }
+    }
+
+    pub async fn list_charts(&self, service: MusicService) -> Result<Vec<MusicChart>, MusicError> {
+        match service {
+            MusicService::Qq => qq::list_charts(&self.http, self.timeout).await,
+            MusicService::Kuwo => kuwo::list_charts(&self.http, self.timeout).await,
+            MusicService::Kugou => kugou::list_charts(&self.http, self.timeout).await,
+            MusicService::Netease => {
+                netease::list_charts(&self.http, &self.cfg, self.timeout).await
+            }
+        }
+    }
+
+    pub async fn chart_tracks(
+        &self,
+        service: MusicService,
+        chart_id: &str,
+    ) -> Result<Vec<MusicTrack>, MusicError> {
+        match service {
+            MusicService::Qq => qq::chart_tracks(&self.http, chart_id, self.timeout).await,
+            MusicService::Kuwo => kuwo::chart_tracks(&self.http, chart_id, self.timeout).await,
+            MusicService::Kugou => kugou::chart_tracks(&self.http, chart_id, self.timeout).await,
+            MusicService::Netease => {
+                netease::chart_tracks(&self.http, &self.cfg, chart_id, self.timeout).await
+            }
+        }
+    }
+
+    /// 每日推荐（仅 QQ / 网易云，需登录）。
+    pub async fn daily_recommendations(
+        &self,
+        service: MusicService,
+        auth: &AuthState,
+    ) -> Result<Vec<MusicTrack>, MusicError> {
+        match service {
+            MusicService::Qq => qq::daily_recommendations(&self.http, auth, self.timeout).await,
+            MusicService::Netease => {
+                netease::daily_recommendations(&self.http, &self.cfg, auth, self.timeout).await
+            }
+            MusicService::Kuwo | MusicService::Kugou => Err(MusicError::UnsupportedService(
+                format!("{}: daily recommendations not supported", service.as_str()),
+            )),
+        }
+    }
+
     pub async fn track_download_url(
         &self,
         service: MusicService,
@@ -195,7 +354,7 @@ impl MusicClient {
                 qq::track_download_url(&self.http, track_id, quality_id, auth, self.timeout).await
             }
             MusicService::Kuwo => {
-                kuwo::track_download_url(&self.http, track_id, quality_id, self.timeout).await
+                kuwo::track_download_url(&self.http, track_id, quality_id, auth, self.timeout).await
             }
             MusicService::Kugou => {
                 kugou::track_download_url(
@@ -221,4 +380,23 @@ impl MusicClient {
             }
         }
     }
+
+    /// 播放地址：账号无权获取所请求音质时返回服务端降级后的地址，而不是报错。
+    pub async fn track_play_url(
+        &self,
+        service: MusicService,
+        track_id: &str,
+        quality_id: &str,
+        auth: &AuthState,
+    ) -> Result<(String, String), MusicError> {
+        match service {
+            MusicService::Kuwo => {
+                kuwo::track_play_url(&self.http, track_id, quality_id, auth, self.timeout).await
+            }
+            _ => {
+                self.track_download_url(service, track_id, quality_id, auth)
+                    .await
+            }
+        }
+    }
 }
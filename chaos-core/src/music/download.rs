@@ -1,4 +1,6 @@
 use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use bytes::Bytes;
@@ -7,6 +9,7 @@ use reqwest::Client;
 use tokio::io::AsyncWriteExt;
 
 use super::error::MusicError;
+use crate::download_scheduler::DownloadTask;
 
 async fn fetch_bytes_stream(
     http: &Client,
@@ -24,14 +27,31 @@ async fn fetch_bytes_stream(
     Ok((status, resp.bytes_stream()))
 }
 
+/// 单文件下载参数。
+#[derive(Debug, Clone, Copy)]
+pub struct DownloadOptions {
+    pub timeout: Duration,
+    pub retries: u32,
+    pub overwrite: bool,
+}
+
+fn canceled(cancel: Option<&Arc<AtomicBool>>) -> bool {
+    cancel.is_some_and(|c| c.load(Ordering::Relaxed))
+}
+
 pub async fn download_url_to_file(
     http: &Client,
     url: &str,
     out_path: &Path,
-    timeout: Duration,
-    retries: u32,
-    overwrite: bool,
+    opt: DownloadOptions,
+    task: Option<&DownloadTask>,
+    cancel: Option<&Arc<AtomicBool>>,
 ) -> Result<u64, MusicError> {
+    let DownloadOptions {
+        timeout,
+        retries,
+        overwrite,
+    } = opt;
     let p = out_path;
     if p.as_os_str().is_empty() {
         return Err(MusicError::InvalidInput("empty out_path".to_string()));
@@ -50,6 +70,16 @@ pub async fn download_url_to_file(
         p.extension().and_then(|s| s.to_str()).unwrap_or_default()
     ));
 
+    // 整首歌占用一个调度器连接名额（含重试）。
+    let _permit = match task {
+        Some(t) => Some(
+            t.acquire(cancel)
+                .await
+                .ok_or_else(|| MusicError::Other("canceled".to_string()))?,
+        ),
+        None => None,
+    };
+
     let mut last_err: Option<String> = None;
     for attempt in 0..=retries {
         if attempt > 0 {
@@ -61,6 +91,9 @@ pub async fn download_url_to_file(
             };
             tokio::time::sleep(Duration::from_millis(d)).await;
         }
+        if canceled(cancel) {
+            return Err(MusicError::Other("canceled".to_string()));
+        }
 
         // Clean tmp before retry (best-effort).
         let _ = tokio::fs::remove_file(&tmp).await;
@@ -82,6 +115,15 @@ pub async fn download_url_to_file(
         let mut bytes: u64 = 0;
         while let Some(chunk) = stream.next().await {
             let chunk = chunk.map_err(MusicError::Http)?;
+            let alive = match task {
+                Some(t) => t.throttle(chunk.len() as u64, cancel).await,
+                None => !canceled(cancel),
+            };
+            if !alive {
+                drop(f);
+                let _ = tokio::fs::remove_file(&tmp).await;
+                return Err(MusicError::Other("canceled".to_string()));
+            }
             bytes += chunk.len() as u64;
             f.write_all(&chunk).await?;
         }
@@ -10,6 +10,9 @@ pub enum MusicError {
     NotConfigured(String),
     #[error("unauthorized: {0}")]
     Unauthorized(String),
+    /// 账号无权获取该音质/资源（未登录或非会员）。
+    #[error("not entitled: {0}")]
+    NotEntitled(String),
     #[error("http error: {0}")]
     Http(#[from] reqwest::Error),
     #[error("io error: {0}")]
@@ -21,3 +24,12 @@ pub enum MusicError {
     #[error("other error: {0}")]
     Other(String),
 }
+
+impl From<crate::ffmpeg::FfmpegError> for MusicError {
+    fn from(e: crate::ffmpeg::FfmpegError) -> Self {
+        match e {
+            crate::ffmpeg::FfmpegError::Io(e) => Self::Io(e),
+            e => Self::Other(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! 本地曲库索引：扫描下载目录，读取已有文件的标签（无标签时按文件名推断），
+//! 用于跨文件名/格式识别“已拥有”的曲目，并判断在线源是否提供更高音质（升级）。
+//!
+//! 文件名推断支持本仓库的默认布局 `歌手/专辑/NN - 标题.ext` 与常见的 `歌手 - 标题.ext`。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use lofty::file::{AudioFile, TaggedFileExt};
+use lofty::tag::{Accessor, ItemKey};
+
+use super::error::MusicError;
+use super::matching::{self, ScoreFields};
+use super::model::{MusicQuality, MusicTrack};
+
+/// 认定为同一首歌的默认最低匹配分（比下载回退更严格）。
+pub const DEFAULT_MIN_SCORE: u8 = 90;
+
+const AUDIO_EXTS: &[&str] = &[
+    "mp3", "flac", "m4a", "mp4", "aac", "ogg", "opus", "wav", "ape", "wv", "aiff",
+];
+const LOSSLESS_EXTS: &[&str] = &["flac", "wav", "ape", "wv", "aiff"];
+const MAX_DEPTH: usize = 8;
+/// 无损统一按该值排序（高于任何有损码率）。
+const LOSSLESS_RANK: u32 = 10_000;
+/// VBR/实测码率的容差：320k 文件实测可能是 318k 等。
+const BITRATE_TOLERANCE_KBPS: u32 = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryEntry {
+    pub path: PathBuf,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub duration_ms: Option<u64>,
+    /// 小写扩展名（`mp3` / `flac` / ...）。
+    pub format: String,
+    pub bitrate_kbps: Option<u32>,
+    /// 按 lofty 读出的编码判断；读不到属性时按扩展名。
+    pub lossless: bool,
+    /// 元数据来自标签；`false` 表示由文件名/目录推断。
+    pub from_tags: bool,
+}
+
+impl LibraryEntry {
+    fn rank(&self) -> u32 {
+        if self.lossless {
+            LOSSLESS_RANK
+        } else {
+            self.bitrate_kbps.unwrap_or(0)
+        }
+    }
+
+    fn fields(&self) -> ScoreFields<'_> {
+        ScoreFields {
+            title: &self.title,
+            artists: &self.artists,
+            album: self.album.as_deref(),
+            duration_ms: self.duration_ms,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum LibraryMatch<'a> {
+    /// 曲库中没有这首歌。
+    Missing,
+    /// 已拥有且音质不低于在线源提供的音质。
+    Owned(&'a LibraryEntry),
+    /// 已拥有，但在线源提供更高音质（例如已有 320k mp3，源有 flac）。
+    UpgradeAvailable {
+        entry: &'a LibraryEntry,
+        quality: MusicQuality,
+    },
+}
+
+fn quality_rank(q: &MusicQuality) -> u32 {
+    if q.lossless {
+        return LOSSLESS_RANK;
+    }
+    q.bitrate_kbps
+        .or_else(|| q.id.rsplit('_').next()?.parse().ok())
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone)]
+pub struct LibraryIndex {
+    entries: Vec<LibraryEntry>,
+    /// 规范化标题 -> entries 下标。
+    by_title: HashMap<String, Vec<usize>>,
+    min_score: u8,
+}
+
+impl Default for LibraryIndex {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            by_title: HashMap::new(),
+            min_score: DEFAULT_MIN_SCORE,
+        }
+    }
+}
+
+impl LibraryIndex {
+    /// 递归扫描 `dir` 下的音频文件（阻塞 IO，异步环境请放到 `spawn_blocking`）。
+    ///
+    /// 目录不存在时返回空索引；单个文件读取失败时退化为按文件名推断。
+    pub fn scan(dir: &Path) -> Result<Self, MusicError> {
+        let mut index = Self::default();
+        if !dir.exists() {
+            return Ok(index);
+        }
+        let mut stack = vec![(dir.to_path_buf(), 0usize)];
+        while let Some((cur, depth)) = stack.pop() {
+            let mut children = std::fs::read_dir(&cur)?
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .collect::<Vec<_>>();
+            children.sort();
+            for path in children {
+                if path.is_dir() {
+                    if depth < MAX_DEPTH {
+                        stack.push((path, depth + 1));
+                    }
+                } else if let Some(entry) = read_entry(dir, &path) {
+                    index.insert(entry);
+                }
+            }
+        }
+        Ok(index)
+    }
+
+    pub fn from_entries(entries: impl IntoIterator<Item = LibraryEntry>) -> Self {
+        let mut index = Self::default();
+        for e in entries {
+            index.insert(e);
+        }
+        index
+    }
+
+    pub fn with_min_score(mut self, min_score: u8) -> Self {
+        self.min_score = min_score.min(100);
+        self
+    }
+
+    pub fn insert(&mut self, entry: LibraryEntry) {
+        let key = matching::normalize(&entry.title);
+        self.by_title
+            .entry(key)
+            .or_default()
+            .push(self.entries.len());
+        self.entries.push(entry);
+    }
+
+    pub fn entries(&self) -> &[LibraryEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 查找与 `track` 是同一首歌的本地文件；多个命中时取音质最高者。
+    pub fn find(&self, track: &MusicTrack) -> Option<&LibraryEntry> {
+        let candidates = self.by_title.get(&matching::normalize(&track.title))?;
+        candidates
+            .iter()
+            .map(|&i| &self.entries[i])
+            .filter(|e| matching::score_fields(track.into(), e.fields()) >= self.min_score)
+            .max_by_key(|e| e.rank())
+    }
+
+    /// 判断是否已拥有 `track`，以及在线源是否有更高音质。
+    ///
+    /// `quality_id` 为将要下载的音质；`None` 时取 `track.qualities` 中最高的音质比较。
+    pub fn check(&self, track: &MusicTrack, quality_id: Option<&str>) -> LibraryMatch<'_> {
+        let Some(entry) = self.find(track) else {
+            return LibraryMatch::Missing;
+        };
+        let offered = match quality_id {
+            Some(id) => track.qualities.iter().find(|q| q.id == id),
+            None => track.qualities.iter().max_by_key(|q| quality_rank(q)),
+        };
+        match offered {
+            Some(q) if quality_rank(q) > entry.rank().saturating_add(BITRATE_TOLERANCE_KBPS) => {
+                LibraryMatch::UpgradeAvailable {
+                    entry,
+                    quality: q.clone(),
+                }
+            }
+            _ => LibraryMatch::Owned(entry),
+        }
+    }
+}
+
+fn split_artists(raw: &str) -> Vec<String> {
+    raw.split(['\0', ';', '、'])
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// 读取单个音频文件；非音频扩展名返回 `None`。`root` 用于按目录布局推断歌手/专辑。
+pub fn read_entry(root: &Path, path: &Path) -> Option<LibraryEntry> {
+    let format = path.extension()?.to_str()?.to_ascii_lowercase();
+    if !AUDIO_EXTS.contains(&format.as_str()) {
+        return None;
+    }
+    let mut entry = entry_from_file_name(root, path, &format)?;
+
+    let Ok(file) = lofty::read_from_path(path) else {
+        return Some(entry);
+    };
+    let props = file.properties();
+    entry.duration_ms = u64::try_from(props.duration().as_millis())
+        .ok()
+        .filter(|&d| d > 0);
+    entry.bitrate_kbps = props.audio_bitrate().filter(|&b| b > 0);
+    // lofty 只为无损编码（FLAC / ALAC / WAV / APE 等）给出位深；m4a 可能是 AAC 也可能是 ALAC。
+    entry.lossless = props.bit_depth().is_some_and(|b| b > 0) || entry.lossless;
+
+    if let Some(tag) = file.primary_tag().or_else(|| file.first_tag())
+        && let Some(title) = tag.title().map(|s| s.trim().to_string())
+        && !title.is_empty()
+    {
+        entry.title = title;
+        let artists = tag
+            .get_strings(&ItemKey::TrackArtist)
+            .flat_map(split_artists)
+            .collect::<Vec<_>>();
+        if !artists.is_empty() {
+            entry.artists = artists;
+        }
+        if let Some(album) = tag.album().map(|s| s.trim().to_string())
+            && !album.is_empty()
+        {
+            entry.album = Some(album);
+        }
+        entry.from_tags = true;
+    }
+    Some(entry)
+}
+
+/// `[NN - ]标题` 或 `歌手 - 标题`；默认布局下用上两级目录作为歌手/专辑。
+fn entry_from_file_name(root: &Path, path: &Path, format: &str) -> Option<LibraryEntry> {
+    let stem = path.file_stem()?.to_str()?.trim();
+    let stem = match stem.split_once(" - ") {
+        Some((no, rest)) if !no.is_empty() && no.chars().all(|c| c.is_ascii_digit()) => rest,
+        _ => stem,
+    };
+    let dirs = path
+        .parent()
+        .and_then(|p| p.strip_prefix(root).ok())
+        .map(|p| {
+            p.components()
+                .filter_map(|c| c.as_os_str().to_str())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let (artists, title) = match stem.split_once(" - ") {
+        Some((artist, title)) => (split_artists(artist), title.trim()),
+        None => (
+            dirs.len()
+                .checked_sub(2)
+                .map(|i| vec![dirs[i].to_string()])
+                .unwrap_or_default(),
+            stem,
+        ),
+    };
+    if title.is_empty() {
+        return None;
+    }
+    let album = (dirs.len() >= 2)
+        .then(|| dirs[dirs.len() - 1])
+        .filter(|a| *a != "Single")
+        .map(|a| a.to_string());
+
+    Some(LibraryEntry {
+        path: path.to_path_buf(),
+        title: title.to_string(),
+        artists,
+        album,
+        duration_ms: None,
+        format: format.to_string(),
+        bitrate_kbps: None,
+        lossless: LOSSLESS_EXTS.contains(&format),
+        from_tags: false,
+    })
+}
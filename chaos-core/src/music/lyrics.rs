@@ -0,0 +1,58 @@
+//! 各音乐服务按曲目 id 返回的歌词的通用处理：逐字歌词（QRC/KRC/YRC）转行级 LRC、
+//! 酷狗 KRC 内嵌的翻译/音译（`[language:...]`）解析。
+
+use super::model::MusicLyrics;
+use crate::lyrics::parse::{self, LyricsFormat, format_lrc_time, krc_language_rows};
+
+pub(crate) fn non_empty(s: Option<String>) -> Option<String> {
+    s.filter(|s| !s.trim().is_empty())
+}
+
+/// QRC / KRC / YRC 转行级 LRC（格式由 [`parse::parse`] 识别）；只保留原文，元数据/JSON 行被跳过。
+pub fn word_lyrics_to_lrc(text: &str) -> String {
+    parse::parse(text)
+        .lines
+        .iter()
+        .map(|l| format!("[{}]{}", format_lrc_time(l.start_ms), l.text))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 解析 KRC 中的 `[language:base64]`：返回 `(翻译, 音译)`，按 KRC 时间行对齐为 LRC。
+pub(crate) fn krc_language(krc: &str) -> (Option<String>, Option<String>) {
+    let starts = parse::parse_as(krc, LyricsFormat::Krc)
+        .lines
+        .iter()
+        .map(|l| l.start_ms)
+        .collect::<Vec<_>>();
+
+    let mut translation = None;
+    let mut romanization = None;
+    for (kind, rows) in krc_language_rows(krc) {
+        let lrc = rows
+            .iter()
+            .zip(&starts)
+            .map(|(text, &start)| format!("[{}]{}", format_lrc_time(start), text.trim()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        // type: 0 = 音译（逐字），1 = 翻译。
+        match kind {
+            0 => romanization = non_empty(Some(lrc)),
+            1 => translation = non_empty(Some(lrc)),
+            _ => {}
+        }
+    }
+    (translation, romanization)
+}
+
+/// 写入 `.lrc` 的内容：原文，若有翻译则空一行后追加翻译。
+pub fn lrc_file_content(lyrics: &MusicLyrics) -> String {
+    let mut content = lyrics.original.clone();
+    if let Some(t) = lyrics.translation.as_deref()
+        && !t.trim().is_empty()
+    {
+        content.push_str("\n\n");
+        content.push_str(t);
+    }
+    content
+}
@@ -0,0 +1,191 @@
+//! 跨平台曲目匹配：在其它 `MusicService` 上查找同一首歌（用于下载失败时切换来源）。
+//!
+//! 打分方式与 `lyrics::match_score::compute_match_percentage` 一致：标题/歌手/专辑相似度 + 时长接近度加权，
+//! 缺失的维度（专辑、时长）不参与加权。
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use super::client::MusicClient;
+use super::model::{MusicService, MusicTrack};
+use crate::lyrics::match_score::{duration_similarity_ms, string_similarity};
+
+const WEIGHT_TITLE: f64 = 0.35;
+const WEIGHT_ARTIST: f64 = 0.30;
+const WEIGHT_ALBUM: f64 = 0.10;
+const WEIGHT_DURATION: f64 = 0.25;
+
+pub const DEFAULT_MIN_SCORE: u8 = 80;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchOptions {
+    /// 按顺序尝试的服务（与源曲目相同的服务会被跳过）。
+    pub services: Vec<MusicService>,
+    /// 最低匹配分（0-100）。
+    pub min_score: u8,
+    /// 每个服务取搜索结果的前 N 条参与打分。
+    pub limit_per_service: u32,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self {
+            services: vec![
+                MusicService::Qq,
+                MusicService::Netease,
+                MusicService::Kugou,
+                MusicService::Kuwo,
+            ],
+            min_score: DEFAULT_MIN_SCORE,
+            limit_per_service: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrackMatch {
+    pub track: MusicTrack,
+    pub score: u8,
+}
+
+pub(crate) fn normalize(s: &str) -> String {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"[\p{P}\p{S}]").expect("normalize regex"));
+    re.replace_all(&s.to_lowercase(), " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 每个源歌手取候选歌手中的最高相似度再求平均；歌手顺序不同不扣分。
+fn artists_similarity(a: &[String], b: &[String]) -> f64 {
+    let a = a
+        .iter()
+        .map(|s| normalize(s))
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+    let b = b
+        .iter()
+        .map(|s| normalize(s))
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = a
+        .iter()
+        .map(|x| {
+            b.iter()
+                .map(|y| string_similarity(x, y))
+                .fold(0.0, f64::max)
+        })
+        .sum();
+    sum / a.len() as f64
+}
+
+/// 参与打分的元数据；本地曲库条目（`library::LibraryEntry`）也借此与在线曲目比较。
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ScoreFields<'a> {
+    pub title: &'a str,
+    pub artists: &'a [String],
+    pub album: Option<&'a str>,
+    pub duration_ms: Option<u64>,
+}
+
+impl<'a> From<&'a MusicTrack> for ScoreFields<'a> {
+    fn from(t: &'a MusicTrack) -> Self {
+        Self {
+            title: &t.title,
+            artists: &t.artists,
+            album: t.album.as_deref(),
+            duration_ms: t.duration_ms,
+        }
+    }
+}
+
+/// 两首曲目是同一首歌的可能性（0-100）。
+pub fn match_score(source: &MusicTrack, candidate: &MusicTrack) -> u8 {
+    score_fields(source.into(), candidate.into())
+}
+
+pub(crate) fn score_fields(source: ScoreFields<'_>, candidate: ScoreFields<'_>) -> u8 {
+    let mut total = 0.0;
+    let mut weight = 0.0;
+
+    total +=
+        string_similarity(&normalize(source.title), &normalize(candidate.title)) * WEIGHT_TITLE;
+    weight += WEIGHT_TITLE;
+    total += artists_similarity(source.artists, candidate.artists) * WEIGHT_ARTIST;
+    weight += WEIGHT_ARTIST;
+
+    let album = |a: Option<&str>| a.map(normalize).filter(|s| !s.is_empty());
+    if let (Some(a), Some(b)) = (album(source.album), album(candidate.album)) {
+        total += string_similarity(&a, &b) * WEIGHT_ALBUM;
+        weight += WEIGHT_ALBUM;
+    }
+    if source.duration_ms.is_some_and(|d| d > 0) && candidate.duration_ms.is_some_and(|d| d > 0) {
+        total +=
+            duration_similarity_ms(source.duration_ms, candidate.duration_ms) * WEIGHT_DURATION;
+        weight += WEIGHT_DURATION;
+    }
+
+    ((total / weight).clamp(0.0, 1.0) * 100.0).round() as u8
+}
+
+/// 用于在其它服务上搜索的关键字：`标题 歌手1 歌手2`。
+pub fn search_keyword(track: &MusicTrack) -> String {
+    std::iter::once(track.title.trim())
+        .chain(track.artists.iter().map(|s| s.trim()))
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 在单个服务上查找最佳匹配（低于 `min_score` 时返回 `None`；搜索失败视为无匹配）。
+pub async fn best_match_on(
+    client: &MusicClient,
+    service: MusicService,
+    track: &MusicTrack,
+    opt: &MatchOptions,
+) -> Option<TrackMatch> {
+    let keyword = search_keyword(track);
+    if keyword.is_empty() {
+        return None;
+    }
+    let candidates = client
+        .search_tracks(service, &keyword, 1, opt.limit_per_service.clamp(1, 50))
+        .await
+        .ok()?;
+    candidates
+        .into_iter()
+        .map(|c| TrackMatch {
+            score: match_score(track, &c),
+            track: c,
+        })
+        .filter(|m| m.score >= opt.min_score)
+        .max_by_key(|m| m.score)
+}
+
+/// 按 `opt.services` 的顺序返回每个其它服务上的最佳匹配（各服务并发搜索）。
+pub async fn find_alternatives(
+    client: &MusicClient,
+    track: &MusicTrack,
+    opt: &MatchOptions,
+) -> Vec<TrackMatch> {
+    let mut services: Vec<MusicService> = Vec::new();
+    for s in &opt.services {
+        if *s != track.service && !services.contains(s) {
+            services.push(*s);
+        }
+    }
+    futures::future::join_all(
+        services
+            .into_iter()
+            .map(|s| best_match_on(client, s, track, opt)),
+    )
+    .await
+    .into_iter()
+    .flatten()
+    .collect()
+}
@@ -1,7 +1,13 @@
+pub mod batch;
 pub mod client;
 pub mod download;
 pub mod error;
+pub mod library;
+pub mod lyrics;
+pub mod matching;
 pub mod model;
+pub mod postprocess;
+pub mod tagging;
 pub mod util;
 
 pub mod providers;
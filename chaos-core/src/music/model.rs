@@ -70,9 +70,39 @@ pub struct MusicTrack {
     pub duration_ms: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cover_url: Option<String>,
+    /// 录音 ISRC（服务返回时填写）；歌词搜索时用于精确查找。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isrc: Option<String>,
     pub qualities: Vec<MusicQuality>,
 }
 
+/// 逐字歌词格式：QQ 音乐 QRC / 酷狗 KRC / 网易云 YRC。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WordLyricsFormat {
+    Qrc,
+    Krc,
+    Yrc,
+}
+
+/// 按曲目 id 从所属服务精确获取的歌词；`original` / `translation` / `romanization` 均为行级 LRC。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicLyrics {
+    pub service: MusicService,
+    pub track_id: String,
+    pub original: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub romanization: Option<String>,
+    /// 服务原始的逐字歌词（格式见 `word_format`）；不提供时为空。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word_lyrics: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word_format: Option<WordLyricsFormat>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MusicAlbum {
@@ -103,6 +133,40 @@ pub struct MusicArtist {
     pub album_count: Option<u32>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicPlaylist {
+    pub service: MusicService,
+    pub id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 榜单（飙升榜/新歌榜/热歌榜...）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicChart {
+    pub service: MusicService,
+    pub id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 更新频率说明，如 “每天更新”。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_frequency: Option<String>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderConfig {
@@ -208,6 +272,32 @@ pub struct KugouUserInfo {
     pub userid: String,
 }
 
+/// 网易云登录账号信息（由 cookie 查询 `/user/account` 得到）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeteaseUserInfo {
+    pub user_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub vip_type: i64,
+}
+
+/// 酷我账号（网页端登录后的 `userid` / `websid`，或 App 端 uid/sid）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KuwoUserInfo {
+    pub uid: String,
+    pub sid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    /// 0 = 非会员；登录校验时由会员信息接口填充。
+    #[serde(default)]
+    pub vip_type: i64,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthState {
@@ -217,13 +307,15 @@ pub struct AuthState {
     pub kugou: Option<KugouUserInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub netease_cookie: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kuwo: Option<KuwoUserInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum MusicDownloadTarget {
     Track {
-        track: MusicTrack,
+        track: Box<MusicTrack>,
     },
     Album {
         service: MusicService,
@@ -233,6 +325,10 @@ pub enum MusicDownloadTarget {
         service: MusicService,
         artist_id: String,
     },
+    Playlist {
+        service: MusicService,
+        playlist_id: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -248,6 +344,22 @@ pub struct MusicDownloadOptions {
     pub concurrency: u32,
     #[serde(default = "default_retries")]
     pub retries: u32,
+    /// 调度优先级（越大越先分配连接），默认 0。
+    #[serde(default)]
+    pub priority: i32,
+    /// 当前源无法下载时，按顺序在这些源上查找同一首歌重试；为空表示不回退。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_services: Vec<MusicService>,
+    /// 回退匹配的最低分（0-100），省略时为 80。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_min_score: Option<u8>,
+    /// 下载前扫描 `out_dir` 建立曲库索引：已拥有同一首歌（文件名/格式不同也算）时跳过，
+    /// 除非本次下载的音质更高。
+    #[serde(default)]
+    pub skip_owned: bool,
+    /// 下载完成后的转码 / 响度标准化；省略时不做后处理。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_process: Option<MusicPostProcessOptions>,
 }
 
 const fn default_concurrency() -> u32 {
@@ -257,7 +369,59 @@ const fn default_retries() -> u32 {
     2
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 转码目标编码。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MusicTranscodeCodec {
+    Mp3,
+    /// AAC（m4a 容器）。
+    Aac,
+    Flac,
+}
+
+impl MusicTranscodeCodec {
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Mp3 => "mp3",
+            Self::Aac => "m4a",
+            Self::Flac => "flac",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicTranscodeOptions {
+    pub codec: MusicTranscodeCodec,
+    /// 有损编码的目标码率（kbps），省略时 mp3 为 320、aac 为 256；flac 忽略。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitrate_kbps: Option<u32>,
+    /// 只转码这些扩展名的源文件（如 `["ogg"]`）；为空表示扩展名与目标不同的都转码。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_formats: Vec<String>,
+    /// 转码后保留源文件。
+    #[serde(default)]
+    pub keep_source: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicPostProcessOptions {
+    /// ffmpeg 可执行文件，默认 `ffmpeg`（从 PATH 查找）。
+    #[serde(default = "default_ffmpeg_path")]
+    pub ffmpeg_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcode: Option<MusicTranscodeOptions>,
+    /// EBU R128 响度扫描，写入 ReplayGain 标签（`REPLAYGAIN_TRACK_GAIN/PEAK`）。
+    #[serde(default)]
+    pub replay_gain: bool,
+}
+
+fn default_ffmpeg_path() -> String {
+    "ffmpeg".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MusicDownloadTotals {
     pub total: u32,
@@ -278,7 +442,7 @@ pub enum MusicJobState {
     Canceled,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MusicDownloadJobResult {
     pub index: u32,
@@ -291,9 +455,17 @@ pub struct MusicDownloadJobResult {
     pub bytes: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// 通过回退源下载时的实际来源服务与曲目 id。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_service: Option<MusicService>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_track_id: Option<String>,
+    /// 曲库中已有的较低音质文件（`skipOwned` 时）；本次下载为升级，成功后该文件被替换。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upgraded_from: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MusicDownloadStatus {
     pub done: bool,
@@ -0,0 +1,179 @@
+//! 下载后处理（需要 ffmpeg）：
+//! - 转码：源格式播放器支持不好（ogg、QQ 的加密容器变体等）时转为 mp3 / aac(m4a) / flac
+//! - 响度：`ebur128` 滤镜扫描 EBU R128 综合响度与真峰值，换算为 ReplayGain 2.0 标签
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, atomic::AtomicBool};
+
+use tokio::process::Command;
+
+use super::error::MusicError;
+use super::model::{MusicTranscodeCodec, MusicTranscodeOptions};
+use super::tagging::{self, ReplayGain};
+use crate::ffmpeg;
+
+/// ReplayGain 2.0 参考响度。
+pub const REPLAY_GAIN_REFERENCE_LUFS: f64 = -18.0;
+
+/// `ebur128` 摘要中的综合响度（LUFS）与真峰值（dBFS）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Loudness {
+    pub integrated_lufs: f64,
+    pub true_peak_dbfs: f64,
+}
+
+impl Loudness {
+    pub fn replay_gain(&self) -> ReplayGain {
+        ReplayGain {
+            track_gain_db: REPLAY_GAIN_REFERENCE_LUFS - self.integrated_lufs,
+            track_peak: 10f64.powf(self.true_peak_dbfs / 20.0),
+        }
+    }
+}
+
+/// 需要转码时返回转码后的路径（同目录同名，扩展名换成目标编码的）。
+pub fn transcode_target(path: &Path, opt: &MusicTranscodeOptions) -> Option<PathBuf> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    if ext == opt.codec.extension() {
+        return None;
+    }
+    if !opt.source_formats.is_empty()
+        && !opt
+            .source_formats
+            .iter()
+            .any(|f| f.trim().trim_start_matches('.').eq_ignore_ascii_case(&ext))
+    {
+        return None;
+    }
+    Some(path.with_extension(opt.codec.extension()))
+}
+
+/// ffmpeg 音频编码参数。
+pub fn codec_args(opt: &MusicTranscodeOptions) -> Vec<String> {
+    let lossy = |encoder: &str, default_kbps: u32| {
+        let kbps = opt.bitrate_kbps.filter(|n| *n > 0).unwrap_or(default_kbps);
+        vec![
+            "-c:a".to_string(),
+            encoder.to_string(),
+            "-b:a".to_string(),
+            format!("{kbps}k"),
+        ]
+    };
+    match opt.codec {
+        MusicTranscodeCodec::Mp3 => lossy("libmp3lame", 320),
+        MusicTranscodeCodec::Aac => lossy("aac", 256),
+        MusicTranscodeCodec::Flac => vec!["-c:a".to_string(), "flac".to_string()],
+    }
+}
+
+/// 转码到 `out_path`：先写 `*.part.<ext>`，成功后再改名，取消/失败时不留半成品。
+pub async fn transcode_ffmpeg(
+    ffmpeg_path: &str,
+    input: &Path,
+    opt: &MusicTranscodeOptions,
+    out_path: &Path,
+    overwrite: bool,
+    cancel: Option<&Arc<AtomicBool>>,
+) -> Result<(), MusicError> {
+    let bin = ffmpeg_path.trim();
+    if bin.is_empty() {
+        return Err(MusicError::InvalidInput("ffmpegPath is empty".to_string()));
+    }
+    if input.as_os_str().is_empty() || out_path.as_os_str().is_empty() {
+        return Err(MusicError::InvalidInput(
+            "empty input/output path".to_string(),
+        ));
+    }
+    if out_path.exists() && !overwrite {
+        return Err(MusicError::InvalidInput(format!(
+            "target exists: {}",
+            out_path.display()
+        )));
+    }
+    let part = out_path.with_extension(format!("part.{}", opt.codec.extension()));
+
+    let mut cmd = Command::new(bin);
+    cmd.arg("-hide_banner")
+        .arg("-loglevel")
+        .arg("error")
+        .arg("-y");
+    cmd.arg("-i").arg(input);
+    // 只保留音轨；标签与封面在之后统一重写。
+    cmd.arg("-map").arg("0:a:0").arg("-map_metadata").arg("-1");
+    cmd.args(codec_args(opt));
+    cmd.arg(&part);
+
+    match ffmpeg::run(cmd, cancel).await {
+        Ok(_) => {
+            tokio::fs::rename(&part, out_path).await?;
+            Ok(())
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&part).await;
+            Err(e.into())
+        }
+    }
+}
+
+/// 从 ffmpeg `ebur128` 滤镜的日志中解析最后一个 `Summary:` 段。
+pub fn parse_ebur128_summary(log: &str) -> Option<Loudness> {
+    let summary = &log[log.rfind("Summary:")?..];
+    let value = |key: &str| {
+        summary.lines().find_map(|line| {
+            let rest = line.trim().strip_prefix(key)?;
+            rest.split_whitespace().next()?.parse::<f64>().ok()
+        })
+    };
+    Some(Loudness {
+        integrated_lufs: value("I:")?,
+        // 纯静音时峰值为 `-inf`。
+        true_peak_dbfs: value("Peak:").unwrap_or(f64::NEG_INFINITY),
+    })
+}
+
+/// EBU R128 响度扫描（整首解码，耗时与时长成正比）。
+pub async fn scan_loudness(
+    ffmpeg_path: &str,
+    path: &Path,
+    cancel: Option<&Arc<AtomicBool>>,
+) -> Result<Loudness, MusicError> {
+    let bin = ffmpeg_path.trim();
+    if bin.is_empty() {
+        return Err(MusicError::InvalidInput("ffmpegPath is empty".to_string()));
+    }
+
+    let mut cmd = Command::new(bin);
+    // 摘要按 info 级别输出；逐帧日志降到 verbose 以免刷屏。
+    cmd.arg("-hide_banner")
+        .arg("-nostats")
+        .arg("-loglevel")
+        .arg("info");
+    cmd.arg("-i").arg(path);
+    cmd.arg("-map").arg("0:a:0");
+    cmd.arg("-af").arg("ebur128=peak=true:framelog=verbose");
+    cmd.arg("-f").arg("null").arg("-");
+
+    let log = ffmpeg::run(cmd, cancel).await?;
+    parse_ebur128_summary(&log)
+        .ok_or_else(|| MusicError::Parse("ffmpeg ebur128: no summary".to_string()))
+}
+
+/// 扫描响度并写入 ReplayGain 标签。
+pub async fn apply_replay_gain(
+    ffmpeg_path: &str,
+    path: &Path,
+    cancel: Option<&Arc<AtomicBool>>,
+) -> Result<ReplayGain, MusicError> {
+    let gain = scan_loudness(ffmpeg_path, path, cancel)
+        .await?
+        .replay_gain();
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || tagging::write_replay_gain(&path, &gain))
+        .await
+        .map_err(|e| MusicError::Other(e.to_string()))??;
+    Ok(gain)
+}
@@ -4,15 +4,26 @@ use std::time::Duration;
 use reqwest::Client;
 use serde_json::{Value, json};
 
+use crate::lyrics::providers::kugou::decrypt_kugou_krc;
 use crate::music::error::MusicError;
+use crate::music::lyrics::{krc_language, word_lyrics_to_lrc};
 use crate::music::model::{
-    AuthState, KugouUserInfo, MusicAlbum, MusicArtist, MusicQuality, MusicService, MusicTrack,
-    ProviderConfig,
+    AuthState, KugouUserInfo, MusicAlbum, MusicArtist, MusicChart, MusicLyrics, MusicPlaylist,
+    MusicQuality, MusicService, MusicTrack, ProviderConfig, WordLyricsFormat,
 };
 
 mod client;
 pub mod signatures;
 
+const SONG_INFO_URL: &str = "http://m.kugou.com/app/i/getSongInfo.php?cmd=playInfo&hash={hash}";
+const KRC_SEARCH_URL: &str = "http://krcs.kugou.com/search?ver=1&man=yes&client=mobi&hash={hash}";
+const KRC_DOWNLOAD_URL: &str = "http://lyrics.kugou.com/download";
+const RANK_LIST_URL: &str = "http://mobilecdnbj.kugou.com/api/v3/rank/list?version=9108&plat=0&showtype=2&parentid=0&apiver=6&area_code=1&withsong=0";
+const RANK_SONG_URL: &str = "http://mobilecdnbj.kugou.com/api/v3/rank/song?version=9108&ranktype=0&plat=0&pagesize=100&area_code=1&page=1&with_res_tag=0&rankid={id}";
+
+const PLAYLIST_PAGE_SIZE: u32 = 300;
+const PLAYLIST_MAX_PAGES: u32 = 100;
+
 fn qualities_from_item(item: &Value) -> Vec<MusicQuality> {
     let mut out = Vec::new();
     let file_128 = item
@@ -189,6 +200,7 @@ pub async fn search_tracks(
             album_id,
             duration_ms,
             cover_url: image,
+            isrc: None,
             qualities,
         });
     }
@@ -624,6 +636,7 @@ pub async fn album_tracks(
                 album_id,
                 duration_ms,
                 cover_url: cover,
+                isrc: None,
                 qualities,
             });
         }
@@ -641,6 +654,598 @@ pub async fn album_tracks(
     Ok(out)
 }
 
+fn json_id(v: Option<&Value>) -> Option<String> {
+    match v? {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+pub async fn search_playlists(
+    http: &Client,
+    _cfg: &ProviderConfig,
+    keyword: &str,
+    page: u32,
+    page_size: u32,
+    timeout: Duration,
+) -> Result<Vec<MusicPlaylist>, MusicError> {
+    let q = keyword.trim();
+    if q.is_empty() {
+        return Ok(vec![]);
+    }
+    let kg = client::KugouClient::new(http);
+    let mut params: BTreeMap<String, String> = BTreeMap::new();
+    params.insert("albumhide".to_string(), "0".to_string());
+    params.insert("iscorrection".to_string(), "1".to_string());
+    params.insert("keyword".to_string(), q.to_string());
+    params.insert("nocollect".to_string(), "0".to_string());
+    params.insert("page".to_string(), page.max(1).to_string());
+    params.insert("pagesize".to_string(), page_size.clamp(1, 50).to_string());
+    params.insert("platform".to_string(), "AndroidFilter".to_string());
+    let json = kg
+        .gateway_get(
+            "/v1/search/special",
+            "complexsearch.kugou.com",
+            params,
+            None,
+            timeout,
+        )
+        .await?;
+    let status = json.get("status").and_then(|v| v.as_i64()).unwrap_or(0);
+    if status != 1 {
+        return Ok(vec![]);
+    }
+    let list = json
+        .pointer("/data/lists")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let mut out = Vec::with_capacity(list.len());
+    for it in list {
+        // 新接口按 global_collection_id（gid）取歌单曲目。
+        let Some(id) = json_id(it.get("gid").or_else(|| it.get("global_collection_id"))) else {
+            continue;
+        };
+        let title = it
+            .get("specialname")
+            .or_else(|| it.get("specialName"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        out.push(MusicPlaylist {
+            service: MusicService::Kugou,
+            id,
+            title,
+            creator: it
+                .get("nickname")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            creator_id: json_id(it.get("suid")),
+            cover_url: it
+                .get("img")
+                .or_else(|| it.get("imgurl"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.replace("{size}", "480")),
+            track_count: it
+                .get("song_count")
+                .and_then(|v| v.as_u64())
+                .and_then(|n| u32::try_from(n).ok()),
+            description: it
+                .get("intro")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .filter(|s| !s.trim().is_empty()),
+        });
+    }
+    Ok(out)
+}
+
+fn map_playlist_song(s: &Value) -> Option<MusicTrack> {
+    let hash = s
+        .get("hash")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .trim()
+        .to_string();
+    if hash.is_empty() {
+        return None;
+    }
+    let mut artists = Vec::new();
+    let mut artist_ids = Vec::new();
+    if let Some(arr) = s.get("singerinfo").and_then(|v| v.as_array()) {
+        for a in arr {
+            if let Some(n) = a.get("name").and_then(|v| v.as_str())
+                && !n.trim().is_empty()
+            {
+                artists.push(n.to_string());
+            }
+            if let Some(i) = json_id(a.get("id")) {
+                artist_ids.push(i);
+            }
+        }
+    }
+    // name 形如 "歌手 - 歌名"。
+    let name = s.get("name").and_then(|v| v.as_str()).unwrap_or("").trim();
+    let title = match name.split_once(" - ") {
+        Some((_, t)) if !artists.is_empty() => t.trim().to_string(),
+        _ => name.to_string(),
+    };
+
+    let mut qualities = vec![MusicQuality {
+        id: "mp3_128".to_string(),
+        label: "MP3 128".to_string(),
+        format: "mp3".to_string(),
+        bitrate_kbps: Some(128),
+        lossless: false,
+    }];
+    let levels: Vec<i64> = s
+        .get("relate_goods")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|g| g.get("level").and_then(|v| v.as_i64()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if levels.contains(&4) {
+        qualities.push(MusicQuality {
+            id: "mp3_320".to_string(),
+            label: "MP3 320".to_string(),
+            format: "mp3".to_string(),
+            bitrate_kbps: Some(320),
+            lossless: false,
+        });
+    }
+    if levels.contains(&5) {
+        qualities.push(MusicQuality {
+            id: "flac".to_string(),
+            label: "FLAC".to_string(),
+            format: "flac".to_string(),
+            bitrate_kbps: Some(2000),
+            lossless: true,
+        });
+    }
+
+    Some(MusicTrack {
+        service: MusicService::Kugou,
+        id: hash,
+        title,
+        artists,
+        artist_ids,
+        album: s
+            .pointer("/albuminfo/name")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .filter(|s| !s.trim().is_empty()),
+        album_id: json_id(s.pointer("/albuminfo/id")).or_else(|| json_id(s.get("album_id"))),
+        duration_ms: s.get("timelen").and_then(|v| v.as_u64()).filter(|n| *n > 0),
+        cover_url: s
+            .get("cover")
+            .and_then(|v| v.as_str())
+            .map(|s| s.replace("{size}", "480")),
+        isrc: None,
+        qualities,
+    })
+}
+
+/// 歌单全部曲目（`playlist_id` 为 global_collection_id），按 `begin_idx` 分页。
+pub async fn playlist_tracks(
+    http: &Client,
+    _cfg: &ProviderConfig,
+    playlist_id: &str,
+    auth: &AuthState,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    let id = playlist_id.trim();
+    if id.is_empty() {
+        return Err(MusicError::InvalidInput("empty playlist_id".to_string()));
+    }
+    let kg = client::KugouClient::new(http);
+    let mut out: Vec<MusicTrack> = Vec::new();
+    for page in 0..PLAYLIST_MAX_PAGES {
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+        params.insert("area_code".to_string(), "1".to_string());
+        params.insert(
+            "begin_idx".to_string(),
+            (page * PLAYLIST_PAGE_SIZE).to_string(),
+        );
+        params.insert("global_collection_id".to_string(), id.to_string());
+        params.insert("mode".to_string(), "1".to_string());
+        params.insert("pagesize".to_string(), PLAYLIST_PAGE_SIZE.to_string());
+        params.insert("personal_switch".to_string(), "1".to_string());
+        params.insert("plat".to_string(), "1".to_string());
+        params.insert("type".to_string(), "1".to_string());
+        let json = kg
+            .gateway_get(
+                "/pubsongs/v2/get_other_list_file_nofilt",
+                "pubsongscdn.kugou.com",
+                params,
+                Some(auth),
+                timeout,
+            )
+            .await?;
+        let status = json.get("status").and_then(|v| v.as_i64()).unwrap_or(0);
+        if status != 1 {
+            return Err(MusicError::Other(format!("kugou playlist status={status}")));
+        }
+        let total = json
+            .pointer("/data/count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let songs = json
+            .pointer("/data/songs")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        if songs.is_empty() {
+            break;
+        }
+        out.extend(songs.iter().filter_map(map_playlist_song));
+        let fetched = u64::from((page + 1) * PLAYLIST_PAGE_SIZE);
+        if (songs.len() as u32) < PLAYLIST_PAGE_SIZE || (total > 0 && fetched >= total) {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+/// 当前登录用户的歌单（创建 + 收藏）。
+pub async fn user_playlists(
+    http: &Client,
+    _cfg: &ProviderConfig,
+    auth: &AuthState,
+    timeout: Duration,
+) -> Result<Vec<MusicPlaylist>, MusicError> {
+    let Some(user) = auth.kugou.as_ref() else {
+        return Err(MusicError::Unauthorized(
+            "kugou: missing token/userid".to_string(),
+        ));
+    };
+    let token = user.token.trim();
+    let userid = user.userid.trim();
+    if token.is_empty() || userid.is_empty() {
+        return Err(MusicError::Unauthorized(
+            "kugou: missing token/userid".to_string(),
+        ));
+    }
+
+    let kg = client::KugouClient::new(http);
+    let payload = json!({
+        "userid": userid,
+        "token": token,
+        "total_ver": 979,
+        "type": 2,
+        "page": 1,
+        "pagesize": 100
+    });
+    let json = kg
+        .gateway_post(
+            "/v7/get_all_list",
+            "cloudlist.service.kugou.com",
+            None,
+            &payload,
+            Some(auth),
+            timeout,
+        )
+        .await?;
+    let status = json.get("status").and_then(|v| v.as_i64()).unwrap_or(0);
+    if status != 1 {
+        return Err(MusicError::Other(format!(
+            "kugou user playlists status={status}"
+        )));
+    }
+    let list = json
+        .pointer("/data/info")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let mut out = Vec::with_capacity(list.len());
+    for it in list {
+        let Some(id) =
+            json_id(it.get("global_collection_id")).or_else(|| json_id(it.get("listid")))
+        else {
+            continue;
+        };
+        out.push(MusicPlaylist {
+            service: MusicService::Kugou,
+            id,
+            title: it
+                .get("name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string(),
+            creator: it
+                .get("list_create_username")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            creator_id: json_id(it.get("list_create_userid")),
+            cover_url: it
+                .get("pic")
+                .and_then(|v| v.as_str())
+                .map(|s| s.replace("{size}", "480"))
+                .filter(|s| !s.trim().is_empty()),
+            track_count: it
+                .get("count")
+                .and_then(|v| v.as_u64())
+                .and_then(|n| u32::try_from(n).ok()),
+            description: it
+                .get("intro")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .filter(|s| !s.trim().is_empty()),
+        });
+    }
+    Ok(out)
+}
+
+async fn get_json(http: &Client, url: &str, timeout: Duration) -> Result<Value, MusicError> {
+    let resp = http
+        .get(url)
+        .header("User-Agent", "Mozilla/5.0")
+        .timeout(timeout)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(resp.json::<Value>().await?)
+}
+
+pub async fn track_detail(
+    http: &Client,
+    track_hash: &str,
+    timeout: Duration,
+) -> Result<MusicTrack, MusicError> {
+    track_detail_with_template(http, SONG_INFO_URL, track_hash, timeout).await
+}
+
+#[doc(hidden)]
+pub async fn track_detail_with_template(
+    http: &Client,
+    template: &str,
+    track_hash: &str,
+    timeout: Duration,
+) -> Result<MusicTrack, MusicError> {
+    let hash = track_hash.trim();
+    if hash.is_empty() {
+        return Err(MusicError::InvalidInput("empty track_id".to_string()));
+    }
+    let url = template.replace("{hash}", &urlencoding::encode(hash));
+    let it = get_json(http, &url, timeout).await?;
+    if it.get("status").and_then(|v| v.as_i64()) != Some(1) {
+        return Err(MusicError::Other(format!("kugou: track {hash} not found")));
+    }
+    let str_of = |k: &str| {
+        it.get(k)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    // fileName 形如 "歌手 - 歌名"；songName / singerName 缺失时用它兜底。
+    let file_name = str_of("fileName").unwrap_or_default();
+    let (fn_artist, fn_title) = file_name
+        .split_once(" - ")
+        .map(|(a, t)| (a.trim().to_string(), t.trim().to_string()))
+        .unwrap_or_else(|| (String::new(), file_name.clone()));
+    let artists = str_of("singerName")
+        .unwrap_or(fn_artist)
+        .split('、')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let size = |k: &str| {
+        it.pointer(&format!("/extra/{k}"))
+            .cloned()
+            .unwrap_or(Value::Null)
+    };
+    let qualities = qualities_from_item(&json!({
+        "FileSize": size("128filesize"),
+        "HQ": { "FileSize": size("320filesize") },
+        "SQ": { "FileSize": size("sqfilesize") },
+    }));
+
+    Ok(MusicTrack {
+        service: MusicService::Kugou,
+        id: hash.to_string(),
+        title: str_of("songName").unwrap_or(fn_title),
+        artists,
+        artist_ids: json_id(it.get("singerId")).into_iter().collect(),
+        album: str_of("album_name").or_else(|| str_of("albumName")),
+        album_id: json_id(it.get("albumid")).filter(|id| id != "0"),
+        duration_ms: it
+            .get("timeLength")
+            .and_then(|v| v.as_u64())
+            .filter(|&s| s > 0)
+            .map(|s| s * 1000),
+        cover_url: str_of("imgUrl").map(|s| s.replace("{size}", "480")),
+        isrc: None,
+        qualities,
+    })
+}
+
+pub async fn list_charts(http: &Client, timeout: Duration) -> Result<Vec<MusicChart>, MusicError> {
+    list_charts_with_template(http, RANK_LIST_URL, timeout).await
+}
+
+#[doc(hidden)]
+pub async fn list_charts_with_template(
+    http: &Client,
+    template: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicChart>, MusicError> {
+    let json = get_json(http, template, timeout).await?;
+    if json.get("status").and_then(|v| v.as_i64()) != Some(1) {
+        return Err(MusicError::Other("kugou rank list failed".to_string()));
+    }
+    let text = |it: &Value, k: &str| {
+        it.get(k)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    Ok(json
+        .pointer("/data/info")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|it| {
+            Some(MusicChart {
+                service: MusicService::Kugou,
+                id: json_id(it.get("rankid"))?,
+                title: text(it, "rankname")?,
+                cover_url: text(it, "imgurl").map(|s| s.replace("{size}", "480")),
+                description: text(it, "intro"),
+                update_frequency: text(it, "update_frequency"),
+            })
+        })
+        .collect())
+}
+
+pub async fn chart_tracks(
+    http: &Client,
+    chart_id: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    chart_tracks_with_template(http, RANK_SONG_URL, chart_id, timeout).await
+}
+
+/// 榜单前 100 首。
+#[doc(hidden)]
+pub async fn chart_tracks_with_template(
+    http: &Client,
+    template: &str,
+    chart_id: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    let id = chart_id.trim();
+    if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(MusicError::InvalidInput(format!(
+            "invalid chart_id: {id:?}"
+        )));
+    }
+    let json = get_json(http, &template.replace("{id}", id), timeout).await?;
+    if json.get("status").and_then(|v| v.as_i64()) != Some(1) {
+        return Err(MusicError::Other(format!("kugou rank {id} failed")));
+    }
+    let mut out = Vec::new();
+    for it in json
+        .pointer("/data/info")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let hash = it
+            .get("hash")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .trim()
+            .to_string();
+        if hash.is_empty() {
+            continue;
+        }
+        // filename 形如 "歌手 - 歌名"。
+        let file_name = it.get("filename").and_then(|v| v.as_str()).unwrap_or("");
+        let (artist, title) = file_name
+            .split_once(" - ")
+            .map(|(a, t)| (a.trim(), t.trim()))
+            .unwrap_or(("", file_name.trim()));
+        let size = |k: &str| it.get(k).cloned().unwrap_or(Value::Null);
+        out.push(MusicTrack {
+            service: MusicService::Kugou,
+            id: hash,
+            title: title.to_string(),
+            artists: artist
+                .split('、')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            artist_ids: Vec::new(),
+            album: None,
+            album_id: json_id(it.get("album_id")).filter(|id| id != "0"),
+            duration_ms: it
+                .get("duration")
+                .and_then(|v| v.as_u64())
+                .filter(|&s| s > 0)
+                .map(|s| s * 1000),
+            cover_url: None,
+            isrc: None,
+            qualities: qualities_from_item(&json!({
+                "FileSize": size("filesize"),
+                "HQ": { "FileSize": size("320filesize") },
+                "SQ": { "FileSize": size("sqfilesize") },
+            })),
+        });
+    }
+    Ok(out)
+}
+
+pub async fn track_lyrics(
+    http: &Client,
+    track_hash: &str,
+    timeout: Duration,
+) -> Result<MusicLyrics, MusicError> {
+    track_lyrics_with_urls(http, KRC_SEARCH_URL, KRC_DOWNLOAD_URL, track_hash, timeout).await
+}
+
+/// 按 hash 查 KRC 候选并下载；翻译/音译取自 KRC 内嵌的 `[language:...]`。
+#[doc(hidden)]
+pub async fn track_lyrics_with_urls(
+    http: &Client,
+    search_template: &str,
+    download_url: &str,
+    track_hash: &str,
+    timeout: Duration,
+) -> Result<MusicLyrics, MusicError> {
+    use base64::Engine as _;
+
+    let hash = track_hash.trim();
+    if hash.is_empty() {
+        return Err(MusicError::InvalidInput("empty track_id".to_string()));
+    }
+    let url = search_template.replace("{hash}", &urlencoding::encode(hash));
+    let search = get_json(http, &url, timeout).await?;
+    let Some(cand) = search.pointer("/candidates/0") else {
+        return Err(MusicError::Other(format!("kugou: no lyrics for {hash}")));
+    };
+    let id = json_id(cand.get("id")).unwrap_or_default();
+    let accesskey = cand.get("accesskey").and_then(|v| v.as_str()).unwrap_or("");
+
+    let resp = http
+        .get(download_url)
+        .query(&[
+            ("ver", "1"),
+            ("client", "pc"),
+            ("id", id.as_str()),
+            ("accesskey", accesskey),
+            ("fmt", "krc"),
+            ("charset", "utf8"),
+        ])
+        .timeout(timeout)
+        .send()
+        .await?
+        .error_for_status()?;
+    let json = resp.json::<Value>().await?;
+    let content = json.get("content").and_then(|v| v.as_str()).unwrap_or("");
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(content.trim())
+        .map_err(|e| MusicError::Parse(format!("kugou: krc base64: {e}")))?;
+    let krc = decrypt_kugou_krc(&bytes)
+        .ok_or_else(|| MusicError::Parse("kugou: failed to decrypt krc".to_string()))?;
+    let original = word_lyrics_to_lrc(&krc);
+    if original.trim().is_empty() {
+        return Err(MusicError::Other(format!("kugou: no lyrics for {hash}")));
+    }
+    let (translation, romanization) = krc_language(&krc);
+    Ok(MusicLyrics {
+        service: MusicService::Kugou,
+        track_id: hash.to_string(),
+        original,
+        translation,
+        romanization,
+        word_lyrics: Some(krc),
+        word_format: Some(WordLyricsFormat::Krc),
+    })
+}
+
 pub async fn track_download_url(
     http: &Client,
     _cfg: &ProviderConfig,
@@ -3,13 +3,28 @@ use std::time::Duration;
 use reqwest::Client;
 use serde_json::Value;
 
+use crate::lyrics::parse::format_lrc_time;
 use crate::music::error::MusicError;
-use crate::music::model::{MusicAlbum, MusicArtist, MusicQuality, MusicService, MusicTrack};
+use crate::music::lyrics::non_empty;
+use crate::music::model::{
+    AuthState, KuwoUserInfo, MusicAlbum, MusicArtist, MusicChart, MusicLyrics, MusicPlaylist,
+    MusicQuality, MusicService, MusicTrack,
+};
 
 const SEARCH_URL: &str = "http://search.kuwo.cn/r.s?client=kt&encoding=utf8&rformat=json&mobi=1&vipver=1&pn={pn}&rn={rn}&correct=1&all={q}&ft={ft}";
 const DOWNLOAD_URL2: &str = "https://mobi.kuwo.cn/mobi.s?f=web&user=0&source=kwplayer_ar_5.0.0.0_B_jiakong_vh.apk&type=convert_url_with_sign&rid={rid}&br={br}";
+const SONG_INFO_LRC_URL: &str =
+    "http://m.kuwo.cn/newh5/singles/songinfoandlrc?musicId={rid}&httpsStatus=1";
+const USER_VIP_URL: &str = "https://vip1.kuwo.cn/vip/v2/user/vip?op=ui&uid={uid}&sid={sid}";
 const ALBUM_INFO_URL: &str = "https://search.kuwo.cn/r.s?pn={pn}&rn={rn}&albumid={albumid}&stype=albuminfo&show_copyright_off=1&alflac=1&pcmp4=1&encoding=utf8&plat=pc&vipver=MUSIC_9.1.1.2_BCS2&devid=38668888&newver=1&pcjson=1";
 const ARTIST_ALBUM_LIST_URL: &str = "https://search.kuwo.cn/r.s?pn=0&rn=10000&artistid={artistid}&stype=albumlist&sortby=1&alflac=1&show_copyright_off=1&pcmp4=1&encoding=utf8&plat=pc&thost=search.kuwo.cn&vipver=MUSIC_9.1.1.2_BCS2&devid=38668888&pcjson=1";
+const BANG_LIST_URL: &str =
+    "http://qukudata.kuwo.cn/q.k?op=query&cont=tree&node=2&pn=0&rn=1000&fmt=json&level=2";
+const BANG_SONG_URL: &str = "http://kbangserver.kuwo.cn/ksong.s?from=pc&fmt=json&pn=0&rn=100&type=bang&data=content&id={id}";
+const PLAYLIST_INFO_URL: &str = "http://nplserver.kuwo.cn/pl.svc?op=getlistinfo&pid={pid}&pn={pn}&rn={rn}&encode=utf8&keyset=pl2012&identity=kuwo&pcmp4=1&vipver=MUSIC_9.0.5.0_W1&newver=1";
+
+const PLAYLIST_PAGE_SIZE: u32 = 500;
+const PLAYLIST_MAX_PAGES: u32 = 100;
 
 const COVER_PREFIX: &str = "https://img3.kuwo.cn/star/albumcover/";
 const ARTIST_COVER_PREFIX: &str = "https://star.kuwo.cn/star/starheads/";
@@ -23,6 +38,11 @@ fn map_br(quality_id: &str) -> &'static str {
     }
 }
 
+/// `map_br` 结果对应的码率（kbps）。
+fn br_kbps(br: &str) -> u32 {
+    br.split('k').next().unwrap_or("").parse().unwrap_or(0)
+}
+
 fn parse_nm_info_to_qualities(nm_info: &str) -> Vec<MusicQuality> {
     let mut out = Vec::new();
     for part in nm_info.split(';') {
@@ -64,6 +84,15 @@ fn parse_nm_info_to_qualities(nm_info: &str) -> Vec<MusicQuality> {
     out
 }
 
+/// 歌单接口里的 id/时长等字段有时是字符串、有时是数字。
+fn str_or_num(v: Option<&Value>) -> Option<String> {
+    match v? {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
 async fn get_json(http: &Client, url: &str, timeout: Duration) -> Result<Value, MusicError> {
     let resp = http
         .get(url)
@@ -178,6 +207,7 @@ pub async fn search_tracks(
             album_id,
             duration_ms,
             cover_url: cover,
+            isrc: None,
             qualities,
         });
     }
@@ -407,6 +437,7 @@ pub async fn album_tracks(
             album_id: album_id.clone(),
             duration_ms,
             cover_url: cover,
+            isrc: None,
             qualities,
         });
     }
@@ -477,29 +508,484 @@ pub async fn artist_albums(
     Ok(out)
 }
 
+pub async fn search_playlists(
+    http: &Client,
+    keyword: &str,
+    page: u32,
+    page_size: u32,
+    timeout: Duration,
+) -> Result<Vec<MusicPlaylist>, MusicError> {
+    let q = keyword.trim();
+    if q.is_empty() {
+        return Ok(vec![]);
+    }
+    let pn = page.saturating_sub(1);
+    let rn = page_size.clamp(1, 100);
+    let q_enc = urlencoding::encode(q);
+    let url = SEARCH_URL
+        .replace("{pn}", &pn.to_string())
+        .replace("{rn}", &rn.to_string())
+        .replace("{q}", q_enc.as_ref())
+        .replace("{ft}", "playlist");
+    let json = get_json(http, &url, timeout).await?;
+    let list = json
+        .get("abslist")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let mut out = Vec::with_capacity(list.len());
+    for it in list {
+        let Some(id) = str_or_num(it.get("playlistid").or_else(|| it.get("PLAYLISTID"))) else {
+            continue;
+        };
+        let title = it
+            .get("name")
+            .or_else(|| it.get("NAME"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        out.push(MusicPlaylist {
+            service: MusicService::Kuwo,
+            id,
+            title,
+            creator: it
+                .get("nickname")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            creator_id: str_or_num(it.get("uid")),
+            cover_url: it
+                .get("pic")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            track_count: str_or_num(it.get("songnum")).and_then(|s| s.parse::<u32>().ok()),
+            description: it
+                .get("intro")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .filter(|s| !s.trim().is_empty()),
+        });
+    }
+    Ok(out)
+}
+
+/// 歌单/榜单 `musiclist` 条目。
+fn map_musiclist_item(it: &Value) -> Option<MusicTrack> {
+    let track_id = str_or_num(it.get("id"))?;
+    let title = it
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let artists: Vec<String> = it
+        .get("artist")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .split('&')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let artist_ids: Vec<String> = str_or_num(it.get("artistid"))
+        .into_iter()
+        .flat_map(|s| {
+            s.split('&')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let nm = it
+        .get("N_MINFO")
+        .or_else(|| it.get("MINFO"))
+        .or_else(|| it.get("minfo"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    Some(MusicTrack {
+        service: MusicService::Kuwo,
+        id: track_id,
+        title,
+        artists,
+        artist_ids,
+        album: it
+            .get("album")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .filter(|s| !s.trim().is_empty()),
+        album_id: str_or_num(it.get("albumid")),
+        duration_ms: str_or_num(it.get("duration"))
+            .and_then(|s| s.parse::<u64>().ok())
+            .map(|s| s * 1000),
+        cover_url: it
+            .get("web_albumpic_short")
+            .and_then(|v| v.as_str())
+            .map(|s| format!("{}{}", COVER_PREFIX, s).replace("/120", "/500")),
+        isrc: None,
+        qualities: parse_nm_info_to_qualities(nm),
+    })
+}
+
+pub async fn playlist_tracks(
+    http: &Client,
+    playlist_id: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    playlist_tracks_with_template(http, PLAYLIST_INFO_URL, playlist_id, timeout).await
+}
+
+/// 按 `PLAYLIST_PAGE_SIZE` 分页拉取，直到取满 `total` 或某页不足一页。
+#[doc(hidden)]
+pub async fn playlist_tracks_with_template(
+    http: &Client,
+    template: &str,
+    playlist_id: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    let id = playlist_id.trim();
+    if id.is_empty() {
+        return Err(MusicError::InvalidInput("empty playlist_id".to_string()));
+    }
+    let mut out: Vec<MusicTrack> = Vec::new();
+    for pn in 0..PLAYLIST_MAX_PAGES {
+        let url = template
+            .replace("{pid}", id)
+            .replace("{pn}", &pn.to_string())
+            .replace("{rn}", &PLAYLIST_PAGE_SIZE.to_string());
+        let json = get_json(http, &url, timeout).await?;
+        let total = str_or_num(json.get("total"))
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(0);
+        let list = json
+            .get("musiclist")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        if list.is_empty() {
+            break;
+        }
+        out.extend(list.iter().filter_map(map_musiclist_item));
+        let fetched = u64::from((pn + 1) * PLAYLIST_PAGE_SIZE);
+        if (list.len() as u32) < PLAYLIST_PAGE_SIZE || (total > 0 && fetched >= total) {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+pub async fn list_charts(http: &Client, timeout: Duration) -> Result<Vec<MusicChart>, MusicError> {
+    list_charts_with_template(http, BANG_LIST_URL, timeout).await
+}
+
+#[doc(hidden)]
+pub async fn list_charts_with_template(
+    http: &Client,
+    template: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicChart>, MusicError> {
+    let json = get_json(http, template, timeout).await?;
+    let text = |it: &Value, k: &str| {
+        it.get(k)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    Ok(json
+        .get("child")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|it| {
+            Some(MusicChart {
+                service: MusicService::Kuwo,
+                id: str_or_num(it.get("sourceid"))?,
+                title: text(it, "name")?,
+                cover_url: text(it, "pic"),
+                description: text(it, "intro"),
+                update_frequency: text(it, "info"),
+            })
+        })
+        .collect())
+}
+
+pub async fn chart_tracks(
+    http: &Client,
+    chart_id: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    chart_tracks_with_template(http, BANG_SONG_URL, chart_id, timeout).await
+}
+
+/// 榜单前 100 首（`chart_id` 为 `sourceid`）。
+#[doc(hidden)]
+pub async fn chart_tracks_with_template(
+    http: &Client,
+    template: &str,
+    chart_id: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    let id = chart_id.trim();
+    if id.is_empty() {
+        return Err(MusicError::InvalidInput("empty chart_id".to_string()));
+    }
+    let url = template.replace("{id}", &urlencoding::encode(id));
+    let json = get_json(http, &url, timeout).await?;
+    Ok(json
+        .get("musiclist")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(map_musiclist_item)
+        .collect())
+}
+
+/// `songinfoandlrc` 同时返回曲目信息与逐行歌词（`data.songinfo` / `data.lrclist`）。
+async fn song_info_and_lrc(
+    http: &Client,
+    template: &str,
+    track_id: &str,
+    timeout: Duration,
+) -> Result<Value, MusicError> {
+    let id = track_id.trim();
+    if id.is_empty() {
+        return Err(MusicError::InvalidInput("empty track_id".to_string()));
+    }
+    let url = template.replace("{rid}", &urlencoding::encode(id));
+    let json = get_json(http, &url, timeout).await?;
+    match json.get("data") {
+        Some(data) if data.is_object() => Ok(data.clone()),
+        _ => Err(MusicError::Other(format!("kuwo: track {id} not found"))),
+    }
+}
+
+pub async fn track_detail(
+    http: &Client,
+    track_id: &str,
+    timeout: Duration,
+) -> Result<MusicTrack, MusicError> {
+    track_detail_with_template(http, SONG_INFO_LRC_URL, track_id, timeout).await
+}
+
+#[doc(hidden)]
+pub async fn track_detail_with_template(
+    http: &Client,
+    template: &str,
+    track_id: &str,
+    timeout: Duration,
+) -> Result<MusicTrack, MusicError> {
+    let data = song_info_and_lrc(http, template, track_id, timeout).await?;
+    let Some(info) = data.get("songinfo").filter(|v| v.is_object()) else {
+        return Err(MusicError::Other(format!(
+            "kuwo: track {} not found",
+            track_id.trim()
+        )));
+    };
+    let str_of = |k: &str| {
+        info.get(k)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    let split = |s: Option<String>| -> Vec<String> {
+        s.unwrap_or_default()
+            .split('&')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    };
+    // coopFormats: ["320kmp3", "128kmp3", "2000kflac", ...]
+    let nm = info
+        .get("coopFormats")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|f| f.as_str())
+        .map(|f| format!("bitrate:{}", br_kbps(f)))
+        .collect::<Vec<_>>()
+        .join(";");
+    Ok(MusicTrack {
+        service: MusicService::Kuwo,
+        id: str_or_num(info.get("id")).unwrap_or_else(|| track_id.trim().to_string()),
+        title: str_of("songName").unwrap_or_default(),
+        artists: split(str_of("artist")),
+        artist_ids: split(str_or_num(info.get("artistId"))),
+        album: str_of("album"),
+        album_id: str_or_num(info.get("albumId")).filter(|id| id != "0"),
+        duration_ms: str_or_num(info.get("duration"))
+            .and_then(|s| s.parse::<u64>().ok())
+            .map(|s| s * 1000),
+        cover_url: str_of("pic"),
+        isrc: None,
+        qualities: parse_nm_info_to_qualities(&nm),
+    })
+}
+
+pub async fn track_lyrics(
+    http: &Client,
+    track_id: &str,
+    timeout: Duration,
+) -> Result<MusicLyrics, MusicError> {
+    track_lyrics_with_template(http, SONG_INFO_LRC_URL, track_id, timeout).await
+}
+
+/// 酷我只有行级歌词；带翻译的歌曲会在原文后紧跟一条同时间戳的译文行。
+#[doc(hidden)]
+pub async fn track_lyrics_with_template(
+    http: &Client,
+    template: &str,
+    track_id: &str,
+    timeout: Duration,
+) -> Result<MusicLyrics, MusicError> {
+    let data = song_info_and_lrc(http, template, track_id, timeout).await?;
+    let mut original = Vec::new();
+    let mut translation = Vec::new();
+    let mut last_time: Option<f64> = None;
+    for row in data
+        .get("lrclist")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let Some(time) = str_or_num(row.get("time")).and_then(|s| s.parse::<f64>().ok()) else {
+            continue;
+        };
+        let text = row
+            .get("lineLyric")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .trim();
+        let ms = (time.max(0.0) * 1000.0).round() as u64;
+        let line = format!("[{}]{text}", format_lrc_time(ms));
+        if last_time == Some(time) {
+            translation.push(line);
+            last_time = None;
+        } else {
+            original.push(line);
+            last_time = Some(time);
+        }
+    }
+    if original.is_empty() {
+        return Err(MusicError::Other(format!(
+            "kuwo: no lyrics for {}",
+            track_id.trim()
+        )));
+    }
+    Ok(MusicLyrics {
+        service: MusicService::Kuwo,
+        track_id: track_id.trim().to_string(),
+        original: original.join("\n"),
+        translation: non_empty(Some(translation.join("\n"))),
+        romanization: None,
+        word_lyrics: None,
+        word_format: None,
+    })
+}
+
+/// 用网页/App 登录得到的 uid + sid 导入酷我账号，并查询会员状态。
+///
+/// sid 无效或已过期时返回 `Unauthorized`。
+pub async fn login_with_token(
+    http: &Client,
+    uid: &str,
+    sid: &str,
+    timeout: Duration,
+) -> Result<KuwoUserInfo, MusicError> {
+    login_with_token_template(http, USER_VIP_URL, uid, sid, timeout).await
+}
+
+#[doc(hidden)]
+pub async fn login_with_token_template(
+    http: &Client,
+    template: &str,
+    uid: &str,
+    sid: &str,
+    timeout: Duration,
+) -> Result<KuwoUserInfo, MusicError> {
+    let (uid, sid) = (uid.trim(), sid.trim());
+    if uid.is_empty() || sid.is_empty() {
+        return Err(MusicError::InvalidInput("kuwo: empty uid/sid".to_string()));
+    }
+    let url = template
+        .replace("{uid}", &urlencoding::encode(uid))
+        .replace("{sid}", &urlencoding::encode(sid));
+    let json = get_json(http, &url, timeout).await?;
+    let code = json.pointer("/meta/code").and_then(|v| v.as_i64());
+    let data = json.get("data").filter(|v| v.is_object());
+    let (Some(200), Some(data)) = (code, data) else {
+        let msg = json
+            .pointer("/meta/desc")
+            .and_then(|v| v.as_str())
+            .unwrap_or("invalid sid");
+        return Err(MusicError::Unauthorized(format!("kuwo: {msg}")));
+    };
+    let vip_type = data
+        .get("vipType")
+        .or_else(|| data.get("vip_type"))
+        .and_then(|v| str_or_num(Some(v)))
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    let nickname = data
+        .get("nickname")
+        .or_else(|| data.get("nickName"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    Ok(KuwoUserInfo {
+        uid: uid.to_string(),
+        sid: sid.to_string(),
+        nickname,
+        vip_type,
+    })
+}
+
+/// 获取下载地址；`auth.kuwo` 存在时以该账号请求（会员可解锁无损/高码率）。
+///
+/// 接口会把无权限的音质静默降级，这里检测到降级时返回 `NotEntitled`，而不是下载到低音质文件。
 pub async fn track_download_url(
     http: &Client,
     track_id: &str,
     quality_id: &str,
+    auth: &AuthState,
     timeout: Duration,
 ) -> Result<(String, String), MusicError> {
-    track_download_url_with_template(http, DOWNLOAD_URL2, track_id, quality_id, timeout).await
+    let t = DOWNLOAD_URL2;
+    track_download_url_with_template(http, t, track_id, quality_id, auth, timeout, true).await
 }
 
+/// 获取播放地址；与 [`track_download_url`] 不同，音质被降级时直接返回服务端给出的地址。
+pub async fn track_play_url(
+    http: &Client,
+    track_id: &str,
+    quality_id: &str,
+    auth: &AuthState,
+    timeout: Duration,
+) -> Result<(String, String), MusicError> {
+    let t = DOWNLOAD_URL2;
+    track_download_url_with_template(http, t, track_id, quality_id, auth, timeout, false).await
+}
+
+/// `strict` 为 `true` 时音质降级返回 `NotEntitled`。
 #[doc(hidden)]
 pub async fn track_download_url_with_template(
     http: &Client,
     template: &str,
     track_id: &str,
     quality_id: &str,
+    auth: &AuthState,
     timeout: Duration,
+    strict: bool,
 ) -> Result<(String, String), MusicError> {
     let id = track_id.trim();
     if id.is_empty() {
         return Err(MusicError::InvalidInput("empty track_id".to_string()));
     }
     let br = map_br(quality_id.trim());
-    let url = template.replace("{rid}", id).replace("{br}", br);
+    let mut url = template.replace("{rid}", id).replace("{br}", br);
+    if let Some(user) = auth.kuwo.as_ref() {
+        let uid = urlencoding::encode(user.uid.trim()).into_owned();
+        url = url.replace("user=0", &format!("user={uid}"));
+        url.push_str(&format!(
+            "&loginUid={uid}&loginSid={}",
+            urlencoding::encode(user.sid.trim())
+        ));
+    }
     let json = get_json(http, &url, timeout).await?;
     let url = json
         .pointer("/data/url")
@@ -516,5 +1002,23 @@ pub async fn track_download_url_with_template(
         .unwrap_or("mp3")
         .trim()
         .to_string();
+
+    let want = br_kbps(br);
+    let got = str_or_num(json.pointer("/data/bitrate")).and_then(|s| s.parse::<u32>().ok());
+    let downgraded = (br.ends_with("flac") && !fmt.eq_ignore_ascii_case("flac"))
+        || got.is_some_and(|b| b > 0 && b < want);
+    if strict && downgraded {
+        let who = match auth.kuwo.as_ref() {
+            Some(u) => format!("account {}", u.uid),
+            None => "anonymous user (login required)".to_string(),
+        };
+        let served = match got {
+            Some(b) => format!("{fmt} {b}k"),
+            None => fmt,
+        };
+        return Err(MusicError::NotEntitled(format!(
+            "kuwo: {quality_id} unavailable for {who} (server offered {served})"
+        )));
+    }
     Ok((url, fmt))
 }
@@ -1,11 +1,17 @@
+use std::io::Cursor;
 use std::time::Duration;
 
+use base64::Engine;
+use image::ImageFormat;
+use qrcode::QrCode;
 use reqwest::Client;
 use serde_json::{Value, json};
 
 use crate::music::error::MusicError;
+use crate::music::lyrics::{non_empty, word_lyrics_to_lrc};
 use crate::music::model::{
-    AuthState, MusicAlbum, MusicArtist, MusicQuality, MusicService, MusicTrack, ProviderConfig,
+    AuthState, MusicAlbum, MusicArtist, MusicChart, MusicLyrics, MusicPlaylist, MusicQuality,
+    MusicService, MusicTrack, NeteaseUserInfo, ProviderConfig, WordLyricsFormat,
 };
 
 const DEFAULT_NETEASE_BASE_URLS: &[&str] = &[
@@ -16,6 +22,9 @@ const DEFAULT_NETEASE_BASE_URLS: &[&str] = &[
     "https://zm.armoe.cn",
 ];
 
+const PLAYLIST_PAGE_SIZE: u32 = 500;
+const PLAYLIST_MAX_PAGES: u32 = 100;
+
 fn bases(cfg: &ProviderConfig) -> Result<Vec<String>, MusicError> {
     let mut out: Vec<String> = Vec::new();
     for raw in &cfg.netease_base_urls {
@@ -275,6 +284,7 @@ fn map_song_to_track(song: &Value) -> Option<MusicTrack> {
         album_id,
         duration_ms,
         cover_url: cover,
+        isrc: None,
         qualities,
     })
 }
@@ -532,6 +542,291 @@ pub async fn artist_albums(
     Ok(out)
 }
 
+fn map_playlist(it: &Value) -> Option<MusicPlaylist> {
+    let id = it
+        .get("id")
+        .and_then(|v| v.as_i64())
+        .map(|n| n.to_string())
+        .unwrap_or_default();
+    if id.is_empty() {
+        return None;
+    }
+    let title = it
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    Some(MusicPlaylist {
+        service: MusicService::Netease,
+        id,
+        title,
+        creator: it
+            .pointer("/creator/nickname")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        creator_id: it
+            .pointer("/creator/userId")
+            .and_then(|v| v.as_i64())
+            .map(|n| n.to_string()),
+        cover_url: it
+            .get("coverImgUrl")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        track_count: it
+            .get("trackCount")
+            .and_then(|v| v.as_i64())
+            .and_then(|n| u32::try_from(n).ok()),
+        description: it
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .filter(|s| !s.trim().is_empty()),
+    })
+}
+
+pub async fn search_playlists(
+    http: &Client,
+    cfg: &ProviderConfig,
+    keyword: &str,
+    page: u32,
+    page_size: u32,
+    timeout: Duration,
+) -> Result<Vec<MusicPlaylist>, MusicError> {
+    let q = keyword.trim();
+    if q.is_empty() {
+        return Ok(vec![]);
+    }
+    let limit = page_size.clamp(1, 50);
+    let payload = json!({
+        "keywords": q,
+        "limit": limit,
+        "type": 1000,
+        "offset": (page.saturating_sub(1) * limit),
+    });
+    let json = post_json_try_bases(http, cfg, "/cloudsearch", &payload, None, timeout).await?;
+    let code = json.get("code").and_then(|v| v.as_i64()).unwrap_or(0);
+    if code != 200 {
+        return Ok(vec![]);
+    }
+    let list = json
+        .pointer("/result/playlists")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    Ok(list.iter().filter_map(map_playlist).collect())
+}
+
+/// 歌单全部曲目；`/playlist/track/all` 按 offset 分页，私密歌单需要登录 cookie。
+pub async fn playlist_tracks(
+    http: &Client,
+    cfg: &ProviderConfig,
+    playlist_id: &str,
+    auth: &AuthState,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    let id = playlist_id.trim();
+    if id.is_empty() {
+        return Err(MusicError::InvalidInput("empty playlist_id".to_string()));
+    }
+    let cookie = auth.netease_cookie.as_deref();
+    let mut out: Vec<MusicTrack> = Vec::new();
+    for page in 0..PLAYLIST_MAX_PAGES {
+        let payload = json!({
+            "id": id,
+            "limit": PLAYLIST_PAGE_SIZE,
+            "offset": page * PLAYLIST_PAGE_SIZE,
+        });
+        let json = post_json_try_bases(http, cfg, "/playlist/track/all", &payload, cookie, timeout)
+            .await?;
+        let code = json.get("code").and_then(|v| v.as_i64()).unwrap_or(0);
+        if code != 200 {
+            return Err(MusicError::Other(format!("netease playlist code={code}")));
+        }
+        let songs = json
+            .get("songs")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        out.extend(songs.iter().filter_map(map_song_to_track));
+        if (songs.len() as u32) < PLAYLIST_PAGE_SIZE {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+/// 当前 cookie 对应用户的歌单（含创建与收藏）。
+pub async fn user_playlists(
+    http: &Client,
+    cfg: &ProviderConfig,
+    auth: &AuthState,
+    timeout: Duration,
+) -> Result<Vec<MusicPlaylist>, MusicError> {
+    let cookie = auth
+        .netease_cookie
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| MusicError::Unauthorized("missing netease cookie".to_string()))?;
+
+    let uid = account_info(http, cfg, cookie, timeout).await?.user_id;
+    let payload = json!({ "uid": uid.parse::<i64>().unwrap_or(0), "limit": 1000, "offset": 0 });
+    let json =
+        post_json_try_bases(http, cfg, "/user/playlist", &payload, Some(cookie), timeout).await?;
+    let code = json.get("code").and_then(|v| v.as_i64()).unwrap_or(0);
+    if code != 200 {
+        return Err(MusicError::Other(format!(
+            "netease user playlists code={code}"
+        )));
+    }
+    let list = json
+        .get("playlist")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    Ok(list.iter().filter_map(map_playlist).collect())
+}
+
+pub async fn list_charts(
+    http: &Client,
+    cfg: &ProviderConfig,
+    timeout: Duration,
+) -> Result<Vec<MusicChart>, MusicError> {
+    let json = post_json_try_bases(http, cfg, "/toplist", &json!({}), None, timeout).await?;
+    let code = json.get("code").and_then(|v| v.as_i64()).unwrap_or(0);
+    if code != 200 {
+        return Err(MusicError::Other(format!("netease toplist code={code}")));
+    }
+    let text = |it: &Value, k: &str| {
+        it.get(k)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    Ok(json
+        .get("list")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|it| {
+            Some(MusicChart {
+                service: MusicService::Netease,
+                id: it.get("id").and_then(|v| v.as_i64())?.to_string(),
+                title: text(it, "name")?,
+                cover_url: text(it, "coverImgUrl"),
+                description: text(it, "description"),
+                update_frequency: text(it, "updateFrequency"),
+            })
+        })
+        .collect())
+}
+
+/// 网易云榜单本身就是歌单。
+pub async fn chart_tracks(
+    http: &Client,
+    cfg: &ProviderConfig,
+    chart_id: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    playlist_tracks(http, cfg, chart_id, &AuthState::default(), timeout).await
+}
+
+/// 每日推荐（需登录 cookie）。
+pub async fn daily_recommendations(
+    http: &Client,
+    cfg: &ProviderConfig,
+    auth: &AuthState,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    let cookie = auth
+        .netease_cookie
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| MusicError::Unauthorized("missing netease cookie".to_string()))?;
+    let json = post_json_try_bases(
+        http,
+        cfg,
+        "/recommend/songs",
+        &json!({}),
+        Some(cookie),
+        timeout,
+    )
+    .await?;
+    match json.get("code").and_then(|v| v.as_i64()).unwrap_or(0) {
+        200 => {}
+        301 => {
+            return Err(MusicError::Unauthorized(
+                "netease: not logged in".to_string(),
+            ));
+        }
+        code => {
+            return Err(MusicError::Other(format!(
+                "netease recommend songs code={code}"
+            )));
+        }
+    }
+    Ok(json
+        .pointer("/data/dailySongs")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(map_song_to_track)
+        .collect())
+}
+
+pub async fn track_detail(
+    http: &Client,
+    cfg: &ProviderConfig,
+    track_id: &str,
+    timeout: Duration,
+) -> Result<MusicTrack, MusicError> {
+    let id = track_id.trim();
+    if id.is_empty() {
+        return Err(MusicError::InvalidInput("empty track_id".to_string()));
+    }
+    let payload = json!({ "ids": id });
+    let json = post_json_try_bases(http, cfg, "/song/detail", &payload, None, timeout).await?;
+    json.pointer("/songs/0")
+        .and_then(map_song_to_track)
+        .ok_or_else(|| MusicError::Other(format!("netease: track {id} not found")))
+}
+
+/// `/lyric/new`：LRC 原文、翻译（`tlyric`）、音译（`romalrc`）与逐字 YRC。
+pub async fn track_lyrics(
+    http: &Client,
+    cfg: &ProviderConfig,
+    track_id: &str,
+    timeout: Duration,
+) -> Result<MusicLyrics, MusicError> {
+    let id = track_id.trim();
+    if id.is_empty() {
+        return Err(MusicError::InvalidInput("empty track_id".to_string()));
+    }
+    let payload = json!({ "id": id });
+    let json = post_json_try_bases(http, cfg, "/lyric/new", &payload, None, timeout).await?;
+    let text = |key: &str| {
+        non_empty(
+            json.pointer(&format!("/{key}/lyric"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+        )
+    };
+    let yrc = text("yrc");
+    let Some(original) = text("lrc").or_else(|| yrc.as_deref().map(word_lyrics_to_lrc)) else {
+        return Err(MusicError::Other(format!("netease: no lyrics for {id}")));
+    };
+    Ok(MusicLyrics {
+        service: MusicService::Netease,
+        track_id: id.to_string(),
+        original,
+        translation: text("tlyric"),
+        romanization: text("romalrc"),
+        word_format: yrc.is_some().then_some(WordLyricsFormat::Yrc),
+        word_lyrics: yrc,
+    })
+}
+
 pub async fn track_download_url(
     http: &Client,
     cfg: &ProviderConfig,
@@ -649,6 +944,247 @@ pub async fn track_download_url(
     )))
 }
 
+// QR 登录：unikey -> 二维码（本地渲染登录链接）-> 轮询 -> cookie。
+
+const QR_LOGIN_URL: &str = "https://music.163.com/login?codekey=";
+
+#[derive(Debug, Clone)]
+pub struct NeteaseQr {
+    pub key: String,
+    /// 二维码内容（网易云 App 扫码打开的链接）。
+    pub url: String,
+    /// PNG 二维码图片 base64（不含 `data:` 前缀）。
+    pub image_base64: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NeteaseQrStatus {
+    /// 801：等待扫码。
+    Waiting,
+    /// 802：已扫码，等待在 App 上确认。
+    Scanned {
+        nickname: Option<String>,
+    },
+    /// 800：二维码已过期。
+    Expired,
+    /// 803：登录成功。
+    Done {
+        cookie: String,
+    },
+    Other {
+        code: i64,
+        message: Option<String>,
+    },
+}
+
+fn qr_png_base64(text: &str) -> Result<String, MusicError> {
+    let code = QrCode::new(text.as_bytes()).map_err(|e| MusicError::Parse(e.to_string()))?;
+    let img = code
+        .render::<image::Luma<u8>>()
+        .max_dimensions(320, 320)
+        .build();
+    let mut buf: Vec<u8> = Vec::new();
+    image::DynamicImage::ImageLuma8(img)
+        .write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)
+        .map_err(|e| MusicError::Parse(e.to_string()))?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(buf))
+}
+
+/// 规范化 API 返回的 cookie：去掉 `Max-Age` / `Path` / `Expires` 等属性，只保留 `k=v` 对（同名取后者）。
+pub fn normalize_cookie(raw: &str) -> String {
+    const ATTRS: &[&str] = &[
+        "max-age", "expires", "path", "domain", "httponly", "secure", "samesite",
+    ];
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    for part in raw.split(';') {
+        let Some((k, v)) = part.split_once('=') else {
+            continue;
+        };
+        let (k, v) = (k.trim(), v.trim());
+        if k.is_empty() || ATTRS.contains(&k.to_ascii_lowercase().as_str()) {
+            continue;
+        }
+        match pairs.iter_mut().find(|(pk, _)| pk == k) {
+            Some(p) => p.1 = v.to_string(),
+            None => pairs.push((k.to_string(), v.to_string())),
+        }
+    }
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub async fn netease_qr_create(
+    http: &Client,
+    cfg: &ProviderConfig,
+    timeout: Duration,
+) -> Result<NeteaseQr, MusicError> {
+    let json = post_json_try_bases(http, cfg, "/login/qr/key", &json!({}), None, timeout).await?;
+    let key = json
+        .pointer("/data/unikey")
+        .or_else(|| json.get("unikey"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .trim()
+        .to_string();
+    if key.is_empty() {
+        let code = json.get("code").and_then(|v| v.as_i64()).unwrap_or(0);
+        return Err(MusicError::Parse(format!(
+            "netease qr key: missing unikey (code={code})"
+        )));
+    }
+    let url = format!("{QR_LOGIN_URL}{key}");
+    let image_base64 = qr_png_base64(&url)?;
+    Ok(NeteaseQr {
+        key,
+        url,
+        image_base64,
+    })
+}
+
+pub async fn netease_qr_poll(
+    http: &Client,
+    cfg: &ProviderConfig,
+    key: &str,
+    timeout: Duration,
+) -> Result<NeteaseQrStatus, MusicError> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(MusicError::InvalidInput("empty key".to_string()));
+    }
+    let json = post_json_try_bases(
+        http,
+        cfg,
+        "/login/qr/check",
+        &json!({ "key": key }),
+        None,
+        timeout,
+    )
+    .await?;
+    let code = json.get("code").and_then(|v| v.as_i64()).unwrap_or(0);
+    let text = |k: &str| {
+        json.get(k)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    Ok(match code {
+        800 => NeteaseQrStatus::Expired,
+        801 => NeteaseQrStatus::Waiting,
+        802 => NeteaseQrStatus::Scanned {
+            nickname: text("nickname"),
+        },
+        803 => {
+            let cookie = text("cookie")
+                .map(|c| normalize_cookie(&c))
+                .unwrap_or_default();
+            if cookie.is_empty() {
+                return Err(MusicError::Parse(
+                    "netease qr check: missing cookie".to_string(),
+                ));
+            }
+            NeteaseQrStatus::Done { cookie }
+        }
+        _ => NeteaseQrStatus::Other {
+            code,
+            message: text("message"),
+        },
+    })
+}
+
+/// 查询 cookie 对应的账号；未登录（匿名 cookie / 已失效）返回 `Unauthorized`。
+pub async fn account_info(
+    http: &Client,
+    cfg: &ProviderConfig,
+    cookie: &str,
+    timeout: Duration,
+) -> Result<NeteaseUserInfo, MusicError> {
+    let cookie = cookie.trim();
+    if cookie.is_empty() {
+        return Err(MusicError::Unauthorized(
+            "missing netease cookie".to_string(),
+        ));
+    }
+    let json = post_json_try_bases(
+        http,
+        cfg,
+        "/user/account",
+        &json!({}),
+        Some(cookie),
+        timeout,
+    )
+    .await?;
+    let code = json.get("code").and_then(|v| v.as_i64()).unwrap_or(0);
+    if code != 200 {
+        return Err(MusicError::Other(format!("netease account code={code}")));
+    }
+    let uid = json
+        .pointer("/account/id")
+        .or_else(|| json.pointer("/profile/userId"))
+        .and_then(|v| v.as_i64())
+        .filter(|n| *n > 0)
+        // 匿名 cookie 也有 account.id，但标记为 anonimousUser。
+        .filter(|_| {
+            json.pointer("/account/anonimousUser")
+                .and_then(|v| v.as_bool())
+                != Some(true)
+        })
+        .ok_or_else(|| MusicError::Unauthorized("netease: not logged in".to_string()))?;
+    let text = |p: &str| {
+        json.pointer(p)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    Ok(NeteaseUserInfo {
+        user_id: uid.to_string(),
+        nickname: text("/profile/nickname"),
+        avatar_url: text("/profile/avatarUrl"),
+        vip_type: json
+            .pointer("/account/vipType")
+            .or_else(|| json.pointer("/profile/vipType"))
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0),
+    })
+}
+
+/// 刷新登录态（`/login/refresh`）；返回合并了新字段的 cookie，接口未下发新 cookie 时返回原 cookie。
+pub async fn refresh_cookie(
+    http: &Client,
+    cfg: &ProviderConfig,
+    cookie: &str,
+    timeout: Duration,
+) -> Result<String, MusicError> {
+    let cookie = cookie.trim();
+    if cookie.is_empty() {
+        return Err(MusicError::Unauthorized(
+            "missing netease cookie".to_string(),
+        ));
+    }
+    let json = post_json_try_bases(
+        http,
+        cfg,
+        "/login/refresh",
+        &json!({}),
+        Some(cookie),
+        timeout,
+    )
+    .await?;
+    let code = json.get("code").and_then(|v| v.as_i64()).unwrap_or(0);
+    if code == 301 {
+        return Err(MusicError::Unauthorized(
+            "netease: login expired".to_string(),
+        ));
+    }
+    if code != 200 {
+        return Err(MusicError::Other(format!("netease refresh code={code}")));
+    }
+    let fresh = json.get("cookie").and_then(|v| v.as_str()).unwrap_or("");
+    Ok(normalize_cookie(&format!("{cookie};{fresh}")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,13 +3,18 @@ use std::time::Duration;
 use reqwest::Client;
 use serde_json::{Value, json};
 
+use crate::lyrics::util::decode_xml_entities;
 use crate::music::error::MusicError;
+use crate::music::lyrics::{non_empty, word_lyrics_to_lrc};
 use crate::music::model::{
-    AuthState, MusicAlbum, MusicArtist, MusicQuality, MusicService, MusicTrack,
+    AuthState, MusicAlbum, MusicArtist, MusicChart, MusicLyrics, MusicPlaylist, MusicQuality,
+    MusicService, MusicTrack, WordLyricsFormat,
 };
 use crate::music::util::quality_fallback_order;
 
 const BASE_URL: &str = "https://u.y.qq.com/cgi-bin/musicu.fcg";
+const PLAYLIST_PAGE_SIZE: u32 = 500;
+const PLAYLIST_MAX_PAGES: u32 = 100;
 
 fn ua() -> &'static str {
     // Keep aligned with refs for higher compatibility.
@@ -87,6 +92,75 @@ fn qualities_from_file(v: &Value) -> Vec<MusicQuality> {
     out
 }
 
+fn map_song_to_track(song: &Value) -> Option<MusicTrack> {
+    let id = song
+        .get("mid")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    if id.is_empty() {
+        return None;
+    }
+    let title = song
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let mut artists = Vec::new();
+    let mut artist_ids = Vec::new();
+    if let Some(arr) = song.get("singer").and_then(|v| v.as_array()) {
+        for s in arr {
+            if let Some(name) = s.get("name").and_then(|v| v.as_str())
+                && !name.trim().is_empty()
+            {
+                artists.push(name.to_string());
+            }
+            if let Some(id) = s.get("mid").and_then(|v| v.as_str())
+                && !id.trim().is_empty()
+            {
+                artist_ids.push(id.to_string());
+            }
+        }
+    }
+    let album = song
+        .pointer("/album/name")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+    let album_id = song
+        .pointer("/album/mid")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+    let pmid = song
+        .pointer("/album/pmid")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let cover_url = (!pmid.trim().is_empty()).then_some(format!(
+        "https://y.qq.com/music/photo_new/T002R800x800M000{pmid}.jpg"
+    ));
+    let duration_ms = song
+        .get("interval")
+        .and_then(|v| v.as_u64())
+        .map(|s| s * 1000);
+    let qualities = song
+        .get("file")
+        .map(qualities_from_file)
+        .unwrap_or_default();
+
+    Some(MusicTrack {
+        service: MusicService::Qq,
+        id,
+        title,
+        artists,
+        artist_ids,
+        album,
+        album_id,
+        duration_ms,
+        cover_url,
+        isrc: None,
+        qualities,
+    })
+}
+
 pub async fn search_tracks(
     http: &Client,
     keyword: &str,
@@ -121,72 +195,7 @@ pub async fn search_tracks(
         .cloned()
         .unwrap_or_default();
 
-    let mut out = Vec::with_capacity(list.len());
-    for it in list {
-        let mid = it
-            .get("mid")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string();
-        if mid.is_empty() {
-            continue;
-        }
-        let title = it
-            .get("name")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string();
-        let mut artists = Vec::new();
-        let mut artist_ids = Vec::new();
-        if let Some(arr) = it.get("singer").and_then(|v| v.as_array()) {
-            for s in arr {
-                if let Some(name) = s.get("name").and_then(|v| v.as_str()) {
-                    if !name.trim().is_empty() {
-                        artists.push(name.to_string());
-                    }
-                }
-                if let Some(id) = s.get("mid").and_then(|v| v.as_str()) {
-                    if !id.trim().is_empty() {
-                        artist_ids.push(id.to_string());
-                    }
-                }
-            }
-        }
-        let album = it
-            .pointer("/album/name")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
-        let album_id = it
-            .pointer("/album/mid")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
-        let pmid = it
-            .pointer("/album/pmid")
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-        let cover_url = (!pmid.trim().is_empty()).then_some(format!(
-            "https://y.qq.com/music/photo_new/T002R800x800M000{pmid}.jpg"
-        ));
-        let duration_ms = it
-            .get("interval")
-            .and_then(|v| v.as_u64())
-            .map(|s| s * 1000);
-        let qualities = it.get("file").map(qualities_from_file).unwrap_or_default();
-
-        out.push(MusicTrack {
-            service: MusicService::Qq,
-            id: mid,
-            title,
-            artists,
-            artist_ids,
-            album,
-            album_id,
-            duration_ms,
-            cover_url,
-            qualities,
-        });
-    }
-    Ok(out)
+    Ok(list.iter().filter_map(map_song_to_track).collect())
 }
 
 pub async fn search_artists(
@@ -365,76 +374,10 @@ pub async fn album_tracks(
         .cloned()
         .unwrap_or_default();
 
-    let mut out = Vec::with_capacity(list.len());
-    for it in list {
-        let song = it.get("songInfo").unwrap_or(&Value::Null);
-        let id = song
-            .get("mid")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string();
-        if id.is_empty() {
-            continue;
-        }
-        let title = song
-            .get("name")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string();
-        let mut artists = Vec::new();
-        let mut artist_ids = Vec::new();
-        if let Some(arr) = song.get("singer").and_then(|v| v.as_array()) {
-            for s in arr {
-                if let Some(name) = s.get("name").and_then(|v| v.as_str()) {
-                    if !name.trim().is_empty() {
-                        artists.push(name.to_string());
-                    }
-                }
-                if let Some(id) = s.get("mid").and_then(|v| v.as_str()) {
-                    if !id.trim().is_empty() {
-                        artist_ids.push(id.to_string());
-                    }
-                }
-            }
-        }
-        let album = song
-            .pointer("/album/name")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
-        let album_id = song
-            .pointer("/album/mid")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
-        let pmid = song
-            .pointer("/album/pmid")
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-        let cover_url = (!pmid.trim().is_empty()).then_some(format!(
-            "https://y.qq.com/music/photo_new/T002R800x800M000{pmid}.jpg"
-        ));
-        let duration_ms = song
-            .get("interval")
-            .and_then(|v| v.as_u64())
-            .map(|s| s * 1000);
-        let qualities = song
-            .get("file")
-            .map(qualities_from_file)
-            .unwrap_or_default();
-
-        out.push(MusicTrack {
-            service: MusicService::Qq,
-            id,
-            title,
-            artists,
-            artist_ids,
-            album,
-            album_id,
-            duration_ms,
-            cover_url,
-            qualities,
-        });
-    }
-    Ok(out)
+    Ok(list
+        .iter()
+        .filter_map(|it| it.get("songInfo").and_then(map_song_to_track))
+        .collect())
 }
 
 pub async fn artist_albums(
@@ -506,6 +449,522 @@ pub async fn artist_albums(
     Ok(out)
 }
 
+pub async fn search_playlists(
+    http: &Client,
+    keyword: &str,
+    page: u32,
+    page_size: u32,
+    timeout: Duration,
+) -> Result<Vec<MusicPlaylist>, MusicError> {
+    let q = keyword.trim();
+    if q.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let body = json!({
+        "comm": { "ct": "19", "cv": "1859", "uin": "0" },
+        "req": {
+            "method": "DoSearchForQQMusicDesktop",
+            "module": "music.search.SearchCgiService",
+            "param": {
+                "search_type": 3,
+                "query": q,
+                "page_num": page.max(1),
+                "num_per_page": page_size.clamp(1, 50),
+                "grp": 1
+            }
+        }
+    });
+
+    let json = post_musicu(http, &body, timeout, None).await?;
+    let list = json
+        .pointer("/req/data/body/songlist/list")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut out = Vec::with_capacity(list.len());
+    for it in list {
+        let id = it
+            .get("dissid")
+            .and_then(|v| {
+                v.as_str()
+                    .map(|s| s.to_string())
+                    .or_else(|| v.as_u64().map(|n| n.to_string()))
+            })
+            .unwrap_or_default();
+        if id.trim().is_empty() {
+            continue;
+        }
+        let title = it
+            .get("dissname")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        out.push(MusicPlaylist {
+            service: MusicService::Qq,
+            id,
+            title,
+            creator: it
+                .pointer("/creator/name")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            creator_id: it
+                .pointer("/creator/qq")
+                .and_then(|v| v.as_u64())
+                .map(|n| n.to_string()),
+            cover_url: it
+                .get("imgurl")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            track_count: it
+                .get("song_count")
+                .and_then(|v| v.as_u64())
+                .and_then(|n| u32::try_from(n).ok()),
+            description: it
+                .get("introduction")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .filter(|s| !s.trim().is_empty()),
+        });
+    }
+    Ok(out)
+}
+
+pub async fn playlist_tracks(
+    http: &Client,
+    playlist_id: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    playlist_tracks_with_base(http, BASE_URL, playlist_id, timeout).await
+}
+
+/// 歌单可能有上千首，按 `PLAYLIST_PAGE_SIZE` 分页拉取直到 `total_song_num`。
+#[doc(hidden)]
+pub async fn playlist_tracks_with_base(
+    http: &Client,
+    base_url: &str,
+    playlist_id: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    let id = playlist_id.trim();
+    let disstid: u64 = id
+        .parse()
+        .map_err(|_| MusicError::InvalidInput(format!("invalid playlist_id: {id:?}")))?;
+
+    let mut out: Vec<MusicTrack> = Vec::new();
+    for page in 0..PLAYLIST_MAX_PAGES {
+        let body = json!({
+            "comm": { "ct": "19", "cv": "1859", "uin": "0" },
+            "req": {
+                "module": "music.srfDissInfo.aiDissInfo",
+                "method": "uniform_get_Dissinfo",
+                "param": {
+                    "disstid": disstid,
+                    "userinfo": 1,
+                    "tag": 1,
+                    "orderlist": 1,
+                    "song_begin": page * PLAYLIST_PAGE_SIZE,
+                    "song_num": PLAYLIST_PAGE_SIZE,
+                    "onlysonglist": 0,
+                    "enc_host_uin": ""
+                }
+            }
+        });
+        let json = post_musicu_at(http, base_url, &body, timeout, None).await?;
+        let code = json
+            .pointer("/req/code")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0);
+        if code != 0 {
+            return Err(MusicError::Other(format!("qq playlist code={code}")));
+        }
+        let total = json
+            .pointer("/req/data/total_song_num")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let list = json
+            .pointer("/req/data/songlist")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        if list.is_empty() {
+            break;
+        }
+        out.extend(list.iter().filter_map(map_song_to_track));
+        let fetched = u64::from((page + 1) * PLAYLIST_PAGE_SIZE);
+        if (list.len() as u32) < PLAYLIST_PAGE_SIZE || (total > 0 && fetched >= total) {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+pub async fn track_detail(
+    http: &Client,
+    song_mid: &str,
+    timeout: Duration,
+) -> Result<MusicTrack, MusicError> {
+    track_detail_with_base(http, BASE_URL, song_mid, timeout).await
+}
+
+#[doc(hidden)]
+pub async fn track_detail_with_base(
+    http: &Client,
+    base_url: &str,
+    song_mid: &str,
+    timeout: Duration,
+) -> Result<MusicTrack, MusicError> {
+    let mid = song_mid.trim();
+    if mid.is_empty() {
+        return Err(MusicError::InvalidInput("empty track_id".to_string()));
+    }
+    let body = json!({
+        "comm": { "ct": 24, "cv": 0 },
+        "req": {
+            "module": "music.pf_song_detail_svr",
+            "method": "get_song_detail_yqq",
+            "param": { "song_mid": mid }
+        }
+    });
+    let json = post_musicu_at(http, base_url, &body, timeout, None).await?;
+    json.pointer("/req/data/track_info")
+        .and_then(map_song_to_track)
+        .ok_or_else(|| MusicError::Other(format!("qq: track {mid} not found")))
+}
+
+/// base64 歌词字段；QRC 包在 XML 的 `LyricContent` 属性里，返回 `(文本, 是否 QRC)`。
+fn decode_lyric_field(v: Option<&Value>) -> Option<(String, bool)> {
+    use base64::Engine as _;
+
+    let raw = v?.as_str()?.trim();
+    let bytes = base64::engine::general_purpose::STANDARD.decode(raw).ok()?;
+    let text = String::from_utf8_lossy(&bytes);
+    const ATTR: &str = "LyricContent=\"";
+    let out = match text.find(ATTR).map(|i| i + ATTR.len()) {
+        Some(start) => {
+            let end = text[start..].find('"').map_or(text.len(), |n| start + n);
+            (decode_xml_entities(&text[start..end]).into_owned(), true)
+        }
+        None => (decode_xml_entities(&text).into_owned(), false),
+    };
+    non_empty(Some(out.0)).map(|t| (t, out.1))
+}
+
+pub async fn track_lyrics(
+    http: &Client,
+    song_mid: &str,
+    timeout: Duration,
+) -> Result<MusicLyrics, MusicError> {
+    track_lyrics_with_base(http, BASE_URL, song_mid, timeout).await
+}
+
+/// `GetPlayLyricInfo`：原文（有 QRC 时为逐字）、翻译与音译。
+#[doc(hidden)]
+pub async fn track_lyrics_with_base(
+    http: &Client,
+    base_url: &str,
+    song_mid: &str,
+    timeout: Duration,
+) -> Result<MusicLyrics, MusicError> {
+    let mid = song_mid.trim();
+    if mid.is_empty() {
+        return Err(MusicError::InvalidInput("empty track_id".to_string()));
+    }
+    let body = json!({
+        "comm": { "ct": "19", "cv": "1873", "uin": "0" },
+        "req": {
+            "module": "music.musichallSong.PlayLyricInfo",
+            "method": "GetPlayLyricInfo",
+            "param": { "songMID": mid, "qrc": 1, "trans": 1, "roma": 1, "crypt": 0 }
+        }
+    });
+    let json = post_musicu_at(http, base_url, &body, timeout, None).await?;
+    let code = json
+        .pointer("/req/code")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(0);
+    if code != 0 {
+        return Err(MusicError::Other(format!("qq lyrics code={code}")));
+    }
+    let field = |key: &str| decode_lyric_field(json.pointer(&format!("/req/data/{key}")));
+    let Some((lyric, is_qrc)) = field("lyric") else {
+        return Err(MusicError::Other(format!("qq: no lyrics for {mid}")));
+    };
+    let as_lrc = |(text, qrc): (String, bool)| if qrc { word_lyrics_to_lrc(&text) } else { text };
+    Ok(MusicLyrics {
+        service: MusicService::Qq,
+        track_id: mid.to_string(),
+        original: as_lrc((lyric.clone(), is_qrc)),
+        translation: field("trans").map(as_lrc),
+        romanization: field("roma").map(as_lrc),
+        word_lyrics: is_qrc.then_some(lyric),
+        word_format: is_qrc.then_some(WordLyricsFormat::Qrc),
+    })
+}
+
+/// 登录态请求的 `comm`（`musicid` 作为 uin、`musickey` 作为 authst），同时返回 uin。
+fn login_comm(auth: &AuthState) -> Result<(Value, String), MusicError> {
+    let cookie = auth
+        .qq
+        .as_ref()
+        .ok_or_else(|| MusicError::Unauthorized("missing qq cookie".to_string()))?;
+    let uin = cookie
+        .musicid
+        .as_deref()
+        .or(cookie.str_musicid.as_deref())
+        .unwrap_or("")
+        .trim()
+        .to_string();
+    let musickey = cookie.musickey.as_deref().unwrap_or("").trim().to_string();
+    if uin.is_empty() || musickey.is_empty() {
+        return Err(MusicError::Unauthorized(
+            "qq cookie missing musicid/musickey".to_string(),
+        ));
+    }
+    let login_type = cookie.login_type.map(|v| v.to_string()).unwrap_or_default();
+    let comm = json!({
+        "ct": "11",
+        "cv": "1003006",
+        "uin": uin,
+        "authst": musickey,
+        "tmeLoginType": login_type
+    });
+    Ok((comm, uin))
+}
+
+pub async fn user_playlists(
+    http: &Client,
+    auth: &AuthState,
+    timeout: Duration,
+) -> Result<Vec<MusicPlaylist>, MusicError> {
+    user_playlists_with_base(http, BASE_URL, auth, timeout).await
+}
+
+/// 当前登录用户创建的歌单（`musicid` 作为 uin）。
+#[doc(hidden)]
+pub async fn user_playlists_with_base(
+    http: &Client,
+    base_url: &str,
+    auth: &AuthState,
+    timeout: Duration,
+) -> Result<Vec<MusicPlaylist>, MusicError> {
+    let (comm, uin) = login_comm(auth)?;
+    let body = json!({
+        "comm": comm,
+        "req": {
+            "module": "music.musicasset.PlaylistBaseRead",
+            "method": "GetPlaylistByUin",
+            "param": { "uin": uin }
+        }
+    });
+    let json = post_musicu_at(http, base_url, &body, timeout, None).await?;
+    let code = json
+        .pointer("/req/code")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(0);
+    if code != 0 {
+        return Err(MusicError::Other(format!("qq user playlists code={code}")));
+    }
+    let list = json
+        .pointer("/req/data/v_playlist")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let nick = auth.qq.as_ref().and_then(|c| c.nick.clone());
+    let mut out = Vec::with_capacity(list.len());
+    for it in list {
+        let id = it
+            .get("tid")
+            .and_then(|v| v.as_u64())
+            .map(|n| n.to_string())
+            .unwrap_or_default();
+        // tid=0 是内部占位（如“我喜欢”未生成歌单 id 时）。
+        if id.is_empty() || id == "0" {
+            continue;
+        }
+        let title = it
+            .get("dirName")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        out.push(MusicPlaylist {
+            service: MusicService::Qq,
+            id,
+            title,
+            creator: nick.clone(),
+            creator_id: Some(uin.clone()),
+            cover_url: it
+                .get("picUrl")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .filter(|s| !s.trim().is_empty()),
+            track_count: it
+                .get("songNum")
+                .and_then(|v| v.as_u64())
+                .and_then(|n| u32::try_from(n).ok()),
+            description: None,
+        });
+    }
+    Ok(out)
+}
+
+pub async fn list_charts(http: &Client, timeout: Duration) -> Result<Vec<MusicChart>, MusicError> {
+    list_charts_with_base(http, BASE_URL, timeout).await
+}
+
+/// 排行榜分组（巅峰榜/地区榜/特色榜...）展开为一维列表。
+#[doc(hidden)]
+pub async fn list_charts_with_base(
+    http: &Client,
+    base_url: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicChart>, MusicError> {
+    let body = json!({
+        "comm": { "ct": 24, "cv": 0 },
+        "req": {
+            "module": "musicToplist.ToplistInfoServer",
+            "method": "GetAll",
+            "param": {}
+        }
+    });
+    let json = post_musicu_at(http, base_url, &body, timeout, None).await?;
+    let code = json
+        .pointer("/req/code")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(0);
+    if code != 0 {
+        return Err(MusicError::Other(format!("qq toplist code={code}")));
+    }
+    let text = |it: &Value, k: &str| {
+        it.get(k)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    let mut out = Vec::new();
+    for group in json
+        .pointer("/req/data/group")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        for it in group
+            .get("toplist")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let Some(id) = it.get("topId").and_then(|v| v.as_i64()) else {
+                continue;
+            };
+            let Some(title) = text(it, "title") else {
+                continue;
+            };
+            out.push(MusicChart {
+                service: MusicService::Qq,
+                id: id.to_string(),
+                title,
+                cover_url: text(it, "frontPicUrl").or_else(|| text(it, "headPicUrl")),
+                description: text(it, "intro"),
+                update_frequency: text(it, "updateTips"),
+            });
+        }
+    }
+    Ok(out)
+}
+
+pub async fn chart_tracks(
+    http: &Client,
+    chart_id: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    chart_tracks_with_base(http, BASE_URL, chart_id, timeout).await
+}
+
+#[doc(hidden)]
+pub async fn chart_tracks_with_base(
+    http: &Client,
+    base_url: &str,
+    chart_id: &str,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    let id = chart_id.trim();
+    let top_id: u64 = id
+        .parse()
+        .map_err(|_| MusicError::InvalidInput(format!("invalid chart_id: {id:?}")))?;
+    let body = json!({
+        "comm": { "ct": 24, "cv": 0 },
+        "req": {
+            "module": "musicToplist.ToplistInfoServer",
+            "method": "GetDetail",
+            "param": { "topId": top_id, "offset": 0, "num": 100, "period": "" }
+        }
+    });
+    let json = post_musicu_at(http, base_url, &body, timeout, None).await?;
+    let code = json
+        .pointer("/req/code")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(0);
+    if code != 0 {
+        return Err(MusicError::Other(format!("qq toplist detail code={code}")));
+    }
+    Ok(json
+        .pointer("/req/data/songInfoList")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(map_song_to_track)
+        .collect())
+}
+
+pub async fn daily_recommendations(
+    http: &Client,
+    auth: &AuthState,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    daily_recommendations_with_base(http, BASE_URL, auth, timeout).await
+}
+
+/// 每日 30 首（需登录）。
+#[doc(hidden)]
+pub async fn daily_recommendations_with_base(
+    http: &Client,
+    base_url: &str,
+    auth: &AuthState,
+    timeout: Duration,
+) -> Result<Vec<MusicTrack>, MusicError> {
+    let (comm, _) = login_comm(auth)?;
+    let body = json!({
+        "comm": comm,
+        "req": {
+            "module": "music.ai_track_daily_svr",
+            "method": "get_daily_track",
+            "param": {}
+        }
+    });
+    let json = post_musicu_at(http, base_url, &body, timeout, None).await?;
+    match json
+        .pointer("/req/code")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(0)
+    {
+        0 => {}
+        1000 => return Err(MusicError::Unauthorized("qq: login expired".to_string())),
+        code => {
+            return Err(MusicError::Other(format!("qq daily track code={code}")));
+        }
+    }
+    Ok(json
+        .pointer("/req/data/tracks")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(map_song_to_track)
+        .collect())
+}
+
 fn map_quality_to_songtype(quality_id: &str) -> (&'static str, &'static str) {
     match quality_id {
         "mp3_128" => ("M500", "mp3"),
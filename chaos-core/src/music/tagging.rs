@@ -0,0 +1,342 @@
+//! 下载完成后写入音频标签：
+//! - mp3：ID3v2.4（TIT2/TPE1/TALB/TRCK + APIC 封面 + USLT/SYLT 歌词）
+//! - flac：Vorbis comments（TITLE/ARTIST/ALBUM/TRACKNUMBER/LYRICS）+ PICTURE 块
+//! - m4a/mp4：ilst 原子（©nam/©ART/©alb/trkn/©lyr + covr）
+//!
+//! ReplayGain（`REPLAYGAIN_TRACK_GAIN/PEAK`）由 [`write_replay_gain`] 单独追加，不覆盖已有标签。
+
+use std::borrow::Cow;
+use std::fs::OpenOptions;
+use std::io::Seek;
+use std::path::Path;
+use std::time::Duration;
+
+use lofty::TextEncoding;
+use lofty::config::{ParseOptions, WriteOptions};
+use lofty::file::AudioFile;
+use lofty::flac::FlacFile;
+use lofty::id3::v2::{
+    BinaryFrame, Frame, FrameId, Id3v2Tag, SyncTextContentType, SynchronizedTextFrame,
+    TimestampFormat, UnsynchronizedTextFrame,
+};
+use lofty::mp4::{Atom, AtomData, AtomIdent, Ilst, Mp4File};
+use lofty::mpeg::MpegFile;
+use lofty::ogg::{OggPictureStorage, VorbisComments};
+use lofty::picture::{Picture, PictureInformation, PictureType};
+use lofty::tag::Accessor;
+
+use super::error::MusicError;
+use crate::lyrics::parse;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackTags {
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub track_no: Option<u32>,
+    /// LRC（带时间轴）或纯文本歌词。
+    pub lyrics: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagFormat {
+    Id3v2,
+    Flac,
+    Mp4,
+}
+
+impl TagFormat {
+    /// 按扩展名选择标签格式；裸 ADTS `.aac` 没有 MP4 容器，不写标签。
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "mp3" => Some(Self::Id3v2),
+            "flac" => Some(Self::Flac),
+            "m4a" | "mp4" => Some(Self::Mp4),
+            _ => None,
+        }
+    }
+}
+
+fn lofty_err(e: lofty::error::LoftyError) -> MusicError {
+    MusicError::Other(format!("tag error: {e}"))
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    let t = s.trim();
+    (!t.is_empty()).then(|| t.to_string())
+}
+
+fn make_cover(data: &[u8]) -> Result<Picture, MusicError> {
+    let mut pic = Picture::from_reader(&mut &data[..]).map_err(lofty_err)?;
+    pic.set_pic_type(PictureType::CoverFront);
+    Ok(pic)
+}
+
+fn build_id3v2(tags: &TrackTags, cover: Option<Picture>) -> Result<Id3v2Tag, MusicError> {
+    let mut tag = Id3v2Tag::default();
+    if let Some(t) = non_empty(&tags.title) {
+        tag.set_title(t);
+    }
+    let artists = tags
+        .artists
+        .iter()
+        .filter_map(|a| non_empty(a))
+        .collect::<Vec<_>>();
+    if !artists.is_empty() {
+        // ID3v2.4 多值文本帧以 NUL 分隔。
+        tag.set_artist(artists.join("\0"));
+    }
+    if let Some(a) = tags.album.as_deref().and_then(non_empty) {
+        tag.set_album(a);
+    }
+    if let Some(n) = tags.track_no {
+        tag.set_track(n);
+    }
+    if let Some(pic) = cover {
+        tag.insert_picture(pic);
+    }
+    if let Some(lrc) = tags.lyrics.as_deref().filter(|s| !s.trim().is_empty()) {
+        let lines = parse::parse(lrc).lines;
+        // 没有时间轴的歌词按纯文本写入 USLT。
+        let plain = if lines.is_empty() {
+            lrc.trim().to_string()
+        } else {
+            lines
+                .iter()
+                .map(|l| l.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        tag.insert(Frame::UnsynchronizedText(UnsynchronizedTextFrame::new(
+            TextEncoding::UTF8,
+            *b"XXX",
+            String::new(),
+            plain,
+        )));
+        if !lines.is_empty() {
+            let synced = lines
+                .into_iter()
+                .map(|l| (u32::try_from(l.start_ms).unwrap_or(u32::MAX), l.text))
+                .collect();
+            let sylt = SynchronizedTextFrame::new(
+                TextEncoding::UTF8,
+                *b"XXX",
+                TimestampFormat::MS,
+                SyncTextContentType::Lyrics,
+                None,
+                synced,
+            );
+            let bytes = sylt.as_bytes().map_err(lofty_err)?;
+            let id = FrameId::new("SYLT").map_err(lofty_err)?;
+            tag.insert(Frame::Binary(BinaryFrame::new(id, bytes)));
+        }
+    }
+    Ok(tag)
+}
+
+fn build_vorbis(tags: &TrackTags) -> VorbisComments {
+    let mut vc = VorbisComments::default();
+    if let Some(t) = non_empty(&tags.title) {
+        vc.insert("TITLE".to_string(), t);
+    }
+    for a in tags.artists.iter().filter_map(|a| non_empty(a)) {
+        vc.push("ARTIST".to_string(), a);
+    }
+    if let Some(a) = tags.album.as_deref().and_then(non_empty) {
+        vc.insert("ALBUM".to_string(), a);
+    }
+    if let Some(n) = tags.track_no {
+        vc.insert("TRACKNUMBER".to_string(), n.to_string());
+    }
+    if let Some(lrc) = tags.lyrics.as_deref().filter(|s| !s.trim().is_empty()) {
+        vc.insert("LYRICS".to_string(), lrc.trim().to_string());
+    }
+    vc
+}
+
+fn build_ilst(tags: &TrackTags, cover: Option<Picture>) -> Ilst {
+    let mut ilst = Ilst::default();
+    if let Some(t) = non_empty(&tags.title) {
+        ilst.set_title(t);
+    }
+    let artists = tags
+        .artists
+        .iter()
+        .filter_map(|a| non_empty(a))
+        .collect::<Vec<_>>();
+    if !artists.is_empty() {
+        ilst.set_artist(artists.join("; "));
+    }
+    if let Some(a) = tags.album.as_deref().and_then(non_empty) {
+        ilst.set_album(a);
+    }
+    if let Some(n) = tags.track_no {
+        ilst.set_track(n);
+    }
+    if let Some(pic) = cover {
+        ilst.insert_picture(pic);
+    }
+    if let Some(lrc) = tags.lyrics.as_deref().filter(|s| !s.trim().is_empty()) {
+        ilst.insert(Atom::new(
+            AtomIdent::Fourcc(*b"\xa9lyr"),
+            AtomData::UTF8(lrc.trim().to_string()),
+        ));
+    }
+    ilst
+}
+
+/// 按扩展名写入标签（会替换文件中已有的同类标签）；`cover` 为图片原始字节（jpeg/png）。
+///
+/// 这是阻塞 IO，异步上下文请使用 [`tag_file`]。
+pub fn write_tags(path: &Path, tags: &TrackTags, cover: Option<&[u8]>) -> Result<(), MusicError> {
+    let format = TagFormat::from_path(path).ok_or_else(|| {
+        MusicError::InvalidInput(format!("unsupported audio format: {}", path.display()))
+    })?;
+    let cover = cover.map(make_cover).transpose()?;
+
+    let mut f = OpenOptions::new().read(true).write(true).open(path)?;
+    let parse = ParseOptions::new().read_properties(false);
+    let write = WriteOptions::default();
+    match format {
+        TagFormat::Id3v2 => {
+            let mut file = MpegFile::read_from(&mut f, parse).map_err(lofty_err)?;
+            file.set_id3v2(build_id3v2(tags, cover)?);
+            f.rewind()?;
+            file.save_to(&mut f, write).map_err(lofty_err)?;
+        }
+        TagFormat::Flac => {
+            let mut file = FlacFile::read_from(&mut f, parse).map_err(lofty_err)?;
+            file.set_vorbis_comments(build_vorbis(tags));
+            if let Some(pic) = cover {
+                let info = PictureInformation::from_picture(&pic).unwrap_or_default();
+                file.remove_picture_type(PictureType::CoverFront);
+                file.insert_picture(pic, Some(info)).map_err(lofty_err)?;
+            }
+            f.rewind()?;
+            file.save_to(&mut f, write).map_err(lofty_err)?;
+        }
+        TagFormat::Mp4 => {
+            let mut file = Mp4File::read_from(&mut f, parse).map_err(lofty_err)?;
+            file.set_ilst(build_ilst(tags, cover));
+            f.rewind()?;
+            file.save_to(&mut f, write).map_err(lofty_err)?;
+        }
+    }
+    Ok(())
+}
+
+/// ReplayGain 曲目增益（dB）与峰值（线性，1.0 = 0 dBFS）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayGain {
+    pub track_gain_db: f64,
+    pub track_peak: f64,
+}
+
+impl ReplayGain {
+    fn gain_text(&self) -> String {
+        format!("{:.2} dB", self.track_gain_db)
+    }
+
+    fn peak_text(&self) -> String {
+        format!("{:.6}", self.track_peak)
+    }
+}
+
+fn freeform_atom(name: &'static str, value: String) -> Atom<'static> {
+    Atom::new(
+        AtomIdent::Freeform {
+            mean: Cow::Borrowed("com.apple.iTunes"),
+            name: Cow::Borrowed(name),
+        },
+        AtomData::UTF8(value),
+    )
+}
+
+/// 追加 ReplayGain 标签（mp3：TXXX；flac：Vorbis comment；m4a：`----:com.apple.iTunes` 原子），
+/// 保留文件中已有的其它标签。
+///
+/// 这是阻塞 IO。
+pub fn write_replay_gain(path: &Path, gain: &ReplayGain) -> Result<(), MusicError> {
+    let format = TagFormat::from_path(path).ok_or_else(|| {
+        MusicError::InvalidInput(format!("unsupported audio format: {}", path.display()))
+    })?;
+
+    let mut f = OpenOptions::new().read(true).write(true).open(path)?;
+    let parse = ParseOptions::new().read_properties(false);
+    let write = WriteOptions::default();
+    match format {
+        TagFormat::Id3v2 => {
+            let mut file = MpegFile::read_from(&mut f, parse).map_err(lofty_err)?;
+            let mut tag = file.remove_id3v2().unwrap_or_default();
+            tag.insert_user_text("REPLAYGAIN_TRACK_GAIN".to_string(), gain.gain_text());
+            tag.insert_user_text("REPLAYGAIN_TRACK_PEAK".to_string(), gain.peak_text());
+            file.set_id3v2(tag);
+            f.rewind()?;
+            file.save_to(&mut f, write).map_err(lofty_err)?;
+        }
+        TagFormat::Flac => {
+            let mut file = FlacFile::read_from(&mut f, parse).map_err(lofty_err)?;
+            let mut vc = file.remove_vorbis_comments().unwrap_or_default();
+            vc.insert("REPLAYGAIN_TRACK_GAIN".to_string(), gain.gain_text());
+            vc.insert("REPLAYGAIN_TRACK_PEAK".to_string(), gain.peak_text());
+            file.set_vorbis_comments(vc);
+            f.rewind()?;
+            file.save_to(&mut f, write).map_err(lofty_err)?;
+        }
+        TagFormat::Mp4 => {
+            let mut file = Mp4File::read_from(&mut f, parse).map_err(lofty_err)?;
+            let mut ilst = file.remove_ilst().unwrap_or_default();
+            ilst.insert(freeform_atom("replaygain_track_gain", gain.gain_text()));
+            ilst.insert(freeform_atom("replaygain_track_peak", gain.peak_text()));
+            file.set_ilst(ilst);
+            f.rewind()?;
+            file.save_to(&mut f, write).map_err(lofty_err)?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn fetch_cover(
+    http: &reqwest::Client,
+    url: &str,
+    timeout: Duration,
+) -> Result<Vec<u8>, MusicError> {
+    let u = url.trim();
+    if u.is_empty() {
+        return Err(MusicError::InvalidInput("empty cover url".to_string()));
+    }
+    let resp = http.get(u).timeout(timeout).send().await?;
+    if !resp.status().is_success() {
+        return Err(MusicError::Other(format!("cover http {}", resp.status())));
+    }
+    Ok(resp.bytes().await?.to_vec())
+}
+
+/// 下载封面（失败时忽略封面）并写入标签。
+pub async fn tag_file(
+    http: &reqwest::Client,
+    path: &Path,
+    tags: &TrackTags,
+    cover_url: Option<&str>,
+    timeout: Duration,
+) -> Result<(), MusicError> {
+    if TagFormat::from_path(path).is_none() {
+        return Ok(());
+    }
+    let cover = match cover_url {
+        Some(u) => fetch_cover(http, u, timeout).await.ok(),
+        None => None,
+    };
+    let path = path.to_path_buf();
+    let tags = tags.clone();
+    tokio::task::spawn_blocking(move || {
+        match write_tags(&path, &tags, cover.as_deref()) {
+            // 封面数据无法识别时退回到不带封面。
+            Err(_) if cover.is_some() => write_tags(&path, &tags, None),
+            r => r,
+        }
+    })
+    .await
+    .map_err(|e| MusicError::Other(e.to_string()))?
+}
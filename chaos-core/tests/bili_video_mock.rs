@@ -1,21 +1,36 @@
+use chaos_core::bili_video::audio::{AudioDownloadOptions, download_audio, fetch_audio_stream};
 use chaos_core::bili_video::auth::login_qr_poll;
 use chaos_core::bili_video::auth::{AuthState, refresh_cookie_if_needed_with};
-use chaos_core::bili_video::parse::{ParsedInput, fetch_view_info, parse_input, parse_video_id};
+use chaos_core::bili_video::live_replay::{
+    ReplayDownloadOptions, download_replay, fetch_replay_info, fetch_replay_parts, list_replays,
+};
+use chaos_core::bili_video::mux::{
+    EmbedOptions, MetadataSource, MuxMetadata, build_metadata, format_pub_date, render_ffmetadata,
+};
+use chaos_core::bili_video::parse::{
+    ParsedInput, ViewPoint, fetch_view_info, fetch_view_points, fetch_view_tags, parse_input,
+    parse_video_id,
+};
 use chaos_core::bili_video::pgc::{fetch_pgc_season_by_ep_id, fetch_pgc_season_by_season_id};
 use chaos_core::bili_video::playurl::{
     choose_qn_by_dfn_priority, fetch_playurl_dash, fetch_playurl_dash_pgc_web, pick_dash_tracks,
 };
 use chaos_core::bili_video::select_page::select_page_indices;
-use chaos_core::bili_video::subtitle::bcc_json_to_srt;
+use chaos_core::bili_video::subtitle::{
+    SubtitleFormat, SubtitleOptions, SubtitleVideo, bcc_json_to_ass, bcc_json_to_bilingual_ass,
+    bcc_json_to_srt, bcc_json_to_vtt, save_subtitles,
+};
 use chaos_core::bili_video::{BiliClient, BiliEndpoints};
 use httpmock::Method::{GET, POST};
 use httpmock::MockServer;
+use std::sync::{Arc, atomic::AtomicBool};
 
 fn mk_client(base: &str) -> BiliClient {
     let ep = BiliEndpoints {
         api_base: base.to_string(),
         passport_base: base.to_string(),
         www_base: base.to_string(),
+        live_api_base: base.to_string(),
     };
     BiliClient::with_endpoints(ep, std::time::Duration::from_secs(2)).expect("client")
 }
@@ -471,3 +486,538 @@ async fn login_qr_poll_done_when_cookie_complete() {
     assert!(cookie.contains("bili_jct=j"));
     assert_eq!(auth.refresh_token.as_deref(), Some("rt"));
 }
+
+#[tokio::test]
+async fn fetch_view_points_and_tags() {
+    let server = MockServer::start();
+    let base = server.base_url();
+
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/x/player/v2")
+            .query_param("bvid", "BV1xx411c7mD")
+            .query_param("cid", "1");
+        then.status(200).json_body(serde_json::json!({
+            "code": 0,
+            "data": {
+                "view_points": [
+                    { "type": 2, "from": 90, "to": 200, "content": "正片" },
+                    { "type": 2, "from": 0, "to": 90, "content": "开场" },
+                    { "type": 2, "from": 200, "to": 200, "content": "bad" }
+                ]
+            }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/x/web-interface/view/detail/tag")
+            .query_param("bvid", "BV1xx411c7mD");
+        then.status(200).json_body(serde_json::json!({
+            "code": 0,
+            "data": [
+                { "tag_id": 1, "tag_name": "音乐" },
+                { "tag_id": 2, "tag_name": " 翻唱 " },
+                { "tag_id": 3, "tag_name": "音乐" }
+            ]
+        }));
+    });
+
+    let client = mk_client(&base);
+    let points = fetch_view_points(&client, "BV1xx411c7mD", "1", None)
+        .await
+        .expect("points");
+    assert_eq!(
+        points,
+        vec![
+            ViewPoint {
+                start_ms: 0,
+                end_ms: 90_000,
+                title: "开场".to_string()
+            },
+            ViewPoint {
+                start_ms: 90_000,
+                end_ms: 200_000,
+                title: "正片".to_string()
+            },
+        ]
+    );
+
+    let tags = fetch_view_tags(&client, "BV1xx411c7mD", None)
+        .await
+        .expect("tags");
+    assert_eq!(tags, vec!["音乐".to_string(), "翻唱".to_string()]);
+
+    let src = MetadataSource {
+        title: "视频",
+        page_title: Some("P2"),
+        owner_name: "UP",
+        bvid: "BV1xx411c7mD",
+        cid: "1",
+        pub_time_unix_s: Some(1_700_000_000),
+        ..Default::default()
+    };
+    let cover = std::env::temp_dir().join("chaos_build_metadata_cover.jpg");
+    assert_eq!(
+        build_metadata(&client, src, EmbedOptions::default(), &cover, None).await,
+        None
+    );
+    let embed = EmbedOptions {
+        metadata: true,
+        chapters: true,
+        cover: true,
+    };
+    let meta = build_metadata(&client, src, embed, &cover, None)
+        .await
+        .expect("meta");
+    assert_eq!(meta.title, "视频 - P2");
+    assert_eq!(meta.artist, "UP");
+    assert_eq!(meta.date, "2023-11-15");
+    assert_eq!(meta.tags.len(), 2);
+    assert_eq!(meta.chapters.len(), 2);
+    // 没有封面 URL 时不下载封面
+    assert_eq!(meta.cover_path, None);
+}
+
+#[test]
+fn ffmetadata_has_globals_and_chapters() {
+    assert_eq!(format_pub_date(0), "1970-01-01");
+    assert_eq!(format_pub_date(1_700_000_000), "2023-11-15");
+
+    let meta = MuxMetadata {
+        title: "a=b".to_string(),
+        artist: "UP".to_string(),
+        bvid: "BV1xx411c7mD".to_string(),
+        date: "2023-11-15".to_string(),
+        description: "line1\nline2; #x".to_string(),
+        tags: vec!["t1".to_string(), "t2".to_string()],
+        chapters: vec![ViewPoint {
+            start_ms: 0,
+            end_ms: 1500,
+            title: "intro".to_string(),
+        }],
+        cover_path: None,
+    };
+    let s = render_ffmetadata(&meta);
+    assert!(s.starts_with(";FFMETADATA1\n"));
+    assert!(s.contains("title=a\\=b\n"));
+    assert!(s.contains("artist=UP\n"));
+    assert!(s.contains("description=line1\\\nline2\\; \\#x\n"));
+    assert!(s.contains("comment=https://www.bilibili.com/video/BV1xx411c7mD\n"));
+    assert!(s.contains("keywords=t1,t2\n"));
+    assert!(s.contains("[CHAPTER]\nTIMEBASE=1/1000\nSTART=0\nEND=1500\ntitle=intro\n"));
+}
+
+#[test]
+fn bcc_to_vtt_and_ass() {
+    let json = serde_json::json!({
+        "body": [
+            { "from": 0.0, "to": 1.23, "content": "hello {x}" },
+            { "from": 61.5, "to": 3725.01, "content": "a\nb" }
+        ]
+    });
+    let vtt = bcc_json_to_vtt(&json).expect("vtt");
+    assert!(vtt.starts_with("WEBVTT\n\n"));
+    assert!(vtt.contains("00:01:01.500 --> 01:02:05.010\na\nb\n"));
+
+    let ass = bcc_json_to_ass(&json).expect("ass");
+    assert!(ass.contains("[Events]"));
+    assert!(ass.contains("Dialogue: 0,0:00:00.00,0:00:01.23,Default,,0,0,0,,hello ｛x｝\n"));
+    assert!(ass.contains("Dialogue: 0,0:01:01.50,1:02:05.01,Default,,0,0,0,,a\\Nb\n"));
+
+    let en = serde_json::json!({ "body": [{ "from": 0.0, "to": 1.23, "content": "hi" }] });
+    let bi = bcc_json_to_bilingual_ass(&json, &en).expect("bilingual");
+    assert!(bi.contains(",Secondary,,0,0,0,,hi\n"));
+    assert!(bi.contains(",Default,,0,0,0,,hello ｛x｝\n"));
+}
+
+#[tokio::test]
+async fn save_subtitles_with_ai_filter_and_bilingual() {
+    let server = MockServer::start();
+    let base = server.base_url();
+
+    server.mock(|when, then| {
+        when.method(GET).path("/x/player/v2");
+        then.status(200).json_body(serde_json::json!({
+            "code": 0,
+            "data": { "subtitle": { "subtitles": [
+                { "lan": "en-US", "lan_doc": "English", "subtitle_url": format!("{base}/bcc/en"), "type": 0 },
+                { "lan": "ai-zh", "lan_doc": "中文（自动生成）", "subtitle_url": "", "type": 1 }
+            ] } }
+        }));
+    });
+    let dm_view = server.mock(|when, then| {
+        when.method(GET)
+            .path("/x/v2/dm/view")
+            .query_param("oid", "2")
+            .query_param("pid", "1");
+        then.status(200).json_body(serde_json::json!({
+            "code": 0,
+            "data": { "subtitle": { "subtitles": [
+                { "lan": "ai-zh", "lan_doc": "中文（自动生成）", "subtitle_url": format!("{base}/bcc/ai-zh"), "type": 1 }
+            ] } }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/bcc/en");
+        then.status(200).json_body(
+            serde_json::json!({ "body": [{ "from": 0.0, "to": 1.0, "content": "hello" }] }),
+        );
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/bcc/ai-zh");
+        then.status(200).json_body(
+            serde_json::json!({ "body": [{ "from": 0.0, "to": 1.0, "content": "你好" }] }),
+        );
+    });
+
+    let client = mk_client(&base);
+    let dir = tempfile::tempdir().expect("tmp");
+    let out = dir.path().join("v.mp4");
+    let video = SubtitleVideo {
+        aid: "1",
+        bvid: "BV1xx",
+        cid: "2",
+    };
+
+    // 未开启 AI：只有 en-US
+    let opt = SubtitleOptions::default();
+    let paths = save_subtitles(&client, video, Some("SESSDATA=x"), &opt, &out, None)
+        .await
+        .expect("save");
+    assert_eq!(paths, vec![dir.path().join("v.en-US.srt")]);
+    assert_eq!(dm_view.hits(), 0);
+
+    // AI + 语言过滤 + 双语 ASS
+    let opt = SubtitleOptions {
+        format: SubtitleFormat::Vtt,
+        langs: vec!["ai-zh".to_string()],
+        include_ai: true,
+        bilingual: Some(("ai-zh".to_string(), "en".to_string())),
+    };
+    let paths = save_subtitles(&client, video, Some("SESSDATA=x"), &opt, &out, None)
+        .await
+        .expect("save");
+    assert_eq!(
+        paths,
+        vec![
+            dir.path().join("v.ai-zh.vtt"),
+            dir.path().join("v.ai-zh+en-US.ass"),
+        ]
+    );
+    let bi = std::fs::read_to_string(dir.path().join("v.ai-zh+en-US.ass")).expect("read");
+    assert!(bi.contains(",Default,,0,0,0,,你好"));
+    assert!(bi.contains(",Secondary,,0,0,0,,hello"));
+
+    // JSON 原样保存但不参与 mux
+    let opt = SubtitleOptions {
+        format: SubtitleFormat::Json,
+        ..Default::default()
+    };
+    let paths = save_subtitles(&client, video, None, &opt, &out, None)
+        .await
+        .expect("save");
+    assert!(paths.is_empty());
+    assert!(dir.path().join("v.en-US.json").exists());
+
+    // 已取消：不再写字幕
+    let cancel = Arc::new(AtomicBool::new(true));
+    let out = dir.path().join("c.mp4");
+    let paths = save_subtitles(&client, video, None, &opt, &out, Some(&cancel))
+        .await
+        .expect("save");
+    assert!(paths.is_empty());
+    assert!(!dir.path().join("c.en-US.json").exists());
+}
+
+#[test]
+fn subtitle_options_from_lists() {
+    let opt = SubtitleOptions::from_lists(SubtitleFormat::Ass, "zh-CN, en", true, Some("zh,en"));
+    assert_eq!(opt.langs, vec!["zh-CN".to_string(), "en".to_string()]);
+    assert_eq!(opt.bilingual, Some(("zh".to_string(), "en".to_string())));
+    let opt = SubtitleOptions::from_lists(SubtitleFormat::Srt, "", false, Some("zh"));
+    assert!(opt.langs.is_empty());
+    assert_eq!(opt.bilingual, None);
+}
+
+#[tokio::test]
+async fn audio_au_parse_info_stream_and_download() {
+    let server = MockServer::start();
+    let base = server.base_url();
+    let client = mk_client(&base);
+
+    let p = parse_input(&client, "https://www.bilibili.com/audio/au1234567")
+        .await
+        .expect("parse");
+    assert_eq!(
+        p,
+        ParsedInput::Audio {
+            sid: "1234567".to_string()
+        }
+    );
+    let p = parse_input(&client, "AU42").await.expect("parse");
+    assert_eq!(
+        p,
+        ParsedInput::Audio {
+            sid: "42".to_string()
+        }
+    );
+
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/audio/music-service-c/web/song/info")
+            .query_param("sid", "42");
+        then.status(200).json_body(serde_json::json!({
+            "code": 0,
+            "msg": "success",
+            "data": {
+                "id": 42,
+                "uid": 7,
+                "uname": "UP",
+                "author": "Singer",
+                "title": "Song/Title",
+                "cover": "https://i0.hdslb.com/x.jpg",
+                "lyric": format!("{base}/lrc/42.lrc"),
+                "duration": 200,
+                "passtime": 1700000000
+            }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/audio/music-service-c/url")
+            .query_param("songid", "42")
+            .query_param("quality", "2");
+        then.status(200).json_body(serde_json::json!({
+            "code": 0,
+            "data": {
+                "type": 2,
+                "cdns": [format!("{base}/stream/320.m4a?k=1")],
+                "qualities": [
+                    { "type": 2, "desc": "320K" },
+                    { "type": 3, "desc": "无损 FLAC" }
+                ]
+            }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/audio/music-service-c/url")
+            .query_param("songid", "42")
+            .query_param("quality", "3");
+        then.status(200).json_body(serde_json::json!({
+            "code": 0,
+            "data": { "type": 3, "cdns": [format!("{base}/stream/lossless.flac")] }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/stream/lossless.flac");
+        then.status(200).body("FLACDATA");
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/lrc/42.lrc");
+        then.status(200).body("[00:01.00]hello\n");
+    });
+
+    let stream = fetch_audio_stream(&client, "au42", None)
+        .await
+        .expect("stream");
+    assert_eq!(stream.quality_type, 3);
+    assert_eq!(stream.ext(), "flac");
+
+    let dir = tempfile::tempdir().expect("tmp");
+    let opt = AudioDownloadOptions {
+        out_dir: dir.path().to_path_buf(),
+        path_template: Some("{{artist}}/{{title}}.{{ext}}".to_string()),
+        overwrite: false,
+        retries: 0,
+        download_lyrics: true,
+        task: None,
+    };
+    let r = download_audio(&client, "42", None, &opt, None, None)
+        .await
+        .expect("download");
+    assert!(!r.skipped);
+    assert_eq!(r.path, dir.path().join("Singer").join("Song_Title.flac"));
+    assert_eq!(std::fs::read(&r.path).expect("read"), b"FLACDATA");
+    assert_eq!(
+        std::fs::read_to_string(r.lyrics_path.expect("lrc")).expect("read"),
+        "[00:01.00]hello\n"
+    );
+
+    let again = download_audio(&client, "42", None, &opt, None, None)
+        .await
+        .expect("download");
+    assert!(again.skipped);
+}
+
+#[tokio::test]
+async fn live_replay_parse_list_info_and_parts() {
+    let server = MockServer::start();
+    let base = server.base_url();
+    let client = mk_client(&base);
+
+    let p = parse_input(
+        &client,
+        "https://live.bilibili.com/record/R1xBV411c7mDabcd?spm_id_from=333",
+    )
+    .await
+    .expect("parse");
+    assert_eq!(
+        p,
+        ParsedInput::LiveReplay {
+            rid: "R1xBV411c7mDabcd".to_string()
+        }
+    );
+
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/room/v1/Room/room_init")
+            .query_param("id", "6");
+        then.status(200).json_body(serde_json::json!({
+            "code": 0,
+            "data": { "room_id": 7734200, "short_id": 6, "uid": 50329118 }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/xlive/web-room/v1/record/getList")
+            .query_param("room_id", "7734200")
+            .query_param("page", "1")
+            .query_param("page_size", "20");
+        then.status(200).json_body(serde_json::json!({
+            "code": 0,
+            "data": {
+                "count": 31,
+                "list": [
+                    {
+                        "rid": "R1ab",
+                        "room_id": 7734200,
+                        "title": "replay 1",
+                        "cover": "https://i0.hdslb.com/c.jpg",
+                        "start_timestamp": 1700000000,
+                        "end_timestamp": 1700003600
+                    },
+                    { "title": "no rid" }
+                ]
+            }
+        }));
+    });
+    let page = list_replays(&client, "6", 1, 20, None).await.expect("list");
+    assert_eq!(page.total, 31);
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].rid, "R1ab");
+    assert_eq!(page.items[0].room_id, "7734200");
+    assert_eq!(page.items[0].duration_ms, Some(3_600_000));
+
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/xlive/web-room/v1/record/getInfoByLiveRecord")
+            .query_param("rid", "R1ab");
+        then.status(200).json_body(serde_json::json!({
+            "code": 0,
+            "data": {
+                "live_record_info": {
+                    "rid": "R1ab",
+                    "room_id": 7734200,
+                    "uid": 50329118,
+                    "title": "replay 1",
+                    "length": 3599000
+                },
+                "anchor_info": { "base_info": { "uname": "Anchor" } }
+            }
+        }));
+    });
+    let info = fetch_replay_info(&client, "R1ab", None)
+        .await
+        .expect("info");
+    assert_eq!(info.owner_name.as_deref(), Some("Anchor"));
+    assert_eq!(info.owner_mid.as_deref(), Some("50329118"));
+    assert_eq!(info.duration_ms, Some(3_599_000));
+
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/xlive/web-room/v1/record/getLiveRecordUrl")
+            .query_param("rid", "R1ab");
+        then.status(200).json_body(serde_json::json!({
+            "code": 0,
+            "data": {
+                "list": [
+                    { "url": format!("{base}/rec/1.flv"), "size": 100, "length": 1000 },
+                    { "url": format!("{base}/rec/2.flv"), "size": 50, "length": 500 },
+                    { "url": "" }
+                ]
+            }
+        }));
+    });
+    let parts = fetch_replay_parts(&client, "R1ab", None)
+        .await
+        .expect("parts");
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[1].size, Some(50));
+
+    for (path, body) in [("/rec/1.flv", "FLV1"), ("/rec/2.flv", "FLV22")] {
+        server.mock(|when, then| {
+            when.method(GET).path(path);
+            then.status(200).body(body);
+        });
+    }
+    let dir = tempfile::tempdir().expect("tmp");
+    let mut opt = ReplayDownloadOptions {
+        out_dir: dir.path().to_path_buf(),
+        file_pattern: "<videoTitle>".to_string(),
+        multi_file_pattern: "<videoTitle>".to_string(),
+        ffmpeg_path: String::new(),
+        concurrency: 1,
+        retries: 0,
+        overwrite: false,
+        skip_mux: false,
+        task: None,
+    };
+    // 需要合并但没有 ffmpeg：下载前报错
+    let err = download_replay(&client, "R1ab", None, &opt, None, None)
+        .await
+        .expect_err("ffmpeg required");
+    assert!(err.to_string().contains("ffmpegPath"));
+    assert!(!dir.path().join("replay 1.part001.flv").exists());
+
+    // skipMux：保留分段，不需要 ffmpeg
+    opt.skip_mux = true;
+    let r = download_replay(&client, "R1ab", None, &opt, None, None)
+        .await
+        .expect("download");
+    assert!(!r.skipped);
+    assert_eq!(r.bytes, 9);
+    assert_eq!(r.path, dir.path().join("replay 1.part001.flv"));
+    assert_eq!(r.parts.len(), 2);
+    assert_eq!(std::fs::read(&r.parts[1]).expect("read"), b"FLV22");
+
+    let again = download_replay(&client, "R1ab", None, &opt, None, None)
+        .await
+        .expect("download");
+    assert!(again.skipped);
+    opt.overwrite = true;
+    let again = download_replay(&client, "R1ab", None, &opt, None, None)
+        .await
+        .expect("download");
+    assert!(!again.skipped);
+}
+
+#[test]
+fn replay_as_single_page_view() {
+    let replay = chaos_core::bili_video::live_replay::LiveReplay {
+        rid: "R1ab".to_string(),
+        room_id: "7734200".to_string(),
+        title: "replay 1".to_string(),
+        cover: None,
+        start_unix_s: Some(1_700_000_000),
+        end_unix_s: None,
+        duration_ms: Some(3_599_000),
+        owner_name: Some("Anchor".to_string()),
+        owner_mid: None,
+    };
+    let view = replay.into_view_info();
+    assert_eq!(view.pub_time_unix_s, Some(1_700_000_000));
+    assert_eq!(view.pages.len(), 1);
+    assert_eq!(view.pages[0].cid, "R1ab");
+    assert_eq!(view.pages[0].duration_s, Some(3599));
+}
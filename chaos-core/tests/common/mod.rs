@@ -0,0 +1,113 @@
+//! 多个集成测试共用的音频文件与曲目构造。
+// 每个测试 crate 只用到其中一部分。
+#![allow(dead_code)]
+
+use std::path::Path;
+
+use chaos_core::music::model::{MusicQuality, MusicService, MusicTrack};
+
+/// MPEG-1 Layer III, 128kbps, 44.1kHz
+pub fn mp3_fixture(path: &Path) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut buf = Vec::new();
+    for _ in 0..10 {
+        buf.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x64]);
+        buf.extend(std::iter::repeat_n(0u8, 413));
+    }
+    std::fs::write(path, buf).unwrap();
+}
+
+/// 最小 FLAC：STREAMINFO + 空 PADDING + 一个帧头。
+pub fn flac_fixture(path: &Path) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut buf = b"fLaC".to_vec();
+    buf.extend_from_slice(&[0x00, 0, 0, 34]);
+    buf.extend_from_slice(&[0x10, 0x00, 0x10, 0x00]);
+    buf.extend_from_slice(&[0; 6]);
+    let packed: u64 = (44100u64 << 44) | (1u64 << 41) | (15u64 << 36);
+    buf.extend_from_slice(&packed.to_be_bytes());
+    buf.extend_from_slice(&[0; 16]);
+    buf.extend_from_slice(&[0x81, 0, 0, 16]);
+    buf.extend_from_slice(&[0; 16]);
+    buf.extend_from_slice(&[0xFF, 0xF8, 0x69, 0x08, 0, 0, 0, 0]);
+    std::fs::write(path, buf).unwrap();
+}
+
+/// MP4 box：4 字节大端长度 + 类型 + 内容。
+pub fn atom(name: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut out = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+    out.extend_from_slice(name);
+    out.extend_from_slice(body);
+    out
+}
+
+/// 只有一条 ALAC 音轨（16 bit / 44.1kHz / 1 秒）的最小 m4a。
+pub fn alac_fixture(path: &Path) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut mvhd = vec![0u8; 100];
+    mvhd[12..16].copy_from_slice(&1000u32.to_be_bytes());
+    mvhd[16..20].copy_from_slice(&1000u32.to_be_bytes());
+
+    let mut mdhd = vec![0u8; 24];
+    mdhd[12..16].copy_from_slice(&1000u32.to_be_bytes());
+    mdhd[16..20].copy_from_slice(&1000u32.to_be_bytes());
+    let mut hdlr = vec![0u8; 8];
+    hdlr.extend_from_slice(b"soun");
+    hdlr.extend_from_slice(&[0; 13]);
+
+    // ALAC 的 magic cookie：frameLength/compatibleVersion 之后是位深。
+    let mut cookie = vec![0u8; 9];
+    cookie.push(16);
+    cookie.extend_from_slice(&[0; 3]);
+    cookie.push(2);
+    cookie.extend_from_slice(&[0; 6]);
+    cookie.extend_from_slice(&0u32.to_be_bytes());
+    cookie.extend_from_slice(&44100u32.to_be_bytes());
+    let mut entry = vec![0u8; 28];
+    entry.extend(atom(b"alac", &cookie));
+    let mut stsd = vec![0, 0, 0, 0, 0, 0, 0, 1];
+    stsd.extend(atom(b"alac", &entry));
+
+    let minf = atom(b"minf", &atom(b"stbl", &atom(b"stsd", &stsd)));
+    let mdia = atom(
+        b"mdia",
+        &[atom(b"mdhd", &mdhd), atom(b"hdlr", &hdlr), minf].concat(),
+    );
+    let moov = atom(
+        b"moov",
+        &[atom(b"mvhd", &mvhd), atom(b"trak", &mdia)].concat(),
+    );
+
+    let mut buf = atom(b"ftyp", b"M4A \0\0\0\0M4A isom");
+    buf.extend(moov);
+    buf.extend(atom(b"mdat", &[0; 16]));
+    std::fs::write(path, buf).unwrap();
+}
+
+/// 无损为 flac，否则 mp3。
+pub fn quality(id: &str, bitrate_kbps: Option<u32>, lossless: bool) -> MusicQuality {
+    MusicQuality {
+        id: id.to_string(),
+        label: id.to_string(),
+        format: if lossless { "flac" } else { "mp3" }.to_string(),
+        bitrate_kbps,
+        lossless,
+    }
+}
+
+/// 只有标题与歌手的曲目；其它字段按需用 `..track(..)` 覆盖。
+pub fn track(service: MusicService, id: &str, title: &str, artists: &[&str]) -> MusicTrack {
+    MusicTrack {
+        service,
+        id: id.to_string(),
+        title: title.to_string(),
+        artists: artists.iter().map(|s| s.to_string()).collect(),
+        artist_ids: vec![],
+        album: None,
+        album_id: None,
+        duration_ms: None,
+        cover_url: None,
+        isrc: None,
+        qualities: vec![],
+    }
+}
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+
+use chaos_core::download_scheduler::{DownloadScheduler, SchedulerConfig};
+
+fn mk(max_connections: u32) -> DownloadScheduler {
+    DownloadScheduler::new(SchedulerConfig {
+        max_connections,
+        rate_limit_bps: None,
+    })
+}
+
+#[tokio::test]
+async fn connection_cap_and_priority_order() {
+    let sched = mk(1);
+    let big = sched.task(0);
+    let song = sched.task(5);
+
+    let held = big.acquire(None).await.expect("permit");
+    assert_eq!(sched.stats().active, 1);
+
+    // 低优先级先排队，高优先级后到，但应先拿到连接。
+    let big2 = big.clone();
+    let low = tokio::spawn(async move {
+        let _p = big2.acquire(None).await.expect("low");
+        Instant::now()
+    });
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    let song2 = song.clone();
+    let high = tokio::spawn(async move {
+        let p = song2.acquire(None).await.expect("high");
+        let t = Instant::now();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        drop(p);
+        t
+    });
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert_eq!(sched.stats().queued, 2);
+
+    drop(held);
+    let t_high = high.await.unwrap();
+    let t_low = low.await.unwrap();
+    assert!(t_high < t_low);
+    assert_eq!(sched.stats().active, 0);
+    assert_eq!(sched.stats().queued, 0);
+}
+
+#[tokio::test]
+async fn same_priority_prefers_task_with_fewer_connections() {
+    let sched = mk(2);
+    let season = sched.task(0);
+    let single = sched.task(0);
+
+    let p1 = season.acquire(None).await.expect("p1");
+    let _p2 = season.acquire(None).await.expect("p2");
+
+    let s2 = season.clone();
+    let season_next = tokio::spawn(async move { s2.acquire(None).await.map(|_| Instant::now()) });
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    let single2 = single.clone();
+    let single_next =
+        tokio::spawn(async move { single2.acquire(None).await.map(|p| (Instant::now(), p)) });
+    tokio::time::sleep(Duration::from_millis(30)).await;
+
+    drop(p1);
+    let (t_single, p_single) = single_next.await.unwrap().expect("single");
+    drop(p_single);
+    let t_season = season_next.await.unwrap().expect("season");
+    assert!(t_single <= t_season);
+}
+
+#[tokio::test]
+async fn pause_resume_and_cancel() {
+    let sched = mk(4);
+    let task = sched.task(0);
+
+    sched.pause();
+    assert!(task.is_paused());
+    let t2 = task.clone();
+    let waiting = tokio::spawn(async move { t2.acquire(None).await.is_some() });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!waiting.is_finished());
+    sched.resume();
+    assert!(waiting.await.unwrap());
+
+    task.pause();
+    let cancel = Arc::new(AtomicBool::new(false));
+    let (t3, c3) = (task.clone(), cancel.clone());
+    let throttled = tokio::spawn(async move { t3.throttle(1, Some(&c3)).await });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+    assert!(!throttled.await.unwrap());
+    task.resume();
+    assert!(task.throttle(1, None).await);
+}
+
+#[tokio::test]
+async fn rate_limit_delays_bytes() {
+    let sched = mk(4);
+    sched.set_rate_limit(Some(100_000));
+    let task = sched.task(0);
+
+    let started = Instant::now();
+    // 令牌从 0 开始：20KB 约需 200ms。
+    for _ in 0..4 {
+        assert!(task.throttle(5_000, None).await);
+    }
+    assert!(started.elapsed() >= Duration::from_millis(150));
+
+    sched.set_rate_limit(Some(0));
+    assert_eq!(sched.stats().rate_limit_bps, None);
+    let started = Instant::now();
+    assert!(task.throttle(10_000_000, None).await);
+    assert!(started.elapsed() < Duration::from_millis(50));
+}
+
+#[tokio::test]
+async fn rate_limit_wait_stops_on_cancel_and_pause() {
+    let sched = mk(4);
+    sched.set_rate_limit(Some(1_000));
+    let task = sched.task(0);
+
+    // 100KB @ 1KB/s：不提前返回的话要等 100 秒。
+    let cancel = Arc::new(AtomicBool::new(false));
+    let (t, c) = (task.clone(), cancel.clone());
+    let throttled = tokio::spawn(async move { t.throttle(100_000, Some(&c)).await });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+    let started = Instant::now();
+    assert!(!throttled.await.unwrap());
+    assert!(started.elapsed() < Duration::from_millis(500));
+
+    // 被取消的请求退还了额度：后面的小请求不用替它还账。
+    let started = Instant::now();
+    assert!(task.throttle(10, None).await);
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    let paused = sched.task(0);
+    let p = paused.clone();
+    let waiting = tokio::spawn(async move { p.throttle(100_000, None).await });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    paused.pause();
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    let started = Instant::now();
+    assert!(task.throttle(10, None).await);
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert!(!waiting.is_finished());
+    waiting.abort();
+}
+
+#[test]
+fn tasks_unregister_on_drop() {
+    let sched = mk(0);
+    assert_eq!(sched.stats().max_connections, 1);
+    let t = sched.task(0);
+    let t2 = t.clone();
+    assert_eq!(t, t2);
+    assert_eq!(sched.stats().tasks, 1);
+    drop(t);
+    assert_eq!(sched.stats().tasks, 1);
+    drop(t2);
+    assert_eq!(sched.stats().tasks, 0);
+}
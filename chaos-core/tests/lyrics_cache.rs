@@ -0,0 +1,372 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use chaos_core::lyrics::cache::LyricsCache;
+use chaos_core::lyrics::core::{search_with_cache, search_with_registry_report};
+use chaos_core::lyrics::error::LyricsError;
+use chaos_core::lyrics::model::{
+    LyricsSearchOptions, LyricsSearchRequest, LyricsSearchResult, LyricsSearchTerm, LyricsService,
+};
+use chaos_core::lyrics::providers::{LyricsProvider, LyricsToken};
+use chaos_core::lyrics::registry::LyricsProviderRegistry;
+use futures::future::BoxFuture;
+
+fn req(title: &str, artist: &str, duration_ms: Option<u64>, limit: usize) -> LyricsSearchRequest {
+    let mut r = LyricsSearchRequest::new(LyricsSearchTerm::Info {
+        title: title.to_string(),
+        artist: artist.to_string(),
+        album: None,
+    });
+    r.duration_ms = duration_ms;
+    r.limit = limit;
+    r
+}
+
+fn result(service: LyricsService, token: &str) -> LyricsSearchResult {
+    LyricsSearchResult {
+        service,
+        service_token: token.to_string(),
+        title: Some("Hello".to_string()),
+        artist: Some("Adele".to_string()),
+        album: None,
+        duration_ms: Some(296_000),
+        match_percentage: 90,
+        quality: 1.0,
+        matched: true,
+        has_translation: false,
+        has_inline_timetags: false,
+        lyrics_original: format!("[00:01.00]{token}"),
+        lyrics_translation: None,
+        offset_ms: 0,
+        debug: None,
+    }
+}
+
+#[test]
+fn lookup_normalizes_key_and_tolerates_duration() {
+    let cache = LyricsCache::in_memory();
+    let opt = LyricsSearchOptions::default();
+    cache.store(
+        &req("Hello", "Adele", Some(296_000), 2),
+        &[
+            result(LyricsService::QQMusic, "q1"),
+            result(LyricsService::QQMusic, "q2"),
+            result(LyricsService::Netease, "n1"),
+        ],
+        &[],
+    );
+
+    let hit = cache
+        .lookup(&req(" hello!", "ADELE", Some(297_400), 2), &opt)
+        .expect("hit")
+        .results;
+    assert_eq!(hit.len(), 2);
+    assert_eq!(hit[0].service_token, "q1");
+    assert!(
+        cache
+            .lookup(&req("Hello", "Adele", Some(310_000), 2), &opt)
+            .is_none()
+    );
+    // QQ 缓存时取满了 2 条，请求更多需要重新搜索；网易云不足 2 条，已是全部结果。
+    let hit = cache
+        .lookup(&req("Hello", "Adele", Some(296_000), 5), &opt)
+        .expect("hit");
+    assert_eq!(hit.missing, [LyricsService::QQMusic, LyricsService::LrcLib]);
+    assert_eq!(hit.results.len(), 1);
+    assert_eq!(hit.results[0].service_token, "n1");
+    let only_netease = LyricsSearchOptions {
+        services: vec![LyricsService::Netease],
+        ..LyricsSearchOptions::default()
+    };
+    let hit = cache
+        .lookup(&req("Hello", "Adele", Some(296_000), 2), &only_netease)
+        .expect("hit");
+    assert_eq!(hit.results.len(), 1);
+    assert_eq!(hit.results[0].service, LyricsService::Netease);
+    assert!(hit.missing.is_empty());
+}
+
+#[test]
+fn uncached_providers_and_fuzzy_results_are_misses() {
+    let cache = LyricsCache::in_memory();
+    let r = req("Hello", "Adele", Some(296_000), 2);
+    cache.store(&r, &[result(LyricsService::QQMusic, "q1")], &[]);
+
+    // 只缓存了 QQ：只要网易云的搜索不命中，QQ + LRCLIB 的搜索只需补查 LRCLIB。
+    let only_netease = LyricsSearchOptions {
+        services: vec![LyricsService::Netease],
+        ..LyricsSearchOptions::default()
+    };
+    assert!(cache.lookup(&r, &only_netease).is_none());
+    let both = LyricsSearchOptions {
+        services: vec![
+            LyricsService::Local,
+            LyricsService::QQMusic,
+            LyricsService::LrcLib,
+        ],
+        ..LyricsSearchOptions::default()
+    };
+    let hit = cache.lookup(&r, &both).expect("hit");
+    assert_eq!(hit.results.len(), 1);
+    assert_eq!(hit.missing, [LyricsService::LrcLib]);
+
+    // 模糊搜索的结果不能满足 exact_only；精确查找的结果两者都能满足。
+    let exact_only = LyricsSearchOptions {
+        exact_only: true,
+        ..only_netease.clone()
+    };
+    cache.store(&r, &[result(LyricsService::Netease, "n1")], &[]);
+    assert!(cache.lookup(&r, &exact_only).is_none());
+    assert!(cache.lookup(&r, &only_netease).is_some());
+    cache.store(
+        &r,
+        &[result(LyricsService::Netease, "n2")],
+        &[LyricsService::Netease],
+    );
+    let hit = cache.lookup(&r, &exact_only).expect("hit");
+    assert_eq!(hit.results[0].service_token, "n2");
+}
+
+#[test]
+fn pins_and_offsets_persist_and_survive_ttl() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sub").join("lyrics_cache.json");
+    let r = req("Hello", "Adele", Some(296_000), 3);
+    {
+        let cache = LyricsCache::open(&path);
+        cache.store(
+            &r,
+            &[
+                result(LyricsService::QQMusic, "q1"),
+                result(LyricsService::Netease, "n1"),
+            ],
+            &[],
+        );
+        cache
+            .pin(&r, result(LyricsService::Netease, "n1"))
+            .expect("pin");
+        cache.set_offset(&r, -250).expect("offset");
+    }
+
+    let cache = LyricsCache::open(&path);
+    assert_eq!(cache.offset_ms(&r), -250);
+    let hit = cache
+        .lookup(&r, &LyricsSearchOptions::default())
+        .expect("hit")
+        .results;
+    assert_eq!(
+        hit.iter()
+            .map(|x| x.service_token.as_str())
+            .collect::<Vec<_>>(),
+        ["n1", "q1"]
+    );
+    assert!(hit.iter().all(|x| x.offset_ms == -250));
+    assert_eq!(hit[0].parsed_lyrics().metadata.offset_ms, -250);
+
+    // 结果过期后仍返回用户固定的歌词。
+    let expired = LyricsCache::open(&path).with_ttl(Duration::ZERO);
+    let hit = expired
+        .lookup(&r, &LyricsSearchOptions::default())
+        .expect("pinned")
+        .results;
+    assert_eq!(hit.len(), 1);
+    assert_eq!(hit[0].service_token, "n1");
+
+    cache.unpin(&r).expect("unpin");
+    cache.set_offset(&r, 0).expect("offset");
+    let reopened = LyricsCache::open(&path).with_ttl(Duration::ZERO);
+    assert!(
+        reopened
+            .lookup(&r, &LyricsSearchOptions::default())
+            .is_none()
+    );
+}
+
+#[test]
+fn max_entries_evicts_least_recently_used_but_keeps_user_data() {
+    let cache = LyricsCache::in_memory().with_max_entries(2);
+    let opt = LyricsSearchOptions::default();
+    let pinned = req("Pinned", "A", None, 1);
+    cache
+        .pin(&pinned, result(LyricsService::QQMusic, "p"))
+        .expect("pin");
+    for title in ["One", "Two", "Three"] {
+        cache.store(
+            &req(title, "A", None, 1),
+            &[result(LyricsService::QQMusic, title)],
+            &[],
+        );
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    assert_eq!(cache.len(), 2);
+    assert!(cache.lookup(&pinned, &opt).is_some());
+    assert!(cache.lookup(&req("Three", "A", None, 1), &opt).is_some());
+    assert!(cache.lookup(&req("One", "A", None, 1), &opt).is_none());
+}
+
+#[tokio::test]
+async fn search_consults_cache_unless_bypassed() {
+    let cache = LyricsCache::in_memory();
+    let http = reqwest::Client::new();
+    let r = req("Hello", "Adele", Some(296_000), 1);
+    cache.store(&r, &[result(LyricsService::QQMusic, "cached")], &[]);
+
+    let only_qq = LyricsSearchOptions {
+        services: vec![LyricsService::QQMusic],
+        ..LyricsSearchOptions::default()
+    };
+    let hit = search_with_cache(&http, &r, only_qq, Some(&cache))
+        .await
+        .expect("search");
+    assert_eq!(hit[0].service_token, "cached");
+
+    // 不请求任何服务：绕过缓存后只剩用户固定的歌词。
+    let bypass = LyricsSearchOptions {
+        services: Vec::new(),
+        bypass_cache: true,
+        ..LyricsSearchOptions::default()
+    };
+    let out = search_with_cache(&http, &r, bypass.clone(), Some(&cache))
+        .await
+        .expect("search");
+    assert!(out.is_empty());
+
+    cache
+        .pin(&r, result(LyricsService::Netease, "pinned"))
+        .expect("pin");
+    let out = search_with_cache(&http, &r, bypass, Some(&cache))
+        .await
+        .expect("search");
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].service_token, "pinned");
+}
+
+/// 记录搜索次数的歌词源，每次返回一条以服务名为 token 的结果。
+struct CountingProvider {
+    service: LyricsService,
+    searches: AtomicUsize,
+}
+
+impl LyricsProvider for CountingProvider {
+    fn service(&self) -> LyricsService {
+        self.service
+    }
+
+    fn search<'a>(
+        &'a self,
+        _http: &'a reqwest::Client,
+        _req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>> {
+        self.searches.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async { Ok(vec![LyricsToken::new(())]) })
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        _http: &'a reqwest::Client,
+        _token: LyricsToken,
+        _req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<LyricsSearchResult, LyricsError>> {
+        Box::pin(async { Ok(result(self.service, self.service.as_str())) })
+    }
+}
+
+#[tokio::test]
+async fn partial_hit_queries_only_uncached_providers_and_persists() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("lyrics_cache.json");
+    let cache = LyricsCache::open(&path);
+    let r = req("Hello", "Adele", Some(296_000), 2);
+    cache.store(&r, &[result(LyricsService::QQMusic, "cached")], &[]);
+
+    let netease = Arc::new(CountingProvider {
+        service: LyricsService::Netease,
+        searches: AtomicUsize::new(0),
+    });
+    let registry = LyricsProviderRegistry::empty();
+    registry.register(netease.clone());
+    let opt = LyricsSearchOptions {
+        services: vec![LyricsService::QQMusic, LyricsService::Netease],
+        ..LyricsSearchOptions::default()
+    };
+    let http = reqwest::Client::new();
+
+    let (out, report) =
+        search_with_registry_report(&http, &r, opt.clone(), &registry, Some(&cache))
+            .await
+            .expect("search");
+    assert!(report.cache_hit);
+    assert_eq!(
+        report
+            .providers
+            .iter()
+            .map(|p| p.service)
+            .collect::<Vec<_>>(),
+        [LyricsService::Netease]
+    );
+    let mut tokens: Vec<_> = out.iter().map(|x| x.service_token.as_str()).collect();
+    tokens.sort();
+    assert_eq!(tokens, ["cached", "netease"]);
+
+    // 两个源都已缓存：不再请求。
+    let (out, report) =
+        search_with_registry_report(&http, &r, opt.clone(), &registry, Some(&cache))
+            .await
+            .expect("search");
+    assert_eq!(out.len(), 2);
+    assert!(report.providers.is_empty());
+    assert_eq!(netease.searches.load(Ordering::SeqCst), 1);
+
+    // 后台写盘完成后重新打开仍然命中。
+    let mut persisted = None;
+    for _ in 0..100 {
+        let reopened = LyricsCache::open(&path);
+        if let Some(hit) = reopened.lookup(&r, &opt)
+            && hit.missing.is_empty()
+        {
+            persisted = Some(hit);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(persisted.expect("persisted").results.len(), 2);
+}
+
+#[test]
+fn unknown_services_in_cache_file_are_skipped() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("lyrics_cache.json");
+    let r = req("Hello", "Adele", Some(296_000), 2);
+    {
+        let cache = LyricsCache::open(&path);
+        cache.store(
+            &r,
+            &[
+                result(LyricsService::QQMusic, "q1"),
+                result(LyricsService::Netease, "n1"),
+            ],
+            &[],
+        );
+        cache
+            .pin(&r, result(LyricsService::Netease, "n1"))
+            .expect("pin");
+    }
+    // 模拟其它进程注册过的自定义歌词源。
+    let json = std::fs::read_to_string(&path)
+        .unwrap()
+        .replace("\"netease\"", "\"not-registered-here\"");
+    std::fs::write(&path, json).unwrap();
+
+    let cache = LyricsCache::open(&path);
+    let entry = cache.entry(&r).expect("entry");
+    assert!(entry.pinned.is_none());
+    assert_eq!(entry.providers.len(), 1);
+    let hit = cache
+        .lookup(&r, &LyricsSearchOptions::default())
+        .expect("hit");
+    assert_eq!(hit.results.len(), 1);
+    assert_eq!(hit.results[0].service_token, "q1");
+}
@@ -20,3 +20,9 @@ fn decode_xml_entities_named_and_numeric() {
     let got = util::decode_xml_entities(s);
     assert_eq!(got.as_ref(), "a&b<c>d\"e'f!!");
 }
+
+#[test]
+fn decode_xml_entities_keeps_non_ascii() {
+    let got = util::decode_xml_entities("愛&amp;夢&#x4e2d;");
+    assert_eq!(got.as_ref(), "愛&夢中");
+}
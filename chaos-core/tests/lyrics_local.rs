@@ -0,0 +1,226 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chaos_core::lyrics::cache::LyricsCache;
+use chaos_core::lyrics::core::search_with_registry;
+use chaos_core::lyrics::error::LyricsError;
+use chaos_core::lyrics::model::{
+    LyricsSearchOptions, LyricsSearchRequest, LyricsSearchResult, LyricsSearchTerm, LyricsService,
+};
+use chaos_core::lyrics::providers::{
+    LocalProvider, LyricsProvider, LyricsToken, resolve_file_request,
+};
+use chaos_core::lyrics::registry::LyricsProviderRegistry;
+use chaos_core::music::tagging::{TrackTags, write_tags};
+use futures::future::BoxFuture;
+use lofty::config::WriteOptions;
+use lofty::file::TaggedFileExt;
+use lofty::tag::{ItemKey, TagExt};
+
+mod common;
+use common::mp3_fixture;
+
+fn tagged_mp3(path: &Path, lyrics: Option<&str>) {
+    mp3_fixture(path);
+    let tags = TrackTags {
+        title: "Hello".to_string(),
+        artists: vec!["Adele".to_string()],
+        album: Some("25".to_string()),
+        track_no: None,
+        lyrics: lyrics.map(str::to_string),
+    };
+    write_tags(path, &tags, None).unwrap();
+}
+
+/// 在线服务的替身：总是返回一条歌词。
+struct RemoteProvider;
+
+impl LyricsProvider for RemoteProvider {
+    fn service(&self) -> LyricsService {
+        LyricsService::custom("remote-stub")
+    }
+
+    fn search<'a>(
+        &'a self,
+        _http: &'a reqwest::Client,
+        _req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>> {
+        Box::pin(async move { Ok(vec![LyricsToken::new(())]) })
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        _http: &'a reqwest::Client,
+        _token: LyricsToken,
+        req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<LyricsSearchResult, LyricsError>> {
+        Box::pin(async move {
+            let (title, artist) = req.term.title_artist();
+            Ok(LyricsSearchResult {
+                service: self.service(),
+                service_token: "remote".to_string(),
+                title: title.map(str::to_string),
+                artist: artist.map(str::to_string),
+                album: None,
+                duration_ms: req.duration_ms,
+                match_percentage: 0,
+                quality: 0.0,
+                matched: false,
+                has_translation: false,
+                has_inline_timetags: true,
+                lyrics_original: "[00:01.00]remote".to_string(),
+                lyrics_translation: None,
+                offset_ms: 0,
+                debug: None,
+            })
+        })
+    }
+}
+
+fn registry() -> LyricsProviderRegistry {
+    let registry = LyricsProviderRegistry::empty();
+    registry.register(Arc::new(LocalProvider));
+    registry.register(Arc::new(RemoteProvider));
+    registry
+}
+
+fn opt() -> LyricsSearchOptions {
+    LyricsSearchOptions {
+        services: vec![LyricsService::custom("remote-stub"), LyricsService::Local],
+        ..LyricsSearchOptions::default()
+    }
+}
+
+fn file_req(path: &Path) -> LyricsSearchRequest {
+    let mut r = LyricsSearchRequest::new(LyricsSearchTerm::File {
+        path: path.to_path_buf(),
+    });
+    r.limit = 5;
+    r
+}
+
+#[test]
+fn file_term_resolves_from_tags_or_file_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let tagged = dir.path().join("01.mp3");
+    tagged_mp3(&tagged, None);
+    let r = resolve_file_request(&file_req(&tagged));
+    assert_eq!(r.file_path.as_deref(), Some(tagged.as_path()));
+    assert_eq!(r.term.title_artist(), (Some("Hello"), Some("Adele")));
+    assert_eq!(r.isrc, None);
+
+    let mut file = lofty::read_from_path(&tagged).unwrap();
+    let tag = file.primary_tag_mut().unwrap();
+    tag.insert_text(ItemKey::Isrc, " gbbks1500214 ".to_string());
+    tag.save_to_path(&tagged, WriteOptions::default()).unwrap();
+    let r = resolve_file_request(&file_req(&tagged));
+    assert_eq!(r.isrc.as_deref(), Some("GBBKS1500214"));
+
+    let untagged = dir.path().join("Adele - Someone Like You.flac");
+    let r = resolve_file_request(&file_req(&untagged));
+    assert_eq!(
+        r.term.title_artist(),
+        (Some("Someone Like You"), Some("Adele"))
+    );
+
+    let r = resolve_file_request(&file_req(&dir.path().join("track07.ogg")));
+    assert_eq!(r.term.description(), "track07");
+    assert_eq!(r.term.title_artist(), (None, None));
+}
+
+#[tokio::test]
+async fn local_sidecars_and_embedded_lyrics_rank_first() {
+    let dir = tempfile::tempdir().unwrap();
+    let audio = dir.path().join("Adele - Hello.mp3");
+    tagged_mp3(&audio, Some("[00:01.00]embedded\n[00:03.50]second"));
+    std::fs::write(
+        dir.path().join("adele - hello.LRC"),
+        "\u{feff}[00:01.00]sidecar",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("Hello (Adele).lrc"),
+        "[00:01.00]<00:01.00>fuz<00:01.40>zy<00:02.00>",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("Someone Like You.lrc"), "[00:01.00]other").unwrap();
+
+    let http = reqwest::Client::new();
+    let out = search_with_registry(&http, &file_req(&audio), opt(), &registry(), None)
+        .await
+        .expect("search");
+    let tokens = out
+        .iter()
+        .map(|r| r.service_token.rsplit(['/', '\\']).next().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tokens,
+        [
+            "adele - hello.LRC",
+            "embedded:SYLT",
+            "embedded:lyrics",
+            "Hello (Adele).lrc",
+            "remote"
+        ]
+    );
+    assert_eq!(out[0].service, LyricsService::Local);
+    assert_eq!(out[0].lyrics_original, "[00:01.00]sidecar");
+    assert_eq!(
+        out[1].lyrics_original,
+        "[00:01.00]embedded\n[00:03.50]second"
+    );
+    // 只有逐字时间戳才算 inline timetags，逐行 LRC 不算。
+    assert!(!out[0].has_inline_timetags);
+    assert!(!out[1].has_inline_timetags);
+    assert!(out[3].has_inline_timetags);
+    assert_eq!(out[0].title.as_deref(), Some("Hello"));
+    assert_eq!(out[4].artist.as_deref(), Some("Adele"));
+
+    // 没有文件路径时本地源不返回任何结果。
+    let mut plain = LyricsSearchRequest::new(LyricsSearchTerm::Info {
+        title: "Hello".to_string(),
+        artist: "Adele".to_string(),
+        album: None,
+    });
+    plain.limit = 5;
+    let out = search_with_registry(&http, &plain, opt(), &registry(), None)
+        .await
+        .expect("search");
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].service_token, "remote");
+}
+
+#[tokio::test]
+async fn local_results_are_not_cached_but_merged_with_cache_hits() {
+    let dir = tempfile::tempdir().unwrap();
+    let audio = dir.path().join("Adele - Hello.mp3");
+    tagged_mp3(&audio, None);
+    let sidecar = dir.path().join("Adele - Hello.lrc");
+    std::fs::write(&sidecar, "[00:01.00]v1").unwrap();
+
+    let cache = LyricsCache::in_memory();
+    let http = reqwest::Client::new();
+    let registry = registry();
+    let out = search_with_registry(&http, &file_req(&audio), opt(), &registry, Some(&cache))
+        .await
+        .expect("search");
+    assert_eq!(out.len(), 2);
+    assert_eq!(cache.len(), 1);
+    let resolved = resolve_file_request(&file_req(&audio));
+    let cached = cache.lookup(&resolved, &opt()).expect("hit").results;
+    assert_eq!(cached.len(), 1);
+    assert_eq!(cached[0].service_token, "remote");
+
+    // 缓存命中时仍重新读取本地文件。
+    std::fs::write(&sidecar, "[00:01.00]v2").unwrap();
+    registry.unregister(LyricsService::custom("remote-stub"));
+    let out = search_with_registry(&http, &file_req(&audio), opt(), &registry, Some(&cache))
+        .await
+        .expect("search");
+    assert_eq!(out.len(), 2);
+    assert_eq!(out[0].lyrics_original, "[00:01.00]v2");
+    assert_eq!(out[1].service_token, "remote");
+}
@@ -0,0 +1,119 @@
+use chaos_core::lyrics::offset::{
+    OffsetEstimateOptions, estimate_offset, estimate_offset_for_file, vocal_onsets_ms,
+};
+use chaos_core::lyrics::parse::parse;
+use chaos_core::tts::wav::{encode_wav_pcm16_mono, f32_to_pcm16_mono};
+
+const LINE_STARTS_MS: [u64; 7] = [2_000, 5_000, 8_500, 11_000, 15_500, 19_000, 22_500];
+
+fn lrc(offset_tag: Option<i64>) -> String {
+    let mut out = offset_tag.map_or(String::new(), |o| format!("[offset:{o}]\n"));
+    for (i, ms) in LINE_STARTS_MS.iter().enumerate() {
+        out.push_str(&format!(
+            "[{:02}:{:02}.{:02}]line {i}\n",
+            ms / 60_000,
+            ms / 1000 % 60,
+            ms % 1000 / 10
+        ));
+    }
+    out
+}
+
+/// 低频嗡声 + 微弱噪声的伴奏上，每行歌词在 `start + delay_ms` 处唱 1.5 秒。
+fn song(sample_rate: u32, delay_ms: u64) -> Vec<f32> {
+    let sr = sample_rate as usize;
+    let mut seed = 1u32;
+    let mut pcm: Vec<f32> = (0..sr * 28)
+        .map(|i| {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            let noise = (seed >> 16) as f32 / 65_536.0 - 0.5;
+            let t = i as f32 / sample_rate as f32;
+            0.3 * (2.0 * std::f32::consts::PI * 50.0 * t).sin() + 0.01 * noise
+        })
+        .collect();
+    for start in LINE_STARTS_MS {
+        let from = (start + delay_ms) as usize * sr / 1000;
+        for (k, s) in pcm[from..from + sr * 3 / 2].iter_mut().enumerate() {
+            let t = k as f32 / sample_rate as f32;
+            *s += 0.5 * (2.0 * std::f32::consts::PI * 440.0 * t).sin();
+        }
+    }
+    pcm
+}
+
+#[test]
+fn onsets_follow_vocal_bursts_not_bass() {
+    let onsets = vocal_onsets_ms(&song(44_100, 0), 44_100).expect("onsets");
+    assert_eq!(onsets.len(), LINE_STARTS_MS.len());
+    for (o, l) in onsets.iter().zip(LINE_STARTS_MS) {
+        assert!(o.abs_diff(l) <= 60, "onset {o} vs line {l}");
+    }
+}
+
+#[test]
+fn estimates_late_and_early_lyrics() {
+    let opt = OffsetEstimateOptions::default();
+    let lyrics = parse(&lrc(None));
+
+    // 人声比歌词晚 1.2 秒：歌词需要推迟显示，offset 为负。
+    let est = estimate_offset(&lyrics, &song(44_100, 1_200), 44_100, &opt)
+        .expect("estimate")
+        .expect("some");
+    assert!((est.offset_ms + 1_200).abs() <= 60, "{est:?}");
+    assert_eq!(est.matched_lines, LINE_STARTS_MS.len());
+    assert!(est.confidence > 0.5, "{est:?}");
+
+    // 人声比歌词早 0.8 秒。
+    let mut early = parse(&lrc(None));
+    for l in &mut early.lines {
+        l.start_ms += 800;
+    }
+    let est = estimate_offset(&early, &song(44_100, 0), 44_100, &opt)
+        .expect("estimate")
+        .expect("some");
+    assert!((est.offset_ms - 800).abs() <= 60, "{est:?}");
+
+    // 行太少或没有人声时不估计。
+    let short = parse("[00:01.00]a\n[00:02.00]b");
+    assert!(
+        estimate_offset(&short, &song(44_100, 0), 44_100, &opt)
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        estimate_offset(&lyrics, &vec![0.0; 44_100 * 5], 44_100, &opt)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn file_estimate_can_be_written_as_offset_tag_or_cache_offset() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("song.wav");
+    let pcm = f32_to_pcm16_mono(&song(22_050, 1_500));
+    std::fs::write(&path, encode_wav_pcm16_mono(22_050, &pcm).unwrap()).unwrap();
+
+    let mut lyrics = parse(&lrc(Some(200)));
+    let est = estimate_offset_for_file(&lyrics, &path, &OffsetEstimateOptions::default())
+        .expect("estimate")
+        .expect("some");
+    assert!((est.offset_ms + 1_500).abs() <= 60, "{est:?}");
+    assert_eq!(est.cache_offset_ms(&lyrics), est.offset_ms - 200);
+
+    est.apply(&mut lyrics);
+    let out = lyrics.to_lrc();
+    assert!(out.starts_with(&format!("[offset:{}]\n", est.offset_ms)));
+    // 人声开始后即高亮对应行，开始前仍是上一行。
+    assert_eq!(lyrics.line_index_at(5_000 + 1_500 + 100), Some(1));
+    assert_eq!(lyrics.line_index_at(5_000 + 1_500 - 200), Some(0));
+
+    assert!(
+        estimate_offset_for_file(
+            &lyrics,
+            &dir.path().join("missing.mp3"),
+            &OffsetEstimateOptions::default()
+        )
+        .is_err()
+    );
+}
@@ -0,0 +1,110 @@
+use base64::Engine as _;
+use chaos_core::lyrics::parse::{self, LyricsFormat};
+use serde_json::json;
+
+#[test]
+fn lrc_metadata_multi_tags_and_inline_translation() {
+    let lrc = "\u{feff}[ti:晴天]\n[ar:周杰伦]\n[offset:+500]\n\
+               [00:01.50][01:00.00]故事的小黄花\n[00:01.50]little yellow flower\n\
+               [00:05:20]从出生那年就飘着\n[00:08]\n";
+    assert_eq!(parse::detect_format(lrc), LyricsFormat::Lrc);
+    let lyrics = parse::parse(lrc);
+    assert_eq!(lyrics.metadata.title.as_deref(), Some("晴天"));
+    assert_eq!(lyrics.metadata.artist.as_deref(), Some("周杰伦"));
+    assert_eq!(lyrics.metadata.offset_ms, 500);
+
+    let starts = lyrics.lines.iter().map(|l| l.start_ms).collect::<Vec<_>>();
+    assert_eq!(starts, [1500, 5200, 8000, 60_000]);
+    assert_eq!(
+        lyrics.lines[0].translation.as_deref(),
+        Some("little yellow flower")
+    );
+    assert_eq!(lyrics.lines[0].end_ms, 5200);
+    assert_eq!(lyrics.lines[3].end_ms, 65_000);
+    assert_eq!(lyrics.lines[3].translation, None);
+
+    // offset 为正：歌词提前 500ms。
+    assert_eq!(lyrics.line_index_at(0), None);
+    assert_eq!(lyrics.line_index_at(1000), Some(0));
+    assert_eq!(lyrics.line_index_at(4800), Some(1));
+
+    let out = lyrics.to_lrc();
+    assert!(out.starts_with("[ti:晴天]\n[ar:周杰伦]\n[offset:500]\n"));
+    assert!(out.contains("[00:01.50]故事的小黄花\n[00:01.50]little yellow flower\n"));
+    assert_eq!(parse::parse(&out), lyrics);
+}
+
+#[test]
+fn separate_translation_merges_by_nearest_timestamp() {
+    let lyrics = parse::parse_with_translation(
+        "[00:01.00]one\n[00:03.00]two\n[00:09.00]three",
+        Some("[00:01.10]一\n[00:03.00]二\n[00:05.00]孤立"),
+    );
+    assert_eq!(lyrics.lines[0].translation.as_deref(), Some("一"));
+    assert_eq!(lyrics.lines[1].translation.as_deref(), Some("二"));
+    assert_eq!(lyrics.lines[2].translation, None);
+}
+
+#[test]
+fn enhanced_lrc_words_round_trip() {
+    let text = "[00:01.00]<00:01.00>Hel<00:01.50>lo <00:02.00>world<00:03.00>\n[00:04.00]plain";
+    assert_eq!(parse::detect_format(text), LyricsFormat::EnhancedLrc);
+    let lyrics = parse::parse(text);
+    let line = &lyrics.lines[0];
+    assert_eq!(line.text, "Hello world");
+    assert_eq!(line.words.len(), 3);
+    assert_eq!((line.words[1].start_ms, line.words[1].end_ms), (1500, 2000));
+    assert_eq!(line.end_ms, 3000);
+    assert!(lyrics.lines[1].words.is_empty());
+    assert_eq!(lyrics.lines[1].end_ms, 9000);
+
+    assert_eq!(lyrics.to_enhanced_lrc(), format!("{text}\n"));
+}
+
+#[test]
+fn word_formats_yrc_klyric_qrc_krc() {
+    let yrc = "{\"t\":0,\"c\":[{\"tx\":\"作词\"}]}\n[1000,2000](1000,500,0)你(1500,1500,0)好";
+    assert_eq!(parse::detect_format(yrc), LyricsFormat::Yrc);
+    let lyrics = parse::parse(yrc);
+    assert_eq!(lyrics.lines.len(), 1);
+    assert_eq!(lyrics.lines[0].text, "你好");
+    assert_eq!(lyrics.lines[0].end_ms, 3000);
+    assert_eq!(lyrics.lines[0].words[1].start_ms, 1500);
+
+    // 旧版 klyric：字时间相对行首。
+    let klyric = "[5000,1000](0,400)Hi (400,600)there";
+    assert_eq!(parse::detect_format(klyric), LyricsFormat::Yrc);
+    let lyrics = parse::parse(klyric);
+    assert_eq!(lyrics.lines[0].text, "Hi there");
+    assert_eq!(lyrics.lines[0].words[1].start_ms, 5400);
+
+    let qrc = "[ti:x]\n[1000,2000]你(1000,500)好(1500,500)";
+    assert_eq!(parse::detect_format(qrc), LyricsFormat::Qrc);
+    let lyrics = parse::parse(qrc);
+    assert_eq!(lyrics.metadata.title.as_deref(), Some("x"));
+    assert_eq!(lyrics.lines[0].words[0].text, "你");
+    assert_eq!(lyrics.lines[0].words[1].end_ms, 2000);
+
+    let language = json!({ "content": [
+        { "type": 0, "lyricContent": [["ni ", "hao"]] },
+        { "type": 1, "lyricContent": [["Hello"], ["World"]] }
+    ] });
+    let krc = format!(
+        "[language:{}]\n[1000,1000]<0,500,0>你<500,500,0>好\n[2000,1000]<0,1000,0>世界",
+        base64::engine::general_purpose::STANDARD.encode(language.to_string())
+    );
+    assert_eq!(parse::detect_format(&krc), LyricsFormat::Krc);
+    let lyrics = parse::parse(&krc);
+    assert_eq!(lyrics.lines[0].words[1].start_ms, 1500);
+    assert_eq!(lyrics.lines[0].translation.as_deref(), Some("Hello"));
+    assert_eq!(lyrics.lines[1].translation.as_deref(), Some("World"));
+}
+
+#[test]
+fn srt_applies_offset_and_skips_empty_lines() {
+    let lyrics = parse::parse("[offset:-1000]\n[00:01.00]one\n[00:02.00]\n[01:02.50]two");
+    assert_eq!(
+        lyrics.to_srt(),
+        "1\n00:00:02,000 --> 00:00:03,000\none\n\n2\n00:01:03,500 --> 00:01:08,500\ntwo\n\n"
+    );
+}
@@ -182,6 +182,70 @@ async fn lrclib_search_returns_synced_lyrics() {
     assert!(item.lyrics_original.contains("[00:01.00]hello"));
 }
 
+#[tokio::test]
+async fn lrclib_exact_get_needs_duration_and_treats_404_as_miss() {
+    let server = MockServer::start();
+
+    let hit = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/get")
+            .query_param("track_name", "Hello")
+            .query_param("artist_name", "Adele")
+            .query_param("album_name", "25")
+            .query_param("duration", "296");
+        then.status(200).body(
+            r#"{"id":3396226,"trackName":"Hello","artistName":"Adele","albumName":"25","duration":295.5,"syncedLyrics":"[00:01.00]hello\n"}"#,
+        );
+    });
+    let miss = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/get")
+            .query_param("track_name", "Unknown");
+        then.status(404).body(
+            r#"{"code":404,"name":"TrackNotFound","message":"Failed to find specified track"}"#,
+        );
+    });
+
+    let p = LrcLibProvider::with_base_url(&format!("{}/api/search", server.base_url()));
+    let http = reqwest::Client::new();
+    let timeout = Duration::from_millis(1000);
+
+    let mut req = LyricsSearchRequest::new(LyricsSearchTerm::Info {
+        title: "Hello".to_string(),
+        artist: "Adele".to_string(),
+        album: Some("25".to_string()),
+    });
+    req.duration_ms = Some(295_600);
+    let toks = p.lookup(&http, &req, timeout).await.unwrap();
+    assert_eq!(toks.len(), 1);
+    let item = p
+        .fetch(&http, toks[0].clone(), &req, timeout)
+        .await
+        .unwrap();
+    assert_eq!(item.service_token, "3396226");
+    assert_eq!(item.album.as_deref(), Some("25"));
+    assert_eq!(item.duration_ms, Some(295_500));
+    assert_eq!(hit.hits(), 1);
+
+    let unknown = req_info("Unknown", "Nobody", Some(200_000));
+    assert!(p.lookup(&http, &unknown, timeout).await.unwrap().is_empty());
+    assert_eq!(miss.hits(), 1);
+
+    // 没有时长或不是标题+歌手时不发请求。
+    let no_duration = req_info("Hello", "Adele", None);
+    assert!(
+        p.lookup(&http, &no_duration, timeout)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let keyword = LyricsSearchRequest::new(LyricsSearchTerm::Keyword {
+        keyword: "Hello".to_string(),
+    });
+    assert!(p.lookup(&http, &keyword, timeout).await.unwrap().is_empty());
+    assert_eq!(hit.hits() + miss.hits(), 2);
+}
+
 fn gzip_bytes(data: &[u8]) -> Vec<u8> {
     let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
     encoder.write_all(data).unwrap();
@@ -33,6 +33,7 @@ fn base_result(
         has_inline_timetags: false,
         lyrics_original: "x".to_string(),
         lyrics_translation: None,
+        offset_ms: 0,
         debug: None,
     }
 }
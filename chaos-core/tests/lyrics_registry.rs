@@ -0,0 +1,442 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chaos_core::lyrics::core::{
+    LyricsSearchEvent, search_stream_with_registry, search_with_registry,
+    search_with_registry_report,
+};
+use chaos_core::lyrics::error::LyricsError;
+use chaos_core::lyrics::model::{
+    LyricsProviderStatus, LyricsSearchOptions, LyricsSearchRequest, LyricsSearchResult,
+    LyricsSearchTerm, LyricsService,
+};
+use chaos_core::lyrics::providers::{LyricsProvider, LyricsToken};
+use chaos_core::lyrics::registry::LyricsProviderRegistry;
+use futures::StreamExt;
+use futures::future::BoxFuture;
+
+/// 内存中的歌词源：token 是歌词文本本身。
+struct StaticProvider {
+    service: LyricsService,
+    lyrics: Vec<&'static str>,
+}
+
+impl LyricsProvider for StaticProvider {
+    fn service(&self) -> LyricsService {
+        self.service
+    }
+
+    fn search<'a>(
+        &'a self,
+        _http: &'a reqwest::Client,
+        _req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>> {
+        Box::pin(async move {
+            Ok(self
+                .lyrics
+                .iter()
+                .map(|s| LyricsToken::new(s.to_string()))
+                .collect())
+        })
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        _http: &'a reqwest::Client,
+        token: LyricsToken,
+        req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<LyricsSearchResult, LyricsError>> {
+        Box::pin(async move {
+            let text = token.downcast::<String>()?;
+            let (title, artist) = req.term.title_artist();
+            Ok(LyricsSearchResult {
+                service: self.service,
+                service_token: text.clone(),
+                title: title.map(str::to_string),
+                artist: artist.map(str::to_string),
+                album: None,
+                duration_ms: req.duration_ms,
+                match_percentage: 0,
+                quality: 0.0,
+                matched: false,
+                has_translation: false,
+                has_inline_timetags: false,
+                lyrics_original: text,
+                lyrics_translation: None,
+                offset_ms: 0,
+                debug: None,
+            })
+        })
+    }
+}
+
+/// 搜索失败或超时的歌词源。
+struct BrokenProvider {
+    service: LyricsService,
+    hang: bool,
+}
+
+impl LyricsProvider for BrokenProvider {
+    fn service(&self) -> LyricsService {
+        self.service
+    }
+
+    fn search<'a>(
+        &'a self,
+        _http: &'a reqwest::Client,
+        _req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>> {
+        Box::pin(async move {
+            if self.hang {
+                futures::future::pending::<()>().await;
+            }
+            Err(LyricsError::Parse("unexpected payload".to_string()))
+        })
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        _http: &'a reqwest::Client,
+        _token: LyricsToken,
+        _req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<LyricsSearchResult, LyricsError>> {
+        Box::pin(async move { Err(LyricsError::UnsupportedTerm) })
+    }
+}
+
+/// 延迟返回的歌词源。
+struct SlowProvider {
+    inner: StaticProvider,
+    delay: Duration,
+}
+
+impl LyricsProvider for SlowProvider {
+    fn service(&self) -> LyricsService {
+        self.inner.service
+    }
+
+    fn search<'a>(
+        &'a self,
+        http: &'a reqwest::Client,
+        req: &'a LyricsSearchRequest,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>> {
+        Box::pin(async move {
+            tokio::time::sleep(self.delay).await;
+            self.inner.search(http, req, timeout).await
+        })
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        http: &'a reqwest::Client,
+        token: LyricsToken,
+        req: &'a LyricsSearchRequest,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<LyricsSearchResult, LyricsError>> {
+        self.inner.fetch(http, token, req, timeout)
+    }
+}
+
+/// 支持按 ISRC 精确查找的歌词源；模糊搜索总是返回另一首。
+struct IsrcProvider {
+    inner: StaticProvider,
+}
+
+impl LyricsProvider for IsrcProvider {
+    fn service(&self) -> LyricsService {
+        self.inner.service
+    }
+
+    fn lookup<'a>(
+        &'a self,
+        _http: &'a reqwest::Client,
+        req: &'a LyricsSearchRequest,
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>> {
+        Box::pin(async move {
+            Ok(match req.isrc.as_deref() {
+                Some("GBBKS1500214") => vec![LyricsToken::new("[00:01.00]exact".to_string())],
+                Some(_) => return Err(LyricsError::Parse("lookup failed".to_string())),
+                None => Vec::new(),
+            })
+        })
+    }
+
+    fn search<'a>(
+        &'a self,
+        http: &'a reqwest::Client,
+        req: &'a LyricsSearchRequest,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<Vec<LyricsToken>, LyricsError>> {
+        self.inner.search(http, req, timeout)
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        http: &'a reqwest::Client,
+        token: LyricsToken,
+        req: &'a LyricsSearchRequest,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<LyricsSearchResult, LyricsError>> {
+        self.inner.fetch(http, token, req, timeout)
+    }
+}
+
+fn req() -> LyricsSearchRequest {
+    let mut r = LyricsSearchRequest::new(LyricsSearchTerm::Info {
+        title: "Hello".to_string(),
+        artist: "Adele".to_string(),
+        album: None,
+    });
+    r.duration_ms = Some(296_000);
+    r
+}
+
+#[test]
+fn custom_service_names_are_interned_and_round_trip() {
+    let svc = LyricsService::custom(" Internal ");
+    assert_eq!(svc.as_str(), "internal");
+    assert!(!svc.is_builtin());
+    assert_eq!(LyricsService::from_str("INTERNAL"), Ok(svc));
+    assert_eq!(LyricsService::custom("163"), LyricsService::Netease);
+    assert!(LyricsService::from_str("never-registered").is_err());
+
+    let json = serde_json::to_string(&[svc, LyricsService::QQMusic]).unwrap();
+    assert_eq!(json, r#"["internal","qq"]"#);
+    let back: Vec<LyricsService> = serde_json::from_str(r#"["internal","qq"]"#).unwrap();
+    assert_eq!(back, [svc, LyricsService::QQMusic]);
+    // 反序列化只认规范名：未知名字与别名都拒绝，且不会被登记。
+    assert!(serde_json::from_str::<LyricsService>(r#""other""#).is_err());
+    assert!(serde_json::from_str::<LyricsService>(r#""163""#).is_err());
+    assert!(LyricsService::from_str("other").is_err());
+}
+
+#[tokio::test]
+async fn registry_runs_custom_providers_and_skips_unregistered() {
+    let internal = LyricsService::custom("internal-server");
+    let registry = LyricsProviderRegistry::empty();
+    registry.register(Arc::new(StaticProvider {
+        service: internal,
+        lyrics: vec!["[00:01.00]a", "[00:01.00]b"],
+    }));
+    // 同一服务后注册的覆盖先注册的。
+    registry.register(Arc::new(StaticProvider {
+        service: internal,
+        lyrics: vec!["[00:01.00]replaced"],
+    }));
+    assert_eq!(registry.services(), [internal]);
+
+    let opt = LyricsSearchOptions {
+        services: vec![LyricsService::Netease, internal],
+        ..LyricsSearchOptions::default()
+    };
+    let http = reqwest::Client::new();
+    let out = search_with_registry(&http, &req(), opt.clone(), &registry, None)
+        .await
+        .expect("search");
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].service, internal);
+    assert_eq!(out[0].lyrics_original, "[00:01.00]replaced");
+    assert!(out[0].matched);
+    assert_eq!(out[0].match_percentage, 100);
+
+    assert!(registry.unregister(internal));
+    let out = search_with_registry(&http, &req(), opt, &registry, None)
+        .await
+        .expect("search");
+    assert!(out.is_empty());
+}
+
+#[test]
+fn builtin_registry_contains_all_services() {
+    let registry = LyricsProviderRegistry::with_builtins();
+    assert_eq!(registry.services(), LyricsService::BUILTIN);
+    let err = futures::executor::block_on(registry.get(LyricsService::Kugou).unwrap().fetch(
+        &reqwest::Client::new(),
+        LyricsToken::new(1u8),
+        &req(),
+        Duration::from_secs(1),
+    ))
+    .expect_err("mismatched token");
+    assert!(matches!(err, LyricsError::Parse(_)));
+}
+
+#[tokio::test]
+async fn report_lists_status_and_counts_per_provider() {
+    let ok = LyricsService::custom("report-ok");
+    let broken = LyricsService::custom("report-broken");
+    let slow = LyricsService::custom("report-slow");
+    let missing = LyricsService::custom("report-missing");
+    let registry = LyricsProviderRegistry::empty();
+    registry.register(Arc::new(StaticProvider {
+        service: ok,
+        lyrics: vec!["[00:01.00]a", "[00:01.00]b", "[00:01.00]c"],
+    }));
+    registry.register(Arc::new(BrokenProvider {
+        service: broken,
+        hang: false,
+    }));
+    registry.register(Arc::new(BrokenProvider {
+        service: slow,
+        hang: true,
+    }));
+
+    let mut r = req();
+    r.limit = 2;
+    let opt = LyricsSearchOptions {
+        services: vec![slow, ok, missing, broken],
+        timeout_ms: 50,
+        ..LyricsSearchOptions::default()
+    };
+    let (out, report) =
+        search_with_registry_report(&reqwest::Client::new(), &r, opt, &registry, None)
+            .await
+            .expect("search");
+    assert_eq!(out.len(), 2);
+    assert!(!report.cache_hit);
+    assert_eq!(report.returned, 2);
+    assert_eq!(
+        report
+            .providers
+            .iter()
+            .map(|p| (p.service, p.status))
+            .collect::<Vec<_>>(),
+        [
+            (slow, LyricsProviderStatus::Timeout),
+            (ok, LyricsProviderStatus::Ok),
+            (missing, LyricsProviderStatus::NotRegistered),
+            (broken, LyricsProviderStatus::Parse),
+        ]
+    );
+    let ok_report = &report.providers[1];
+    assert_eq!(ok_report.candidates, 3);
+    assert_eq!(ok_report.fetched, 2);
+    assert_eq!(ok_report.matched, 2);
+    assert_eq!(ok_report.filtered, 0);
+    assert!(report.providers[0].latency_ms >= 50);
+    assert!(
+        report.providers[3]
+            .error
+            .as_deref()
+            .is_some_and(|e| e.contains("unexpected payload"))
+    );
+}
+
+#[tokio::test]
+async fn stream_yields_each_provider_before_final_results() {
+    let fast = LyricsService::custom("stream-fast");
+    let slow = LyricsService::custom("stream-slow");
+    let registry = LyricsProviderRegistry::empty();
+    registry.register(Arc::new(StaticProvider {
+        service: fast,
+        lyrics: vec!["[00:01.00]fast"],
+    }));
+    registry.register(Arc::new(SlowProvider {
+        inner: StaticProvider {
+            service: slow,
+            lyrics: vec!["[00:01.00]slow"],
+        },
+        delay: Duration::from_millis(100),
+    }));
+
+    let opt = LyricsSearchOptions {
+        services: vec![slow, fast],
+        ..LyricsSearchOptions::default()
+    };
+    let events = search_stream_with_registry(reqwest::Client::new(), req(), opt, &registry, None)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(events.len(), 3);
+    match &events[0] {
+        LyricsSearchEvent::Provider { report, results } => {
+            assert_eq!(report.service, fast);
+            assert_eq!(results[0].lyrics_original, "[00:01.00]fast");
+            assert_eq!(results[0].match_percentage, 100);
+        }
+        other => panic!("unexpected event: {other:?}"),
+    }
+    assert!(
+        matches!(&events[1], LyricsSearchEvent::Provider { report, .. } if report.service == slow)
+    );
+    match &events[2] {
+        LyricsSearchEvent::Done { results, report } => {
+            // 同分时按 `services` 顺序排列。
+            assert_eq!(
+                results.iter().map(|r| r.service).collect::<Vec<_>>(),
+                [slow, fast]
+            );
+            assert_eq!(report.returned, 2);
+            assert_eq!(
+                report
+                    .providers
+                    .iter()
+                    .map(|p| p.service)
+                    .collect::<Vec<_>>(),
+                [slow, fast]
+            );
+        }
+        other => panic!("unexpected event: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn exact_lookup_runs_before_fuzzy_search() {
+    let svc = LyricsService::custom("isrc-server");
+    let registry = LyricsProviderRegistry::empty();
+    registry.register(Arc::new(IsrcProvider {
+        inner: StaticProvider {
+            service: svc,
+            lyrics: vec!["[00:01.00]fuzzy"],
+        },
+    }));
+    let opt = LyricsSearchOptions {
+        services: vec![svc],
+        ..LyricsSearchOptions::default()
+    };
+    let http = reqwest::Client::new();
+    let search = |r: LyricsSearchRequest, opt: LyricsSearchOptions| {
+        let (http, registry) = (&http, &registry);
+        async move {
+            search_with_registry_report(http, &r, opt, registry, None)
+                .await
+                .expect("search")
+        }
+    };
+
+    // 精确命中时不再模糊搜索。
+    let mut by_isrc = req();
+    by_isrc.isrc = Some("GBBKS1500214".to_string());
+    let (out, report) = search(by_isrc, opt.clone()).await;
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].lyrics_original, "[00:01.00]exact");
+    assert!(report.providers[0].exact);
+    assert_eq!(report.providers[0].candidates, 1);
+
+    // 没有精确结果、或精确查找出错时回退到模糊搜索。
+    let (out, report) = search(req(), opt.clone()).await;
+    assert_eq!(out[0].lyrics_original, "[00:01.00]fuzzy");
+    assert!(!report.providers[0].exact);
+    let mut bad = req();
+    bad.isrc = Some("XX0000000000".to_string());
+    let (out, report) = search(bad.clone(), opt.clone()).await;
+    assert_eq!(out[0].lyrics_original, "[00:01.00]fuzzy");
+    assert_eq!(report.providers[0].status, LyricsProviderStatus::Ok);
+
+    // exact_only：不回退，精确查找的错误如实报告。
+    let exact_only = LyricsSearchOptions {
+        exact_only: true,
+        ..opt
+    };
+    let (out, report) = search(req(), exact_only.clone()).await;
+    assert!(out.is_empty());
+    assert_eq!(report.providers[0].status, LyricsProviderStatus::Ok);
+    let (out, report) = search(bad, exact_only).await;
+    assert!(out.is_empty());
+    assert_eq!(report.providers[0].status, LyricsProviderStatus::Parse);
+}
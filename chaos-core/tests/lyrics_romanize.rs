@@ -0,0 +1,184 @@
+use chaos_core::llm::{LlmClient, LlmConfig};
+use chaos_core::lyrics::model::{LyricsSearchResult, LyricsService};
+use chaos_core::lyrics::parse::parse;
+use chaos_core::lyrics::romanize::{
+    RomanizeOptions, Script, detect_script, romanize_lyrics, romanize_text,
+};
+use chaos_core::lyrics::translate::{translate_missing_lines, translate_result};
+use httpmock::Method::POST;
+use httpmock::MockServer;
+
+fn rom(text: &str) -> Option<String> {
+    romanize_text(text, RomanizeOptions::default())
+}
+
+#[test]
+fn detects_script_per_line() {
+    assert_eq!(detect_script("君の名は"), Some(Script::Japanese));
+    assert_eq!(detect_script("사랑해 baby"), Some(Script::Korean));
+    assert_eq!(detect_script("晴天"), Some(Script::Chinese));
+    assert_eq!(detect_script("Hello, world"), None);
+    assert_eq!(rom("Hello, world"), None);
+}
+
+#[test]
+fn kana_to_hepburn() {
+    assert_eq!(rom("さくら").as_deref(), Some("sakura"));
+    assert_eq!(rom("きょう しゃしん").as_deref(), Some("kyou shashin"));
+    assert_eq!(rom("ちょっと まって").as_deref(), Some("chotto matte"));
+    assert_eq!(rom("マッチ").as_deref(), Some("matchi"));
+    assert_eq!(rom("コーヒー").as_deref(), Some("koohii"));
+    assert_eq!(
+        rom("パーティー フォー ユー").as_deref(),
+        Some("paatii foo yuu")
+    );
+    assert_eq!(rom("きんえん").as_deref(), Some("kin'en"));
+    // 汉字没有读音词典，原样保留；假名连写，不做分词。
+    assert_eq!(rom("君のこと、好き").as_deref(), Some("君 nokoto、好 ki"));
+}
+
+#[test]
+fn hangul_to_revised_romanization() {
+    assert_eq!(rom("안녕하세요").as_deref(), Some("annyeonghaseyo"));
+    assert_eq!(rom("사랑해 baby").as_deref(), Some("saranghae baby"));
+    // 连音：收音移到下一音节。
+    assert_eq!(rom("한국어").as_deref(), Some("hangugeo"));
+    assert_eq!(rom("읽어").as_deref(), Some("ilgeo"));
+    assert_eq!(rom("설날").as_deref(), Some("seollal"));
+    assert_eq!(rom("신라").as_deref(), Some("silla"));
+    assert_eq!(rom("밥").as_deref(), Some("bap"));
+}
+
+#[test]
+fn han_to_pinyin() {
+    assert_eq!(rom("晴天").as_deref(), Some("qíng tiān"));
+    assert_eq!(rom("我爱你 baby!").as_deref(), Some("wǒ ài nǐ baby!"));
+    let plain = RomanizeOptions {
+        pinyin_tone_marks: false,
+    };
+    assert_eq!(romanize_text("晴天", plain).as_deref(), Some("qing tian"));
+}
+
+#[test]
+fn romanized_lyrics_keep_timeline() {
+    let lyrics = parse(
+        "[offset:200]\n[00:01.00]さくら\n[00:03.00]Hello\n[00:05.00]<00:05.00>晴<00:05.50>天<00:06.00>",
+    );
+    let r = romanize_lyrics(&lyrics, RomanizeOptions::default());
+    assert_eq!(r.lines.len(), lyrics.lines.len());
+    for (a, b) in r.lines.iter().zip(&lyrics.lines) {
+        assert_eq!((a.start_ms, a.end_ms), (b.start_ms, b.end_ms));
+    }
+    assert_eq!(r.lines[0].text, "sakura");
+    assert_eq!(r.lines[1].text, "Hello");
+    assert_eq!(r.lines[2].words[0].text, "qíng ");
+    assert_eq!(r.lines[2].words[1].text, "tiān");
+    assert_eq!(r.metadata.offset_ms, 200);
+    assert!(r.to_lrc().contains("[00:01.00]sakura\n"));
+}
+
+fn llm(server: &MockServer) -> LlmClient {
+    LlmClient::new(LlmConfig {
+        base_url: server.base_url(),
+        api_key: "sk-test".to_string(),
+        model: "m".to_string(),
+        timeout_ms: 5_000,
+        ..LlmConfig::default()
+    })
+    .expect("client")
+}
+
+#[tokio::test]
+async fn llm_fills_only_missing_translation_lines() {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/chat/completions")
+            .body_contains("Target language: English")
+            .body_contains(r#"\"0\":\"さくら\""#)
+            .body_contains(r#"\"3\":\"君が好き\""#);
+        then.status(200).json_body(serde_json::json!({
+            "choices": [{ "message": { "content":
+                "```json\n{\"0\": \"cherry blossoms\", \"1\": \"ignored\", \"3\": \"I love you\", \"9\": \"x\"}\n```"
+            } }]
+        }));
+    });
+
+    let mut lyrics = parse("[00:01.00]さくら\n[00:02.00]\n[00:03.00]夢\n[00:04.00]君が好き");
+    lyrics.lines[2].translation = Some("dream".to_string());
+    let filled = translate_missing_lines(&llm(&server), &mut lyrics, "English")
+        .await
+        .expect("translate");
+    assert_eq!(m.hits(), 1);
+    assert_eq!(filled, 2);
+    let tr: Vec<_> = lyrics
+        .lines
+        .iter()
+        .map(|l| l.translation.as_deref())
+        .collect();
+    assert_eq!(
+        tr,
+        [
+            Some("cherry blossoms"),
+            None,
+            Some("dream"),
+            Some("I love you")
+        ]
+    );
+}
+
+#[tokio::test]
+async fn llm_translation_is_written_back_to_search_result() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/chat/completions");
+        then.status(200).json_body(serde_json::json!({
+            "choices": [{ "message": { "content": "{\"1\": \"晴天\"}" } }]
+        }));
+    });
+
+    let mut result = LyricsSearchResult {
+        service: LyricsService::LrcLib,
+        service_token: "1".to_string(),
+        title: None,
+        artist: None,
+        album: None,
+        duration_ms: None,
+        match_percentage: 0,
+        quality: 0.0,
+        matched: false,
+        has_translation: true,
+        has_inline_timetags: true,
+        lyrics_original: "[offset:300]\n[00:01.00]Rain\n[00:02.00]Sunny day".to_string(),
+        lyrics_translation: Some("[offset:300]\n[00:01.00]雨".to_string()),
+        offset_ms: 0,
+        debug: None,
+    };
+    let filled = translate_result(&llm(&server), &mut result, "简体中文")
+        .await
+        .expect("translate");
+    assert_eq!(filled, 1);
+    assert_eq!(
+        result.lyrics_translation.as_deref(),
+        Some("[offset:300]\n[00:01.00]雨\n[00:02.00]晴天\n")
+    );
+    let parsed = result.parsed_lyrics();
+    assert_eq!(parsed.lines[0].translation.as_deref(), Some("雨"));
+    assert_eq!(parsed.lines[1].translation.as_deref(), Some("晴天"));
+
+    // 回复不是 JSON 时报错，结果保持不变。
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/chat/completions");
+        then.status(200).json_body(serde_json::json!({
+            "choices": [{ "message": { "content": "sorry" } }]
+        }));
+    });
+    let mut lyrics = parse("[00:01.00]Rain");
+    assert!(
+        translate_missing_lines(&llm(&server), &mut lyrics, "简体中文")
+            .await
+            .is_err()
+    );
+    assert!(lyrics.lines[0].translation.is_none());
+}
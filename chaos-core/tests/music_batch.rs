@@ -0,0 +1,193 @@
+use std::time::Duration;
+
+use chaos_core::music::batch::{
+    BatchItem, DownloadEvent, DownloadJob, DownloadJobOptions, choose_quality_id,
+};
+use chaos_core::music::client::MusicClient;
+use chaos_core::music::model::{
+    AuthState, MusicDownloadOptions, MusicDownloadTarget, MusicJobState, MusicService, MusicTrack,
+    ProviderConfig,
+};
+use httpmock::prelude::*;
+
+mod common;
+use common::quality;
+
+fn track(id: &str, title: &str) -> MusicTrack {
+    MusicTrack {
+        album: Some("Album".to_string()),
+        ..common::track(MusicService::Netease, id, title, &["Artist"])
+    }
+}
+
+fn setup(server: &MockServer) -> (MusicClient, AuthState) {
+    let cfg = ProviderConfig {
+        netease_base_urls: vec![server.base_url()],
+        ..ProviderConfig::default()
+    };
+    let auth = AuthState {
+        netease_cookie: Some("MUSIC_U=x".to_string()),
+        ..AuthState::default()
+    };
+    (MusicClient::new(cfg).expect("client"), auth)
+}
+
+fn mock_track(server: &MockServer, id: &str) {
+    let audio = server.url(format!("/audio/{id}.mp3"));
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/song/download/url")
+            .body_contains(format!("\"id\":\"{id}\""));
+        then.status(200)
+            .json_body(serde_json::json!({ "code": 200, "data": { "url": audio, "type": "mp3" } }));
+    });
+}
+
+fn options(out_dir: &std::path::Path, concurrency: u32) -> DownloadJobOptions {
+    let mut o = DownloadJobOptions::new(MusicDownloadOptions {
+        quality_id: "mp3_320".to_string(),
+        out_dir: out_dir.to_string_lossy().to_string(),
+        path_template: Some("{{title}}.{{ext}}".to_string()),
+        overwrite: false,
+        concurrency,
+        retries: 0,
+        priority: 0,
+        fallback_services: vec![],
+        fallback_min_score: None,
+        skip_owned: false,
+        post_process: None,
+    });
+    o.fetch_lyrics = false;
+    o.write_tags = false;
+    o
+}
+
+#[test]
+fn quality_falls_back_to_best_available() {
+    let mut t = track("1", "x");
+    assert_eq!(choose_quality_id(&t, "flac"), None);
+    t.qualities = vec![
+        quality("mp3_128", None, false),
+        quality("mp3_320", None, false),
+    ];
+    assert_eq!(choose_quality_id(&t, "mp3_128").as_deref(), Some("mp3_128"));
+    assert_eq!(choose_quality_id(&t, "flac").as_deref(), Some("mp3_320"));
+    assert_eq!(choose_quality_id(&t, " "), None);
+}
+
+#[tokio::test]
+async fn downloads_skips_and_fails_with_events() {
+    let server = MockServer::start();
+    mock_track(&server, "1");
+    mock_track(&server, "3");
+    server.mock(|when, then| {
+        when.method(GET).path("/audio/1.mp3");
+        then.status(200).body("audio-one");
+    });
+    // id=2 没有任何 mock：所有地址接口 404 -> job 失败。
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Three.mp3"), b"old").unwrap();
+
+    let (client, auth) = setup(&server);
+    let items = [("1", "One"), ("2", "Two"), ("3", "Three")]
+        .into_iter()
+        .map(|(id, title)| BatchItem {
+            track: track(id, title),
+            track_no: None,
+        })
+        .collect();
+    let job = DownloadJob::spawn(client, auth, items, options(dir.path(), 2));
+    let mut rx = job.subscribe();
+
+    let st = tokio::time::timeout(Duration::from_secs(10), job.wait())
+        .await
+        .expect("job finished");
+    assert!(st.done);
+    assert!(job.is_done());
+    assert_eq!(st.totals.total, 3);
+    assert_eq!(
+        (st.totals.done, st.totals.failed, st.totals.skipped),
+        (1, 1, 1)
+    );
+    assert_eq!(st.jobs[0].state, MusicJobState::Done);
+    assert_eq!(st.jobs[0].bytes, Some(9));
+    assert_eq!(
+        std::fs::read(dir.path().join("One.mp3")).unwrap(),
+        b"audio-one"
+    );
+    assert_eq!(st.jobs[1].state, MusicJobState::Failed);
+    assert!(st.jobs[1].error.is_some());
+    assert_eq!(st.jobs[2].state, MusicJobState::Skipped);
+    assert_eq!(std::fs::read(dir.path().join("Three.mp3")).unwrap(), b"old");
+
+    let mut finished_jobs = 0;
+    let mut finished = None;
+    while let Ok(ev) = rx.try_recv() {
+        match ev {
+            DownloadEvent::Job(j) if j.state != MusicJobState::Running => finished_jobs += 1,
+            DownloadEvent::Job(_) => {}
+            DownloadEvent::Finished(t) => finished = Some(t),
+        }
+    }
+    assert_eq!(finished_jobs, 3);
+    assert_eq!(finished, Some(st.totals));
+}
+
+#[tokio::test]
+async fn cancel_marks_pending_jobs() {
+    let server = MockServer::start();
+    mock_track(&server, "1");
+    server.mock(|when, then| {
+        when.method(GET).path("/audio/1.mp3");
+        then.status(200).delay(Duration::from_secs(5)).body("slow");
+    });
+
+    let dir = tempfile::tempdir().unwrap();
+    let (client, auth) = setup(&server);
+    let items = vec![
+        BatchItem {
+            track: track("1", "One"),
+            track_no: Some(1),
+        },
+        BatchItem {
+            track: track("2", "Two"),
+            track_no: Some(2),
+        },
+    ];
+    let job = DownloadJob::spawn(client, auth, items, options(dir.path(), 1));
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(job.status().jobs[0].state, MusicJobState::Running);
+
+    job.cancel();
+    let st = job.wait().await;
+    assert!(st.done);
+    assert_eq!(st.totals.canceled, 2);
+    assert!(st.jobs.iter().all(|j| j.state == MusicJobState::Canceled));
+}
+
+#[tokio::test]
+async fn start_validates_options_and_resolves_track_target() {
+    let server = MockServer::start();
+    let (client, auth) = setup(&server);
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut bad = options(dir.path(), 1);
+    bad.download.out_dir = " ".to_string();
+    let target = MusicDownloadTarget::Track {
+        track: Box::new(track("9", "Nine")),
+    };
+    assert!(
+        DownloadJob::start(client.clone(), target.clone(), auth.clone(), bad)
+            .await
+            .is_err()
+    );
+
+    let job = DownloadJob::start(client, target, auth, options(dir.path(), 1))
+        .await
+        .expect("start");
+    let st = job.wait().await;
+    assert_eq!(st.totals.total, 1);
+    assert_eq!(st.jobs[0].track_id.as_deref(), Some("9"));
+    assert_eq!(st.jobs[0].state, MusicJobState::Failed);
+}
@@ -0,0 +1,231 @@
+use std::time::Duration;
+
+use chaos_core::music::client::MusicClient;
+use chaos_core::music::error::MusicError;
+use chaos_core::music::model::{AuthState, MusicService, ProviderConfig, QqMusicCookie};
+use chaos_core::music::providers::{kugou, kuwo, qq};
+use httpmock::Method::{GET, POST};
+use httpmock::MockServer;
+use serde_json::json;
+
+#[tokio::test]
+async fn netease_charts_and_daily_recommendations() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/toplist");
+        then.status(200).json_body(json!({
+            "code": 200,
+            "list": [
+                { "id": 3778678, "name": "热歌榜", "coverImgUrl": "http://c", "description": "", "updateFrequency": "每周四更新" },
+                { "id": 0, "name": "" }
+            ]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/playlist/track/all")
+            .body_contains("\"id\":\"3778678\"");
+        then.status(200).json_body(json!({
+            "code": 200,
+            "songs": [{ "id": 1, "name": "Song", "ar": [{ "id": 2, "name": "A" }], "al": { "id": 3, "name": "Al" }, "dt": 1000 }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/recommend/songs")
+            .header("Cookie", "MUSIC_U=x");
+        then.status(200).json_body(json!({
+            "code": 200,
+            "data": { "dailySongs": [{ "id": 9, "name": "Daily", "ar": [], "al": {}, "dt": 2000 }] }
+        }));
+    });
+
+    let client = MusicClient::new(ProviderConfig {
+        netease_base_urls: vec![server.base_url()],
+        ..ProviderConfig::default()
+    })
+    .expect("client");
+
+    let charts = client
+        .list_charts(MusicService::Netease)
+        .await
+        .expect("charts");
+    assert_eq!(charts.len(), 1);
+    assert_eq!(charts[0].id, "3778678");
+    assert_eq!(charts[0].description, None);
+    assert_eq!(charts[0].update_frequency.as_deref(), Some("每周四更新"));
+
+    let tracks = client
+        .chart_tracks(MusicService::Netease, "3778678")
+        .await
+        .expect("chart tracks");
+    assert_eq!(tracks.len(), 1);
+    assert_eq!(tracks[0].artists, ["A"]);
+
+    let err = client
+        .daily_recommendations(MusicService::Netease, &AuthState::default())
+        .await
+        .expect_err("no cookie");
+    assert!(matches!(err, MusicError::Unauthorized(_)));
+    let auth = AuthState {
+        netease_cookie: Some("MUSIC_U=x".to_string()),
+        ..Default::default()
+    };
+    let daily = client
+        .daily_recommendations(MusicService::Netease, &auth)
+        .await
+        .expect("daily");
+    assert_eq!(daily[0].id, "9");
+
+    let err = client
+        .daily_recommendations(MusicService::Kuwo, &auth)
+        .await
+        .expect_err("kuwo");
+    assert!(matches!(err, MusicError::UnsupportedService(_)));
+}
+
+#[tokio::test]
+async fn qq_toplist_detail_and_daily_tracks() {
+    let server = MockServer::start();
+    let base_url = format!("{}/cgi-bin/musicu.fcg", server.base_url());
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/cgi-bin/musicu.fcg")
+            .body_contains("\"method\":\"GetAll\"");
+        then.status(200).json_body(json!({
+            "req": { "code": 0, "data": { "group": [
+                { "groupName": "巅峰榜", "toplist": [
+                    { "topId": 62, "title": "飙升榜", "frontPicUrl": "http://f", "intro": "", "updateTips": "每天更新" },
+                    { "topId": 26, "title": "热歌榜", "headPicUrl": "http://h" }
+                ] }
+            ] } }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/cgi-bin/musicu.fcg")
+            .body_contains("\"method\":\"GetDetail\"")
+            .body_contains("\"topId\":62");
+        then.status(200).json_body(json!({
+            "req": { "code": 0, "data": { "songInfoList": [
+                { "mid": "m1", "name": "Up", "singer": [{ "name": "S", "mid": "s" }], "album": { "name": "A", "mid": "a", "pmid": "" }, "interval": 100, "file": { "size_320mp3": 1 } }
+            ] } }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/cgi-bin/musicu.fcg")
+            .body_contains("get_daily_track")
+            .body_contains("\"authst\":\"key\"");
+        then.status(200).json_body(json!({
+            "req": { "code": 0, "data": { "tracks": [
+                { "mid": "d1", "name": "Daily", "singer": [], "album": {}, "interval": 10, "file": {} }
+            ] } }
+        }));
+    });
+
+    let http = reqwest::Client::new();
+    let timeout = Duration::from_secs(5);
+    let charts = qq::list_charts_with_base(&http, &base_url, timeout)
+        .await
+        .expect("charts");
+    assert_eq!(charts.len(), 2);
+    assert_eq!(charts[0].update_frequency.as_deref(), Some("每天更新"));
+    assert_eq!(charts[1].cover_url.as_deref(), Some("http://h"));
+
+    let tracks = qq::chart_tracks_with_base(&http, &base_url, "62", timeout)
+        .await
+        .expect("tracks");
+    assert_eq!(tracks[0].id, "m1");
+    assert!(
+        qq::chart_tracks_with_base(&http, &base_url, "abc", timeout)
+            .await
+            .is_err()
+    );
+
+    let auth = AuthState {
+        qq: Some(QqMusicCookie {
+            musicid: Some("123".to_string()),
+            musickey: Some("key".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let daily = qq::daily_recommendations_with_base(&http, &base_url, &auth, timeout)
+        .await
+        .expect("daily");
+    assert_eq!(daily[0].id, "d1");
+}
+
+#[tokio::test]
+async fn kugou_and_kuwo_rank_lists() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/kugou/rank/list");
+        then.status(200).json_body(json!({
+            "status": 1,
+            "data": { "info": [{ "rankid": 8888, "rankname": "TOP500", "imgurl": "http://i/{size}/x.jpg", "intro": "热度", "update_frequency": "每天" }] }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/kugou/rank/song")
+            .query_param("rankid", "8888");
+        then.status(200).json_body(json!({
+            "status": 1,
+            "data": { "info": [{ "hash": "H1", "filename": "歌手A、歌手B - 歌名", "duration": 200, "filesize": 1, "320filesize": 2, "sqfilesize": 0, "album_id": "0" }] }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/kuwo/bang/list");
+        then.status(200).json_body(json!({
+            "child": [{ "sourceid": "93", "name": "酷我飙升榜", "pic": "http://p", "intro": "", "info": "每日更新" }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/kuwo/bang/song").query_param("id", "93");
+        then.status(200).json_body(json!({
+            "musiclist": [{ "id": "228908", "name": "晴天", "artist": "周杰伦", "artistid": "336", "album": "叶惠美", "albumid": "1", "MINFO": "level:hh,bitrate:320,format:mp3,size:9Mb" }]
+        }));
+    });
+
+    let http = reqwest::Client::new();
+    let timeout = Duration::from_secs(5);
+    let base = server.base_url();
+
+    let charts =
+        kugou::list_charts_with_template(&http, &format!("{base}/kugou/rank/list"), timeout)
+            .await
+            .expect("kugou charts");
+    assert_eq!(charts[0].id, "8888");
+    assert_eq!(charts[0].cover_url.as_deref(), Some("http://i/480/x.jpg"));
+    let tracks = kugou::chart_tracks_with_template(
+        &http,
+        &format!("{base}/kugou/rank/song?rankid={{id}}"),
+        "8888",
+        timeout,
+    )
+    .await
+    .expect("kugou tracks");
+    assert_eq!(tracks[0].title, "歌名");
+    assert_eq!(tracks[0].artists, ["歌手A", "歌手B"]);
+    assert_eq!(tracks[0].album_id, None);
+    assert_eq!(tracks[0].duration_ms, Some(200_000));
+    assert_eq!(tracks[0].qualities.len(), 2);
+
+    let charts = kuwo::list_charts_with_template(&http, &format!("{base}/kuwo/bang/list"), timeout)
+        .await
+        .expect("kuwo charts");
+    assert_eq!(charts[0].id, "93");
+    assert_eq!(charts[0].description, None);
+    let tracks = kuwo::chart_tracks_with_template(
+        &http,
+        &format!("{base}/kuwo/bang/song?id={{id}}"),
+        "93",
+        timeout,
+    )
+    .await
+    .expect("kuwo tracks");
+    assert_eq!(tracks[0].id, "228908");
+    assert_eq!(tracks[0].qualities[0].id, "mp3_320");
+}
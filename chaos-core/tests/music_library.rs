@@ -0,0 +1,238 @@
+use chaos_core::music::batch::{BatchItem, DownloadEvent, DownloadJob, DownloadJobOptions};
+use chaos_core::music::client::MusicClient;
+use chaos_core::music::library::{LibraryIndex, LibraryMatch};
+use chaos_core::music::model::{
+    AuthState, MusicDownloadOptions, MusicJobState, MusicService, MusicTrack, ProviderConfig,
+};
+use chaos_core::music::tagging::{TrackTags, write_tags};
+use httpmock::prelude::*;
+
+mod common;
+use common::{alac_fixture, flac_fixture, mp3_fixture, quality};
+
+fn track(id: &str, title: &str, artist: &str) -> MusicTrack {
+    MusicTrack {
+        qualities: vec![
+            quality("mp3_128", Some(128), false),
+            quality("mp3_320", Some(320), false),
+            quality("flac", Some(2000), true),
+        ],
+        ..common::track(MusicService::Netease, id, title, &[artist])
+    }
+}
+
+/// 默认布局（无标签）、`歌手 - 标题` 文件名、任意文件名 + 标签。
+fn library_dir() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    mp3_fixture(&dir.path().join("周杰伦/叶惠美/03 - 晴天.mp3"));
+    mp3_fixture(&dir.path().join("Adele - Someone Like You.mp3"));
+    let tagged = dir.path().join("misc/track01.flac");
+    flac_fixture(&tagged);
+    let tags = TrackTags {
+        title: "Hello".to_string(),
+        artists: vec!["Adele".to_string()],
+        album: Some("25".to_string()),
+        ..TrackTags::default()
+    };
+    write_tags(&tagged, &tags, None).unwrap();
+    std::fs::write(dir.path().join("misc/notes.txt"), "not audio").unwrap();
+    dir
+}
+
+#[test]
+fn scan_reads_tags_and_file_names() {
+    let dir = library_dir();
+    let index = LibraryIndex::scan(dir.path()).unwrap();
+    assert_eq!(index.len(), 3);
+
+    let by_title = |t: &str| index.entries().iter().find(|e| e.title == t).unwrap();
+    let qingtian = by_title("晴天");
+    assert_eq!(qingtian.artists, ["周杰伦"]);
+    assert_eq!(qingtian.album.as_deref(), Some("叶惠美"));
+    assert_eq!(qingtian.bitrate_kbps, Some(128));
+    assert!(!qingtian.from_tags && !qingtian.lossless);
+
+    let someone = by_title("Someone Like You");
+    assert_eq!(someone.artists, ["Adele"]);
+    assert_eq!(someone.album, None);
+
+    let hello = by_title("Hello");
+    assert!(hello.from_tags && hello.lossless);
+    assert_eq!(hello.artists, ["Adele"]);
+    assert_eq!(hello.album.as_deref(), Some("25"));
+    assert_eq!(hello.format, "flac");
+
+    assert!(
+        LibraryIndex::scan(&dir.path().join("missing"))
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn check_reports_owned_upgrade_and_missing() {
+    let dir = library_dir();
+    let index = LibraryIndex::scan(dir.path()).unwrap();
+
+    let qingtian = track("1", "晴天", "周杰伦");
+    assert!(matches!(
+        index.check(&qingtian, Some("mp3_128")),
+        LibraryMatch::Owned(e) if e.format == "mp3"
+    ));
+    match index.check(&qingtian, None) {
+        LibraryMatch::UpgradeAvailable { entry, quality } => {
+            assert!(entry.path.ends_with("03 - 晴天.mp3"));
+            assert_eq!(quality.id, "flac");
+        }
+        other => panic!("expected upgrade, got {other:?}"),
+    }
+    assert!(matches!(
+        index.check(&qingtian, Some("mp3_320")),
+        LibraryMatch::UpgradeAvailable { .. }
+    ));
+
+    // 已有 flac：任何音质都不算升级。
+    assert!(matches!(
+        index.check(&track("2", "Hello", "Adele"), None),
+        LibraryMatch::Owned(e) if e.from_tags
+    ));
+    // 同名不同歌手 / 不存在的歌曲。
+    assert!(matches!(
+        index.check(&track("3", "Hello", "Lionel Richie"), None),
+        LibraryMatch::Missing
+    ));
+    assert!(matches!(
+        index.check(&track("4", "稻香", "周杰伦"), None),
+        LibraryMatch::Missing
+    ));
+}
+
+#[test]
+fn alac_m4a_counts_as_lossless() {
+    let dir = tempfile::tempdir().unwrap();
+    alac_fixture(&dir.path().join("Adele - Hello.m4a"));
+    let index = LibraryIndex::scan(dir.path()).unwrap();
+    let hello = &index.entries()[0];
+    assert_eq!(hello.format, "m4a");
+    assert!(hello.lossless);
+    assert!(matches!(
+        index.check(&track("2", "Hello", "Adele"), None),
+        LibraryMatch::Owned(_)
+    ));
+}
+
+#[tokio::test]
+async fn batch_skip_owned_uses_library_index() {
+    let dir = library_dir();
+    let client = MusicClient::new(ProviderConfig {
+        netease_base_urls: vec!["http://127.0.0.1:9".to_string()],
+        ..ProviderConfig::default()
+    })
+    .unwrap();
+    let mut options = DownloadJobOptions::new(MusicDownloadOptions {
+        quality_id: "flac".to_string(),
+        out_dir: dir.path().to_string_lossy().to_string(),
+        path_template: None,
+        overwrite: false,
+        concurrency: 1,
+        retries: 0,
+        priority: 0,
+        fallback_services: vec![],
+        fallback_min_score: None,
+        skip_owned: true,
+        post_process: None,
+    });
+    options.fetch_lyrics = false;
+    options.write_tags = false;
+    let items = vec![
+        BatchItem {
+            track: track("10", "Hello", "Adele"),
+            track_no: None,
+        },
+        BatchItem {
+            track: track("11", "晴天", "周杰伦"),
+            track_no: None,
+        },
+    ];
+
+    let st = DownloadJob::spawn(client, AuthState::default(), items, options)
+        .wait()
+        .await;
+    let owned = &st.jobs[0];
+    assert_eq!(owned.state, MusicJobState::Skipped);
+    assert_eq!(owned.error.as_deref(), Some("skipped: already in library"));
+    assert!(owned.path.as_deref().unwrap().ends_with("track01.flac"));
+    // 只有 mp3，请求 flac -> 仍尝试下载（此处无可用源，失败）。
+    assert_eq!(st.jobs[1].state, MusicJobState::Failed);
+    assert_eq!(st.totals.skipped, 1);
+}
+
+#[tokio::test]
+async fn batch_upgrade_replaces_library_file() {
+    let server = MockServer::start();
+    let audio = server.url("/audio/5.flac");
+    server.mock(|when, then| {
+        when.method(POST).path("/song/download/url");
+        then.status(200).json_body(
+            serde_json::json!({ "code": 200, "data": { "url": audio, "type": "flac" } }),
+        );
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/audio/5.flac");
+        then.status(200).body("flac-audio");
+    });
+
+    let dir = library_dir();
+    let client = MusicClient::new(ProviderConfig {
+        netease_base_urls: vec![server.base_url()],
+        ..ProviderConfig::default()
+    })
+    .unwrap();
+    let auth = AuthState {
+        netease_cookie: Some("MUSIC_U=x".to_string()),
+        ..AuthState::default()
+    };
+    let mut options = DownloadJobOptions::new(MusicDownloadOptions {
+        quality_id: "flac".to_string(),
+        out_dir: dir.path().to_string_lossy().to_string(),
+        path_template: Some("{{artist}} - {{title}}.{{ext}}".to_string()),
+        overwrite: false,
+        concurrency: 1,
+        retries: 0,
+        priority: 0,
+        fallback_services: vec![],
+        fallback_min_score: None,
+        skip_owned: true,
+        post_process: None,
+    });
+    options.fetch_lyrics = false;
+    options.write_tags = false;
+    let items = vec![BatchItem {
+        track: track("5", "Someone Like You", "Adele"),
+        track_no: None,
+    }];
+
+    let job = DownloadJob::spawn(client, auth, items, options);
+    let mut rx = job.subscribe();
+    let st = job.wait().await;
+    let j = &st.jobs[0];
+    assert_eq!(j.state, MusicJobState::Done, "{:?}", j.error);
+    let old = dir.path().join("Adele - Someone Like You.mp3");
+    assert_eq!(j.upgraded_from.as_deref(), Some(&*old.to_string_lossy()));
+    assert!(!old.exists());
+    assert_eq!(
+        std::fs::read(dir.path().join("Adele - Someone Like You.flac")).unwrap(),
+        b"flac-audio"
+    );
+
+    // 开始运行时的事件即带上 upgradedFrom。
+    let mut running = None;
+    while let Ok(ev) = rx.try_recv() {
+        if let DownloadEvent::Job(j) = ev
+            && j.state == MusicJobState::Running
+        {
+            running = Some(j);
+        }
+    }
+    assert!(running.unwrap().upgraded_from.is_some());
+}
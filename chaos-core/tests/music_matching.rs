@@ -0,0 +1,209 @@
+use chaos_core::music::batch::{BatchItem, DownloadJob, DownloadJobOptions};
+use chaos_core::music::client::MusicClient;
+use chaos_core::music::matching::{MatchOptions, find_alternatives, match_score, search_keyword};
+use chaos_core::music::model::{
+    AuthState, MusicDownloadOptions, MusicJobState, MusicService, MusicTrack, ProviderConfig,
+};
+use httpmock::prelude::*;
+
+mod common;
+
+fn track(
+    service: MusicService,
+    id: &str,
+    title: &str,
+    artists: &[&str],
+    album: Option<&str>,
+    duration_ms: Option<u64>,
+) -> MusicTrack {
+    MusicTrack {
+        album: album.map(|s| s.to_string()),
+        duration_ms,
+        ..common::track(service, id, title, artists)
+    }
+}
+
+fn source() -> MusicTrack {
+    track(
+        MusicService::Qq,
+        "qqmid",
+        "晴天",
+        &["周杰伦"],
+        Some("叶惠美"),
+        Some(269_000),
+    )
+}
+
+fn netease_song(id: i64, name: &str, artist: &str, album: &str, dt: i64) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": name,
+        "dt": dt,
+        "ar": [{ "id": 1, "name": artist }],
+        "al": { "id": 2, "name": album, "picUrl": "" }
+    })
+}
+
+fn mock_search(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/cloudsearch");
+        then.status(200).json_body(serde_json::json!({
+            "code": 200,
+            "result": { "songs": [
+                netease_song(54, "晴天 (Live)", "周杰伦", "2004 无与伦比演唱会", 301_000),
+                netease_song(55, "晴天", "周杰伦", "叶惠美", 269_500),
+                netease_song(56, "雨天", "孙燕姿", "Stefanie", 250_000),
+            ] }
+        }));
+    });
+}
+
+fn client(server: &MockServer) -> MusicClient {
+    MusicClient::new(ProviderConfig {
+        netease_base_urls: vec![server.base_url()],
+        ..ProviderConfig::default()
+    })
+    .expect("client")
+}
+
+#[test]
+fn score_prefers_same_song() {
+    let src = source();
+    let same = track(
+        MusicService::Netease,
+        "1",
+        "晴天",
+        &["周杰伦"],
+        Some("叶惠美"),
+        Some(270_000),
+    );
+    let live = track(
+        MusicService::Netease,
+        "2",
+        "晴天 (Live)",
+        &["周杰伦"],
+        Some("2004 无与伦比演唱会"),
+        Some(301_000),
+    );
+    let other = track(MusicService::Netease, "3", "雨天", &["孙燕姿"], None, None);
+    assert!(match_score(&src, &same) >= 95);
+    assert!(match_score(&src, &live) < match_score(&src, &same));
+    assert!(match_score(&src, &other) < 60);
+
+    // 歌手顺序、大小写与标点不影响；缺失时长/专辑不拉低分数。
+    let a = track(
+        MusicService::Kuwo,
+        "a",
+        "Hello, World",
+        &["A", "B"],
+        None,
+        None,
+    );
+    let b = track(
+        MusicService::Kugou,
+        "b",
+        "hello world",
+        &["b", "a"],
+        Some("X"),
+        Some(1000),
+    );
+    assert_eq!(match_score(&a, &b), 100);
+    assert_eq!(search_keyword(&a), "Hello, World A B");
+}
+
+#[tokio::test]
+async fn finds_best_alternative_above_min_score() {
+    let server = MockServer::start();
+    mock_search(&server);
+    let client = client(&server);
+
+    let opt = MatchOptions {
+        services: vec![MusicService::Qq, MusicService::Netease],
+        ..MatchOptions::default()
+    };
+    let alts = find_alternatives(&client, &source(), &opt).await;
+    assert_eq!(alts.len(), 1);
+    assert_eq!(alts[0].track.service, MusicService::Netease);
+    assert_eq!(alts[0].track.id, "55");
+
+    let strict = MatchOptions {
+        services: vec![MusicService::Netease],
+        min_score: 101,
+        ..MatchOptions::default()
+    };
+    assert!(
+        find_alternatives(&client, &source(), &strict)
+            .await
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn batch_falls_back_to_matched_source() {
+    let server = MockServer::start();
+    mock_search(&server);
+    let audio = server.url("/audio/55.mp3");
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/song/download/url")
+            .body_contains("\"id\":\"55\"");
+        then.status(200)
+            .json_body(serde_json::json!({ "code": 200, "data": { "url": audio, "type": "mp3" } }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/audio/55.mp3");
+        then.status(200).body("netease-audio");
+    });
+
+    let dir = tempfile::tempdir().unwrap();
+    let download = MusicDownloadOptions {
+        quality_id: "mp3_320".to_string(),
+        out_dir: dir.path().to_string_lossy().to_string(),
+        path_template: Some("{{artist}} - {{title}}.{{ext}}".to_string()),
+        overwrite: false,
+        concurrency: 1,
+        retries: 0,
+        priority: 0,
+        fallback_services: vec![MusicService::Netease],
+        fallback_min_score: None,
+        skip_owned: false,
+        post_process: None,
+    };
+    let mut options = DownloadJobOptions::new(download);
+    options.fetch_lyrics = false;
+    options.write_tags = false;
+
+    // QQ 未登录 -> 取地址失败 -> 回退到网易云的匹配结果。
+    let auth = AuthState {
+        netease_cookie: Some("MUSIC_U=x".to_string()),
+        ..AuthState::default()
+    };
+    let items = vec![BatchItem {
+        track: source(),
+        track_no: None,
+    }];
+    let job = DownloadJob::spawn(
+        client(&server),
+        auth.clone(),
+        items.clone(),
+        options.clone(),
+    );
+    let st = job.wait().await;
+    let j = &st.jobs[0];
+    assert_eq!(j.state, MusicJobState::Done, "{:?}", j.error);
+    assert_eq!(j.source_service, Some(MusicService::Netease));
+    assert_eq!(j.source_track_id.as_deref(), Some("55"));
+    assert_eq!(
+        std::fs::read(dir.path().join("周杰伦 - 晴天.mp3")).unwrap(),
+        b"netease-audio"
+    );
+
+    // 不配置回退源时保持原来的失败行为。
+    options.download.fallback_services.clear();
+    options.download.overwrite = true;
+    let st = DownloadJob::spawn(client(&server), auth, items, options)
+        .wait()
+        .await;
+    assert_eq!(st.jobs[0].state, MusicJobState::Failed);
+    assert_eq!(st.jobs[0].source_service, None);
+}
@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use base64::Engine;
+use chaos_core::music::error::MusicError;
+use chaos_core::music::model::ProviderConfig;
+use chaos_core::music::providers::netease::{
+    NeteaseQrStatus, account_info, netease_qr_create, netease_qr_poll, normalize_cookie,
+    refresh_cookie,
+};
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn cfg(server: &MockServer) -> ProviderConfig {
+    ProviderConfig {
+        netease_base_urls: vec![server.base_url()],
+        ..ProviderConfig::default()
+    }
+}
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[test]
+fn cookie_attributes_are_dropped() {
+    assert_eq!(
+        normalize_cookie(
+            "MUSIC_U=abc; Max-Age=1296000; Expires=Sat, 01 Jan 2000 00:00:00 GMT; Path=/;;__csrf=x; HTTPOnly;MUSIC_U=def"
+        ),
+        "MUSIC_U=def; __csrf=x"
+    );
+}
+
+#[tokio::test]
+async fn qr_create_renders_login_url() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/login/qr/key");
+        then.status(200)
+            .json_body(json!({ "code": 200, "data": { "code": 200, "unikey": "uk-1" } }));
+    });
+
+    let http = reqwest::Client::new();
+    let qr = netease_qr_create(&http, &cfg(&server), TIMEOUT)
+        .await
+        .expect("qr");
+    assert_eq!(qr.key, "uk-1");
+    assert_eq!(qr.url, "https://music.163.com/login?codekey=uk-1");
+    let png = base64::engine::general_purpose::STANDARD
+        .decode(qr.image_base64)
+        .unwrap();
+    assert!(png.starts_with(b"\x89PNG"));
+}
+
+#[tokio::test]
+async fn qr_poll_maps_codes() {
+    let server = MockServer::start();
+    for (key, body) in [
+        (
+            "k800",
+            json!({ "code": 800, "message": "二维码不存在或已过期" }),
+        ),
+        ("k801", json!({ "code": 801, "message": "等待扫码" })),
+        ("k802", json!({ "code": 802, "nickname": "bob" })),
+        (
+            "k803",
+            json!({ "code": 803, "cookie": "MUSIC_U=u1; Max-Age=100; Path=/;;__csrf=c1; Path=/" }),
+        ),
+        (
+            "k8821",
+            json!({ "code": 8821, "message": "需要行为验证码验证" }),
+        ),
+    ] {
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/login/qr/check")
+                .body_contains(format!("\"key\":\"{key}\""));
+            then.status(200).json_body(body);
+        });
+    }
+
+    let http = reqwest::Client::new();
+    let cfg = cfg(&server);
+    let poll = |key: &'static str| {
+        let http = http.clone();
+        let cfg = cfg.clone();
+        async move { netease_qr_poll(&http, &cfg, key, TIMEOUT).await.unwrap() }
+    };
+    assert_eq!(poll("k800").await, NeteaseQrStatus::Expired);
+    assert_eq!(poll("k801").await, NeteaseQrStatus::Waiting);
+    assert_eq!(
+        poll("k802").await,
+        NeteaseQrStatus::Scanned {
+            nickname: Some("bob".to_string())
+        }
+    );
+    assert_eq!(
+        poll("k803").await,
+        NeteaseQrStatus::Done {
+            cookie: "MUSIC_U=u1; __csrf=c1".to_string()
+        }
+    );
+    assert_eq!(
+        poll("k8821").await,
+        NeteaseQrStatus::Other {
+            code: 8821,
+            message: Some("需要行为验证码验证".to_string())
+        }
+    );
+
+    let err = netease_qr_poll(&http, &cfg, " ", TIMEOUT)
+        .await
+        .expect_err("empty key");
+    assert!(matches!(err, MusicError::InvalidInput(_)));
+}
+
+#[tokio::test]
+async fn account_info_rejects_anonymous_cookie() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/user/account")
+            .header("Cookie", "MUSIC_U=u1");
+        then.status(200).json_body(json!({
+            "code": 200,
+            "account": { "id": 77, "vipType": 11 },
+            "profile": { "userId": 77, "nickname": "bob", "avatarUrl": "http://img/a.jpg" }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/user/account")
+            .header("Cookie", "MUSIC_A=anon");
+        then.status(200).json_body(json!({
+            "code": 200,
+            "account": { "id": 5, "anonimousUser": true },
+            "profile": null
+        }));
+    });
+
+    let http = reqwest::Client::new();
+    let cfg = cfg(&server);
+    let user = account_info(&http, &cfg, "MUSIC_U=u1", TIMEOUT)
+        .await
+        .expect("account");
+    assert_eq!(user.user_id, "77");
+    assert_eq!(user.nickname.as_deref(), Some("bob"));
+    assert_eq!(user.avatar_url.as_deref(), Some("http://img/a.jpg"));
+    assert_eq!(user.vip_type, 11);
+
+    let err = account_info(&http, &cfg, "MUSIC_A=anon", TIMEOUT)
+        .await
+        .expect_err("anonymous");
+    assert!(matches!(err, MusicError::Unauthorized(_)));
+    let err = account_info(&http, &cfg, "", TIMEOUT)
+        .await
+        .expect_err("empty");
+    assert!(matches!(err, MusicError::Unauthorized(_)));
+}
+
+#[tokio::test]
+async fn refresh_cookie_merges_new_values() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/login/refresh")
+            .header("Cookie", "MUSIC_U=old; __csrf=c1");
+        then.status(200)
+            .json_body(json!({ "code": 200, "cookie": "MUSIC_U=new; Max-Age=100; Path=/" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/login/refresh")
+            .header("Cookie", "MUSIC_U=gone");
+        then.status(200).json_body(json!({ "code": 301 }));
+    });
+
+    let http = reqwest::Client::new();
+    let cfg = cfg(&server);
+    let cookie = refresh_cookie(&http, &cfg, "MUSIC_U=old; __csrf=c1", TIMEOUT)
+        .await
+        .expect("refresh");
+    assert_eq!(cookie, "MUSIC_U=new; __csrf=c1");
+
+    let err = refresh_cookie(&http, &cfg, "MUSIC_U=gone", TIMEOUT)
+        .await
+        .expect_err("expired");
+    assert!(matches!(err, MusicError::Unauthorized(_)));
+}
@@ -0,0 +1,327 @@
+use std::time::Duration;
+
+use chaos_core::music::batch::resolve_target;
+use chaos_core::music::client::MusicClient;
+use chaos_core::music::error::MusicError;
+use chaos_core::music::model::{
+    AuthState, MusicDownloadTarget, MusicService, ProviderConfig, QqMusicCookie,
+};
+use chaos_core::music::providers::{kugou, kuwo, qq};
+use httpmock::Method::{GET, POST};
+use httpmock::MockServer;
+use serde_json::{Value, json};
+
+fn netease_client(server: &MockServer) -> MusicClient {
+    MusicClient::new(ProviderConfig {
+        netease_base_urls: vec![server.base_url()],
+        ..ProviderConfig::default()
+    })
+    .expect("client")
+}
+
+fn netease_songs(ids: std::ops::Range<i64>) -> Vec<Value> {
+    ids.map(|id| json!({ "id": id, "name": format!("song {id}"), "ar": [{ "id": 1, "name": "A" }], "al": { "id": 2, "name": "Al" }, "dt": 1000 }))
+        .collect()
+}
+
+fn qq_songs(range: std::ops::Range<u32>) -> Vec<Value> {
+    range
+        .map(|i| json!({ "mid": format!("mid{i}"), "name": format!("song {i}"), "singer": [{ "name": "S", "mid": "smid" }], "album": { "name": "Al", "mid": "amid", "pmid": "" }, "interval": 200 }))
+        .collect()
+}
+
+#[tokio::test]
+async fn netease_search_playlists_maps_fields() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/cloudsearch")
+            .body_contains("\"type\":1000");
+        then.status(200).json_body(json!({
+            "code": 200,
+            "result": { "playlists": [{
+                "id": 42,
+                "name": "Chill",
+                "coverImgUrl": "http://img/1.jpg",
+                "trackCount": 1200,
+                "creator": { "nickname": "bob", "userId": 7 },
+                "description": ""
+            }] }
+        }));
+    });
+
+    let out = netease_client(&server)
+        .search_playlists(MusicService::Netease, "chill", 1, 20)
+        .await
+        .expect("search");
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].id, "42");
+    assert_eq!(out[0].title, "Chill");
+    assert_eq!(out[0].creator.as_deref(), Some("bob"));
+    assert_eq!(out[0].creator_id.as_deref(), Some("7"));
+    assert_eq!(out[0].track_count, Some(1200));
+    assert_eq!(out[0].description, None);
+}
+
+#[tokio::test]
+async fn netease_search_playlists_offset_uses_clamped_limit() {
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/cloudsearch")
+            .body_contains("\"limit\":50")
+            .body_contains("\"offset\":100");
+        then.status(200)
+            .json_body(json!({ "code": 200, "result": { "playlists": [] } }));
+    });
+
+    let out = netease_client(&server)
+        .search_playlists(MusicService::Netease, "chill", 3, 100)
+        .await
+        .expect("search");
+    m.assert();
+    assert!(out.is_empty());
+}
+
+#[tokio::test]
+async fn netease_playlist_tracks_follows_pages() {
+    let server = MockServer::start();
+    let first = server.mock(|when, then| {
+        when.method(POST)
+            .path("/playlist/track/all")
+            .header("Cookie", "MUSIC_U=x")
+            .body_contains("\"offset\":0");
+        then.status(200)
+            .json_body(json!({ "code": 200, "songs": netease_songs(0..500) }));
+    });
+    let second = server.mock(|when, then| {
+        when.method(POST)
+            .path("/playlist/track/all")
+            .body_contains("\"offset\":500");
+        then.status(200)
+            .json_body(json!({ "code": 200, "songs": netease_songs(500..502) }));
+    });
+
+    let auth = AuthState {
+        netease_cookie: Some("MUSIC_U=x".to_string()),
+        ..AuthState::default()
+    };
+    let tracks = netease_client(&server)
+        .playlist_tracks(MusicService::Netease, "9", &auth)
+        .await
+        .expect("tracks");
+    first.assert();
+    second.assert();
+    assert_eq!(tracks.len(), 502);
+    assert_eq!(tracks[501].id, "501");
+}
+
+#[tokio::test]
+async fn netease_user_playlists_requires_login() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/user/account");
+        then.status(200)
+            .json_body(json!({ "code": 200, "account": { "id": 77 } }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/user/playlist")
+            .body_contains("\"uid\":77");
+        then.status(200).json_body(json!({
+            "code": 200,
+            "playlist": [
+                { "id": 1, "name": "我喜欢的音乐", "trackCount": 3, "creator": { "nickname": "me", "userId": 77 } },
+                { "id": 2, "name": "Fav", "trackCount": 10 }
+            ]
+        }));
+    });
+
+    let client = netease_client(&server);
+    let err = client
+        .user_playlists(MusicService::Netease, &AuthState::default())
+        .await
+        .expect_err("no cookie");
+    assert!(matches!(err, MusicError::Unauthorized(_)));
+
+    let auth = AuthState {
+        netease_cookie: Some("MUSIC_U=x".to_string()),
+        ..AuthState::default()
+    };
+    let out = client
+        .user_playlists(MusicService::Netease, &auth)
+        .await
+        .expect("playlists");
+    assert_eq!(
+        out.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(),
+        ["1", "2"]
+    );
+    assert_eq!(out[0].creator.as_deref(), Some("me"));
+}
+
+#[tokio::test]
+async fn playlist_target_resolves_unique_tracks_without_track_no() {
+    let server = MockServer::start();
+    let mut songs = netease_songs(0..3);
+    songs.push(songs[0].clone());
+    server.mock(|when, then| {
+        when.method(POST).path("/playlist/track/all");
+        then.status(200)
+            .json_body(json!({ "code": 200, "songs": songs }));
+    });
+
+    let target = MusicDownloadTarget::Playlist {
+        service: MusicService::Netease,
+        playlist_id: "9".to_string(),
+    };
+    let items = resolve_target(&netease_client(&server), target, &AuthState::default())
+        .await
+        .expect("resolve");
+    assert_eq!(
+        items
+            .iter()
+            .map(|i| i.track.id.as_str())
+            .collect::<Vec<_>>(),
+        ["0", "1", "2"]
+    );
+    assert!(items.iter().all(|i| i.track_no.is_none()));
+}
+
+#[tokio::test]
+async fn qq_playlist_tracks_follows_total() {
+    let server = MockServer::start();
+    let base_url = format!("{}/cgi-bin/musicu.fcg", server.base_url());
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/cgi-bin/musicu.fcg")
+            .body_contains("\"song_begin\":0");
+        then.status(200).json_body(json!({
+            "req": { "code": 0, "data": { "total_song_num": 501, "songlist": qq_songs(0..500) } }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/cgi-bin/musicu.fcg")
+            .body_contains("\"song_begin\":500");
+        then.status(200).json_body(json!({
+            "req": { "code": 0, "data": { "total_song_num": 501, "songlist": qq_songs(500..501) } }
+        }));
+    });
+
+    let http = reqwest::Client::new();
+    let timeout = Duration::from_secs(5);
+    let tracks = qq::playlist_tracks_with_base(&http, &base_url, "7039123", timeout)
+        .await
+        .expect("tracks");
+    assert_eq!(tracks.len(), 501);
+    assert_eq!(tracks[500].id, "mid500");
+    assert_eq!(tracks[0].artists, ["S"]);
+    assert_eq!(tracks[0].duration_ms, Some(200_000));
+
+    let err = qq::playlist_tracks_with_base(&http, &base_url, "abc", timeout)
+        .await
+        .expect_err("non-numeric id");
+    assert!(matches!(err, MusicError::InvalidInput(_)));
+}
+
+#[tokio::test]
+async fn qq_user_playlists_uses_cookie_uin() {
+    let server = MockServer::start();
+    let base_url = format!("{}/cgi-bin/musicu.fcg", server.base_url());
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/cgi-bin/musicu.fcg")
+            .body_contains("GetPlaylistByUin")
+            .body_contains("\"authst\":\"key\"");
+        then.status(200).json_body(json!({
+            "req": { "code": 0, "data": { "v_playlist": [
+                { "tid": 0, "dirName": "placeholder" },
+                { "tid": 8001, "dirName": "Mine", "songNum": 12, "picUrl": "" }
+            ] } }
+        }));
+    });
+
+    let http = reqwest::Client::new();
+    let timeout = Duration::from_secs(5);
+    let err = qq::user_playlists_with_base(&http, &base_url, &AuthState::default(), timeout)
+        .await
+        .expect_err("no cookie");
+    assert!(matches!(err, MusicError::Unauthorized(_)));
+
+    let auth = AuthState {
+        qq: Some(QqMusicCookie {
+            musicid: Some("123".to_string()),
+            musickey: Some("key".to_string()),
+            nick: Some("me".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let out = qq::user_playlists_with_base(&http, &base_url, &auth, timeout)
+        .await
+        .expect("playlists");
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].id, "8001");
+    assert_eq!(out[0].track_count, Some(12));
+    assert_eq!(out[0].creator_id.as_deref(), Some("123"));
+    assert_eq!(out[0].cover_url, None);
+}
+
+#[tokio::test]
+async fn kuwo_playlist_tracks_follows_pages() {
+    let server = MockServer::start();
+    let page = |range: std::ops::Range<u32>| -> Vec<Value> {
+        range
+            .map(|i| json!({ "id": i, "name": format!("song {i}"), "artist": "A&B", "album": "Al", "albumid": "5", "duration": "180", "N_MINFO": "level:p,bitrate:320,format:mp3,size:1" }))
+            .collect()
+    };
+    server.mock(|when, then| {
+        when.method(GET).path("/pl.svc").query_param("pn", "0");
+        then.status(200)
+            .json_body(json!({ "total": 501, "musiclist": page(0..500) }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/pl.svc").query_param("pn", "1");
+        then.status(200)
+            .json_body(json!({ "total": "501", "musiclist": page(500..501) }));
+    });
+
+    let template = format!(
+        "{}/pl.svc?pid={{pid}}&pn={{pn}}&rn={{rn}}",
+        server.base_url()
+    );
+    let http = reqwest::Client::new();
+    let tracks =
+        kuwo::playlist_tracks_with_template(&http, &template, "3567", Duration::from_secs(5))
+            .await
+            .expect("tracks");
+    assert_eq!(tracks.len(), 501);
+    assert_eq!(tracks[500].id, "500");
+    assert_eq!(tracks[0].artists, ["A", "B"]);
+    assert_eq!(tracks[0].duration_ms, Some(180_000));
+    assert_eq!(tracks[0].qualities[0].id, "mp3_320");
+}
+
+#[tokio::test]
+async fn playlist_calls_reject_missing_input_or_login() {
+    let http = reqwest::Client::new();
+    let cfg = ProviderConfig::default();
+    let auth = AuthState::default();
+    let timeout = Duration::from_secs(1);
+
+    let err = kugou::playlist_tracks(&http, &cfg, " ", &auth, timeout)
+        .await
+        .expect_err("empty id");
+    assert!(matches!(err, MusicError::InvalidInput(_)));
+    let err = kugou::user_playlists(&http, &cfg, &auth, timeout)
+        .await
+        .expect_err("no login");
+    assert!(matches!(err, MusicError::Unauthorized(_)));
+
+    let client = MusicClient::new(cfg).expect("client");
+    let err = client
+        .user_playlists(MusicService::Kuwo, &auth)
+        .await
+        .expect_err("kuwo login unsupported");
+    assert!(matches!(err, MusicError::UnsupportedService(_)));
+}
@@ -0,0 +1,180 @@
+use std::path::Path;
+use std::time::Duration;
+
+use chaos_core::music::batch::{BatchItem, DownloadJob, DownloadJobOptions};
+use chaos_core::music::client::MusicClient;
+use chaos_core::music::model::{
+    AuthState, MusicDownloadOptions, MusicJobState, MusicPostProcessOptions, MusicService,
+    MusicTrack, MusicTranscodeCodec, MusicTranscodeOptions, ProviderConfig,
+};
+use chaos_core::music::postprocess::{
+    Loudness, codec_args, parse_ebur128_summary, transcode_target,
+};
+use httpmock::Method::{GET, POST};
+use httpmock::MockServer;
+
+mod common;
+use common::quality;
+
+const EBUR128_LOG: &str = "\
+Input #0, flac, from 'a.flac':
+[Parsed_ebur128_0 @ 0x55d0] Summary:
+
+  Integrated loudness:
+    I:         -9.6 LUFS
+    Threshold: -19.9 LUFS
+
+  Loudness range:
+    LRA:         4.1 LU
+    Threshold: -29.9 LUFS
+    LRA low:   -12.5 LUFS
+    LRA high:   -8.4 LUFS
+
+  True peak:
+    Peak:        0.4 dBFS
+";
+
+fn transcode(codec: MusicTranscodeCodec) -> MusicTranscodeOptions {
+    MusicTranscodeOptions {
+        codec,
+        bitrate_kbps: None,
+        source_formats: vec![],
+        keep_source: false,
+    }
+}
+
+#[test]
+fn parses_ebur128_summary_into_replay_gain() {
+    let l = parse_ebur128_summary(EBUR128_LOG).expect("summary");
+    assert_eq!(
+        l,
+        Loudness {
+            integrated_lufs: -9.6,
+            true_peak_dbfs: 0.4,
+        }
+    );
+    let rg = l.replay_gain();
+    assert!((rg.track_gain_db - -8.4).abs() < 1e-9);
+    assert!((rg.track_peak - 1.047_128).abs() < 1e-6);
+
+    let silent = parse_ebur128_summary("Summary:\n    I:  -70.0 LUFS\n    Peak:  -inf dBFS\n")
+        .expect("silent");
+    assert_eq!(silent.replay_gain().track_peak, 0.0);
+    assert_eq!(parse_ebur128_summary("no summary here"), None);
+}
+
+#[test]
+fn transcode_target_respects_codec_and_source_formats() {
+    let mp3 = transcode(MusicTranscodeCodec::Mp3);
+    assert_eq!(transcode_target(Path::new("d/a.mp3"), &mp3), None);
+    assert_eq!(
+        transcode_target(Path::new("d/a.ogg"), &mp3).as_deref(),
+        Some(Path::new("d/a.mp3"))
+    );
+
+    let only_ogg = MusicTranscodeOptions {
+        source_formats: vec![".OGG".to_string()],
+        ..transcode(MusicTranscodeCodec::Aac)
+    };
+    assert_eq!(
+        transcode_target(Path::new("a.ogg"), &only_ogg).as_deref(),
+        Some(Path::new("a.m4a"))
+    );
+    assert_eq!(transcode_target(Path::new("a.flac"), &only_ogg), None);
+
+    assert_eq!(codec_args(&mp3), ["-c:a", "libmp3lame", "-b:a", "320k"]);
+    let aac = MusicTranscodeOptions {
+        bitrate_kbps: Some(192),
+        ..transcode(MusicTranscodeCodec::Aac)
+    };
+    assert_eq!(codec_args(&aac), ["-c:a", "aac", "-b:a", "192k"]);
+    assert_eq!(
+        codec_args(&transcode(MusicTranscodeCodec::Flac)),
+        ["-c:a", "flac"]
+    );
+}
+
+fn track(id: &str, title: &str) -> MusicTrack {
+    MusicTrack {
+        qualities: vec![quality("flac", Some(2000), true)],
+        ..common::track(MusicService::Netease, id, title, &[])
+    }
+}
+
+#[tokio::test]
+async fn batch_transcode_skips_existing_target_and_cleans_up_on_failure() {
+    let server = MockServer::start();
+    for id in ["1", "2"] {
+        let audio = server.url(format!("/audio/{id}.flac"));
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/song/download/url")
+                .body_contains(format!("\"id\":\"{id}\""));
+            then.status(200).json_body(
+                serde_json::json!({ "code": 200, "data": { "url": audio, "type": "flac" } }),
+            );
+        });
+    }
+    server.mock(|when, then| {
+        when.method(GET).path("/audio/2.flac");
+        then.status(200).body("flac-two");
+    });
+
+    let dir = tempfile::tempdir().unwrap();
+    // 转码目标已存在 -> 跳过（不下载源文件）。
+    std::fs::write(dir.path().join("One.mp3"), b"old").unwrap();
+
+    let client = MusicClient::new(ProviderConfig {
+        netease_base_urls: vec![server.base_url()],
+        ..ProviderConfig::default()
+    })
+    .expect("client");
+    let auth = AuthState {
+        netease_cookie: Some("MUSIC_U=x".to_string()),
+        ..AuthState::default()
+    };
+    let items = [("1", "One"), ("2", "Two")]
+        .into_iter()
+        .map(|(id, title)| BatchItem {
+            track: track(id, title),
+            track_no: None,
+        })
+        .collect();
+    let mut options = DownloadJobOptions::new(MusicDownloadOptions {
+        quality_id: "flac".to_string(),
+        out_dir: dir.path().to_string_lossy().to_string(),
+        path_template: Some("{{title}}.{{ext}}".to_string()),
+        overwrite: false,
+        concurrency: 1,
+        retries: 0,
+        priority: 0,
+        fallback_services: vec![],
+        fallback_min_score: None,
+        skip_owned: false,
+        post_process: Some(MusicPostProcessOptions {
+            ffmpeg_path: dir
+                .path()
+                .join("missing-ffmpeg")
+                .to_string_lossy()
+                .to_string(),
+            transcode: Some(transcode(MusicTranscodeCodec::Mp3)),
+            replay_gain: true,
+        }),
+    });
+    options.fetch_lyrics = false;
+    options.write_tags = false;
+
+    let job = DownloadJob::spawn(client, auth, items, options);
+    let st = tokio::time::timeout(Duration::from_secs(10), job.wait())
+        .await
+        .expect("job finished");
+    assert_eq!(st.jobs[0].state, MusicJobState::Skipped);
+    assert!(st.jobs[0].path.as_deref().unwrap().ends_with("One.mp3"));
+    assert!(!dir.path().join("One.flac").exists());
+
+    // ffmpeg 不可用 -> 失败，且不留下源文件与半成品。
+    assert_eq!(st.jobs[1].state, MusicJobState::Failed);
+    assert!(st.jobs[1].error.as_deref().unwrap().contains("ffmpeg"));
+    assert!(!dir.path().join("Two.flac").exists());
+    assert!(!dir.path().join("Two.mp3").exists());
+}
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
-use chaos_core::music::model::{AuthState, QqMusicCookie};
+use chaos_core::music::error::MusicError;
+use chaos_core::music::model::{AuthState, KuwoUserInfo, QqMusicCookie};
 use chaos_core::music::providers::{kuwo, qq};
 use httpmock::Method::{GET, POST};
 use httpmock::MockServer;
@@ -78,10 +79,152 @@ async fn kuwo_download_url_parses_response() {
         &template,
         "123",
         "mp3_320",
+        &AuthState::default(),
         Duration::from_secs(5),
+        true,
     )
     .await
     .expect("download url");
     assert_eq!(ext, "mp3");
     assert_eq!(url, "http://example/1.mp3");
 }
+
+#[tokio::test]
+async fn kuwo_flac_requires_entitled_account() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/mobi.s")
+            .query_param("rid", "456")
+            .query_param("br", "320kmp3");
+        then.status(200).json_body(serde_json::json!({
+            "data": { "url": "http://example/2.mp3", "format": "mp3", "bitrate": "128" }
+        }));
+    });
+    // 匿名：接口静默降级为 320k mp3。
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/mobi.s")
+            .query_param("user", "0")
+            .query_param("br", "2000kflac");
+        then.status(200).json_body(serde_json::json!({
+            "data": { "url": "http://example/1.mp3", "format": "mp3", "bitrate": 320 }
+        }));
+    });
+    let vip = server.mock(|when, then| {
+        when.method(GET)
+            .path("/mobi.s")
+            .query_param("user", "42")
+            .query_param("loginUid", "42")
+            .query_param("loginSid", "s/1")
+            .query_param("br", "2000kflac");
+        then.status(200).json_body(serde_json::json!({
+            "data": { "url": "http://example/1.flac", "format": "flac", "bitrate": 2000 }
+        }));
+    });
+
+    let template = format!("{}/mobi.s?user=0&rid={{rid}}&br={{br}}", server.base_url());
+    let http = reqwest::Client::new();
+    let timeout = Duration::from_secs(5);
+    let err = kuwo::track_download_url_with_template(
+        &http,
+        &template,
+        "123",
+        "flac",
+        &AuthState::default(),
+        timeout,
+        true,
+    )
+    .await
+    .expect_err("anonymous flac");
+    assert!(
+        matches!(&err, MusicError::NotEntitled(msg) if msg.contains("login required")),
+        "{err}"
+    );
+
+    let auth = AuthState {
+        kuwo: Some(KuwoUserInfo {
+            uid: "42".to_string(),
+            sid: "s/1".to_string(),
+            nickname: None,
+            vip_type: 1,
+        }),
+        ..AuthState::default()
+    };
+    let (url, ext) = kuwo::track_download_url_with_template(
+        &http, &template, "123", "flac", &auth, timeout, true,
+    )
+    .await
+    .expect("vip flac");
+    vip.assert();
+    assert_eq!(ext, "flac");
+    assert_eq!(url, "http://example/1.flac");
+
+    // 320k 被降级为 128k 同样视为无权限。
+    let err = kuwo::track_download_url_with_template(
+        &http,
+        &template,
+        "456",
+        "mp3_320",
+        &AuthState::default(),
+        timeout,
+        true,
+    )
+    .await
+    .expect_err("downgraded mp3");
+    assert!(matches!(err, MusicError::NotEntitled(_)), "{err}");
+
+    // 播放（非严格）：直接使用降级后的地址。
+    let (url, ext) = kuwo::track_download_url_with_template(
+        &http,
+        &template,
+        "123",
+        "flac",
+        &AuthState::default(),
+        timeout,
+        false,
+    )
+    .await
+    .expect("play url");
+    assert_eq!(ext, "mp3");
+    assert_eq!(url, "http://example/1.mp3");
+}
+
+#[tokio::test]
+async fn kuwo_login_with_token_reads_vip_info() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/vip")
+            .query_param("uid", "42")
+            .query_param("sid", "good");
+        then.status(200).json_body(serde_json::json!({
+            "meta": { "code": 200 },
+            "data": { "nickname": "me", "vipType": "2" }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/vip").query_param("sid", "bad");
+        then.status(200)
+            .json_body(serde_json::json!({ "meta": { "code": 1001, "desc": "sid expired" } }));
+    });
+
+    let template = format!("{}/vip?uid={{uid}}&sid={{sid}}", server.base_url());
+    let http = reqwest::Client::new();
+    let timeout = Duration::from_secs(5);
+    let user = kuwo::login_with_token_template(&http, &template, " 42 ", "good", timeout)
+        .await
+        .expect("login");
+    assert_eq!(user.uid, "42");
+    assert_eq!(user.nickname.as_deref(), Some("me"));
+    assert_eq!(user.vip_type, 2);
+
+    let err = kuwo::login_with_token_template(&http, &template, "42", "bad", timeout)
+        .await
+        .expect_err("expired");
+    assert!(matches!(&err, MusicError::Unauthorized(msg) if msg.contains("sid expired")));
+    let err = kuwo::login_with_token_template(&http, &template, "42", "", timeout)
+        .await
+        .expect_err("empty sid");
+    assert!(matches!(err, MusicError::InvalidInput(_)));
+}
@@ -0,0 +1,261 @@
+use std::path::Path;
+use std::time::Duration;
+
+use chaos_core::music::tagging::{
+    ReplayGain, TagFormat, TrackTags, tag_file, write_replay_gain, write_tags,
+};
+use httpmock::prelude::*;
+use lofty::config::ParseOptions;
+use lofty::file::AudioFile;
+use lofty::flac::FlacFile;
+use lofty::id3::v2::{Frame, FrameId, Id3v2Tag, SynchronizedTextFrame};
+use lofty::mp4::{AtomIdent, Mp4File};
+use lofty::mpeg::MpegFile;
+use lofty::ogg::OggPictureStorage;
+use lofty::tag::{Accessor, Tag};
+
+mod common;
+use common::{atom, flac_fixture, mp3_fixture};
+
+// 1x1 PNG
+const PNG: &[u8] = &[
+    0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1F, 0x15, 0xC4,
+    0x89, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0x00, 0x01, 0x00, 0x00,
+    0x05, 0x00, 0x01, 0x0D, 0x0A, 0x2D, 0xB4, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE,
+    0x42, 0x60, 0x82,
+];
+
+const LRC: &str = "[ar:Someone]\n[00:01.00]first line\n[00:02.50][00:04.00]chorus\n";
+
+fn m4a_fixture(path: &Path) {
+    let mut mvhd = vec![0u8; 100];
+    mvhd[12..16].copy_from_slice(&1000u32.to_be_bytes());
+    mvhd[16..20].copy_from_slice(&1000u32.to_be_bytes());
+    let mut buf = atom(b"ftyp", b"M4A \0\0\0\0M4A isom");
+    buf.extend(atom(b"moov", &atom(b"mvhd", &mvhd)));
+    buf.extend(atom(b"mdat", &[0; 16]));
+    std::fs::write(path, buf).unwrap();
+}
+
+fn tags() -> TrackTags {
+    TrackTags {
+        title: "Song".to_string(),
+        artists: vec!["A".to_string(), "B".to_string()],
+        album: Some("Album".to_string()),
+        track_no: Some(3),
+        lyrics: Some(LRC.to_string()),
+    }
+}
+
+fn apic_count(tag: &Id3v2Tag) -> usize {
+    Tag::from(tag.clone()).pictures().len()
+}
+
+fn opts() -> ParseOptions {
+    ParseOptions::new().read_properties(false)
+}
+
+fn read_id3v2(path: &Path) -> Id3v2Tag {
+    let mut f = std::fs::File::open(path).unwrap();
+    let file = MpegFile::read_from(&mut f, opts()).unwrap();
+    file.id3v2().expect("id3v2").clone()
+}
+
+#[test]
+fn id3v2_lyrics_frames_follow_lrc_timeline() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a.mp3");
+    mp3_fixture(&path);
+    write_tags(&path, &tags(), None).unwrap();
+
+    let tag = read_id3v2(&path);
+    let Some(Frame::UnsynchronizedText(uslt)) = tag.get(&FrameId::new("USLT").unwrap()) else {
+        panic!("missing USLT");
+    };
+    assert_eq!(uslt.content, "first line\nchorus\nchorus");
+    let Some(Frame::Binary(bin)) = tag.get(&FrameId::new("SYLT").unwrap()) else {
+        panic!("missing SYLT");
+    };
+    let sylt = SynchronizedTextFrame::parse(&bin.data, bin.flags()).unwrap();
+    assert_eq!(
+        sylt.content,
+        vec![
+            (1000, "first line".to_string()),
+            (2500, "chorus".to_string()),
+            (4000, "chorus".to_string()),
+        ]
+    );
+
+    // 没有时间轴时只写纯文本 USLT。
+    let plain = TrackTags {
+        lyrics: Some(" plain ".to_string()),
+        ..tags()
+    };
+    write_tags(&path, &plain, None).unwrap();
+    let tag = read_id3v2(&path);
+    let Some(Frame::UnsynchronizedText(uslt)) = tag.get(&FrameId::new("USLT").unwrap()) else {
+        panic!("missing USLT");
+    };
+    assert_eq!(uslt.content, "plain");
+    assert!(tag.get(&FrameId::new("SYLT").unwrap()).is_none());
+}
+
+#[test]
+fn writes_id3v2_with_cover_and_lyrics() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a.mp3");
+    mp3_fixture(&path);
+    write_tags(&path, &tags(), Some(PNG)).unwrap();
+
+    let mut f = std::fs::File::open(&path).unwrap();
+    let file = MpegFile::read_from(&mut f, opts()).unwrap();
+    let tag = file.id3v2().expect("id3v2");
+    assert_eq!(tag.title().as_deref(), Some("Song"));
+    assert_eq!(tag.album().as_deref(), Some("Album"));
+    assert_eq!(tag.track(), Some(3));
+    assert_eq!(tag.artist().as_deref(), Some("A/B"));
+    assert_eq!(apic_count(tag), 1);
+    assert!(tag.get(&FrameId::new("USLT").unwrap()).is_some());
+    assert!(tag.get(&FrameId::new("SYLT").unwrap()).is_some());
+}
+
+#[test]
+fn writes_flac_vorbis_comments_and_picture() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a.flac");
+    flac_fixture(&path);
+    write_tags(&path, &tags(), Some(PNG)).unwrap();
+    // 重复写入不应产生重复的封面。
+    write_tags(&path, &tags(), Some(PNG)).unwrap();
+
+    let mut f = std::fs::File::open(&path).unwrap();
+    let file = FlacFile::read_from(&mut f, opts()).unwrap();
+    let vc = file.vorbis_comments().expect("vorbis comments");
+    assert_eq!(vc.get("TITLE"), Some("Song"));
+    assert_eq!(vc.get_all("ARTIST").collect::<Vec<_>>(), vec!["A", "B"]);
+    assert_eq!(vc.get("TRACKNUMBER"), Some("3"));
+    assert!(vc.get("LYRICS").unwrap().contains("[00:01.00]first line"));
+    assert_eq!(file.pictures().len(), 1);
+}
+
+#[test]
+fn tag_format_follows_container() {
+    let fmt = |name: &str| TagFormat::from_path(Path::new(name));
+    assert_eq!(fmt("a.MP3"), Some(TagFormat::Id3v2));
+    assert_eq!(fmt("a.flac"), Some(TagFormat::Flac));
+    assert_eq!(fmt("a.m4a"), Some(TagFormat::Mp4));
+    assert_eq!(fmt("a.mp4"), Some(TagFormat::Mp4));
+    assert_eq!(fmt("a.aac"), None);
+    assert_eq!(fmt("a.ogg"), None);
+}
+
+#[test]
+fn writes_mp4_atoms() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a.m4a");
+    m4a_fixture(&path);
+    write_tags(&path, &tags(), Some(PNG)).unwrap();
+
+    let mut f = std::fs::File::open(&path).unwrap();
+    let file = Mp4File::read_from(&mut f, opts()).unwrap();
+    let ilst = file.ilst().expect("ilst");
+    assert_eq!(ilst.title().as_deref(), Some("Song"));
+    assert_eq!(ilst.artist().as_deref(), Some("A; B"));
+    assert_eq!(ilst.pictures().map(|p| p.count()), Some(1));
+    assert!(ilst.get(&AtomIdent::Fourcc(*b"\xa9lyr")).is_some());
+}
+
+#[test]
+fn replay_gain_is_added_without_dropping_tags() {
+    let dir = tempfile::tempdir().unwrap();
+    let gain = ReplayGain {
+        track_gain_db: -4.2,
+        track_peak: 0.988_553,
+    };
+
+    let mp3 = dir.path().join("a.mp3");
+    mp3_fixture(&mp3);
+    write_tags(&mp3, &tags(), Some(PNG)).unwrap();
+    write_replay_gain(&mp3, &gain).unwrap();
+    let mut f = std::fs::File::open(&mp3).unwrap();
+    let file = MpegFile::read_from(&mut f, opts()).unwrap();
+    let tag = file.id3v2().unwrap();
+    assert_eq!(tag.title().as_deref(), Some("Song"));
+    assert_eq!(apic_count(tag), 1);
+    assert_eq!(tag.get_user_text("REPLAYGAIN_TRACK_GAIN"), Some("-4.20 dB"));
+    assert_eq!(tag.get_user_text("REPLAYGAIN_TRACK_PEAK"), Some("0.988553"));
+
+    let flac = dir.path().join("a.flac");
+    flac_fixture(&flac);
+    write_tags(&flac, &tags(), None).unwrap();
+    write_replay_gain(&flac, &gain).unwrap();
+    let mut f = std::fs::File::open(&flac).unwrap();
+    let file = FlacFile::read_from(&mut f, opts()).unwrap();
+    let vc = file.vorbis_comments().unwrap();
+    assert_eq!(vc.title().as_deref(), Some("Song"));
+    assert_eq!(vc.get("REPLAYGAIN_TRACK_GAIN"), Some("-4.20 dB"));
+
+    let m4a = dir.path().join("a.m4a");
+    m4a_fixture(&m4a);
+    write_tags(&m4a, &tags(), None).unwrap();
+    write_replay_gain(&m4a, &gain).unwrap();
+    let mut f = std::fs::File::open(&m4a).unwrap();
+    let file = Mp4File::read_from(&mut f, opts()).unwrap();
+    let ilst = file.ilst().unwrap();
+    assert_eq!(ilst.title().as_deref(), Some("Song"));
+    let peak = AtomIdent::Freeform {
+        mean: "com.apple.iTunes".into(),
+        name: "replaygain_track_peak".into(),
+    };
+    assert!(ilst.get(&peak).is_some());
+}
+
+#[tokio::test]
+async fn tag_file_fetches_cover_and_tolerates_bad_cover() {
+    let server = MockServer::start();
+    let ok = server.mock(|when, then| {
+        when.method(GET).path("/cover.png");
+        then.status(200).body(PNG);
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/broken.jpg");
+        then.status(200).body("not an image");
+    });
+
+    let dir = tempfile::tempdir().unwrap();
+    let http = reqwest::Client::new();
+    let timeout = Duration::from_secs(5);
+
+    let a = dir.path().join("a.mp3");
+    mp3_fixture(&a);
+    tag_file(&http, &a, &tags(), Some(&server.url("/cover.png")), timeout)
+        .await
+        .unwrap();
+    ok.assert();
+    let mut f = std::fs::File::open(&a).unwrap();
+    let file = MpegFile::read_from(&mut f, opts()).unwrap();
+    assert_eq!(apic_count(file.id3v2().unwrap()), 1);
+
+    let b = dir.path().join("b.mp3");
+    mp3_fixture(&b);
+    tag_file(
+        &http,
+        &b,
+        &tags(),
+        Some(&server.url("/broken.jpg")),
+        timeout,
+    )
+    .await
+    .unwrap();
+    let mut f = std::fs::File::open(&b).unwrap();
+    let file = MpegFile::read_from(&mut f, opts()).unwrap();
+    let tag = file.id3v2().unwrap();
+    assert_eq!(tag.title().as_deref(), Some("Song"));
+    assert_eq!(apic_count(tag), 0);
+
+    // 不支持的格式直接跳过。
+    let c = dir.path().join("c.wav");
+    std::fs::write(&c, b"RIFF").unwrap();
+    tag_file(&http, &c, &tags(), None, timeout).await.unwrap();
+}
@@ -0,0 +1,245 @@
+use std::io::Write;
+use std::time::Duration;
+
+use base64::Engine as _;
+use chaos_core::music::client::MusicClient;
+use chaos_core::music::lyrics::word_lyrics_to_lrc;
+use chaos_core::music::model::{MusicService, ProviderConfig, WordLyricsFormat};
+use chaos_core::music::providers::{kugou, kuwo, qq};
+use httpmock::Method::{GET, POST};
+use httpmock::MockServer;
+use serde_json::json;
+
+fn b64(s: &str) -> String {
+    base64::engine::general_purpose::STANDARD.encode(s)
+}
+
+fn netease_client(server: &MockServer) -> MusicClient {
+    MusicClient::new(ProviderConfig {
+        netease_base_urls: vec![server.base_url()],
+        ..ProviderConfig::default()
+    })
+    .expect("client")
+}
+
+#[test]
+fn word_lyrics_convert_to_lrc() {
+    // QRC / KRC / YRC 的行标签相同，逐字标签位置不同。
+    let qrc =
+        "[ti:x]\n[1000,2000]你(1000,500)好(1500,500)\n[63500,1000]Hi (63500,400)there(63900,600)";
+    assert_eq!(
+        word_lyrics_to_lrc(qrc),
+        "[00:01.00]你好\n[01:03.50]Hi there"
+    );
+    let krc = "[1000,2000]<0,500,0>你<500,500,0>好";
+    assert_eq!(word_lyrics_to_lrc(krc), "[00:01.00]你好");
+    let yrc = "{\"t\":0,\"c\":[{\"tx\":\"作词\"}]}\n[1000,2000](1000,500,0)你(1500,500,0)好";
+    assert_eq!(word_lyrics_to_lrc(yrc), "[00:01.00]你好");
+}
+
+#[tokio::test]
+async fn netease_track_detail_and_lyrics() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/song/detail")
+            .body_contains("\"ids\":\"33894312\"");
+        then.status(200).json_body(json!({
+            "code": 200,
+            "songs": [{ "id": 33894312, "name": "情非得已", "ar": [{ "id": 1, "name": "庾澄庆" }], "al": { "id": 2, "name": "Al", "picUrl": "http://p" }, "dt": 1000, "h": {}, "sq": {} }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/lyric/new")
+            .body_contains("\"id\":\"33894312\"");
+        then.status(200).json_body(json!({
+            "code": 200,
+            "lrc": { "lyric": "[00:01.00]原文" },
+            "tlyric": { "lyric": "[00:01.00]translation" },
+            "romalrc": { "lyric": "" },
+            "yrc": { "lyric": "[1000,500](1000,500,0)原文" }
+        }));
+    });
+
+    let client = netease_client(&server);
+    let track = client
+        .track_detail(MusicService::Netease, "33894312")
+        .await
+        .expect("detail");
+    assert_eq!(track.title, "情非得已");
+    assert_eq!(track.artists, ["庾澄庆"]);
+    assert_eq!(track.qualities.len(), 2);
+
+    let lyrics = client
+        .track_lyrics(MusicService::Netease, "33894312")
+        .await
+        .expect("lyrics");
+    assert_eq!(lyrics.original, "[00:01.00]原文");
+    assert_eq!(lyrics.translation.as_deref(), Some("[00:01.00]translation"));
+    assert_eq!(lyrics.romanization, None);
+    assert_eq!(lyrics.word_format, Some(WordLyricsFormat::Yrc));
+}
+
+#[tokio::test]
+async fn qq_lyrics_extract_qrc_and_roma() {
+    let server = MockServer::start();
+    let base_url = format!("{}/cgi-bin/musicu.fcg", server.base_url());
+    let qrc = r#"<?xml version="1.0" encoding="utf-8"?><QrcInfos><LyricInfo LyricCount="1"><Lyric_1 LyricType="1" LyricContent="[ti:x]
+[1000,2000]愛(1000,1000)&amp;(2000,1000)
+"/></LyricInfo></QrcInfos>"#;
+    let roma =
+        r#"<QrcInfos><Lyric_1 LyricType="1" LyricContent="[1000,2000]ai (1000,1000)"/></QrcInfos>"#;
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/cgi-bin/musicu.fcg")
+            .body_contains("GetPlayLyricInfo")
+            .body_contains("\"songMID\":\"mid1\"");
+        then.status(200).json_body(json!({
+            "req": { "code": 0, "data": { "lyric": b64(qrc), "trans": b64("[00:01.00]love"), "roma": b64(roma) } }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/cgi-bin/musicu.fcg")
+            .body_contains("get_song_detail_yqq");
+        then.status(200).json_body(json!({
+            "req": { "code": 0, "data": { "track_info": { "mid": "mid1", "name": "Song", "singer": [{ "name": "S", "mid": "s" }], "album": { "name": "A", "mid": "a", "pmid": "" }, "interval": 200, "file": { "size_flac": 1 } } } }
+        }));
+    });
+
+    let http = reqwest::Client::new();
+    let timeout = Duration::from_secs(5);
+    let lyrics = qq::track_lyrics_with_base(&http, &base_url, "mid1", timeout)
+        .await
+        .expect("lyrics");
+    assert_eq!(lyrics.original, "[00:01.00]愛&");
+    assert_eq!(lyrics.translation.as_deref(), Some("[00:01.00]love"));
+    assert_eq!(lyrics.romanization.as_deref(), Some("[00:01.00]ai"));
+    assert_eq!(lyrics.word_format, Some(WordLyricsFormat::Qrc));
+    assert!(
+        lyrics
+            .word_lyrics
+            .unwrap()
+            .contains("愛(1000,1000)&(2000,1000)")
+    );
+
+    let track = qq::track_detail_with_base(&http, &base_url, "mid1", timeout)
+        .await
+        .expect("detail");
+    assert_eq!(track.id, "mid1");
+    assert_eq!(track.duration_ms, Some(200_000));
+    assert_eq!(track.qualities[0].id, "flac");
+}
+
+#[tokio::test]
+async fn kugou_lyrics_decrypt_krc_with_language() {
+    let server = MockServer::start();
+    let language = json!({ "content": [
+        { "type": 1, "lyricContent": [["你好"], ["世界"]] },
+        { "type": 0, "lyricContent": [["ni ", "hao"], ["shi ", "jie"]] }
+    ] });
+    let krc = format!(
+        "[ar:x]\n[language:{}]\n[1000,1000]<0,500,0>Hel<500,500,0>lo\n[2000,1000]<0,1000,0>World\n",
+        b64(&language.to_string())
+    );
+    let mut z = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    z.write_all(krc.as_bytes()).unwrap();
+    const KEY: [u8; 16] = [
+        64, 71, 97, 119, 94, 50, 116, 71, 81, 54, 49, 45, 206, 210, 110, 105,
+    ];
+    let mut payload = b"krc1".to_vec();
+    payload.extend(
+        z.finish()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(i, b)| b ^ KEY[i & 0x0f]),
+    );
+    let content = base64::engine::general_purpose::STANDARD.encode(payload);
+
+    server.mock(|when, then| {
+        when.method(GET).path("/search").query_param("hash", "ABC");
+        then.status(200)
+            .json_body(json!({ "candidates": [{ "id": "7", "accesskey": "k" }] }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/download")
+            .query_param("id", "7")
+            .query_param("accesskey", "k");
+        then.status(200)
+            .json_body(json!({ "content": content, "fmt": "krc" }));
+    });
+
+    let http = reqwest::Client::new();
+    let lyrics = kugou::track_lyrics_with_urls(
+        &http,
+        &format!("{}/search?hash={{hash}}", server.base_url()),
+        &format!("{}/download", server.base_url()),
+        "ABC",
+        Duration::from_secs(5),
+    )
+    .await
+    .expect("lyrics");
+    assert_eq!(lyrics.original, "[00:01.00]Hello\n[00:02.00]World");
+    assert_eq!(
+        lyrics.translation.as_deref(),
+        Some("[00:01.00]你好\n[00:02.00]世界")
+    );
+    assert_eq!(
+        lyrics.romanization.as_deref(),
+        Some("[00:01.00]ni hao\n[00:02.00]shi jie")
+    );
+    assert_eq!(lyrics.word_format, Some(WordLyricsFormat::Krc));
+}
+
+#[tokio::test]
+async fn kuwo_detail_and_lyrics_split_translation() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/songinfoandlrc").query_param("musicId", "228908");
+        then.status(200).json_body(json!({
+            "status": 200,
+            "data": {
+                "songinfo": { "id": "228908", "songName": "晴天", "artist": "周杰伦&A", "artistId": "336", "album": "叶惠美", "albumId": "1", "duration": "269", "pic": "http://p", "coopFormats": ["128kmp3", "320kmp3", "2000kflac"] },
+                "lrclist": [
+                    { "time": "1.5", "lineLyric": "故事的小黄花" },
+                    { "time": "1.5", "lineLyric": "little yellow flower" },
+                    { "time": "63.25", "lineLyric": "从出生那年就飘着" }
+                ]
+            }
+        }));
+    });
+
+    let template = format!("{}/songinfoandlrc?musicId={{rid}}", server.base_url());
+    let http = reqwest::Client::new();
+    let timeout = Duration::from_secs(5);
+    let track = kuwo::track_detail_with_template(&http, &template, "228908", timeout)
+        .await
+        .expect("detail");
+    assert_eq!(track.title, "晴天");
+    assert_eq!(track.artists, ["周杰伦", "A"]);
+    assert_eq!(track.duration_ms, Some(269_000));
+    assert_eq!(
+        track
+            .qualities
+            .iter()
+            .map(|q| q.id.as_str())
+            .collect::<Vec<_>>(),
+        ["flac", "mp3_128", "mp3_320"]
+    );
+
+    let lyrics = kuwo::track_lyrics_with_template(&http, &template, "228908", timeout)
+        .await
+        .expect("lyrics");
+    assert_eq!(
+        lyrics.original,
+        "[00:01.50]故事的小黄花\n[01:03.25]从出生那年就飘着"
+    );
+    assert_eq!(
+        lyrics.translation.as_deref(),
+        Some("[00:01.50]little yellow flower")
+    );
+    assert_eq!(lyrics.word_format, None);
+}
@@ -4,6 +4,10 @@ mod cli;
 mod win {
     use crate::cli::{CliOptions, TransportMode};
     use chaos_app::ChaosApp;
+    use chaos_app::lyrics::sync::{
+        DEFAULT_TICK_INTERVAL, LyricsSync, LyricsSyncEvent, PlaybackClock,
+    };
+    use chaos_core::download_scheduler::{DownloadScheduler, DownloadTask};
     use chaos_core::{bili_video, lyrics, music, now_playing};
     use chaos_daemon::run_jsonrpc_over_lsp;
     use chaos_proto::{
@@ -15,6 +19,7 @@ mod win {
         BiliCheckLoginResult,
         BiliDownloadCancelParams,
         BiliDownloadJobStatus,
+        BiliDownloadOptions,
         BiliDownloadStartParams,
         BiliDownloadStartResult,
         BiliDownloadStatus,
@@ -22,6 +27,9 @@ mod win {
         BiliDownloadTotals,
         BiliJobPhase,
         BiliJobState,
+        BiliLiveReplay,
+        BiliLiveReplayListParams,
+        BiliLiveReplayListResult,
         BiliLoginQr,
         BiliLoginQrCreateParams,
         BiliLoginQrCreateV2Params,
@@ -36,12 +44,15 @@ mod win {
         BiliParsedVideo,
         BiliRefreshCookieParams,
         BiliRefreshCookieResult,
+        BiliSubtitleFormat,
         BiliTask,
         BiliTaskAddParams,
         BiliTaskAddResult,
         BiliTaskCancelParams,
         BiliTaskDetail,
         BiliTaskGetParams,
+        BiliTaskPauseParams,
+        BiliTaskResumeParams,
         BiliTasksGetParams,
         BiliTasksGetResult,
         BiliTasksRemoveFinishedParams,
@@ -52,8 +63,11 @@ mod win {
         DanmakuConnectResult,
         DanmakuDisconnectParams,
         DanmakuFetchImageParams,
+        DownloadSchedulerSetParams,
+        DownloadSchedulerState,
         // music
         KugouUserInfo,
+        KuwoUserInfo,
         LiveCloseParams,
         LiveDirCategoriesParams,
         LiveDirCategory,
@@ -67,15 +81,32 @@ mod win {
         LlmChatParams,
         LlmChatResult,
         LlmConfigSetParams,
+        LyricsPinParams,
+        LyricsProviderReport,
+        LyricsSearchDiagnosticsResult,
+        LyricsSearchDoneNotif,
         LyricsSearchParams,
+        LyricsSearchReport,
         LyricsSearchResult,
+        LyricsSearchResultNotif,
+        LyricsSearchStreamStartResult,
+        LyricsSetOffsetParams,
+        LyricsSyncStartParams,
+        LyricsSyncStartResult,
+        LyricsSyncStopParams,
+        LyricsSyncUpdateParams,
         MusicAlbum,
         MusicAlbumTracksParams,
         MusicArtist,
         MusicArtistAlbumsParams,
         MusicAuthState,
+        MusicChart,
+        MusicChartTracksParams,
+        MusicDailyRecommendationsParams,
         MusicDownloadCancelParams,
         MusicDownloadJobResult,
+        MusicDownloadPauseParams,
+        MusicDownloadResumeParams,
         MusicDownloadStartParams,
         MusicDownloadStartResult,
         MusicDownloadStatus,
@@ -83,17 +114,27 @@ mod win {
         MusicDownloadTarget,
         MusicDownloadTotals,
         MusicJobState,
+        MusicKuwoLoginTokenParams,
+        MusicListChartsParams,
         MusicLoginQr,
         MusicLoginQrCreateParams,
         MusicLoginQrPollParams,
         MusicLoginQrPollResult,
         MusicLoginQrState,
         MusicLoginType,
+        MusicLyrics,
+        MusicNeteaseCookieResult,
+        MusicNeteaseRefreshCookieParams,
+        MusicPlaylist,
+        MusicPlaylistTracksParams,
         MusicProviderConfig,
         MusicRefreshCookieParams,
         MusicSearchParams,
         MusicService,
         MusicTrack,
+        MusicTrackParams,
+        MusicUserPlaylistsParams,
+        NeteaseUserInfo,
         NowPlayingSession,
         NowPlayingSnapshot,
         NowPlayingSnapshotParams,
@@ -115,10 +156,10 @@ mod win {
         VoiceChatStreamCancelParams,
         VoiceChatStreamStartParams,
         VoiceChatStreamStartResult,
+        WordLyricsFormat,
     };
-    use std::collections::{HashMap, HashSet};
+    use std::collections::HashMap;
     use std::env;
-    use std::path::PathBuf;
     use std::pin::Pin;
     use std::str::FromStr;
     use std::sync::{
@@ -127,7 +168,6 @@ mod win {
     };
     use std::task::{Context, Poll};
     use std::time::{SystemTime, UNIX_EPOCH};
-    use tokio::fs;
     use tokio::io::{AsyncRead, AsyncWrite, Stdin, Stdout};
     use tokio::sync::mpsc;
     use tokio::sync::{Mutex, Semaphore};
@@ -171,80 +211,6 @@ mod win {
         }
     }
 
-    async fn try_download_lyrics_for_track(
-        http: &reqwest::Client,
-        track: &MusicTrack,
-        audio_path: &std::path::Path,
-        overwrite: bool,
-    ) -> Result<(), String> {
-        // Best-effort: lyrics download should not fail the audio download job.
-        let title = (track.title.as_str()).trim();
-        if title.is_empty() {
-            return Ok(());
-        }
-
-        let artist = track
-            .artists
-            .iter()
-            .filter(|s| !s.trim().is_empty())
-            .cloned()
-            .collect::<Vec<_>>()
-            .join(" / ");
-
-        let term = if artist.trim().is_empty() {
-            lyrics::model::LyricsSearchTerm::Keyword {
-                keyword: title.to_string(),
-            }
-        } else {
-            lyrics::model::LyricsSearchTerm::Info {
-                title: title.to_string(),
-                artist,
-                album: track.album.clone().filter(|s| !s.trim().is_empty()),
-            }
-        };
-
-        let mut req = lyrics::model::LyricsSearchRequest::new(term);
-        req.duration_ms = track.duration_ms;
-        req.limit = 1;
-
-        let opt = lyrics::model::LyricsSearchOptions {
-            timeout_ms: 8000,
-            strict_match: false,
-            services: vec![
-                lyrics::model::LyricsService::QQMusic,
-                lyrics::model::LyricsService::Netease,
-                lyrics::model::LyricsService::LrcLib,
-            ],
-        };
-
-        let mut items = lyrics::core::search_with_http(http, &req, opt)
-            .await
-            .map_err(|e| e.to_string())?;
-        let Some(best) = items.pop() else {
-            return Ok(());
-        };
-        if best.lyrics_original.trim().is_empty() {
-            return Ok(());
-        }
-
-        let lrc_path = audio_path.with_extension("lrc");
-        if !overwrite && lrc_path.exists() {
-            return Ok(());
-        }
-
-        let mut content = best.lyrics_original;
-        if let Some(t) = best.lyrics_translation {
-            if !t.trim().is_empty() {
-                content.push_str("\n\n");
-                content.push_str(&t);
-            }
-        }
-        fs::write(&lrc_path, content)
-            .await
-            .map_err(|e| e.to_string())?;
-        Ok(())
-    }
-
     fn map_quality_to_proto(q: music::model::MusicQuality) -> chaos_proto::MusicQuality {
         chaos_proto::MusicQuality {
             id: q.id,
@@ -266,10 +232,142 @@ mod win {
             album_id: t.album_id,
             duration_ms: t.duration_ms,
             cover_url: t.cover_url,
+            isrc: t.isrc,
             qualities: t.qualities.into_iter().map(map_quality_to_proto).collect(),
         }
     }
 
+    fn map_quality_to_core(q: chaos_proto::MusicQuality) -> music::model::MusicQuality {
+        music::model::MusicQuality {
+            id: q.id,
+            label: q.label,
+            format: q.format,
+            bitrate_kbps: q.bitrate_kbps,
+            lossless: q.lossless,
+        }
+    }
+
+    fn map_track_to_core(t: MusicTrack) -> music::model::MusicTrack {
+        music::model::MusicTrack {
+            service: map_service_to_core(t.service),
+            id: t.id,
+            title: t.title,
+            artists: t.artists,
+            artist_ids: t.artist_ids,
+            album: t.album,
+            album_id: t.album_id,
+            duration_ms: t.duration_ms,
+            cover_url: t.cover_url,
+            isrc: t.isrc,
+            qualities: t.qualities.into_iter().map(map_quality_to_core).collect(),
+        }
+    }
+
+    fn map_download_target_to_core(t: MusicDownloadTarget) -> music::model::MusicDownloadTarget {
+        match t {
+            MusicDownloadTarget::Track { track } => music::model::MusicDownloadTarget::Track {
+                track: Box::new(map_track_to_core(*track)),
+            },
+            MusicDownloadTarget::Album { service, album_id } => {
+                music::model::MusicDownloadTarget::Album {
+                    service: map_service_to_core(service),
+                    album_id,
+                }
+            }
+            MusicDownloadTarget::ArtistAll { service, artist_id } => {
+                music::model::MusicDownloadTarget::ArtistAll {
+                    service: map_service_to_core(service),
+                    artist_id,
+                }
+            }
+            MusicDownloadTarget::Playlist {
+                service,
+                playlist_id,
+            } => music::model::MusicDownloadTarget::Playlist {
+                service: map_service_to_core(service),
+                playlist_id,
+            },
+        }
+    }
+
+    fn map_download_options_to_core(
+        o: chaos_proto::MusicDownloadOptions,
+    ) -> music::model::MusicDownloadOptions {
+        music::model::MusicDownloadOptions {
+            quality_id: o.quality_id,
+            out_dir: o.out_dir,
+            path_template: o.path_template,
+            overwrite: o.overwrite,
+            concurrency: o.concurrency,
+            retries: o.retries,
+            priority: o.priority,
+            fallback_services: o
+                .fallback_services
+                .into_iter()
+                .map(map_service_to_core)
+                .collect(),
+            fallback_min_score: o.fallback_min_score,
+            skip_owned: o.skip_owned,
+            post_process: o
+                .post_process
+                .map(|pp| music::model::MusicPostProcessOptions {
+                    ffmpeg_path: pp.ffmpeg_path,
+                    transcode: pp.transcode.map(|t| music::model::MusicTranscodeOptions {
+                        codec: match t.codec {
+                            chaos_proto::MusicTranscodeCodec::Mp3 => {
+                                music::model::MusicTranscodeCodec::Mp3
+                            }
+                            chaos_proto::MusicTranscodeCodec::Aac => {
+                                music::model::MusicTranscodeCodec::Aac
+                            }
+                            chaos_proto::MusicTranscodeCodec::Flac => {
+                                music::model::MusicTranscodeCodec::Flac
+                            }
+                        },
+                        bitrate_kbps: t.bitrate_kbps,
+                        source_formats: t.source_formats,
+                        keep_source: t.keep_source,
+                    }),
+                    replay_gain: pp.replay_gain,
+                }),
+        }
+    }
+
+    fn map_download_status_to_proto(s: music::model::MusicDownloadStatus) -> MusicDownloadStatus {
+        MusicDownloadStatus {
+            done: s.done,
+            totals: MusicDownloadTotals {
+                total: s.totals.total,
+                done: s.totals.done,
+                failed: s.totals.failed,
+                skipped: s.totals.skipped,
+                canceled: s.totals.canceled,
+            },
+            jobs: s
+                .jobs
+                .into_iter()
+                .map(|j| MusicDownloadJobResult {
+                    index: j.index,
+                    track_id: j.track_id,
+                    state: match j.state {
+                        music::model::MusicJobState::Pending => MusicJobState::Pending,
+                        music::model::MusicJobState::Running => MusicJobState::Running,
+                        music::model::MusicJobState::Done => MusicJobState::Done,
+                        music::model::MusicJobState::Failed => MusicJobState::Failed,
+                        music::model::MusicJobState::Skipped => MusicJobState::Skipped,
+                        music::model::MusicJobState::Canceled => MusicJobState::Canceled,
+                    },
+                    path: j.path,
+                    bytes: j.bytes,
+                    error: j.error,
+                    source_service: j.source_service.map(map_service_to_proto),
+                    source_track_id: j.source_track_id,
+                    upgraded_from: j.upgraded_from,
+                })
+                .collect(),
+        }
+    }
+
     fn map_album_to_proto(a: music::model::MusicAlbum) -> MusicAlbum {
         MusicAlbum {
             service: map_service_to_proto(a.service),
@@ -283,6 +381,30 @@ mod win {
         }
     }
 
+    fn map_playlist_to_proto(p: music::model::MusicPlaylist) -> MusicPlaylist {
+        MusicPlaylist {
+            service: map_service_to_proto(p.service),
+            id: p.id,
+            title: p.title,
+            creator: p.creator,
+            creator_id: p.creator_id,
+            cover_url: p.cover_url,
+            track_count: p.track_count,
+            description: p.description,
+        }
+    }
+
+    fn map_chart_to_proto(c: music::model::MusicChart) -> MusicChart {
+        MusicChart {
+            service: map_service_to_proto(c.service),
+            id: c.id,
+            title: c.title,
+            cover_url: c.cover_url,
+            description: c.description,
+            update_frequency: c.update_frequency,
+        }
+    }
+
     fn map_artist_to_proto(a: music::model::MusicArtist) -> MusicArtist {
         MusicArtist {
             service: map_service_to_proto(a.service),
@@ -325,6 +447,214 @@ mod win {
         }
     }
 
+    fn map_netease_user_to_proto(u: music::model::NeteaseUserInfo) -> NeteaseUserInfo {
+        NeteaseUserInfo {
+            user_id: u.user_id,
+            nickname: u.nickname,
+            avatar_url: u.avatar_url,
+            vip_type: u.vip_type,
+        }
+    }
+
+    fn map_lyrics_to_proto(l: music::model::MusicLyrics) -> MusicLyrics {
+        MusicLyrics {
+            service: map_service_to_proto(l.service),
+            track_id: l.track_id,
+            original: l.original,
+            translation: l.translation,
+            romanization: l.romanization,
+            word_lyrics: l.word_lyrics,
+            word_format: l.word_format.map(|f| match f {
+                music::model::WordLyricsFormat::Qrc => WordLyricsFormat::Qrc,
+                music::model::WordLyricsFormat::Krc => WordLyricsFormat::Krc,
+                music::model::WordLyricsFormat::Yrc => WordLyricsFormat::Yrc,
+            }),
+        }
+    }
+
+    fn lyrics_request_from_params(
+        params: LyricsSearchParams,
+    ) -> Result<
+        (
+            lyrics::model::LyricsSearchRequest,
+            lyrics::model::LyricsSearchOptions,
+        ),
+        String,
+    > {
+        let title = params.title.trim().to_string();
+        let file_path = params
+            .file_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(std::path::PathBuf::from);
+        if title.is_empty() && file_path.is_none() {
+            return Err("title is empty".to_string());
+        }
+
+        let artist = params.artist.as_deref().unwrap_or("").trim().to_string();
+        let album = params.album.as_deref().unwrap_or("").trim().to_string();
+
+        let term = if title.is_empty()
+            && let Some(path) = file_path.clone()
+        {
+            lyrics::model::LyricsSearchTerm::File { path }
+        } else if artist.is_empty() {
+            lyrics::model::LyricsSearchTerm::Keyword { keyword: title }
+        } else {
+            lyrics::model::LyricsSearchTerm::Info {
+                title,
+                artist,
+                album: (!album.is_empty()).then_some(album),
+            }
+        };
+
+        let mut req = lyrics::model::LyricsSearchRequest::new(term);
+        req.duration_ms = params.duration_ms.filter(|v| *v > 0);
+        if let Some(limit) = params.limit {
+            req.limit = (limit as usize).clamp(1, 50);
+        }
+        req.file_path = file_path;
+        req.isrc = params
+            .isrc
+            .as_deref()
+            .map(|s| s.trim().to_uppercase())
+            .filter(|s| !s.is_empty());
+
+        let mut opt = lyrics::model::LyricsSearchOptions::default();
+        if let Some(v) = params.timeout_ms {
+            opt.timeout_ms = v.max(1);
+        }
+        if let Some(v) = params.strict_match {
+            opt.strict_match = v;
+        }
+        if let Some(v) = params.bypass_cache {
+            opt.bypass_cache = v;
+        }
+        if let Some(v) = params.exact_only {
+            opt.exact_only = v;
+        }
+
+        if let Some(services) = params.services {
+            let mut out = Vec::new();
+            for s in services {
+                let s = s.trim().to_string();
+                if s.is_empty() {
+                    continue;
+                }
+                let svc = lyrics::model::LyricsService::from_str(&s).map_err(|e| e)?;
+                out.push(svc);
+            }
+            if !out.is_empty() {
+                opt.services = out;
+            }
+        }
+
+        Ok((req, opt))
+    }
+
+    fn map_lyrics_result_to_proto(x: lyrics::model::LyricsSearchResult) -> LyricsSearchResult {
+        LyricsSearchResult {
+            service: x.service.as_str().to_string(),
+            service_token: x.service_token,
+            title: x.title,
+            artist: x.artist,
+            album: x.album,
+            duration_ms: x.duration_ms,
+            match_percentage: x.match_percentage,
+            quality: x.quality,
+            matched: x.matched,
+            has_translation: x.has_translation,
+            has_inline_timetags: x.has_inline_timetags,
+            lyrics_original: x.lyrics_original,
+            lyrics_translation: x.lyrics_translation,
+            offset_ms: x.offset_ms,
+            debug: x.debug,
+        }
+    }
+
+    fn map_lyrics_result_to_core(
+        x: LyricsSearchResult,
+    ) -> Result<lyrics::model::LyricsSearchResult, String> {
+        Ok(lyrics::model::LyricsSearchResult {
+            service: lyrics::model::LyricsService::from_str(&x.service)?,
+            service_token: x.service_token,
+            title: x.title,
+            artist: x.artist,
+            album: x.album,
+            duration_ms: x.duration_ms,
+            match_percentage: x.match_percentage,
+            quality: x.quality,
+            matched: x.matched,
+            has_translation: x.has_translation,
+            has_inline_timetags: x.has_inline_timetags,
+            lyrics_original: x.lyrics_original,
+            lyrics_translation: x.lyrics_translation,
+            offset_ms: 0,
+            debug: x.debug,
+        })
+    }
+
+    /// `lyrics.pin` / `lyrics.setOffset` 定位缓存条目用的请求（与 `lyrics.search` 的搜索词规则一致）。
+    fn lyrics_cache_request(
+        title: &str,
+        artist: Option<&str>,
+        duration_ms: Option<u64>,
+    ) -> Result<lyrics::model::LyricsSearchRequest, String> {
+        let title = title.trim().to_string();
+        if title.is_empty() {
+            return Err("title is empty".to_string());
+        }
+        let artist = artist.unwrap_or("").trim().to_string();
+        let term = if artist.is_empty() {
+            lyrics::model::LyricsSearchTerm::Keyword { keyword: title }
+        } else {
+            lyrics::model::LyricsSearchTerm::Info {
+                title,
+                artist,
+                album: None,
+            }
+        };
+        let mut req = lyrics::model::LyricsSearchRequest::new(term);
+        req.duration_ms = duration_ms.filter(|v| *v > 0);
+        Ok(req)
+    }
+
+    fn lyrics_count(n: usize) -> u32 {
+        u32::try_from(n).unwrap_or(u32::MAX)
+    }
+
+    fn map_lyrics_provider_report_to_proto(
+        p: lyrics::model::LyricsProviderReport,
+    ) -> LyricsProviderReport {
+        LyricsProviderReport {
+            service: p.service.as_str().to_string(),
+            status: p.status.as_str().to_string(),
+            http_status: p.http_status,
+            error: p.error,
+            exact: p.exact,
+            latency_ms: p.latency_ms,
+            candidates: lyrics_count(p.candidates),
+            fetched: lyrics_count(p.fetched),
+            fetch_failed: lyrics_count(p.fetch_failed),
+            matched: lyrics_count(p.matched),
+            filtered: lyrics_count(p.filtered),
+        }
+    }
+
+    fn map_lyrics_report_to_proto(r: lyrics::model::LyricsSearchReport) -> LyricsSearchReport {
+        LyricsSearchReport {
+            cache_hit: r.cache_hit,
+            elapsed_ms: r.elapsed_ms,
+            returned: lyrics_count(r.returned),
+            providers: r
+                .providers
+                .into_iter()
+                .map(map_lyrics_provider_report_to_proto)
+                .collect(),
+        }
+    }
+
     fn map_auth_to_core(auth: MusicAuthState) -> music::model::AuthState {
         music::model::AuthState {
             qq: auth.qq.map(|c| music::model::QqMusicCookie {
@@ -348,6 +678,21 @@ mod win {
                 userid: u.userid,
             }),
             netease_cookie: auth.netease_cookie,
+            kuwo: auth.kuwo.map(|u| music::model::KuwoUserInfo {
+                uid: u.uid,
+                sid: u.sid,
+                nickname: u.nickname,
+                vip_type: u.vip_type,
+            }),
+        }
+    }
+
+    fn map_kuwo_user_to_proto(u: music::model::KuwoUserInfo) -> KuwoUserInfo {
+        KuwoUserInfo {
+            uid: u.uid,
+            sid: u.sid,
+            nickname: u.nickname,
+            vip_type: u.vip_type,
         }
     }
 
@@ -370,6 +715,54 @@ mod win {
         }
     }
 
+    fn map_bili_subtitle_options(o: &BiliDownloadOptions) -> bili_video::subtitle::SubtitleOptions {
+        use bili_video::subtitle::{SubtitleFormat, SubtitleOptions};
+        let format = match o.subtitle_format {
+            BiliSubtitleFormat::Srt => SubtitleFormat::Srt,
+            BiliSubtitleFormat::Ass => SubtitleFormat::Ass,
+            BiliSubtitleFormat::Vtt => SubtitleFormat::Vtt,
+            BiliSubtitleFormat::Json => SubtitleFormat::Json,
+        };
+        SubtitleOptions::from_lists(
+            format,
+            &o.subtitle_langs,
+            o.subtitle_include_ai,
+            o.subtitle_bilingual.as_deref(),
+        )
+    }
+
+    fn map_bili_embed_options(o: &BiliDownloadOptions) -> bili_video::mux::EmbedOptions {
+        bili_video::mux::EmbedOptions {
+            metadata: o.embed_metadata,
+            chapters: o.embed_chapters,
+            cover: o.embed_cover,
+        }
+    }
+
+    fn map_bili_view(view: bili_video::parse::ViewInfo) -> BiliParsedVideo {
+        BiliParsedVideo {
+            aid: view.aid,
+            bvid: view.bvid,
+            title: view.title,
+            desc: view.desc,
+            pic: view.pic,
+            owner_name: view.owner_name,
+            owner_mid: view.owner_mid,
+            pub_time_unix_s: view.pub_time_unix_s,
+            pages: view
+                .pages
+                .into_iter()
+                .map(|p| BiliPage {
+                    page_number: p.page_number,
+                    cid: p.cid,
+                    page_title: p.page_title,
+                    duration_s: p.duration_s,
+                    dimension: p.dimension,
+                })
+                .collect(),
+        }
+    }
+
     fn map_bili_auth_to_core(a: Option<BiliAuthState>) -> bili_video::auth::AuthState {
         let a = a.unwrap_or_default();
         bili_video::auth::AuthState {
@@ -426,22 +819,6 @@ mod win {
         })
     }
 
-    fn choose_quality_id(track: &MusicTrack, requested: &str) -> Option<String> {
-        let req = requested.trim();
-        if req.is_empty() {
-            return None;
-        }
-        if track.qualities.iter().any(|q| q.id == req) {
-            return Some(req.to_string());
-        }
-        for q in music::util::quality_fallback_order() {
-            if track.qualities.iter().any(|x| x.id == q) {
-                return Some(q.to_string());
-            }
-        }
-        None
-    }
-
     #[derive(Debug)]
     struct QqLoginSession {
         created_at_ms: i64,
@@ -458,10 +835,9 @@ mod win {
     }
 
     #[derive(Debug)]
-    struct DownloadSession {
-        status: Arc<Mutex<MusicDownloadStatus>>,
-        cancel: Arc<AtomicBool>,
-        handle: tokio::task::JoinHandle<()>,
+    struct NeteaseLoginSession {
+        created_at_ms: i64,
+        key: String,
     }
 
     #[derive(Debug)]
@@ -470,7 +846,9 @@ mod win {
         cfg: Mutex<music::model::ProviderConfig>,
         qq_sessions: Mutex<HashMap<String, QqLoginSession>>,
         kugou_sessions: Mutex<HashMap<String, KugouLoginSession>>,
-        downloads: Mutex<HashMap<String, DownloadSession>>,
+        netease_sessions: Mutex<HashMap<String, NeteaseLoginSession>>,
+        downloads: Mutex<HashMap<String, music::batch::DownloadJob>>,
+        scheduler: DownloadScheduler,
     }
 
     impl MusicManager {
@@ -482,7 +860,9 @@ mod win {
                 cfg: Mutex::new(cfg),
                 qq_sessions: Mutex::new(HashMap::new()),
                 kugou_sessions: Mutex::new(HashMap::new()),
+                netease_sessions: Mutex::new(HashMap::new()),
                 downloads: Mutex::new(HashMap::new()),
+                scheduler: DownloadScheduler::global().clone(),
             })
         }
 
@@ -523,6 +903,7 @@ mod win {
         api: BiliApiType,
         status: Arc<Mutex<BiliDownloadStatus>>,
         cancel: Arc<AtomicBool>,
+        task: DownloadTask,
         handle: tokio::task::JoinHandle<()>,
     }
 
@@ -532,6 +913,7 @@ mod win {
         login_sessions: Mutex<HashMap<String, BiliLoginSession>>,
         tv_login_sessions: Mutex<HashMap<String, BiliTvLoginSession>>,
         downloads: Mutex<HashMap<String, BiliDownloadSession>>,
+        scheduler: DownloadScheduler,
     }
 
     impl BiliManager {
@@ -542,6 +924,7 @@ mod win {
                 login_sessions: Mutex::new(HashMap::new()),
                 tv_login_sessions: Mutex::new(HashMap::new()),
                 downloads: Mutex::new(HashMap::new()),
+                scheduler: DownloadScheduler::global().clone(),
             })
         }
 
@@ -1268,6 +1651,143 @@ mod win {
         Ok(())
     }
 
+    /// 跟随 Now Playing 时读取快照的间隔。
+    const LYRICS_SYNC_SNAPSHOT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+    struct LyricsSyncSession {
+        sync: LyricsSync,
+        poll: Option<tokio::task::JoinHandle<()>>,
+    }
+
+    /// `lyrics.sync.*` 订阅：每个订阅一个 [`LyricsSync`]，事件带上 `syncId` 推送给订阅的连接。
+    #[derive(Default)]
+    struct LyricsSyncManager {
+        sessions: Mutex<HashMap<String, LyricsSyncSession>>,
+    }
+
+    fn parse_sync_lyrics(
+        original: &str,
+        translation: Option<&str>,
+    ) -> Option<lyrics::parse::Lyrics> {
+        (!original.trim().is_empty())
+            .then(|| lyrics::parse::parse_with_translation(original, translation))
+    }
+
+    impl LyricsSyncManager {
+        fn new() -> Self {
+            Self::default()
+        }
+
+        async fn start(
+            self: Arc<Self>,
+            params: LyricsSyncStartParams,
+            notif_tx: mpsc::UnboundedSender<chaos_daemon::DaemonNotif>,
+        ) -> Result<LyricsSyncStartResult, String> {
+            let sync_id = gen_session_id("lyrics_sync");
+            let interval = params
+                .interval_ms
+                .map_or(DEFAULT_TICK_INTERVAL, std::time::Duration::from_millis);
+            let (sync, mut events) = LyricsSync::spawn(interval);
+            if let Some(l) = params
+                .lyrics_original
+                .as_deref()
+                .and_then(|o| parse_sync_lyrics(o, params.lyrics_translation.as_deref()))
+            {
+                sync.set_lyrics(Some(l));
+            }
+
+            // 先注册会话：轮询任务找不到会话时会直接退出。
+            self.sessions
+                .lock()
+                .await
+                .insert(sync_id.clone(), LyricsSyncSession { sync, poll: None });
+
+            if params.follow_now_playing.unwrap_or(true) {
+                let mgr = self.clone();
+                let id = sync_id.clone();
+                let poll = tokio::spawn(async move {
+                    loop {
+                        let snap = tokio::task::spawn_blocking(|| {
+                            now_playing::snapshot(now_playing::NowPlayingOptions {
+                                include_thumbnail: false,
+                                ..Default::default()
+                            })
+                        })
+                        .await;
+                        {
+                            let sessions = mgr.sessions.lock().await;
+                            let Some(s) = sessions.get(&id) else {
+                                return;
+                            };
+                            if let Ok(Ok(snap)) = snap {
+                                s.sync.update_snapshot(snap);
+                            }
+                        }
+                        tokio::time::sleep(LYRICS_SYNC_SNAPSHOT_INTERVAL).await;
+                    }
+                });
+                match self.sessions.lock().await.get_mut(&sync_id) {
+                    Some(s) => s.poll = Some(poll),
+                    // 期间已被 `lyrics.sync.stop` 移除。
+                    None => poll.abort(),
+                }
+            }
+
+            let mgr = self.clone();
+            let id = sync_id.clone();
+            tokio::spawn(async move {
+                while let Some(ev) = events.recv().await {
+                    let notif = match ev.with_sync_id(&id) {
+                        LyricsSyncEvent::LineChanged(n) => {
+                            chaos_daemon::DaemonNotif::LyricsLineChanged(n)
+                        }
+                        LyricsSyncEvent::WordProgress(n) => {
+                            chaos_daemon::DaemonNotif::LyricsWordProgress(n)
+                        }
+                    };
+                    if notif_tx.send(notif).is_err() {
+                        // 订阅的连接已断开。
+                        break;
+                    }
+                }
+                mgr.remove(&id).await;
+            });
+            Ok(LyricsSyncStartResult { sync_id })
+        }
+
+        async fn update(&self, params: LyricsSyncUpdateParams) -> Result<OkReply, String> {
+            let sessions = self.sessions.lock().await;
+            let s = sessions
+                .get(&params.sync_id)
+                .ok_or_else(|| "lyrics sync not found".to_string())?;
+            if let Some(original) = params.lyrics_original.as_deref() {
+                s.sync.set_lyrics(parse_sync_lyrics(
+                    original,
+                    params.lyrics_translation.as_deref(),
+                ));
+            }
+            if let Some(c) = params.clock {
+                s.sync.update_clock(Some(PlaybackClock {
+                    position_ms: c.position_ms,
+                    rate: c.rate.unwrap_or(1.0),
+                    paused: c.paused,
+                }));
+            }
+            Ok(OkReply { ok: true })
+        }
+
+        async fn remove(&self, sync_id: &str) -> bool {
+            let Some(s) = self.sessions.lock().await.remove(sync_id) else {
+                return false;
+            };
+            if let Some(poll) = s.poll {
+                poll.abort();
+            }
+            s.sync.stop().await;
+            true
+        }
+    }
+
     struct Svc {
         app: std::sync::Arc<ChaosApp>,
         music: Arc<MusicManager>,
@@ -1275,6 +1795,7 @@ mod win {
         tts: Arc<TtsManager>,
         llm: Arc<LlmManager>,
         voice_chat: Arc<VoiceChatManager>,
+        lyrics_sync: Arc<LyricsSyncManager>,
     }
 
     impl chaos_daemon::ChaosService for Svc {
@@ -1396,75 +1917,136 @@ mod win {
             &self,
             params: LyricsSearchParams,
         ) -> Result<Vec<LyricsSearchResult>, String> {
-            let title = params.title.trim().to_string();
-            if title.is_empty() {
-                return Err("title is empty".to_string());
-            }
+            let (req, opt) = lyrics_request_from_params(params)?;
+            let items = lyrics::core::search(&req, opt)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(items.into_iter().map(map_lyrics_result_to_proto).collect())
+        }
 
-            let artist = params.artist.as_deref().unwrap_or("").trim().to_string();
-            let album = params.album.as_deref().unwrap_or("").trim().to_string();
+        async fn lyrics_search_diagnostics(
+            &self,
+            params: LyricsSearchParams,
+        ) -> Result<LyricsSearchDiagnosticsResult, String> {
+            let (req, opt) = lyrics_request_from_params(params)?;
+            let (items, report) = lyrics::core::search_with_report(&req, opt)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(LyricsSearchDiagnosticsResult {
+                results: items.into_iter().map(map_lyrics_result_to_proto).collect(),
+                report: map_lyrics_report_to_proto(report),
+            })
+        }
 
-            let term = if artist.is_empty() {
-                lyrics::model::LyricsSearchTerm::Keyword { keyword: title }
-            } else {
-                lyrics::model::LyricsSearchTerm::Info {
-                    title,
-                    artist,
-                    album: (!album.is_empty()).then_some(album),
+        async fn lyrics_search_stream(
+            &self,
+            params: LyricsSearchParams,
+            notif_tx: mpsc::UnboundedSender<chaos_daemon::DaemonNotif>,
+        ) -> Result<LyricsSearchStreamStartResult, String> {
+            use futures_util::StreamExt;
+
+            let (req, opt) = lyrics_request_from_params(params)?;
+            let mut events = lyrics::core::search_stream(&req, opt).map_err(|e| e.to_string())?;
+            let search_id = gen_session_id("lyrics");
+            let id = search_id.clone();
+            tokio::spawn(async move {
+                while let Some(ev) = events.next().await {
+                    let notif = match ev {
+                        lyrics::core::LyricsSearchEvent::Cached { results } => {
+                            chaos_daemon::DaemonNotif::LyricsSearchResult(LyricsSearchResultNotif {
+                                search_id: id.clone(),
+                                cached: true,
+                                service: None,
+                                results: results
+                                    .into_iter()
+                                    .map(map_lyrics_result_to_proto)
+                                    .collect(),
+                                report: None,
+                            })
+                        }
+                        lyrics::core::LyricsSearchEvent::Provider { report, results } => {
+                            chaos_daemon::DaemonNotif::LyricsSearchResult(LyricsSearchResultNotif {
+                                search_id: id.clone(),
+                                cached: false,
+                                service: Some(report.service.as_str().to_string()),
+                                results: results
+                                    .into_iter()
+                                    .map(map_lyrics_result_to_proto)
+                                    .collect(),
+                                report: Some(map_lyrics_provider_report_to_proto(report)),
+                            })
+                        }
+                        lyrics::core::LyricsSearchEvent::Done { results, report } => {
+                            chaos_daemon::DaemonNotif::LyricsSearchDone(LyricsSearchDoneNotif {
+                                search_id: id.clone(),
+                                results: results
+                                    .into_iter()
+                                    .map(map_lyrics_result_to_proto)
+                                    .collect(),
+                                report: map_lyrics_report_to_proto(report),
+                            })
+                        }
+                    };
+                    if notif_tx.send(notif).is_err() {
+                        // 连接已断开，不必继续搜索。
+                        return;
+                    }
                 }
-            };
+            });
+            Ok(LyricsSearchStreamStartResult { search_id })
+        }
 
-            let mut req = lyrics::model::LyricsSearchRequest::new(term);
-            req.duration_ms = params.duration_ms.filter(|v| *v > 0);
-            if let Some(limit) = params.limit {
-                req.limit = (limit as usize).clamp(1, 50);
-            }
+        async fn lyrics_pin(&self, params: LyricsPinParams) -> Result<OkReply, String> {
+            let req =
+                lyrics_cache_request(&params.title, params.artist.as_deref(), params.duration_ms)?;
+            let result = params.result.map(map_lyrics_result_to_core).transpose()?;
+            tokio::task::spawn_blocking(move || {
+                let cache = lyrics::cache::LyricsCache::global();
+                match result {
+                    Some(r) => cache.pin(&req, r),
+                    None => cache.unpin(&req),
+                }
+            })
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())?;
+            Ok(OkReply { ok: true })
+        }
 
-            let mut opt = lyrics::model::LyricsSearchOptions::default();
-            if let Some(v) = params.timeout_ms {
-                opt.timeout_ms = v.max(1);
-            }
-            if let Some(v) = params.strict_match {
-                opt.strict_match = v;
-            }
+        async fn lyrics_set_offset(
+            &self,
+            params: LyricsSetOffsetParams,
+        ) -> Result<OkReply, String> {
+            let req =
+                lyrics_cache_request(&params.title, params.artist.as_deref(), params.duration_ms)?;
+            let offset_ms = params.offset_ms;
+            tokio::task::spawn_blocking(move || {
+                lyrics::cache::LyricsCache::global().set_offset(&req, offset_ms)
+            })
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())?;
+            Ok(OkReply { ok: true })
+        }
 
-            if let Some(services) = params.services {
-                let mut out = Vec::new();
-                for s in services {
-                    let s = s.trim().to_string();
-                    if s.is_empty() {
-                        continue;
-                    }
-                    let svc = lyrics::model::LyricsService::from_str(&s).map_err(|e| e)?;
-                    out.push(svc);
-                }
-                if !out.is_empty() {
-                    opt.services = out;
-                }
-            }
+        async fn lyrics_sync_start(
+            &self,
+            params: LyricsSyncStartParams,
+            notif_tx: mpsc::UnboundedSender<chaos_daemon::DaemonNotif>,
+        ) -> Result<LyricsSyncStartResult, String> {
+            self.lyrics_sync.clone().start(params, notif_tx).await
+        }
 
-            let items = lyrics::core::search(&req, opt)
-                .await
-                .map_err(|e| e.to_string())?;
-            Ok(items
-                .into_iter()
-                .map(|x| LyricsSearchResult {
-                    service: x.service.as_str().to_string(),
-                    service_token: x.service_token,
-                    title: x.title,
-                    artist: x.artist,
-                    album: x.album,
-                    duration_ms: x.duration_ms,
-                    match_percentage: x.match_percentage,
-                    quality: x.quality,
-                    matched: x.matched,
-                    has_translation: x.has_translation,
-                    has_inline_timetags: x.has_inline_timetags,
-                    lyrics_original: x.lyrics_original,
-                    lyrics_translation: x.lyrics_translation,
-                    debug: x.debug,
-                })
-                .collect())
+        async fn lyrics_sync_update(
+            &self,
+            params: LyricsSyncUpdateParams,
+        ) -> Result<OkReply, String> {
+            self.lyrics_sync.update(params).await
+        }
+
+        async fn lyrics_sync_stop(&self, params: LyricsSyncStopParams) -> Result<OkReply, String> {
+            let ok = self.lyrics_sync.remove(&params.sync_id).await;
+            Ok(OkReply { ok })
         }
 
         async fn tts_sft_start(
@@ -1675,6 +2257,117 @@ mod win {
             Ok(out.into_iter().map(map_album_to_proto).collect())
         }
 
+        async fn music_search_playlists(
+            &self,
+            params: MusicSearchParams,
+        ) -> Result<Vec<MusicPlaylist>, String> {
+            let keyword = params.keyword.trim().to_string();
+            if keyword.is_empty() {
+                return Ok(vec![]);
+            }
+            let page = params.page.max(1);
+            let page_size = params.page_size.clamp(1, 50).max(1);
+            let svc = map_service_to_core(params.service);
+            let client = self.music.get_client().await;
+            let out = client
+                .search_playlists(svc, &keyword, page, page_size)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(out.into_iter().map(map_playlist_to_proto).collect())
+        }
+
+        async fn music_playlist_tracks(
+            &self,
+            params: MusicPlaylistTracksParams,
+        ) -> Result<Vec<MusicTrack>, String> {
+            let playlist_id = params.playlist_id.trim().to_string();
+            if playlist_id.is_empty() {
+                return Ok(vec![]);
+            }
+            let svc = map_service_to_core(params.service);
+            let auth = map_auth_to_core(params.auth);
+            let client = self.music.get_client().await;
+            let out = client
+                .playlist_tracks(svc, &playlist_id, &auth)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(out.into_iter().map(map_track_to_proto).collect())
+        }
+
+        async fn music_user_playlists(
+            &self,
+            params: MusicUserPlaylistsParams,
+        ) -> Result<Vec<MusicPlaylist>, String> {
+            let svc = map_service_to_core(params.service);
+            let auth = map_auth_to_core(params.auth);
+            let client = self.music.get_client().await;
+            let out = client
+                .user_playlists(svc, &auth)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(out.into_iter().map(map_playlist_to_proto).collect())
+        }
+
+        async fn music_track_detail(&self, params: MusicTrackParams) -> Result<MusicTrack, String> {
+            let svc = map_service_to_core(params.service);
+            let client = self.music.get_client().await;
+            let out = client
+                .track_detail(svc, params.track_id.trim())
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(map_track_to_proto(out))
+        }
+
+        async fn music_track_lyrics(
+            &self,
+            params: MusicTrackParams,
+        ) -> Result<MusicLyrics, String> {
+            let svc = map_service_to_core(params.service);
+            let client = self.music.get_client().await;
+            let out = client
+                .track_lyrics(svc, params.track_id.trim())
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(map_lyrics_to_proto(out))
+        }
+
+        async fn music_list_charts(
+            &self,
+            params: MusicListChartsParams,
+        ) -> Result<Vec<MusicChart>, String> {
+            let svc = map_service_to_core(params.service);
+            let client = self.music.get_client().await;
+            let out = client.list_charts(svc).await.map_err(|e| e.to_string())?;
+            Ok(out.into_iter().map(map_chart_to_proto).collect())
+        }
+
+        async fn music_chart_tracks(
+            &self,
+            params: MusicChartTracksParams,
+        ) -> Result<Vec<MusicTrack>, String> {
+            let svc = map_service_to_core(params.service);
+            let client = self.music.get_client().await;
+            let out = client
+                .chart_tracks(svc, params.chart_id.trim())
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(out.into_iter().map(map_track_to_proto).collect())
+        }
+
+        async fn music_daily_recommendations(
+            &self,
+            params: MusicDailyRecommendationsParams,
+        ) -> Result<Vec<MusicTrack>, String> {
+            let svc = map_service_to_core(params.service);
+            let auth = map_auth_to_core(params.auth);
+            let client = self.music.get_client().await;
+            let out = client
+                .daily_recommendations(svc, &auth)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(out.into_iter().map(map_track_to_proto).collect())
+        }
+
         async fn music_track_play_url(
             &self,
             params: chaos_proto::MusicTrackPlayUrlParams,
@@ -1694,7 +2387,7 @@ mod win {
 
             let client = self.music.get_client().await;
             let (url, ext) = client
-                .track_download_url(svc, &track_id, &q, &auth)
+                .track_play_url(svc, &track_id, &q, &auth)
                 .await
                 .map_err(|e| e.to_string())?;
             Ok(chaos_proto::MusicTrackPlayUrlResult { url, ext })
@@ -1764,6 +2457,8 @@ mod win {
                     message: Some("login session timeout".to_string()),
                     cookie: None,
                     kugou_user: None,
+                    netease_cookie: None,
+                    netease_user: None,
                 });
             }
 
@@ -1794,6 +2489,8 @@ mod win {
                     message: msg,
                     cookie: None,
                     kugou_user: None,
+                    netease_cookie: None,
+                    netease_user: None,
                 });
             }
 
@@ -1835,6 +2532,8 @@ mod win {
                 message: None,
                 cookie: Some(cookie),
                 kugou_user: None,
+                netease_cookie: None,
+                netease_user: None,
             })
         }
 
@@ -1950,6 +2649,8 @@ mod win {
                     message: Some("login session timeout".to_string()),
                     cookie: None,
                     kugou_user: None,
+                    netease_cookie: None,
+                    netease_user: None,
                 });
             }
 
@@ -1986,6 +2687,8 @@ mod win {
                         token: u.token,
                         userid: u.userid,
                     }),
+                    netease_cookie: None,
+                    netease_user: None,
                 });
             }
 
@@ -1995,290 +2698,182 @@ mod win {
                 message: None,
                 cookie: None,
                 kugou_user: None,
+                netease_cookie: None,
+                netease_user: None,
             })
         }
 
-        async fn music_download_start(
+        async fn music_netease_login_qr_create(
             &self,
-            params: MusicDownloadStartParams,
-        ) -> Result<MusicDownloadStartResult, String> {
-            let out_dir = params.options.out_dir.trim().to_string();
-            if out_dir.is_empty() {
-                return Err("options.outDir is empty".to_string());
-            }
-            let quality_id = params.options.quality_id.trim().to_string();
-            if quality_id.is_empty() {
-                return Err("options.qualityId is empty".to_string());
-            }
-
-            let cfg = map_provider_config_to_core(params.config);
-            self.music.set_config(cfg.clone()).await;
+            params: MusicLoginQrCreateParams,
+        ) -> Result<MusicLoginQr, String> {
             let client = self.music.get_client().await;
+            let cfg = self.music.get_cfg().await;
+            let qr =
+                music::providers::netease::netease_qr_create(&client.http, &cfg, client.timeout)
+                    .await
+                    .map_err(|e| e.to_string())?;
 
-            let mut auth = map_auth_to_core(params.auth);
-
-            let target_service = match &params.target {
-                MusicDownloadTarget::Track { track } => track.service,
-                MusicDownloadTarget::Album { service, .. } => *service,
-                MusicDownloadTarget::ArtistAll { service, .. } => *service,
-            };
-            if matches!(target_service, MusicService::Netease) && auth.netease_cookie.is_none() {
-                if let Ok(c) = music::providers::netease::fetch_anonymous_cookie(
-                    &client.http,
-                    &cfg,
-                    client.timeout,
-                )
-                .await
-                {
-                    auth.netease_cookie = Some(c);
-                }
+            let session_id = gen_session_id("neteaselogin");
+            let created_at_unix_ms = now_unix_ms();
+            {
+                let mut sessions = self.music.netease_sessions.lock().await;
+                sessions.insert(
+                    session_id.clone(),
+                    NeteaseLoginSession {
+                        created_at_ms: created_at_unix_ms,
+                        key: qr.key.clone(),
+                    },
+                );
             }
 
-            let mut items: Vec<(MusicTrack, Option<u32>)> = Vec::new();
-            match params.target {
-                MusicDownloadTarget::Track { track } => items.push((track, None)),
-                MusicDownloadTarget::Album { service, album_id } => {
-                    let tracks = client
-                        .album_tracks(map_service_to_core(service), &album_id)
-                        .await
-                        .map_err(|e| e.to_string())?;
-                    for (idx, t) in tracks.into_iter().enumerate() {
-                        items.push((map_track_to_proto(t), Some((idx as u32) + 1)));
-                    }
-                }
-                MusicDownloadTarget::ArtistAll { service, artist_id } => {
-                    let albums = client
-                        .artist_albums(map_service_to_core(service), &artist_id)
-                        .await
-                        .map_err(|e| e.to_string())?;
-                    let mut seen: HashSet<String> = HashSet::new();
-                    for alb in albums {
-                        let album_title = alb.title.clone();
-                        let tracks = client
-                            .album_tracks(map_service_to_core(service), &alb.id)
-                            .await
-                            .unwrap_or_default();
-                        for (idx, mut t) in tracks.into_iter().enumerate() {
-                            if !seen.insert(t.id.clone()) {
-                                continue;
-                            }
-                            if t.album.is_none() {
-                                t.album = Some(album_title.clone());
-                            }
-                            items.push((map_track_to_proto(t), Some((idx as u32) + 1)));
-                        }
-                    }
-                }
-            }
+            // 网易云只支持 App 扫码；loginType 仅原样回显。
+            Ok(MusicLoginQr {
+                session_id,
+                login_type: params.login_type,
+                mime: "image/png".to_string(),
+                base64: qr.image_base64,
+                identifier: qr.key,
+                created_at_unix_ms,
+            })
+        }
 
-            let total = u32::try_from(items.len()).unwrap_or(u32::MAX);
-            let status = MusicDownloadStatus {
-                done: total == 0,
-                totals: MusicDownloadTotals {
-                    total,
-                    done: 0,
-                    failed: 0,
-                    skipped: 0,
-                    canceled: 0,
-                },
-                jobs: items
-                    .iter()
-                    .enumerate()
-                    .map(|(i, (t, _))| MusicDownloadJobResult {
-                        index: i as u32,
-                        track_id: Some(t.id.clone()),
-                        state: MusicJobState::Pending,
-                        path: None,
-                        bytes: None,
-                        error: None,
-                    })
-                    .collect(),
-            };
+        async fn music_netease_login_qr_poll(
+            &self,
+            params: MusicLoginQrPollParams,
+        ) -> Result<MusicLoginQrPollResult, String> {
+            use music::providers::netease::{self, NeteaseQrStatus};
 
-            let session_id = gen_session_id("musicdl");
-            let status = Arc::new(Mutex::new(status));
-            let cancel = Arc::new(AtomicBool::new(false));
+            let sid = params.session_id.trim().to_string();
+            if sid.is_empty() {
+                return Err("sessionId is empty".to_string());
+            }
 
-            let out_dir = PathBuf::from(out_dir);
-            let opts = params.options;
-            let core_auth = auth.clone();
-            let req_quality = quality_id;
-            let path_template = opts
-                .path_template
-                .as_deref()
-                .map(|s| s.trim())
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string());
+            let mut sessions = self.music.netease_sessions.lock().await;
+            let Some(sess) = sessions.get(&sid) else {
+                return Err("session not found".to_string());
+            };
+            let mut out = MusicLoginQrPollResult {
+                session_id: sid.clone(),
+                state: MusicLoginQrState::Scan,
+                message: None,
+                cookie: None,
+                kugou_user: None,
+                netease_cookie: None,
+                netease_user: None,
+            };
+            if now_unix_ms().saturating_sub(sess.created_at_ms) > 5 * 60 * 1000 {
+                sessions.remove(&sid);
+                out.state = MusicLoginQrState::Timeout;
+                out.message = Some("login session timeout".to_string());
+                return Ok(out);
+            }
 
-            let st = Arc::clone(&status);
-            let cancel_flag = Arc::clone(&cancel);
-            let handle = tokio::spawn(async move {
-                if items.is_empty() {
-                    let mut s = st.lock().await;
-                    s.done = true;
-                    return;
+            let client = self.music.get_client().await;
+            let cfg = self.music.get_cfg().await;
+            let status = netease::netease_qr_poll(&client.http, &cfg, &sess.key, client.timeout)
+                .await
+                .map_err(|e| e.to_string())?;
+            match status {
+                NeteaseQrStatus::Waiting => {}
+                NeteaseQrStatus::Scanned { nickname } => {
+                    out.state = MusicLoginQrState::Confirm;
+                    out.message = nickname;
                 }
-
-                let concurrency = opts.concurrency.max(1).min(16) as usize;
-                let retries = opts.retries.min(10);
-                let overwrite = opts.overwrite;
-
-                let (tx, rx) = tokio::sync::mpsc::channel::<(u32, MusicTrack, Option<u32>)>(
-                    items.len().max(1),
-                );
-                for (idx, (t, no)) in items.into_iter().enumerate() {
-                    let _ = tx.send((idx as u32, t, no)).await;
+                NeteaseQrStatus::Expired => {
+                    sessions.remove(&sid);
+                    out.state = MusicLoginQrState::Timeout;
+                    out.message = Some("qrcode expired".to_string());
+                }
+                NeteaseQrStatus::Done { cookie } => {
+                    sessions.remove(&sid);
+                    out.state = MusicLoginQrState::Done;
+                    // Best-effort：账号信息查询失败不影响登录结果。
+                    out.netease_user =
+                        netease::account_info(&client.http, &cfg, &cookie, client.timeout)
+                            .await
+                            .ok()
+                            .map(map_netease_user_to_proto);
+                    out.netease_cookie = Some(cookie);
                 }
-                drop(tx);
-                let rx = Arc::new(Mutex::new(rx));
-
-                let mut joinset = tokio::task::JoinSet::new();
-                for _ in 0..concurrency {
-                    let rx = Arc::clone(&rx);
-                    let st = Arc::clone(&st);
-                    let cancel = Arc::clone(&cancel_flag);
-                    let client = client.clone();
-                    let auth = core_auth.clone();
-                    let out_dir = out_dir.clone();
-                    let req_quality = req_quality.clone();
-                    let path_template = path_template.clone();
-                    joinset.spawn(async move {
-                        loop {
-                            if cancel.load(Ordering::Relaxed) {
-                                return;
-                            }
-                            let next = {
-                                let mut locked = rx.lock().await;
-                                locked.recv().await
-                            };
-                            let Some((index, track, track_no)) = next else {
-                                return;
-                            };
+                NeteaseQrStatus::Other { code, message } => {
+                    out.state = MusicLoginQrState::Other;
+                    out.message = Some(message.unwrap_or_else(|| format!("code={code}")));
+                }
+            }
+            Ok(out)
+        }
 
-                            {
-                                let mut s = st.lock().await;
-                                if let Some(job) = s.jobs.get_mut(index as usize) {
-                                    job.state = MusicJobState::Running;
-                                }
-                            }
+        async fn music_netease_refresh_cookie(
+            &self,
+            params: MusicNeteaseRefreshCookieParams,
+        ) -> Result<MusicNeteaseCookieResult, String> {
+            let client = self.music.get_client().await;
+            let cfg = self.music.get_cfg().await;
+            let cookie = music::providers::netease::refresh_cookie(
+                &client.http,
+                &cfg,
+                &params.cookie,
+                client.timeout,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            let user = music::providers::netease::account_info(
+                &client.http,
+                &cfg,
+                &cookie,
+                client.timeout,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            Ok(MusicNeteaseCookieResult {
+                cookie,
+                user: map_netease_user_to_proto(user),
+            })
+        }
 
-                            let core_svc = map_service_to_core(track.service);
-                            let chosen_quality = choose_quality_id(&track, &req_quality)
-                                .unwrap_or_else(|| req_quality.clone());
-
-                            let res: Result<(PathBuf, Option<u64>, Option<String>), String> =
-                                async {
-                                    let (url, ext) = client
-                                        .track_download_url(
-                                            core_svc,
-                                            &track.id,
-                                            &chosen_quality,
-                                            &auth,
-                                        )
-                                        .await
-                                        .map_err(|e| e.to_string())?;
-                                    let path = if let Some(tpl) = path_template.as_deref() {
-                                        music::util::build_track_path_by_template(
-                                            &out_dir,
-                                            tpl,
-                                            &track.artists,
-                                            track.album.as_deref(),
-                                            track_no,
-                                            &track.title,
-                                            &ext,
-                                        )
-                                    } else {
-                                        music::util::build_track_path(
-                                            &out_dir,
-                                            &track.artists,
-                                            track.album.as_deref(),
-                                            track_no,
-                                            &track.title,
-                                            &ext,
-                                        )
-                                    };
-                                    if path.exists() && !overwrite {
-                                        return Ok((
-                                            path,
-                                            None,
-                                            Some("skipped: target exists".to_string()),
-                                        ));
-                                    }
-                                    let bytes = music::download::download_url_to_file(
-                                        &client.http,
-                                        &url,
-                                        &path,
-                                        client.timeout,
-                                        retries,
-                                        overwrite,
-                                    )
-                                    .await
-                                    .map_err(|e| e.to_string())?;
-                                    Ok((path, Some(bytes), None))
-                                }
-                                .await;
-
-                            match res {
-                                Ok((path, bytes, skipped_msg)) => {
-                                    if skipped_msg.is_none() && bytes.is_some() {
-                                        let _ = try_download_lyrics_for_track(
-                                            &client.http,
-                                            &track,
-                                            &path,
-                                            overwrite,
-                                        )
-                                        .await;
-                                    }
-                                    let mut s = st.lock().await;
-                                    let mut inc_skipped: u32 = 0;
-                                    let mut inc_done: u32 = 0;
-                                    if let Some(job) = s.jobs.get_mut(index as usize) {
-                                        job.path = Some(path.to_string_lossy().to_string());
-                                        job.bytes = bytes;
-                                        job.error = skipped_msg;
-                                        if job.error.is_some() {
-                                            job.state = MusicJobState::Skipped;
-                                            inc_skipped = 1;
-                                        } else {
-                                            job.state = MusicJobState::Done;
-                                            inc_done = 1;
-                                        }
-                                    }
-                                    s.totals.skipped = s.totals.skipped.saturating_add(inc_skipped);
-                                    s.totals.done = s.totals.done.saturating_add(inc_done);
-                                }
-                                Err(e) => {
-                                    let mut s = st.lock().await;
-                                    let mut inc_failed: u32 = 0;
-                                    if let Some(job) = s.jobs.get_mut(index as usize) {
-                                        job.state = MusicJobState::Failed;
-                                        job.error = Some(e);
-                                        inc_failed = 1;
-                                    }
-                                    s.totals.failed = s.totals.failed.saturating_add(inc_failed);
-                                }
-                            }
-                        }
-                    });
-                }
+        async fn music_kuwo_login_token(
+            &self,
+            params: MusicKuwoLoginTokenParams,
+        ) -> Result<KuwoUserInfo, String> {
+            let client = self.music.get_client().await;
+            let user = music::providers::kuwo::login_with_token(
+                &client.http,
+                &params.uid,
+                &params.sid,
+                client.timeout,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            Ok(map_kuwo_user_to_proto(user))
+        }
 
-                while joinset.join_next().await.is_some() {}
-                let mut s = st.lock().await;
-                s.done = true;
-            });
+        async fn music_download_start(
+            &self,
+            params: MusicDownloadStartParams,
+        ) -> Result<MusicDownloadStartResult, String> {
+            let cfg = map_provider_config_to_core(params.config);
+            self.music.set_config(cfg).await;
+            let client = self.music.get_client().await;
 
-            {
-                let mut downloads = self.music.downloads.lock().await;
-                downloads.insert(
-                    session_id.clone(),
-                    DownloadSession {
-                        status,
-                        cancel,
-                        handle,
-                    },
-                );
-            }
+            let mut options =
+                music::batch::DownloadJobOptions::new(map_download_options_to_core(params.options));
+            options.task = Some(self.music.scheduler.task(options.download.priority));
+            let job = music::batch::DownloadJob::start(
+                client,
+                map_download_target_to_core(params.target),
+                map_auth_to_core(params.auth),
+                options,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
 
+            let session_id = gen_session_id("musicdl");
+            self.music
+                .downloads
+                .lock()
+                .await
+                .insert(session_id.clone(), job);
             Ok(MusicDownloadStartResult { session_id })
         }
 
@@ -2291,10 +2886,10 @@ mod win {
                 return Err("sessionId is empty".to_string());
             }
             let downloads = self.music.downloads.lock().await;
-            let Some(sess) = downloads.get(&sid) else {
+            let Some(job) = downloads.get(&sid) else {
                 return Err("download session not found".to_string());
             };
-            Ok(sess.status.lock().await.clone())
+            Ok(map_download_status_to_proto(job.status()))
         }
 
         async fn music_download_cancel(
@@ -2305,31 +2900,74 @@ mod win {
             if sid.is_empty() {
                 return Err("sessionId is empty".to_string());
             }
-
-            let mut downloads = self.music.downloads.lock().await;
-            let Some(sess) = downloads.get_mut(&sid) else {
+            let downloads = self.music.downloads.lock().await;
+            let Some(job) = downloads.get(&sid) else {
                 return Err("download session not found".to_string());
             };
+            job.cancel();
+            Ok(OkReply { ok: true })
+        }
 
-            sess.cancel.store(true, Ordering::Relaxed);
-            sess.handle.abort();
-
-            let mut st = sess.status.lock().await;
-            if !st.done {
-                let mut canceled: u32 = 0;
-                for job in st.jobs.iter_mut() {
-                    if matches!(job.state, MusicJobState::Pending | MusicJobState::Running) {
-                        job.state = MusicJobState::Canceled;
-                        canceled = canceled.saturating_add(1);
-                    }
-                }
-                st.totals.canceled = st.totals.canceled.saturating_add(canceled);
-                st.done = true;
+        async fn music_download_pause(
+            &self,
+            params: MusicDownloadPauseParams,
+        ) -> Result<OkReply, String> {
+            let sid = params.session_id.trim().to_string();
+            if sid.is_empty() {
+                return Err("sessionId is empty".to_string());
             }
+            let downloads = self.music.downloads.lock().await;
+            let Some(job) = downloads.get(&sid) else {
+                return Err("download session not found".to_string());
+            };
+            job.pause();
+            Ok(OkReply { ok: true })
+        }
 
+        async fn music_download_resume(
+            &self,
+            params: MusicDownloadResumeParams,
+        ) -> Result<OkReply, String> {
+            let sid = params.session_id.trim().to_string();
+            if sid.is_empty() {
+                return Err("sessionId is empty".to_string());
+            }
+            let downloads = self.music.downloads.lock().await;
+            let Some(job) = downloads.get(&sid) else {
+                return Err("download session not found".to_string());
+            };
+            job.resume();
             Ok(OkReply { ok: true })
         }
 
+        async fn download_scheduler_set(
+            &self,
+            params: DownloadSchedulerSetParams,
+        ) -> Result<DownloadSchedulerState, String> {
+            // music 与 bili 共用同一个调度器。
+            let sched = &self.music.scheduler;
+            if let Some(n) = params.max_connections {
+                sched.set_max_connections(n);
+            }
+            if let Some(bps) = params.rate_limit_bps {
+                sched.set_rate_limit(Some(bps));
+            }
+            match params.paused {
+                Some(true) => sched.pause(),
+                Some(false) => sched.resume(),
+                None => {}
+            }
+            let s = sched.stats();
+            Ok(DownloadSchedulerState {
+                max_connections: s.max_connections,
+                rate_limit_bps: s.rate_limit_bps,
+                paused: s.paused,
+                active: s.active,
+                queued: s.queued,
+                tasks: s.tasks,
+            })
+        }
+
         async fn bili_login_qr_create(
             &self,
             _params: BiliLoginQrCreateParams,
@@ -2643,30 +3281,8 @@ mod win {
                         .await
                         .map_err(|e| e.to_string())?;
 
-                    let pages = view
-                        .pages
-                        .into_iter()
-                        .map(|p| BiliPage {
-                            page_number: p.page_number,
-                            cid: p.cid,
-                            page_title: p.page_title,
-                            duration_s: p.duration_s,
-                            dimension: p.dimension,
-                        })
-                        .collect::<Vec<_>>();
-
                     Ok(BiliParseResult {
-                        videos: vec![BiliParsedVideo {
-                            aid: view.aid,
-                            bvid: view.bvid,
-                            title: view.title,
-                            desc: view.desc,
-                            pic: view.pic,
-                            owner_name: view.owner_name,
-                            owner_mid: view.owner_mid,
-                            pub_time_unix_s: view.pub_time_unix_s,
-                            pages,
-                        }],
+                        videos: vec![map_bili_view(view)],
                     })
                 }
                 bili_video::parse::ParsedInput::BangumiEpisode { ep_id } => {
@@ -2749,7 +3365,60 @@ mod win {
                         }],
                     })
                 }
+                bili_video::parse::ParsedInput::Audio { sid } => {
+                    let info = bili_video::audio::fetch_song_info(&client, &sid, cookie)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    Ok(BiliParseResult {
+                        videos: vec![map_bili_view(info.into_view_info())],
+                    })
+                }
+                bili_video::parse::ParsedInput::LiveReplay { rid } => {
+                    let info = bili_video::live_replay::fetch_replay_info(&client, &rid, cookie)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    Ok(BiliParseResult {
+                        videos: vec![map_bili_view(info.into_view_info())],
+                    })
+                }
+            }
+        }
+
+        async fn bili_live_replay_list(
+            &self,
+            params: BiliLiveReplayListParams,
+        ) -> Result<BiliLiveReplayListResult, String> {
+            let room_id = params.room_id.trim().to_string();
+            if room_id.is_empty() {
+                return Err("roomId is empty".to_string());
             }
+            let auth = map_bili_auth_to_core(params.auth);
+            let client = self.bili.get_client().await;
+            let page = bili_video::live_replay::list_replays(
+                &client,
+                &room_id,
+                params.page,
+                params.page_size,
+                auth.cookie.as_deref(),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            Ok(BiliLiveReplayListResult {
+                items: page
+                    .items
+                    .into_iter()
+                    .map(|r| BiliLiveReplay {
+                        rid: r.rid,
+                        room_id: r.room_id,
+                        title: r.title,
+                        cover: r.cover,
+                        start_unix_s: r.start_unix_s,
+                        end_unix_s: r.end_unix_s,
+                        duration_ms: r.duration_ms,
+                    })
+                    .collect(),
+                total: page.total,
+            })
         }
 
         async fn bili_download_start(
@@ -2789,6 +3458,8 @@ mod win {
             let status2 = status.clone();
             let cancel2 = cancel.clone();
             let params2 = params.clone();
+            let dl_task = mgr.scheduler.task(params.options.priority);
+            let sched_task = dl_task.clone();
             let handle = tokio::spawn(async move {
                 fn recompute_totals(st: &mut BiliDownloadStatus) {
                     let mut done: u32 = 0;
@@ -3009,7 +3680,7 @@ mod win {
                         "mp4",
                     );
 
-                    if out_mp4.exists() {
+                    if out_mp4.exists() && !params2.options.overwrite {
                         let mut st = status2.lock().await;
                         if let Some(j) = st.jobs.get_mut(job_idx) {
                             j.state = BiliJobState::Skipped;
@@ -3060,11 +3731,16 @@ mod win {
                         &v.base_url,
                         &headers,
                         &video_tmp,
-                        params2.options.concurrency,
-                        params2.options.retries,
-                        true,
-                        Some(&cancel2),
-                        Some(cb),
+                        bili_video::download::DownloadOptions {
+                            concurrency: params2.options.concurrency,
+                            retries: params2.options.retries,
+                            overwrite: true,
+                        },
+                        bili_video::download::DownloadCtx {
+                            cancel: Some(cancel2.clone()),
+                            progress: Some(cb),
+                            task: Some(dl_task.clone()),
+                        },
                     );
                     tokio::pin!(dl);
                     let video_res = loop {
@@ -3128,11 +3804,16 @@ mod win {
                         &a.base_url,
                         &headers,
                         &audio_tmp,
-                        params2.options.concurrency,
-                        params2.options.retries,
-                        true,
-                        Some(&cancel2),
-                        Some(cb),
+                        bili_video::download::DownloadOptions {
+                            concurrency: params2.options.concurrency,
+                            retries: params2.options.retries,
+                            overwrite: true,
+                        },
+                        bili_video::download::DownloadCtx {
+                            cancel: Some(cancel2.clone()),
+                            progress: Some(cb),
+                            task: Some(dl_task.clone()),
+                        },
                     );
                     tokio::pin!(dl);
                     let audio_res = loop {
@@ -3173,26 +3854,21 @@ mod win {
                                 j.phase = BiliJobPhase::Subtitle;
                             }
                         }
-                        if let Ok(subs) = bili_video::subtitle::fetch_subtitles(
-                            &client, &view.bvid, &page.cid, cookie,
+                        if let Ok(paths) = bili_video::subtitle::save_subtitles(
+                            &client,
+                            bili_video::subtitle::SubtitleVideo {
+                                aid: &view.aid,
+                                bvid: &view.bvid,
+                                cid: &page.cid,
+                            },
+                            cookie,
+                            &map_bili_subtitle_options(&params2.options),
+                            &out_mp4,
+                            Some(&cancel2),
                         )
                         .await
                         {
-                            for s in subs {
-                                if cancel2.load(Ordering::Relaxed) {
-                                    break;
-                                }
-                                if let Ok(srt) = bili_video::subtitle::download_subtitle_srt(
-                                    &client, &s.url, cookie,
-                                )
-                                .await
-                                {
-                                    let lang = music::util::sanitize_component(&s.lang);
-                                    let path = out_mp4.with_extension(format!("{lang}.srt"));
-                                    let _ = tokio::fs::write(&path, srt).await;
-                                    sub_paths.push(path);
-                                }
-                            }
+                            sub_paths = paths;
                         }
                     }
 
@@ -3228,11 +3904,24 @@ mod win {
                         }
                     }
 
+                    let opts = &params2.options;
+                    let cover_tmp = out_mp4.with_extension("cover.jpg");
+                    let meta = bili_video::mux::build_metadata(
+                        &client,
+                        bili_video::mux::MetadataSource::from_view(&view, page),
+                        map_bili_embed_options(opts),
+                        &cover_tmp,
+                        cookie,
+                    )
+                    .await;
                     let mux_res = bili_video::mux::mux_ffmpeg(
-                        &params2.options.ffmpeg_path,
-                        &video_tmp,
-                        &audio_tmp,
-                        &sub_paths,
+                        &opts.ffmpeg_path,
+                        bili_video::mux::MuxInputs {
+                            video: &video_tmp,
+                            audio: &audio_tmp,
+                            subtitles: &sub_paths,
+                            metadata: meta.as_ref(),
+                        },
                         &out_mp4,
                         true,
                         Some(&cancel2),
@@ -3242,6 +3931,7 @@ mod win {
                     // cleanup tmp
                     let _ = tokio::fs::remove_file(&video_tmp).await;
                     let _ = tokio::fs::remove_file(&audio_tmp).await;
+                    let _ = tokio::fs::remove_file(&cover_tmp).await;
 
                     match mux_res {
                         Ok(()) => {
@@ -3293,6 +3983,7 @@ mod win {
                         api: params.api,
                         status,
                         cancel,
+                        task: sched_task,
                         handle,
                     },
                 );
@@ -3432,6 +4123,8 @@ mod win {
             let options = params.options.clone();
             let out_dir2 = out_dir.clone();
             let input2 = input.clone();
+            let dl_task = mgr.scheduler.task(options.priority);
+            let sched_task = dl_task.clone();
 
             let handle = tokio::spawn(async move {
                 fn recompute_totals(st: &mut BiliDownloadStatus) {
@@ -3482,6 +4175,9 @@ mod win {
                     video_title: String,
                     owner_name: String,
                     owner_mid: String,
+                    desc: String,
+                    cover_url: Option<String>,
+                    pub_time_unix_s: Option<i64>,
                 }
 
                 let (all_count, jobs): (usize, Vec<JobInput>) =
@@ -3535,6 +4231,9 @@ mod win {
                                         video_title: view.title.clone(),
                                         owner_name: view.owner_name.clone().unwrap_or_default(),
                                         owner_mid: view.owner_mid.clone().unwrap_or_default(),
+                                        desc: view.desc.clone().unwrap_or_default(),
+                                        cover_url: view.pic.clone(),
+                                        pub_time_unix_s: view.pub_time_unix_s,
                                     }
                                 })
                                 .collect::<Vec<_>>();
@@ -3592,6 +4291,9 @@ mod win {
                                     },
                                     owner_name: "".to_string(),
                                     owner_mid: "".to_string(),
+                                    desc: String::new(),
+                                    cover_url: e.cover.clone().or_else(|| season.cover.clone()),
+                                    pub_time_unix_s: None,
                                 })
                                 .collect::<Vec<_>>();
                             (season.episodes.len(), jobs)
@@ -3648,10 +4350,202 @@ mod win {
                                     },
                                     owner_name: "".to_string(),
                                     owner_mid: "".to_string(),
+                                    desc: String::new(),
+                                    cover_url: e.cover.clone().or_else(|| season.cover.clone()),
+                                    pub_time_unix_s: None,
                                 })
                                 .collect::<Vec<_>>();
                             (season.episodes.len(), jobs)
                         }
+                        Ok(bili_video::parse::ParsedInput::Audio { sid }) => {
+                            // 音频区：单文件直下（无 mux），路径沿用音乐下载模板。
+                            {
+                                let mut st = status2.lock().await;
+                                st.jobs = vec![BiliDownloadJobStatus {
+                                    index: 0,
+                                    page_number: Some(1),
+                                    cid: Some(sid.clone()),
+                                    title: format!("au{sid}"),
+                                    state: BiliJobState::Running,
+                                    phase: BiliJobPhase::Audio,
+                                    bytes_downloaded: 0,
+                                    bytes_total: None,
+                                    speed_bps: None,
+                                    path: None,
+                                    error: None,
+                                }];
+                                st.totals.total = 1;
+                            }
+                            let prog_downloaded = Arc::new(std::sync::atomic::AtomicU64::new(0));
+                            let prog_total = Arc::new(std::sync::atomic::AtomicU64::new(0));
+                            let prog_has_total = Arc::new(AtomicBool::new(false));
+                            let cb: bili_video::download::ProgressCb = {
+                                let prog_downloaded = prog_downloaded.clone();
+                                let prog_total = prog_total.clone();
+                                let prog_has_total = prog_has_total.clone();
+                                Arc::new(move |d, t| {
+                                    prog_downloaded.store(d, Ordering::Relaxed);
+                                    if let Some(tt) = t {
+                                        prog_total.store(tt, Ordering::Relaxed);
+                                        prog_has_total.store(true, Ordering::Relaxed);
+                                    }
+                                })
+                            };
+                            let opt = bili_video::audio::AudioDownloadOptions {
+                                out_dir: std::path::PathBuf::from(options.out_dir.trim()),
+                                path_template: options.audio_path_template.clone(),
+                                overwrite: options.overwrite,
+                                retries: options.retries,
+                                download_lyrics: true,
+                                task: Some(dl_task.clone()),
+                            };
+                            let mut tick =
+                                tokio::time::interval(std::time::Duration::from_millis(260));
+                            let dl = bili_video::audio::download_audio(
+                                &client,
+                                &sid,
+                                cookie,
+                                &opt,
+                                Some(&cancel2),
+                                Some(cb),
+                            );
+                            tokio::pin!(dl);
+                            let res = loop {
+                                tokio::select! {
+                                    r = &mut dl => break r,
+                                    _ = tick.tick() => {
+                                        let d = prog_downloaded.load(Ordering::Relaxed);
+                                        let t = prog_has_total.load(Ordering::Relaxed).then(|| prog_total.load(Ordering::Relaxed));
+                                        let mut st = status2.lock().await;
+                                        if let Some(j) = st.jobs.get_mut(0) { j.bytes_downloaded = d; j.bytes_total = t; }
+                                    }
+                                }
+                            };
+                            let mut st = status2.lock().await;
+                            if let Some(j) = st.jobs.get_mut(0) {
+                                match res {
+                                    Ok(r) => {
+                                        j.state = if r.skipped {
+                                            BiliJobState::Skipped
+                                        } else {
+                                            BiliJobState::Done
+                                        };
+                                        j.title = r.info.title;
+                                        j.path = Some(r.path.to_string_lossy().to_string());
+                                        if r.skipped {
+                                            j.error = Some("target exists".to_string());
+                                        }
+                                    }
+                                    Err(e) => {
+                                        j.state = if cancel2.load(Ordering::Relaxed) {
+                                            BiliJobState::Canceled
+                                        } else {
+                                            BiliJobState::Failed
+                                        };
+                                        j.error = Some(e.to_string());
+                                    }
+                                }
+                            }
+                            recompute_totals(&mut st);
+                            st.done = true;
+                            return;
+                        }
+                        Ok(bili_video::parse::ParsedInput::LiveReplay { rid }) => {
+                            // 直播回放：分段下载后由 ffmpeg 合并（skipMux 时保留 FLV 分段），文件名沿用 filePattern（cid 为 rid）。
+                            {
+                                let mut st = status2.lock().await;
+                                st.jobs = vec![BiliDownloadJobStatus {
+                                    index: 0,
+                                    page_number: Some(1),
+                                    cid: Some(rid.clone()),
+                                    title: rid.clone(),
+                                    state: BiliJobState::Running,
+                                    phase: BiliJobPhase::Video,
+                                    bytes_downloaded: 0,
+                                    bytes_total: None,
+                                    speed_bps: None,
+                                    path: None,
+                                    error: None,
+                                }];
+                                st.totals.total = 1;
+                            }
+                            let prog_downloaded = Arc::new(std::sync::atomic::AtomicU64::new(0));
+                            let prog_total = Arc::new(std::sync::atomic::AtomicU64::new(0));
+                            let prog_has_total = Arc::new(AtomicBool::new(false));
+                            let cb: bili_video::download::ProgressCb = {
+                                let prog_downloaded = prog_downloaded.clone();
+                                let prog_total = prog_total.clone();
+                                let prog_has_total = prog_has_total.clone();
+                                Arc::new(move |d, t| {
+                                    prog_downloaded.store(d, Ordering::Relaxed);
+                                    if let Some(tt) = t {
+                                        prog_total.store(tt, Ordering::Relaxed);
+                                        prog_has_total.store(true, Ordering::Relaxed);
+                                    }
+                                })
+                            };
+                            let opt = bili_video::live_replay::ReplayDownloadOptions {
+                                out_dir: std::path::PathBuf::from(&out_dir2),
+                                file_pattern: options.file_pattern.clone(),
+                                multi_file_pattern: options.multi_file_pattern.clone(),
+                                ffmpeg_path: options.ffmpeg_path.clone(),
+                                concurrency: options.concurrency,
+                                retries: options.retries,
+                                overwrite: options.overwrite,
+                                skip_mux: options.skip_mux,
+                                task: Some(dl_task.clone()),
+                            };
+                            let mut tick =
+                                tokio::time::interval(std::time::Duration::from_millis(260));
+                            let dl = bili_video::live_replay::download_replay(
+                                &client,
+                                &rid,
+                                cookie,
+                                &opt,
+                                Some(&cancel2),
+                                Some(cb),
+                            );
+                            tokio::pin!(dl);
+                            let res = loop {
+                                tokio::select! {
+                                    r = &mut dl => break r,
+                                    _ = tick.tick() => {
+                                        let d = prog_downloaded.load(Ordering::Relaxed);
+                                        let t = prog_has_total.load(Ordering::Relaxed).then(|| prog_total.load(Ordering::Relaxed));
+                                        let mut st = status2.lock().await;
+                                        if let Some(j) = st.jobs.get_mut(0) { j.bytes_downloaded = d; j.bytes_total = t; }
+                                    }
+                                }
+                            };
+                            let mut st = status2.lock().await;
+                            if let Some(j) = st.jobs.get_mut(0) {
+                                match res {
+                                    Ok(r) => {
+                                        j.title = r.info.title;
+                                        j.path = Some(r.path.to_string_lossy().to_string());
+                                        if r.skipped {
+                                            j.state = BiliJobState::Skipped;
+                                            j.error = Some("target exists".to_string());
+                                        } else {
+                                            j.state = BiliJobState::Done;
+                                            j.phase = BiliJobPhase::Mux;
+                                            j.bytes_downloaded = r.bytes;
+                                        }
+                                    }
+                                    Err(e) => {
+                                        j.state = if cancel2.load(Ordering::Relaxed) {
+                                            BiliJobState::Canceled
+                                        } else {
+                                            BiliJobState::Failed
+                                        };
+                                        j.error = Some(e.to_string());
+                                    }
+                                }
+                            }
+                            recompute_totals(&mut st);
+                            st.done = true;
+                            return;
+                        }
                         Err(e) => {
                             let mut st = status2.lock().await;
                             st.done = true;
@@ -3907,7 +4801,7 @@ mod win {
                         "mp4",
                     );
 
-                    if out_mp4.exists() {
+                    if out_mp4.exists() && !options.overwrite {
                         let mut st = status2.lock().await;
                         if let Some(j) = st.jobs.get_mut(job_idx) {
                             j.state = BiliJobState::Skipped;
@@ -3994,11 +4888,16 @@ mod win {
                         &v.base_url,
                         &headers,
                         &video_tmp,
-                        options.concurrency,
-                        options.retries,
-                        true,
-                        Some(&cancel2),
-                        Some(video_prog.clone()),
+                        bili_video::download::DownloadOptions {
+                            concurrency: options.concurrency,
+                            retries: options.retries,
+                            overwrite: true,
+                        },
+                        bili_video::download::DownloadCtx {
+                            cancel: Some(cancel2.clone()),
+                            progress: Some(video_prog.clone()),
+                            task: Some(dl_task.clone()),
+                        },
                     )
                     .await;
 
@@ -4021,11 +4920,16 @@ mod win {
                                     u,
                                     &headers,
                                     &video_tmp,
-                                    options.concurrency,
-                                    options.retries,
-                                    true,
-                                    Some(&cancel2),
-                                    Some(video_prog.clone()),
+                                    bili_video::download::DownloadOptions {
+                                        concurrency: options.concurrency,
+                                        retries: options.retries,
+                                        overwrite: true,
+                                    },
+                                    bili_video::download::DownloadCtx {
+                                        cancel: Some(cancel2.clone()),
+                                        progress: Some(video_prog.clone()),
+                                        task: Some(dl_task.clone()),
+                                    },
                                 )
                                 .await
                                 {
@@ -4082,11 +4986,16 @@ mod win {
                         &a.base_url,
                         &headers,
                         &audio_tmp,
-                        options.concurrency,
-                        options.retries,
-                        true,
-                        Some(&cancel2),
-                        Some(audio_prog.clone()),
+                        bili_video::download::DownloadOptions {
+                            concurrency: options.concurrency,
+                            retries: options.retries,
+                            overwrite: true,
+                        },
+                        bili_video::download::DownloadCtx {
+                            cancel: Some(cancel2.clone()),
+                            progress: Some(audio_prog.clone()),
+                            task: Some(dl_task.clone()),
+                        },
                     )
                     .await;
                     let audio_res = match audio_res {
@@ -4107,11 +5016,16 @@ mod win {
                                     u,
                                     &headers,
                                     &audio_tmp,
-                                    options.concurrency,
-                                    options.retries,
-                                    true,
-                                    Some(&cancel2),
-                                    Some(audio_prog.clone()),
+                                    bili_video::download::DownloadOptions {
+                                        concurrency: options.concurrency,
+                                        retries: options.retries,
+                                        overwrite: true,
+                                    },
+                                    bili_video::download::DownloadCtx {
+                                        cancel: Some(cancel2.clone()),
+                                        progress: Some(audio_prog.clone()),
+                                        task: Some(dl_task.clone()),
+                                    },
                                 )
                                 .await
                                 {
@@ -4152,26 +5066,21 @@ mod win {
                             }
                         }
                         if !job.bvid.trim().is_empty() {
-                            if let Ok(subs) = bili_video::subtitle::fetch_subtitles(
-                                &client, &job.bvid, &job.cid, cookie,
+                            if let Ok(paths) = bili_video::subtitle::save_subtitles(
+                                &client,
+                                bili_video::subtitle::SubtitleVideo {
+                                    aid: &job.aid,
+                                    bvid: &job.bvid,
+                                    cid: &job.cid,
+                                },
+                                cookie,
+                                &map_bili_subtitle_options(&options),
+                                &out_mp4,
+                                Some(&cancel2),
                             )
                             .await
                             {
-                                for s in subs {
-                                    if cancel2.load(Ordering::Relaxed) {
-                                        break;
-                                    }
-                                    if let Ok(srt) = bili_video::subtitle::download_subtitle_srt(
-                                        &client, &s.url, cookie,
-                                    )
-                                    .await
-                                    {
-                                        let lang = music::util::sanitize_component(&s.lang);
-                                        let path = out_mp4.with_extension(format!("{lang}.srt"));
-                                        let _ = tokio::fs::write(&path, srt).await;
-                                        sub_paths.push(path);
-                                    }
-                                }
+                                sub_paths = paths;
                             }
                         }
                     }
@@ -4214,11 +5123,32 @@ mod win {
                         }
                     }
 
+                    let cover_tmp = out_mp4.with_extension("cover.jpg");
+                    let meta = bili_video::mux::build_metadata(
+                        &client,
+                        bili_video::mux::MetadataSource {
+                            title: &job.video_title,
+                            page_title: (all_count > 1).then_some(job.title.as_str()),
+                            owner_name: &job.owner_name,
+                            bvid: &job.bvid,
+                            cid: &job.cid,
+                            description: &job.desc,
+                            pub_time_unix_s: job.pub_time_unix_s,
+                            cover_url: job.cover_url.as_deref(),
+                        },
+                        map_bili_embed_options(&options),
+                        &cover_tmp,
+                        cookie,
+                    )
+                    .await;
                     let mux_res = bili_video::mux::mux_ffmpeg(
                         &options.ffmpeg_path,
-                        &video_tmp,
-                        &audio_tmp,
-                        &sub_paths,
+                        bili_video::mux::MuxInputs {
+                            video: &video_tmp,
+                            audio: &audio_tmp,
+                            subtitles: &sub_paths,
+                            metadata: meta.as_ref(),
+                        },
                         &out_mp4,
                         true,
                         Some(&cancel2),
@@ -4227,6 +5157,7 @@ mod win {
 
                     let _ = tokio::fs::remove_file(&video_tmp).await;
                     let _ = tokio::fs::remove_file(&audio_tmp).await;
+                    let _ = tokio::fs::remove_file(&cover_tmp).await;
 
                     match mux_res {
                         Ok(()) => {
@@ -4284,6 +5215,7 @@ mod win {
                         api,
                         status,
                         cancel,
+                        task: sched_task,
                         handle,
                     },
                 );
@@ -4383,6 +5315,32 @@ mod win {
             .await
         }
 
+        async fn bili_task_pause(&self, params: BiliTaskPauseParams) -> Result<OkReply, String> {
+            let tid = params.task_id.trim().to_string();
+            if tid.is_empty() {
+                return Err("taskId is empty".to_string());
+            }
+            let downloads = self.bili.downloads.lock().await;
+            let Some(sess) = downloads.get(&tid) else {
+                return Err("task not found".to_string());
+            };
+            sess.task.pause();
+            Ok(OkReply { ok: true })
+        }
+
+        async fn bili_task_resume(&self, params: BiliTaskResumeParams) -> Result<OkReply, String> {
+            let tid = params.task_id.trim().to_string();
+            if tid.is_empty() {
+                return Err("taskId is empty".to_string());
+            }
+            let downloads = self.bili.downloads.lock().await;
+            let Some(sess) = downloads.get(&tid) else {
+                return Err("task not found".to_string());
+            };
+            sess.task.resume();
+            Ok(OkReply { ok: true })
+        }
+
         async fn bili_tasks_remove_finished(
             &self,
             params: BiliTasksRemoveFinishedParams,
@@ -4487,6 +5445,7 @@ mod win {
                 tts,
                 llm,
                 voice_chat,
+                lyrics_sync: Arc::new(LyricsSyncManager::new()),
             };
 
             run_jsonrpc_over_lsp(&svc, rw, auth_token)
@@ -4520,6 +5479,7 @@ mod win {
                 tts,
                 llm,
                 voice_chat,
+                lyrics_sync: Arc::new(LyricsSyncManager::new()),
             };
 
             run_jsonrpc_over_lsp(&svc, server, auth_token)
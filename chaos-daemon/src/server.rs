@@ -8,6 +8,8 @@ use chaos_proto::{
     BiliDownloadStartResult,
     BiliDownloadStatus,
     BiliDownloadStatusParams,
+    BiliLiveReplayListParams,
+    BiliLiveReplayListResult,
     BiliLoginQr,
     BiliLoginQrCreateParams,
     BiliLoginQrCreateV2Params,
@@ -23,6 +25,8 @@ use chaos_proto::{
     BiliTaskCancelParams,
     BiliTaskDetail,
     BiliTaskGetParams,
+    BiliTaskPauseParams,
+    BiliTaskResumeParams,
     BiliTasksGetParams,
     BiliTasksGetResult,
     BiliTasksRemoveFinishedParams,
@@ -32,6 +36,9 @@ use chaos_proto::{
     DanmakuConnectResult,
     DanmakuDisconnectParams,
     DanmakuFetchImageParams,
+    DownloadSchedulerSetParams,
+    DownloadSchedulerState,
+    KuwoUserInfo,
     LiveCloseParams,
     LiveDirCategoriesParams,
     LiveDirCategory,
@@ -46,13 +53,26 @@ use chaos_proto::{
     LlmChatResult,
     // llm + voice chat
     LlmConfigSetParams,
+    LyricsLineChangedNotif,
+    LyricsPinParams,
+    LyricsSearchDiagnosticsResult,
+    LyricsSearchDoneNotif,
     LyricsSearchParams,
     LyricsSearchResult,
+    LyricsSearchResultNotif,
+    LyricsSearchStreamStartResult,
+    LyricsSetOffsetParams,
+    LyricsSyncStartParams,
+    LyricsSyncStartResult,
+    LyricsSyncStopParams,
+    LyricsSyncUpdateParams,
+    LyricsWordProgressNotif,
     METHOD_BILI_CHECK_LOGIN,
     // bili
     METHOD_BILI_DOWNLOAD_CANCEL,
     METHOD_BILI_DOWNLOAD_START,
     METHOD_BILI_DOWNLOAD_STATUS,
+    METHOD_BILI_LIVE_REPLAY_LIST,
     METHOD_BILI_LOGIN_QR_CREATE,
     METHOD_BILI_LOGIN_QR_CREATE_V2,
     METHOD_BILI_LOGIN_QR_POLL,
@@ -62,12 +82,15 @@ use chaos_proto::{
     METHOD_BILI_TASK_ADD,
     METHOD_BILI_TASK_CANCEL,
     METHOD_BILI_TASK_GET,
+    METHOD_BILI_TASK_PAUSE,
+    METHOD_BILI_TASK_RESUME,
     METHOD_BILI_TASKS_GET,
     METHOD_BILI_TASKS_REMOVE_FINISHED,
     METHOD_DAEMON_PING,
     METHOD_DANMAKU_CONNECT,
     METHOD_DANMAKU_DISCONNECT,
     METHOD_DANMAKU_FETCH_IMAGE,
+    METHOD_DOWNLOAD_SCHEDULER_SET,
     METHOD_LIVE_CLOSE,
     METHOD_LIVE_DIR_CATEGORIES,
     METHOD_LIVE_DIR_CATEGORY_ROOMS,
@@ -77,23 +100,43 @@ use chaos_proto::{
     METHOD_LIVESTREAM_DECODE_MANIFEST,
     METHOD_LLM_CHAT,
     METHOD_LLM_CONFIG_SET,
+    METHOD_LYRICS_PIN,
     METHOD_LYRICS_SEARCH,
+    METHOD_LYRICS_SEARCH_STREAM,
+    METHOD_LYRICS_SET_OFFSET,
+    METHOD_LYRICS_SYNC_START,
+    METHOD_LYRICS_SYNC_STOP,
+    METHOD_LYRICS_SYNC_UPDATE,
     // music
     METHOD_MUSIC_ALBUM_TRACKS,
     METHOD_MUSIC_ARTIST_ALBUMS,
+    METHOD_MUSIC_CHART_TRACKS,
     METHOD_MUSIC_CONFIG_SET,
+    METHOD_MUSIC_DAILY_RECOMMENDATIONS,
     METHOD_MUSIC_DOWNLOAD_CANCEL,
+    METHOD_MUSIC_DOWNLOAD_PAUSE,
+    METHOD_MUSIC_DOWNLOAD_RESUME,
     METHOD_MUSIC_DOWNLOAD_START,
     METHOD_MUSIC_DOWNLOAD_STATUS,
     METHOD_MUSIC_KUGOU_LOGIN_QR_CREATE,
     METHOD_MUSIC_KUGOU_LOGIN_QR_POLL,
+    METHOD_MUSIC_KUWO_LOGIN_TOKEN,
+    METHOD_MUSIC_LIST_CHARTS,
+    METHOD_MUSIC_NETEASE_LOGIN_QR_CREATE,
+    METHOD_MUSIC_NETEASE_LOGIN_QR_POLL,
+    METHOD_MUSIC_NETEASE_REFRESH_COOKIE,
+    METHOD_MUSIC_PLAYLIST_TRACKS,
     METHOD_MUSIC_QQ_LOGIN_QR_CREATE,
     METHOD_MUSIC_QQ_LOGIN_QR_POLL,
     METHOD_MUSIC_QQ_REFRESH_COOKIE,
     METHOD_MUSIC_SEARCH_ALBUMS,
     METHOD_MUSIC_SEARCH_ARTISTS,
+    METHOD_MUSIC_SEARCH_PLAYLISTS,
     METHOD_MUSIC_SEARCH_TRACKS,
+    METHOD_MUSIC_TRACK_DETAIL,
+    METHOD_MUSIC_TRACK_LYRICS,
     METHOD_MUSIC_TRACK_PLAY_URL,
+    METHOD_MUSIC_USER_PLAYLISTS,
     METHOD_NOW_PLAYING_SNAPSHOT,
     // tts
     METHOD_TTS_SFT_CANCEL,
@@ -105,22 +148,40 @@ use chaos_proto::{
     MusicAlbumTracksParams,
     MusicArtist,
     MusicArtistAlbumsParams,
+    MusicChart,
+    MusicChartTracksParams,
+    MusicDailyRecommendationsParams,
     MusicDownloadCancelParams,
+    MusicDownloadPauseParams,
+    MusicDownloadResumeParams,
     MusicDownloadStartParams,
     MusicDownloadStartResult,
     MusicDownloadStatus,
     MusicDownloadStatusParams,
+    MusicKuwoLoginTokenParams,
+    MusicListChartsParams,
     MusicLoginQr,
     MusicLoginQrCreateParams,
     MusicLoginQrPollParams,
     MusicLoginQrPollResult,
+    MusicLyrics,
+    MusicNeteaseCookieResult,
+    MusicNeteaseRefreshCookieParams,
+    MusicPlaylist,
+    MusicPlaylistTracksParams,
     MusicProviderConfig,
     MusicRefreshCookieParams,
     MusicSearchParams,
     MusicTrack,
+    MusicTrackParams,
     MusicTrackPlayUrlParams,
     MusicTrackPlayUrlResult,
+    MusicUserPlaylistsParams,
     NOTIF_DANMAKU_MESSAGE,
+    NOTIF_LYRICS_LINE_CHANGED,
+    NOTIF_LYRICS_SEARCH_DONE,
+    NOTIF_LYRICS_SEARCH_RESULT,
+    NOTIF_LYRICS_WORD_PROGRESS,
     NOTIF_TTS_SFT_STATUS_CHANGED,
     NOTIF_VOICE_CHAT_CHUNK,
     NowPlayingSnapshot,
@@ -151,6 +212,10 @@ pub enum DaemonNotif {
     Danmaku(chaos_proto::DanmakuMessage),
     VoiceChatChunk(VoiceChatChunkNotif),
     TtsSftStatusChanged(TtsSftStatusNotif),
+    LyricsSearchResult(LyricsSearchResultNotif),
+    LyricsSearchDone(LyricsSearchDoneNotif),
+    LyricsLineChanged(LyricsLineChangedNotif),
+    LyricsWordProgress(LyricsWordProgressNotif),
 }
 
 pub trait ChaosService: Send + Sync + 'static {
@@ -191,6 +256,45 @@ pub trait ChaosService: Send + Sync + 'static {
         params: LyricsSearchParams,
     ) -> impl Future<Output = Result<Vec<LyricsSearchResult>, String>> + Send;
 
+    fn lyrics_search_diagnostics(
+        &self,
+        params: LyricsSearchParams,
+    ) -> impl Future<Output = Result<LyricsSearchDiagnosticsResult, String>> + Send;
+
+    /// 立即返回 `searchId`；结果通过 `lyrics.searchResult` / `lyrics.searchDone` 推送。
+    fn lyrics_search_stream(
+        &self,
+        params: LyricsSearchParams,
+        notif_tx: mpsc::UnboundedSender<DaemonNotif>,
+    ) -> impl Future<Output = Result<LyricsSearchStreamStartResult, String>> + Send;
+
+    fn lyrics_pin(
+        &self,
+        params: LyricsPinParams,
+    ) -> impl Future<Output = Result<OkReply, String>> + Send;
+
+    fn lyrics_set_offset(
+        &self,
+        params: LyricsSetOffsetParams,
+    ) -> impl Future<Output = Result<OkReply, String>> + Send;
+
+    /// 订阅歌词时间轴；`lyrics.lineChanged` / `lyrics.wordProgress` 推送到本连接。
+    fn lyrics_sync_start(
+        &self,
+        params: LyricsSyncStartParams,
+        notif_tx: mpsc::UnboundedSender<DaemonNotif>,
+    ) -> impl Future<Output = Result<LyricsSyncStartResult, String>> + Send;
+
+    fn lyrics_sync_update(
+        &self,
+        params: LyricsSyncUpdateParams,
+    ) -> impl Future<Output = Result<OkReply, String>> + Send;
+
+    fn lyrics_sync_stop(
+        &self,
+        params: LyricsSyncStopParams,
+    ) -> impl Future<Output = Result<OkReply, String>> + Send;
+
     // ----- tts (CosyVoice SFT) -----
     fn tts_sft_start(
         &self,
@@ -303,6 +407,46 @@ pub trait ChaosService: Send + Sync + 'static {
         params: MusicArtistAlbumsParams,
     ) -> impl Future<Output = Result<Vec<MusicAlbum>, String>> + Send;
 
+    fn music_search_playlists(
+        &self,
+        params: MusicSearchParams,
+    ) -> impl Future<Output = Result<Vec<MusicPlaylist>, String>> + Send;
+
+    fn music_playlist_tracks(
+        &self,
+        params: MusicPlaylistTracksParams,
+    ) -> impl Future<Output = Result<Vec<MusicTrack>, String>> + Send;
+
+    fn music_user_playlists(
+        &self,
+        params: MusicUserPlaylistsParams,
+    ) -> impl Future<Output = Result<Vec<MusicPlaylist>, String>> + Send;
+
+    fn music_track_detail(
+        &self,
+        params: MusicTrackParams,
+    ) -> impl Future<Output = Result<MusicTrack, String>> + Send;
+
+    fn music_track_lyrics(
+        &self,
+        params: MusicTrackParams,
+    ) -> impl Future<Output = Result<MusicLyrics, String>> + Send;
+
+    fn music_list_charts(
+        &self,
+        params: MusicListChartsParams,
+    ) -> impl Future<Output = Result<Vec<MusicChart>, String>> + Send;
+
+    fn music_chart_tracks(
+        &self,
+        params: MusicChartTracksParams,
+    ) -> impl Future<Output = Result<Vec<MusicTrack>, String>> + Send;
+
+    fn music_daily_recommendations(
+        &self,
+        params: MusicDailyRecommendationsParams,
+    ) -> impl Future<Output = Result<Vec<MusicTrack>, String>> + Send;
+
     fn music_track_play_url(
         &self,
         params: MusicTrackPlayUrlParams,
@@ -333,6 +477,26 @@ pub trait ChaosService: Send + Sync + 'static {
         params: MusicLoginQrPollParams,
     ) -> impl Future<Output = Result<MusicLoginQrPollResult, String>> + Send;
 
+    fn music_netease_login_qr_create(
+        &self,
+        params: MusicLoginQrCreateParams,
+    ) -> impl Future<Output = Result<MusicLoginQr, String>> + Send;
+
+    fn music_netease_login_qr_poll(
+        &self,
+        params: MusicLoginQrPollParams,
+    ) -> impl Future<Output = Result<MusicLoginQrPollResult, String>> + Send;
+
+    fn music_netease_refresh_cookie(
+        &self,
+        params: MusicNeteaseRefreshCookieParams,
+    ) -> impl Future<Output = Result<MusicNeteaseCookieResult, String>> + Send;
+
+    fn music_kuwo_login_token(
+        &self,
+        params: MusicKuwoLoginTokenParams,
+    ) -> impl Future<Output = Result<KuwoUserInfo, String>> + Send;
+
     fn music_download_start(
         &self,
         params: MusicDownloadStartParams,
@@ -348,6 +512,21 @@ pub trait ChaosService: Send + Sync + 'static {
         params: MusicDownloadCancelParams,
     ) -> impl Future<Output = Result<OkReply, String>> + Send;
 
+    fn music_download_pause(
+        &self,
+        params: MusicDownloadPauseParams,
+    ) -> impl Future<Output = Result<OkReply, String>> + Send;
+
+    fn music_download_resume(
+        &self,
+        params: MusicDownloadResumeParams,
+    ) -> impl Future<Output = Result<OkReply, String>> + Send;
+
+    fn download_scheduler_set(
+        &self,
+        params: DownloadSchedulerSetParams,
+    ) -> impl Future<Output = Result<DownloadSchedulerState, String>> + Send;
+
     // ----- bilibili video (BV/AV) -----
     fn bili_login_qr_create(
         &self,
@@ -384,6 +563,11 @@ pub trait ChaosService: Send + Sync + 'static {
         params: BiliParseParams,
     ) -> impl Future<Output = Result<BiliParseResult, String>> + Send;
 
+    fn bili_live_replay_list(
+        &self,
+        params: BiliLiveReplayListParams,
+    ) -> impl Future<Output = Result<BiliLiveReplayListResult, String>> + Send;
+
     fn bili_download_start(
         &self,
         params: BiliDownloadStartParams,
@@ -420,6 +604,16 @@ pub trait ChaosService: Send + Sync + 'static {
         params: BiliTaskCancelParams,
     ) -> impl Future<Output = Result<OkReply, String>> + Send;
 
+    fn bili_task_pause(
+        &self,
+        params: BiliTaskPauseParams,
+    ) -> impl Future<Output = Result<OkReply, String>> + Send;
+
+    fn bili_task_resume(
+        &self,
+        params: BiliTaskResumeParams,
+    ) -> impl Future<Output = Result<OkReply, String>> + Send;
+
     fn bili_tasks_remove_finished(
         &self,
         params: BiliTasksRemoveFinishedParams,
@@ -470,6 +664,10 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                     DaemonNotif::Danmaku(m) => (NOTIF_DANMAKU_MESSAGE, serde_json::to_value(m).unwrap_or(Value::Null)),
                     DaemonNotif::VoiceChatChunk(m) => (NOTIF_VOICE_CHAT_CHUNK, serde_json::to_value(m).unwrap_or(Value::Null)),
                     DaemonNotif::TtsSftStatusChanged(m) => (NOTIF_TTS_SFT_STATUS_CHANGED, serde_json::to_value(m).unwrap_or(Value::Null)),
+                    DaemonNotif::LyricsSearchResult(m) => (NOTIF_LYRICS_SEARCH_RESULT, serde_json::to_value(m).unwrap_or(Value::Null)),
+                    DaemonNotif::LyricsSearchDone(m) => (NOTIF_LYRICS_SEARCH_DONE, serde_json::to_value(m).unwrap_or(Value::Null)),
+                    DaemonNotif::LyricsLineChanged(m) => (NOTIF_LYRICS_LINE_CHANGED, serde_json::to_value(m).unwrap_or(Value::Null)),
+                    DaemonNotif::LyricsWordProgress(m) => (NOTIF_LYRICS_WORD_PROGRESS, serde_json::to_value(m).unwrap_or(Value::Null)),
                 };
                 let payload = json!({
                     "jsonrpc": "2.0",
@@ -575,9 +773,18 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.lyrics_search(params).await {
+                        let res = if params.diagnostics.unwrap_or(false) {
+                            svc.lyrics_search_diagnostics(params)
+                                .await
+                                .map(|v| serde_json::to_value(v).unwrap())
+                        } else {
+                            svc.lyrics_search(params)
+                                .await
+                                .map(|v| serde_json::to_value(v).unwrap())
+                        };
+                        match res {
                             Ok(res) => {
-                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let resp = JsonRpcResponse::ok(id, res);
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
                                 let _ = write_lsp_frame(&mut w, &bytes).await;
                             }
@@ -588,8 +795,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_TTS_SFT_START => {
-                        let params: TtsSftStartParams = match decode_params(req.params) {
+                    METHOD_LYRICS_SEARCH_STREAM => {
+                        let params: LyricsSearchParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -598,7 +805,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.tts_sft_start(params, notif_tx.clone()).await {
+                        match svc.lyrics_search_stream(params, notif_tx.clone()).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -611,8 +818,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_TTS_SFT_STATUS => {
-                        let params: TtsSftStatusParams = match decode_params(req.params) {
+                    METHOD_LYRICS_PIN => {
+                        let params: LyricsPinParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -621,7 +828,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.tts_sft_status(params).await {
+                        match svc.lyrics_pin(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -634,8 +841,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_TTS_SFT_CANCEL => {
-                        let params: TtsSftCancelParams = match decode_params(req.params) {
+                    METHOD_LYRICS_SET_OFFSET => {
+                        let params: LyricsSetOffsetParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -644,7 +851,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.tts_sft_cancel(params).await {
+                        match svc.lyrics_set_offset(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -657,8 +864,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_LLM_CONFIG_SET => {
-                        let params: LlmConfigSetParams = match decode_params(req.params) {
+                    METHOD_LYRICS_SYNC_START => {
+                        let params: LyricsSyncStartParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -667,7 +874,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.llm_config_set(params).await {
+                        match svc.lyrics_sync_start(params, notif_tx.clone()).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -680,8 +887,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_LLM_CHAT => {
-                        let params: LlmChatParams = match decode_params(req.params) {
+                    METHOD_LYRICS_SYNC_UPDATE => {
+                        let params: LyricsSyncUpdateParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -690,7 +897,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.llm_chat(params).await {
+                        match svc.lyrics_sync_update(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -703,13 +910,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_VOICE_CHAT_STREAM_START => {
-                        if let Some(prev) = active_voice_chat_session_id.take() {
-                            let _ = svc
-                                .voice_chat_stream_cancel(VoiceChatStreamCancelParams { session_id: prev.clone() })
-                                .await;
-                        }
-                        let params: VoiceChatStreamStartParams = match decode_params(req.params) {
+                    METHOD_LYRICS_SYNC_STOP => {
+                        let params: LyricsSyncStopParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -718,9 +920,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.voice_chat_stream_start(params, notif_tx.clone()).await {
+                        match svc.lyrics_sync_stop(params).await {
                             Ok(res) => {
-                                active_voice_chat_session_id = Some(res.session_id.clone());
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
                                 let _ = write_lsp_frame(&mut w, &bytes).await;
@@ -732,8 +933,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_VOICE_CHAT_STREAM_CANCEL => {
-                        let params: VoiceChatStreamCancelParams = match decode_params(req.params) {
+                    METHOD_TTS_SFT_START => {
+                        let params: TtsSftStartParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -742,12 +943,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        let sid = params.session_id.clone();
-                        match svc.voice_chat_stream_cancel(params).await {
+                        match svc.tts_sft_start(params, notif_tx.clone()).await {
                             Ok(res) => {
-                                if active_voice_chat_session_id.as_deref() == Some(&sid) {
-                                    active_voice_chat_session_id = None;
-                                }
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
                                 let _ = write_lsp_frame(&mut w, &bytes).await;
@@ -759,8 +956,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_CONFIG_SET => {
-                        let params: MusicProviderConfig = match decode_params(req.params) {
+                    METHOD_TTS_SFT_STATUS => {
+                        let params: TtsSftStatusParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -769,7 +966,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_config_set(params).await {
+                        match svc.tts_sft_status(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -782,8 +979,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_SEARCH_TRACKS => {
-                        let params: MusicSearchParams = match decode_params(req.params) {
+                    METHOD_TTS_SFT_CANCEL => {
+                        let params: TtsSftCancelParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -792,7 +989,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_search_tracks(params).await {
+                        match svc.tts_sft_cancel(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -805,8 +1002,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_SEARCH_ALBUMS => {
-                        let params: MusicSearchParams = match decode_params(req.params) {
+                    METHOD_LLM_CONFIG_SET => {
+                        let params: LlmConfigSetParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -815,7 +1012,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_search_albums(params).await {
+                        match svc.llm_config_set(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -828,8 +1025,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_SEARCH_ARTISTS => {
-                        let params: MusicSearchParams = match decode_params(req.params) {
+                    METHOD_LLM_CHAT => {
+                        let params: LlmChatParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -838,7 +1035,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_search_artists(params).await {
+                        match svc.llm_chat(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -851,8 +1048,13 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_ALBUM_TRACKS => {
-                        let params: MusicAlbumTracksParams = match decode_params(req.params) {
+                    METHOD_VOICE_CHAT_STREAM_START => {
+                        if let Some(prev) = active_voice_chat_session_id.take() {
+                            let _ = svc
+                                .voice_chat_stream_cancel(VoiceChatStreamCancelParams { session_id: prev.clone() })
+                                .await;
+                        }
+                        let params: VoiceChatStreamStartParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -861,8 +1063,9 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_album_tracks(params).await {
+                        match svc.voice_chat_stream_start(params, notif_tx.clone()).await {
                             Ok(res) => {
+                                active_voice_chat_session_id = Some(res.session_id.clone());
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
                                 let _ = write_lsp_frame(&mut w, &bytes).await;
@@ -874,8 +1077,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_ARTIST_ALBUMS => {
-                        let params: MusicArtistAlbumsParams = match decode_params(req.params) {
+                    METHOD_VOICE_CHAT_STREAM_CANCEL => {
+                        let params: VoiceChatStreamCancelParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -884,8 +1087,12 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_artist_albums(params).await {
+                        let sid = params.session_id.clone();
+                        match svc.voice_chat_stream_cancel(params).await {
                             Ok(res) => {
+                                if active_voice_chat_session_id.as_deref() == Some(&sid) {
+                                    active_voice_chat_session_id = None;
+                                }
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
                                 let _ = write_lsp_frame(&mut w, &bytes).await;
@@ -897,8 +1104,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_TRACK_PLAY_URL => {
-                        let params: MusicTrackPlayUrlParams = match decode_params(req.params) {
+                    METHOD_MUSIC_CONFIG_SET => {
+                        let params: MusicProviderConfig = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -907,7 +1114,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_track_play_url(params).await {
+                        match svc.music_config_set(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -920,8 +1127,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_QQ_LOGIN_QR_CREATE => {
-                        let params: MusicLoginQrCreateParams = match decode_params(req.params) {
+                    METHOD_MUSIC_SEARCH_TRACKS => {
+                        let params: MusicSearchParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -930,7 +1137,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_qq_login_qr_create(params).await {
+                        match svc.music_search_tracks(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -943,8 +1150,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_QQ_LOGIN_QR_POLL => {
-                        let params: MusicLoginQrPollParams = match decode_params(req.params) {
+                    METHOD_MUSIC_SEARCH_ALBUMS => {
+                        let params: MusicSearchParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -953,7 +1160,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_qq_login_qr_poll(params).await {
+                        match svc.music_search_albums(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -966,8 +1173,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_QQ_REFRESH_COOKIE => {
-                        let params: MusicRefreshCookieParams = match decode_params(req.params) {
+                    METHOD_MUSIC_SEARCH_ARTISTS => {
+                        let params: MusicSearchParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -976,7 +1183,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_qq_refresh_cookie(params).await {
+                        match svc.music_search_artists(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -989,8 +1196,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_KUGOU_LOGIN_QR_CREATE => {
-                        let params: MusicLoginQrCreateParams = match decode_params(req.params) {
+                    METHOD_MUSIC_ALBUM_TRACKS => {
+                        let params: MusicAlbumTracksParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -999,7 +1206,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_kugou_login_qr_create(params).await {
+                        match svc.music_album_tracks(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1012,8 +1219,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_KUGOU_LOGIN_QR_POLL => {
-                        let params: MusicLoginQrPollParams = match decode_params(req.params) {
+                    METHOD_MUSIC_ARTIST_ALBUMS => {
+                        let params: MusicArtistAlbumsParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1022,7 +1229,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_kugou_login_qr_poll(params).await {
+                        match svc.music_artist_albums(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1035,8 +1242,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_DOWNLOAD_START => {
-                        let params: MusicDownloadStartParams = match decode_params(req.params) {
+                    METHOD_MUSIC_SEARCH_PLAYLISTS => {
+                        let params: MusicSearchParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1045,7 +1252,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_download_start(params).await {
+                        match svc.music_search_playlists(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1058,8 +1265,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_DOWNLOAD_STATUS => {
-                        let params: MusicDownloadStatusParams = match decode_params(req.params) {
+                    METHOD_MUSIC_PLAYLIST_TRACKS => {
+                        let params: MusicPlaylistTracksParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1068,7 +1275,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_download_status(params).await {
+                        match svc.music_playlist_tracks(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1081,8 +1288,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_MUSIC_DOWNLOAD_CANCEL => {
-                        let params: MusicDownloadCancelParams = match decode_params(req.params) {
+                    METHOD_MUSIC_USER_PLAYLISTS => {
+                        let params: MusicUserPlaylistsParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1091,7 +1298,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.music_download_cancel(params).await {
+                        match svc.music_user_playlists(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1104,8 +1311,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_LOGIN_QR_CREATE => {
-                        let params: BiliLoginQrCreateParams = match decode_params(req.params) {
+                    METHOD_MUSIC_TRACK_DETAIL => {
+                        let params: MusicTrackParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1114,7 +1321,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_login_qr_create(params).await {
+                        match svc.music_track_detail(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1127,8 +1334,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_LOGIN_QR_CREATE_V2 => {
-                        let params: BiliLoginQrCreateV2Params = match decode_params(req.params) {
+                    METHOD_MUSIC_TRACK_LYRICS => {
+                        let params: MusicTrackParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1137,7 +1344,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_login_qr_create_v2(params).await {
+                        match svc.music_track_lyrics(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1150,8 +1357,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_LOGIN_QR_POLL => {
-                        let params: BiliLoginQrPollParams = match decode_params(req.params) {
+                    METHOD_MUSIC_LIST_CHARTS => {
+                        let params: MusicListChartsParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1160,7 +1367,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_login_qr_poll(params).await {
+                        match svc.music_list_charts(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1173,8 +1380,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_LOGIN_QR_POLL_V2 => {
-                        let params: BiliLoginQrPollParams = match decode_params(req.params) {
+                    METHOD_MUSIC_CHART_TRACKS => {
+                        let params: MusicChartTracksParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1183,7 +1390,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_login_qr_poll_v2(params).await {
+                        match svc.music_chart_tracks(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1196,8 +1403,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_CHECK_LOGIN => {
-                        let params: BiliCheckLoginParams = match decode_params(req.params) {
+                    METHOD_MUSIC_DAILY_RECOMMENDATIONS => {
+                        let params: MusicDailyRecommendationsParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1206,7 +1413,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_check_login(params).await {
+                        match svc.music_daily_recommendations(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1219,8 +1426,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_REFRESH_COOKIE => {
-                        let params: BiliRefreshCookieParams = match decode_params(req.params) {
+                    METHOD_MUSIC_TRACK_PLAY_URL => {
+                        let params: MusicTrackPlayUrlParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1229,7 +1436,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_refresh_cookie(params).await {
+                        match svc.music_track_play_url(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1242,8 +1449,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_PARSE => {
-                        let params: BiliParseParams = match decode_params(req.params) {
+                    METHOD_MUSIC_QQ_LOGIN_QR_CREATE => {
+                        let params: MusicLoginQrCreateParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1252,7 +1459,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_parse(params).await {
+                        match svc.music_qq_login_qr_create(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1265,8 +1472,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_DOWNLOAD_START => {
-                        let params: BiliDownloadStartParams = match decode_params(req.params) {
+                    METHOD_MUSIC_QQ_LOGIN_QR_POLL => {
+                        let params: MusicLoginQrPollParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1275,7 +1482,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_download_start(params).await {
+                        match svc.music_qq_login_qr_poll(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1288,8 +1495,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_DOWNLOAD_STATUS => {
-                        let params: BiliDownloadStatusParams = match decode_params(req.params) {
+                    METHOD_MUSIC_QQ_REFRESH_COOKIE => {
+                        let params: MusicRefreshCookieParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1298,7 +1505,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_download_status(params).await {
+                        match svc.music_qq_refresh_cookie(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1311,8 +1518,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_DOWNLOAD_CANCEL => {
-                        let params: BiliDownloadCancelParams = match decode_params(req.params) {
+                    METHOD_MUSIC_KUGOU_LOGIN_QR_CREATE => {
+                        let params: MusicLoginQrCreateParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1321,7 +1528,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_download_cancel(params).await {
+                        match svc.music_kugou_login_qr_create(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1334,8 +1541,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_TASK_ADD => {
-                        let params: BiliTaskAddParams = match decode_params(req.params) {
+                    METHOD_MUSIC_KUGOU_LOGIN_QR_POLL => {
+                        let params: MusicLoginQrPollParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1344,7 +1551,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_task_add(params).await {
+                        match svc.music_kugou_login_qr_poll(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1357,8 +1564,8 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_TASKS_GET => {
-                        let params: BiliTasksGetParams = match decode_params(req.params) {
+                    METHOD_MUSIC_NETEASE_LOGIN_QR_CREATE => {
+                        let params: MusicLoginQrCreateParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1367,7 +1574,7 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                                 continue;
                             }
                         };
-                        match svc.bili_tasks_get(params).await {
+                        match svc.music_netease_login_qr_create(params).await {
                             Ok(res) => {
                                 let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
                                 let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
@@ -1380,8 +1587,514 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
-                    METHOD_BILI_TASK_GET => {
-                        let params: BiliTaskGetParams = match decode_params(req.params) {
+                    METHOD_MUSIC_NETEASE_LOGIN_QR_POLL => {
+                        let params: MusicLoginQrPollParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.music_netease_login_qr_poll(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_MUSIC_NETEASE_REFRESH_COOKIE => {
+                        let params: MusicNeteaseRefreshCookieParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.music_netease_refresh_cookie(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_MUSIC_KUWO_LOGIN_TOKEN => {
+                        let params: MusicKuwoLoginTokenParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.music_kuwo_login_token(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_MUSIC_DOWNLOAD_START => {
+                        let params: MusicDownloadStartParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.music_download_start(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_MUSIC_DOWNLOAD_STATUS => {
+                        let params: MusicDownloadStatusParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.music_download_status(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_MUSIC_DOWNLOAD_CANCEL => {
+                        let params: MusicDownloadCancelParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.music_download_cancel(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_MUSIC_DOWNLOAD_PAUSE => {
+                        let params: MusicDownloadPauseParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.music_download_pause(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_MUSIC_DOWNLOAD_RESUME => {
+                        let params: MusicDownloadResumeParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.music_download_resume(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_DOWNLOAD_SCHEDULER_SET => {
+                        let params: DownloadSchedulerSetParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.download_scheduler_set(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_LOGIN_QR_CREATE => {
+                        let params: BiliLoginQrCreateParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_login_qr_create(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_LOGIN_QR_CREATE_V2 => {
+                        let params: BiliLoginQrCreateV2Params = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_login_qr_create_v2(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_LOGIN_QR_POLL => {
+                        let params: BiliLoginQrPollParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_login_qr_poll(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_LOGIN_QR_POLL_V2 => {
+                        let params: BiliLoginQrPollParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_login_qr_poll_v2(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_CHECK_LOGIN => {
+                        let params: BiliCheckLoginParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_check_login(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_REFRESH_COOKIE => {
+                        let params: BiliRefreshCookieParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_refresh_cookie(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_PARSE => {
+                        let params: BiliParseParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_parse(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_LIVE_REPLAY_LIST => {
+                        let params: BiliLiveReplayListParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_live_replay_list(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_DOWNLOAD_START => {
+                        let params: BiliDownloadStartParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_download_start(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_DOWNLOAD_STATUS => {
+                        let params: BiliDownloadStatusParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_download_status(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_DOWNLOAD_CANCEL => {
+                        let params: BiliDownloadCancelParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_download_cancel(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_TASK_ADD => {
+                        let params: BiliTaskAddParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_task_add(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_TASKS_GET => {
+                        let params: BiliTasksGetParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_tasks_get(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_TASK_GET => {
+                        let params: BiliTaskGetParams = match decode_params(req.params) {
                             Ok(v) => v,
                             Err(e) => {
                                 let resp = JsonRpcResponse::err(id, e);
@@ -1426,6 +2139,52 @@ pub async fn run_jsonrpc_over_lsp<S: ChaosService, RW: AsyncRead + AsyncWrite +
                             }
                         }
                     }
+                    METHOD_BILI_TASK_PAUSE => {
+                        let params: BiliTaskPauseParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_task_pause(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
+                    METHOD_BILI_TASK_RESUME => {
+                        let params: BiliTaskResumeParams = match decode_params(req.params) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                let resp = JsonRpcResponse::err(id, e);
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                                continue;
+                            }
+                        };
+                        match svc.bili_task_resume(params).await {
+                            Ok(res) => {
+                                let resp = JsonRpcResponse::ok(id, serde_json::to_value(res).unwrap());
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                            Err(msg) => {
+                                let resp = JsonRpcResponse::err(id, JsonRpcError::new(RpcErrorCode::InternalError, msg));
+                                let bytes = serde_json::to_vec(&resp).unwrap_or_else(|_| b"{}".to_vec());
+                                let _ = write_lsp_frame(&mut w, &bytes).await;
+                            }
+                        }
+                    }
                     METHOD_BILI_TASKS_REMOVE_FINISHED => {
                         let params: BiliTasksRemoveFinishedParams = match decode_params(req.params) {
                             Ok(v) => v,
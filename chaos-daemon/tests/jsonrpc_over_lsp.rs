@@ -12,6 +12,8 @@ use chaos_proto::{
     BiliDownloadStatus,
     BiliDownloadStatusParams,
     BiliDownloadTotals,
+    BiliLiveReplayListParams,
+    BiliLiveReplayListResult,
     BiliLoginQr,
     BiliLoginQrCreateParams,
     BiliLoginQrCreateV2Params,
@@ -29,6 +31,8 @@ use chaos_proto::{
     BiliTaskCancelParams,
     BiliTaskDetail,
     BiliTaskGetParams,
+    BiliTaskPauseParams,
+    BiliTaskResumeParams,
     BiliTasksGetParams,
     BiliTasksGetResult,
     BiliTasksRemoveFinishedParams,
@@ -38,8 +42,11 @@ use chaos_proto::{
     DanmakuFetchImageParams,
     DanmakuFetchImageResult,
     DanmakuMessage,
+    DownloadSchedulerSetParams,
+    DownloadSchedulerState,
     // music
     KugouUserInfo,
+    KuwoUserInfo,
     LiveCloseParams,
     LiveDirCategoriesParams,
     LiveDirCategory,
@@ -60,29 +67,57 @@ use chaos_proto::{
     LlmChatResult,
     // llm + voice chat
     LlmConfigSetParams,
+    LyricsLineChangedNotif,
+    LyricsPinParams,
+    LyricsProviderReport,
+    LyricsSearchDiagnosticsResult,
+    LyricsSearchDoneNotif,
     LyricsSearchParams,
+    LyricsSearchReport,
     LyricsSearchResult,
+    LyricsSearchResultNotif,
+    LyricsSearchStreamStartResult,
+    LyricsSetOffsetParams,
+    LyricsSyncStartParams,
+    LyricsSyncStartResult,
+    LyricsSyncStopParams,
+    LyricsSyncUpdateParams,
     MusicAlbum,
     MusicAlbumTracksParams,
     MusicArtist,
     MusicArtistAlbumsParams,
+    MusicChart,
+    MusicChartTracksParams,
+    MusicDailyRecommendationsParams,
     MusicDownloadCancelParams,
+    MusicDownloadPauseParams,
+    MusicDownloadResumeParams,
     MusicDownloadStartParams,
     MusicDownloadStartResult,
     MusicDownloadStatus,
     MusicDownloadStatusParams,
     MusicDownloadTotals,
+    MusicKuwoLoginTokenParams,
+    MusicListChartsParams,
     MusicLoginQr,
     MusicLoginQrCreateParams,
     MusicLoginQrPollParams,
     MusicLoginQrPollResult,
     MusicLoginQrState,
+    MusicLyrics,
+    MusicNeteaseCookieResult,
+    MusicNeteaseRefreshCookieParams,
+    MusicPlaylist,
+    MusicPlaylistTracksParams,
     MusicProviderConfig,
     MusicRefreshCookieParams,
     MusicSearchParams,
     MusicTrack,
+    MusicTrackParams,
     MusicTrackPlayUrlParams,
     MusicTrackPlayUrlResult,
+    MusicUserPlaylistsParams,
+    NeteaseUserInfo,
     NowPlayingSession,
     NowPlayingSnapshot,
     NowPlayingSnapshotParams,
@@ -264,10 +299,108 @@ impl ChaosService for FakeSvc {
             has_inline_timetags: true,
             lyrics_original: "[00:01.00]hello".to_string(),
             lyrics_translation: None,
+            offset_ms: 0,
             debug: None,
         }])
     }
 
+    async fn lyrics_search_diagnostics(
+        &self,
+        params: LyricsSearchParams,
+    ) -> Result<LyricsSearchDiagnosticsResult, String> {
+        let results = self.lyrics_search(params).await?;
+        Ok(LyricsSearchDiagnosticsResult {
+            report: LyricsSearchReport {
+                cache_hit: false,
+                elapsed_ms: 12,
+                returned: results.len() as u32,
+                providers: vec![LyricsProviderReport {
+                    service: "netease".to_string(),
+                    status: "timeout".to_string(),
+                    http_status: None,
+                    error: Some("provider search timeout".to_string()),
+                    exact: false,
+                    latency_ms: 10,
+                    candidates: 0,
+                    fetched: 0,
+                    fetch_failed: 0,
+                    matched: 0,
+                    filtered: 0,
+                }],
+            },
+            results,
+        })
+    }
+
+    async fn lyrics_search_stream(
+        &self,
+        params: LyricsSearchParams,
+        notif_tx: tokio::sync::mpsc::UnboundedSender<DaemonNotif>,
+    ) -> Result<LyricsSearchStreamStartResult, String> {
+        let results = self.lyrics_search(params).await?;
+        let search_id = "lyr1".to_string();
+        let _ = notif_tx.send(DaemonNotif::LyricsSearchResult(LyricsSearchResultNotif {
+            search_id: search_id.clone(),
+            cached: false,
+            service: Some("qq".to_string()),
+            results: results.clone(),
+            report: None,
+        }));
+        let _ = notif_tx.send(DaemonNotif::LyricsSearchDone(LyricsSearchDoneNotif {
+            search_id: search_id.clone(),
+            results,
+            report: LyricsSearchReport::default(),
+        }));
+        Ok(LyricsSearchStreamStartResult { search_id })
+    }
+
+    async fn lyrics_pin(&self, params: LyricsPinParams) -> Result<OkReply, String> {
+        let ok = params.title == "Hello"
+            && params
+                .result
+                .is_some_and(|r| r.service == "netease" && r.service_token == "n1");
+        Ok(OkReply { ok })
+    }
+
+    async fn lyrics_set_offset(&self, params: LyricsSetOffsetParams) -> Result<OkReply, String> {
+        Ok(OkReply {
+            ok: params.offset_ms == -250 && params.duration_ms == Some(296_000),
+        })
+    }
+
+    async fn lyrics_sync_start(
+        &self,
+        params: LyricsSyncStartParams,
+        notif_tx: tokio::sync::mpsc::UnboundedSender<DaemonNotif>,
+    ) -> Result<LyricsSyncStartResult, String> {
+        let sync_id = "sync1".to_string();
+        let _ = notif_tx.send(DaemonNotif::LyricsLineChanged(LyricsLineChangedNotif {
+            sync_id: sync_id.clone(),
+            line_index: Some(0),
+            position_ms: 1_000,
+            start_ms: Some(1_000),
+            end_ms: None,
+            text: params.lyrics_original,
+            translation: None,
+        }));
+        Ok(LyricsSyncStartResult { sync_id })
+    }
+
+    async fn lyrics_sync_update(&self, params: LyricsSyncUpdateParams) -> Result<OkReply, String> {
+        Ok(OkReply {
+            ok: params.sync_id == "sync1"
+                && params
+                    .clock
+                    .is_some_and(|c| c.position_ms == 2_000 && c.paused),
+        })
+    }
+
+    async fn lyrics_sync_stop(&self, params: LyricsSyncStopParams) -> Result<OkReply, String> {
+        Ok(OkReply {
+            ok: params.sync_id == "sync1",
+        })
+    }
+
     async fn tts_sft_start(
         &self,
         _params: TtsSftStartParams,
@@ -447,6 +580,83 @@ impl ChaosService for FakeSvc {
         Ok(vec![])
     }
 
+    async fn music_search_playlists(
+        &self,
+        _params: MusicSearchParams,
+    ) -> Result<Vec<MusicPlaylist>, String> {
+        Ok(vec![])
+    }
+
+    async fn music_playlist_tracks(
+        &self,
+        _params: MusicPlaylistTracksParams,
+    ) -> Result<Vec<MusicTrack>, String> {
+        Ok(vec![])
+    }
+
+    async fn music_user_playlists(
+        &self,
+        _params: MusicUserPlaylistsParams,
+    ) -> Result<Vec<MusicPlaylist>, String> {
+        Ok(vec![])
+    }
+
+    async fn music_track_detail(&self, params: MusicTrackParams) -> Result<MusicTrack, String> {
+        Ok(MusicTrack {
+            service: params.service,
+            id: params.track_id,
+            title: "Song".to_string(),
+            artists: vec![],
+            artist_ids: vec![],
+            album: None,
+            album_id: None,
+            duration_ms: None,
+            cover_url: None,
+            isrc: None,
+            qualities: vec![],
+        })
+    }
+
+    async fn music_track_lyrics(&self, params: MusicTrackParams) -> Result<MusicLyrics, String> {
+        Ok(MusicLyrics {
+            service: params.service,
+            track_id: params.track_id,
+            original: "[00:01.00]hi".to_string(),
+            translation: None,
+            romanization: None,
+            word_lyrics: None,
+            word_format: None,
+        })
+    }
+
+    async fn music_list_charts(
+        &self,
+        params: MusicListChartsParams,
+    ) -> Result<Vec<MusicChart>, String> {
+        Ok(vec![MusicChart {
+            service: params.service,
+            id: "26".to_string(),
+            title: "热歌榜".to_string(),
+            cover_url: None,
+            description: None,
+            update_frequency: None,
+        }])
+    }
+
+    async fn music_chart_tracks(
+        &self,
+        _params: MusicChartTracksParams,
+    ) -> Result<Vec<MusicTrack>, String> {
+        Ok(vec![])
+    }
+
+    async fn music_daily_recommendations(
+        &self,
+        _params: MusicDailyRecommendationsParams,
+    ) -> Result<Vec<MusicTrack>, String> {
+        Ok(vec![])
+    }
+
     async fn music_track_play_url(
         &self,
         _params: MusicTrackPlayUrlParams,
@@ -481,6 +691,8 @@ impl ChaosService for FakeSvc {
             message: None,
             cookie: None,
             kugou_user: None,
+            netease_cookie: None,
+            netease_user: None,
         })
     }
 
@@ -518,6 +730,64 @@ impl ChaosService for FakeSvc {
                 token: "t".to_string(),
                 userid: "u".to_string(),
             }),
+            netease_cookie: None,
+            netease_user: None,
+        })
+    }
+
+    async fn music_netease_login_qr_create(
+        &self,
+        params: MusicLoginQrCreateParams,
+    ) -> Result<MusicLoginQr, String> {
+        Ok(MusicLoginQr {
+            session_id: "netease".to_string(),
+            login_type: params.login_type,
+            mime: "image/png".to_string(),
+            base64: "".to_string(),
+            identifier: "unikey".to_string(),
+            created_at_unix_ms: 0,
+        })
+    }
+
+    async fn music_netease_login_qr_poll(
+        &self,
+        params: MusicLoginQrPollParams,
+    ) -> Result<MusicLoginQrPollResult, String> {
+        Ok(MusicLoginQrPollResult {
+            session_id: params.session_id,
+            state: MusicLoginQrState::Done,
+            message: None,
+            cookie: None,
+            kugou_user: None,
+            netease_cookie: Some("MUSIC_U=x".to_string()),
+            netease_user: None,
+        })
+    }
+
+    async fn music_netease_refresh_cookie(
+        &self,
+        params: MusicNeteaseRefreshCookieParams,
+    ) -> Result<MusicNeteaseCookieResult, String> {
+        Ok(MusicNeteaseCookieResult {
+            cookie: params.cookie,
+            user: NeteaseUserInfo {
+                user_id: "1".to_string(),
+                nickname: None,
+                avatar_url: None,
+                vip_type: 0,
+            },
+        })
+    }
+
+    async fn music_kuwo_login_token(
+        &self,
+        params: MusicKuwoLoginTokenParams,
+    ) -> Result<KuwoUserInfo, String> {
+        Ok(KuwoUserInfo {
+            uid: params.uid,
+            sid: params.sid,
+            nickname: None,
+            vip_type: 0,
         })
     }
 
@@ -554,6 +824,34 @@ impl ChaosService for FakeSvc {
         Ok(OkReply { ok: true })
     }
 
+    async fn music_download_pause(
+        &self,
+        _params: MusicDownloadPauseParams,
+    ) -> Result<OkReply, String> {
+        Ok(OkReply { ok: true })
+    }
+
+    async fn music_download_resume(
+        &self,
+        _params: MusicDownloadResumeParams,
+    ) -> Result<OkReply, String> {
+        Ok(OkReply { ok: true })
+    }
+
+    async fn download_scheduler_set(
+        &self,
+        params: DownloadSchedulerSetParams,
+    ) -> Result<DownloadSchedulerState, String> {
+        Ok(DownloadSchedulerState {
+            max_connections: params.max_connections.unwrap_or(16),
+            rate_limit_bps: params.rate_limit_bps.filter(|v| *v > 0),
+            paused: params.paused.unwrap_or(false),
+            active: 0,
+            queued: 0,
+            tasks: 0,
+        })
+    }
+
     async fn bili_login_qr_create(
         &self,
         _params: BiliLoginQrCreateParams,
@@ -591,6 +889,16 @@ impl ChaosService for FakeSvc {
         Ok(BiliParseResult { videos: vec![] })
     }
 
+    async fn bili_live_replay_list(
+        &self,
+        _params: BiliLiveReplayListParams,
+    ) -> Result<BiliLiveReplayListResult, String> {
+        Ok(BiliLiveReplayListResult {
+            items: vec![],
+            total: 0,
+        })
+    }
+
     async fn bili_download_start(
         &self,
         _params: BiliDownloadStartParams,
@@ -705,6 +1013,14 @@ impl ChaosService for FakeSvc {
         Ok(OkReply { ok: true })
     }
 
+    async fn bili_task_pause(&self, _params: BiliTaskPauseParams) -> Result<OkReply, String> {
+        Ok(OkReply { ok: true })
+    }
+
+    async fn bili_task_resume(&self, _params: BiliTaskResumeParams) -> Result<OkReply, String> {
+        Ok(OkReply { ok: true })
+    }
+
     async fn bili_tasks_remove_finished(
         &self,
         _params: BiliTasksRemoveFinishedParams,
@@ -781,6 +1097,135 @@ async fn jsonrpc_request_response_and_notification_flow() {
     assert!(v11["result"].is_array());
     assert_eq!(v11["result"][0]["matchPercentage"], 80);
 
+    let lyr = json!({
+        "jsonrpc": "2.0",
+        "id": 111,
+        "method": "lyrics.search",
+        "params": { "title": "Hello", "artist": "Adele", "diagnostics": true }
+    });
+    let lyr_bytes = serde_json::to_vec(&lyr).unwrap();
+    write_lsp_frame(&mut w, &lyr_bytes).await.unwrap();
+    let resp111 = timeout(Duration::from_secs(3), read_lsp_frame(&mut br, 32 * 1024))
+        .await
+        .unwrap()
+        .unwrap();
+    let v111: serde_json::Value = serde_json::from_slice(&resp111).unwrap();
+    assert_eq!(v111["id"], 111);
+    assert_eq!(v111["result"]["results"][0]["matchPercentage"], 80);
+    assert_eq!(v111["result"]["report"]["returned"], 1);
+    assert_eq!(
+        v111["result"]["report"]["providers"][0]["status"],
+        "timeout"
+    );
+    assert_eq!(v111["result"]["report"]["providers"][0]["latencyMs"], 10);
+
+    let lyr = json!({
+        "jsonrpc": "2.0",
+        "id": 112,
+        "method": "lyrics.searchStream",
+        "params": { "title": "Hello", "artist": "Adele" }
+    });
+    let lyr_bytes = serde_json::to_vec(&lyr).unwrap();
+    write_lsp_frame(&mut w, &lyr_bytes).await.unwrap();
+    let mut frames = Vec::new();
+    for _ in 0..3 {
+        let frame = timeout(Duration::from_secs(3), read_lsp_frame(&mut br, 32 * 1024))
+            .await
+            .unwrap()
+            .unwrap();
+        frames.push(serde_json::from_slice::<serde_json::Value>(&frame).unwrap());
+    }
+    assert_eq!(frames[0]["id"], 112);
+    assert_eq!(frames[0]["result"]["searchId"], "lyr1");
+    assert_eq!(frames[1]["method"], "lyrics.searchResult");
+    assert_eq!(frames[1]["params"]["searchId"], "lyr1");
+    assert_eq!(frames[1]["params"]["service"], "qq");
+    assert_eq!(frames[1]["params"]["results"][0]["matchPercentage"], 80);
+    assert_eq!(frames[2]["method"], "lyrics.searchDone");
+    assert_eq!(frames[2]["params"]["results"][0]["serviceToken"], "tok");
+
+    for (id, method, params) in [
+        (
+            113,
+            "lyrics.pin",
+            json!({
+                "title": "Hello",
+                "result": {
+                    "service": "netease", "serviceToken": "n1", "matchPercentage": 90,
+                    "quality": 1.0, "matched": true, "hasTranslation": false,
+                    "hasInlineTimetags": false, "lyricsOriginal": "[00:01.00]x"
+                }
+            }),
+        ),
+        (
+            114,
+            "lyrics.setOffset",
+            json!({ "title": "Hello", "durationMs": 296000, "offsetMs": -250 }),
+        ),
+    ] {
+        let req = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        write_lsp_frame(&mut w, &serde_json::to_vec(&req).unwrap())
+            .await
+            .unwrap();
+        let resp = timeout(Duration::from_secs(3), read_lsp_frame(&mut br, 4096))
+            .await
+            .unwrap()
+            .unwrap();
+        let v: serde_json::Value = serde_json::from_slice(&resp).unwrap();
+        assert_eq!(v["id"], id);
+        assert_eq!(v["result"]["ok"], true, "{method}");
+    }
+
+    let sync = json!({
+        "jsonrpc": "2.0",
+        "id": 115,
+        "method": "lyrics.sync.start",
+        "params": { "lyricsOriginal": "hello", "followNowPlaying": false }
+    });
+    write_lsp_frame(&mut w, &serde_json::to_vec(&sync).unwrap())
+        .await
+        .unwrap();
+    let mut frames = Vec::new();
+    for _ in 0..2 {
+        let frame = timeout(Duration::from_secs(3), read_lsp_frame(&mut br, 4096))
+            .await
+            .unwrap()
+            .unwrap();
+        frames.push(serde_json::from_slice::<serde_json::Value>(&frame).unwrap());
+    }
+    let resp = frames
+        .iter()
+        .find(|f| f["id"] == 115)
+        .expect("sync start response");
+    assert_eq!(resp["result"]["syncId"], "sync1");
+    let notif = frames
+        .iter()
+        .find(|f| f["method"] == "lyrics.lineChanged")
+        .expect("lineChanged notif");
+    assert_eq!(notif["params"]["syncId"], "sync1");
+    assert_eq!(notif["params"]["text"], "hello");
+
+    for (id, method, params) in [
+        (
+            116,
+            "lyrics.sync.update",
+            json!({ "syncId": "sync1", "clock": { "positionMs": 2000, "paused": true } }),
+        ),
+        (117, "lyrics.sync.stop", json!({ "syncId": "sync1" })),
+    ] {
+        let req = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        write_lsp_frame(&mut w, &serde_json::to_vec(&req).unwrap())
+            .await
+            .unwrap();
+        let resp = timeout(Duration::from_secs(3), read_lsp_frame(&mut br, 4096))
+            .await
+            .unwrap()
+            .unwrap();
+        let v: serde_json::Value = serde_json::from_slice(&resp).unwrap();
+        assert_eq!(v["id"], id);
+        assert_eq!(v["result"]["ok"], true, "{method}");
+    }
+
     // 3.5) liveDir.categories
     let cats = json!({
         "jsonrpc": "2.0",
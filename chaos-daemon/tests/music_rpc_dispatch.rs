@@ -92,6 +92,50 @@ impl ChaosService for TestSvc {
         Ok(vec![])
     }
 
+    async fn lyrics_search_diagnostics(
+        &self,
+        _params: LyricsSearchParams,
+    ) -> Result<LyricsSearchDiagnosticsResult, String> {
+        Ok(LyricsSearchDiagnosticsResult {
+            results: vec![],
+            report: LyricsSearchReport::default(),
+        })
+    }
+
+    async fn lyrics_search_stream(
+        &self,
+        _params: LyricsSearchParams,
+        _notif_tx: tokio::sync::mpsc::UnboundedSender<DaemonNotif>,
+    ) -> Result<LyricsSearchStreamStartResult, String> {
+        Ok(LyricsSearchStreamStartResult {
+            search_id: "lyrics".to_string(),
+        })
+    }
+
+    async fn lyrics_pin(&self, _params: LyricsPinParams) -> Result<OkReply, String> {
+        Ok(OkReply { ok: true })
+    }
+
+    async fn lyrics_set_offset(&self, _params: LyricsSetOffsetParams) -> Result<OkReply, String> {
+        Ok(OkReply { ok: true })
+    }
+
+    async fn lyrics_sync_start(
+        &self,
+        _params: LyricsSyncStartParams,
+        _notif_tx: tokio::sync::mpsc::UnboundedSender<DaemonNotif>,
+    ) -> Result<LyricsSyncStartResult, String> {
+        Err("not implemented".to_string())
+    }
+
+    async fn lyrics_sync_update(&self, _params: LyricsSyncUpdateParams) -> Result<OkReply, String> {
+        Err("not implemented".to_string())
+    }
+
+    async fn lyrics_sync_stop(&self, _params: LyricsSyncStopParams) -> Result<OkReply, String> {
+        Err("not implemented".to_string())
+    }
+
     async fn tts_sft_start(
         &self,
         _params: TtsSftStartParams,
@@ -267,6 +311,83 @@ impl ChaosService for TestSvc {
         Ok(vec![])
     }
 
+    async fn music_search_playlists(
+        &self,
+        _params: MusicSearchParams,
+    ) -> Result<Vec<MusicPlaylist>, String> {
+        Ok(vec![])
+    }
+
+    async fn music_playlist_tracks(
+        &self,
+        _params: MusicPlaylistTracksParams,
+    ) -> Result<Vec<MusicTrack>, String> {
+        Ok(vec![])
+    }
+
+    async fn music_user_playlists(
+        &self,
+        _params: MusicUserPlaylistsParams,
+    ) -> Result<Vec<MusicPlaylist>, String> {
+        Ok(vec![])
+    }
+
+    async fn music_track_detail(&self, params: MusicTrackParams) -> Result<MusicTrack, String> {
+        Ok(MusicTrack {
+            service: params.service,
+            id: params.track_id,
+            title: "Song".to_string(),
+            artists: vec![],
+            artist_ids: vec![],
+            album: None,
+            album_id: None,
+            duration_ms: None,
+            cover_url: None,
+            isrc: None,
+            qualities: vec![],
+        })
+    }
+
+    async fn music_track_lyrics(&self, params: MusicTrackParams) -> Result<MusicLyrics, String> {
+        Ok(MusicLyrics {
+            service: params.service,
+            track_id: params.track_id,
+            original: "[00:01.00]hi".to_string(),
+            translation: None,
+            romanization: None,
+            word_lyrics: None,
+            word_format: None,
+        })
+    }
+
+    async fn music_list_charts(
+        &self,
+        params: MusicListChartsParams,
+    ) -> Result<Vec<MusicChart>, String> {
+        Ok(vec![MusicChart {
+            service: params.service,
+            id: "26".to_string(),
+            title: "热歌榜".to_string(),
+            cover_url: None,
+            description: None,
+            update_frequency: None,
+        }])
+    }
+
+    async fn music_chart_tracks(
+        &self,
+        _params: MusicChartTracksParams,
+    ) -> Result<Vec<MusicTrack>, String> {
+        Ok(vec![])
+    }
+
+    async fn music_daily_recommendations(
+        &self,
+        _params: MusicDailyRecommendationsParams,
+    ) -> Result<Vec<MusicTrack>, String> {
+        Ok(vec![])
+    }
+
     async fn music_track_play_url(
         &self,
         _params: MusicTrackPlayUrlParams,
@@ -301,6 +422,8 @@ impl ChaosService for TestSvc {
             message: None,
             cookie: None,
             kugou_user: None,
+            netease_cookie: None,
+            netease_user: None,
         })
     }
 
@@ -335,6 +458,64 @@ impl ChaosService for TestSvc {
             message: None,
             cookie: None,
             kugou_user: None,
+            netease_cookie: None,
+            netease_user: None,
+        })
+    }
+
+    async fn music_netease_login_qr_create(
+        &self,
+        params: MusicLoginQrCreateParams,
+    ) -> Result<MusicLoginQr, String> {
+        Ok(MusicLoginQr {
+            session_id: "netease".to_string(),
+            login_type: params.login_type,
+            mime: "image/png".to_string(),
+            base64: "".to_string(),
+            identifier: "unikey".to_string(),
+            created_at_unix_ms: 0,
+        })
+    }
+
+    async fn music_netease_login_qr_poll(
+        &self,
+        params: MusicLoginQrPollParams,
+    ) -> Result<MusicLoginQrPollResult, String> {
+        Ok(MusicLoginQrPollResult {
+            session_id: params.session_id,
+            state: MusicLoginQrState::Done,
+            message: None,
+            cookie: None,
+            kugou_user: None,
+            netease_cookie: Some("MUSIC_U=x".to_string()),
+            netease_user: None,
+        })
+    }
+
+    async fn music_netease_refresh_cookie(
+        &self,
+        params: MusicNeteaseRefreshCookieParams,
+    ) -> Result<MusicNeteaseCookieResult, String> {
+        Ok(MusicNeteaseCookieResult {
+            cookie: params.cookie,
+            user: NeteaseUserInfo {
+                user_id: "1".to_string(),
+                nickname: None,
+                avatar_url: None,
+                vip_type: 0,
+            },
+        })
+    }
+
+    async fn music_kuwo_login_token(
+        &self,
+        params: MusicKuwoLoginTokenParams,
+    ) -> Result<KuwoUserInfo, String> {
+        Ok(KuwoUserInfo {
+            uid: params.uid,
+            sid: params.sid,
+            nickname: None,
+            vip_type: 0,
         })
     }
 
@@ -371,6 +552,34 @@ impl ChaosService for TestSvc {
         Ok(OkReply { ok: true })
     }
 
+    async fn music_download_pause(
+        &self,
+        _params: MusicDownloadPauseParams,
+    ) -> Result<OkReply, String> {
+        Ok(OkReply { ok: true })
+    }
+
+    async fn music_download_resume(
+        &self,
+        _params: MusicDownloadResumeParams,
+    ) -> Result<OkReply, String> {
+        Ok(OkReply { ok: true })
+    }
+
+    async fn download_scheduler_set(
+        &self,
+        params: DownloadSchedulerSetParams,
+    ) -> Result<DownloadSchedulerState, String> {
+        Ok(DownloadSchedulerState {
+            max_connections: params.max_connections.unwrap_or(16),
+            rate_limit_bps: params.rate_limit_bps.filter(|v| *v > 0),
+            paused: params.paused.unwrap_or(false),
+            active: 0,
+            queued: 0,
+            tasks: 0,
+        })
+    }
+
     async fn bili_login_qr_create(
         &self,
         _params: BiliLoginQrCreateParams,
@@ -452,6 +661,16 @@ impl ChaosService for TestSvc {
         })
     }
 
+    async fn bili_live_replay_list(
+        &self,
+        _params: BiliLiveReplayListParams,
+    ) -> Result<BiliLiveReplayListResult, String> {
+        Ok(BiliLiveReplayListResult {
+            items: vec![],
+            total: 0,
+        })
+    }
+
     async fn bili_download_start(
         &self,
         _params: BiliDownloadStartParams,
@@ -535,6 +754,14 @@ impl ChaosService for TestSvc {
         Ok(OkReply { ok: true })
     }
 
+    async fn bili_task_pause(&self, _params: BiliTaskPauseParams) -> Result<OkReply, String> {
+        Ok(OkReply { ok: true })
+    }
+
+    async fn bili_task_resume(&self, _params: BiliTaskResumeParams) -> Result<OkReply, String> {
+        Ok(OkReply { ok: true })
+    }
+
     async fn bili_tasks_remove_finished(
         &self,
         _params: BiliTasksRemoveFinishedParams,
@@ -624,5 +851,56 @@ async fn music_methods_are_dispatchable() {
         Some("sid")
     );
 
+    // music.netease.loginQrPoll
+    let resp = rpc_call(
+        &mut cw,
+        &mut br,
+        6,
+        "music.netease.loginQrPoll",
+        json!({ "sessionId": "netease" }),
+    )
+    .await;
+    assert_eq!(
+        resp.pointer("/result/neteaseCookie")
+            .and_then(|v| v.as_str()),
+        Some("MUSIC_U=x")
+    );
+
+    // music.trackLyrics
+    let resp = rpc_call(
+        &mut cw,
+        &mut br,
+        7,
+        "music.trackLyrics",
+        json!({ "service": "kugou", "trackId": "HASH" }),
+    )
+    .await;
+    assert_eq!(
+        resp.pointer("/result/trackId").and_then(|v| v.as_str()),
+        Some("HASH")
+    );
+    assert_eq!(
+        resp.pointer("/result/original").and_then(|v| v.as_str()),
+        Some("[00:01.00]hi")
+    );
+
+    // music.listCharts
+    let resp = rpc_call(
+        &mut cw,
+        &mut br,
+        8,
+        "music.listCharts",
+        json!({ "service": "netease" }),
+    )
+    .await;
+    assert_eq!(
+        resp.pointer("/result/0/title").and_then(|v| v.as_str()),
+        Some("热歌榜")
+    );
+    assert_eq!(
+        resp.pointer("/result/0/service").and_then(|v| v.as_str()),
+        Some("netease")
+    );
+
     server_task.abort();
 }
@@ -20,8 +20,24 @@ char *chaos_music_search_artists_json(const char *params_json_utf8);
 
 char *chaos_music_album_tracks_json(const char *params_json_utf8);
 
+char *chaos_music_track_detail_json(const char *params_json_utf8);
+
+char *chaos_music_track_lyrics_json(const char *params_json_utf8);
+
 char *chaos_music_artist_albums_json(const char *params_json_utf8);
 
+char *chaos_music_search_playlists_json(const char *params_json_utf8);
+
+char *chaos_music_playlist_tracks_json(const char *params_json_utf8);
+
+char *chaos_music_user_playlists_json(const char *params_json_utf8);
+
+char *chaos_music_list_charts_json(const char *params_json_utf8);
+
+char *chaos_music_chart_tracks_json(const char *params_json_utf8);
+
+char *chaos_music_daily_recommendations_json(const char *params_json_utf8);
+
 char *chaos_music_track_play_url_json(const char *params_json_utf8);
 
 char *chaos_music_qq_login_qr_create_json(const char *login_type_utf8);
@@ -34,12 +50,26 @@ char *chaos_music_kugou_login_qr_create_json(const char *login_type_utf8);
 
 char *chaos_music_kugou_login_qr_poll_json(const char *session_id_utf8);
 
+char *chaos_music_netease_login_qr_create_json(const char *login_type_utf8);
+
+char *chaos_music_netease_login_qr_poll_json(const char *session_id_utf8);
+
+char *chaos_music_netease_refresh_cookie_json(const char *params_json_utf8);
+
+char *chaos_music_kuwo_login_token_json(const char *params_json_utf8);
+
 char *chaos_music_download_start_json(const char *start_params_json_utf8);
 
 char *chaos_music_download_status_json(const char *session_id_utf8);
 
 char *chaos_music_download_cancel_json(const char *session_id_utf8);
 
+char *chaos_music_download_pause_json(const char *session_id_utf8);
+
+char *chaos_music_download_resume_json(const char *session_id_utf8);
+
+char *chaos_download_scheduler_set_json(const char *params_json_utf8);
+
 char *chaos_music_download_blocking_json(const char *start_params_json_utf8);
 
 // -----------------------------
@@ -60,6 +90,8 @@ char *chaos_bili_refresh_cookie_json(const char *params_json_utf8);
 
 char *chaos_bili_parse_json(const char *params_json_utf8);
 
+char *chaos_bili_live_replay_list_json(const char *params_json_utf8);
+
 char *chaos_bili_download_start_json(const char *params_json_utf8);
 
 char *chaos_bili_download_status_json(const char *session_id_utf8);
@@ -74,6 +106,10 @@ char *chaos_bili_task_get_json(const char *params_json_utf8);
 
 char *chaos_bili_task_cancel_json(const char *params_json_utf8);
 
+char *chaos_bili_task_pause_json(const char *params_json_utf8);
+
+char *chaos_bili_task_resume_json(const char *params_json_utf8);
+
 char *chaos_bili_tasks_remove_finished_json(const char *params_json_utf8);
 
 uint32_t chaos_ffi_api_version(void);
@@ -101,7 +137,8 @@ char *chaos_lyrics_search_json(const char *title_utf8,
                                uint32_t limit,
                                uint8_t strict_match,
                                const char *services_csv_utf8_or_null,
-                               uint32_t timeout_ms);
+                               uint32_t timeout_ms,
+                               const char *isrc_utf8_or_null);
 
 char *chaos_subtitle_download_item_json(const char *item_json_utf8,
                                         const char *out_dir_utf8,
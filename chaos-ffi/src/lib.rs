@@ -12,6 +12,7 @@ use std::time::Duration;
 
 use libc::{c_char, c_void};
 
+use chaos_core::download_scheduler::{DownloadScheduler, DownloadTask};
 use chaos_core::live_directory;
 use chaos_core::{bili_video, danmaku, livestream, lyrics, music, now_playing, subtitle};
 use chaos_proto::{
@@ -22,12 +23,16 @@ use chaos_proto::{
     BiliCheckLoginParams,
     BiliCheckLoginResult,
     BiliDownloadJobStatus,
+    BiliDownloadOptions,
     BiliDownloadStartParams,
     BiliDownloadStartResult,
     BiliDownloadStatus,
     BiliDownloadTotals,
     BiliJobPhase,
     BiliJobState,
+    BiliLiveReplay,
+    BiliLiveReplayListParams,
+    BiliLiveReplayListResult,
     BiliLoginQr,
     BiliLoginQrCreateV2Params,
     BiliLoginQrPollResult,
@@ -40,19 +45,25 @@ use chaos_proto::{
     BiliParsedVideo,
     BiliRefreshCookieParams,
     BiliRefreshCookieResult,
+    BiliSubtitleFormat,
     BiliTask,
     BiliTaskAddParams,
     BiliTaskAddResult,
     BiliTaskCancelParams,
     BiliTaskDetail,
     BiliTaskGetParams,
+    BiliTaskPauseParams,
+    BiliTaskResumeParams,
     BiliTasksGetParams,
     BiliTasksGetResult,
     BiliTasksRemoveFinishedParams,
     BiliTvAuth,
     BiliWebAuth,
+    DownloadSchedulerSetParams,
+    DownloadSchedulerState,
     // music (FFI JSON shape follows chaos-proto)
     KugouUserInfo,
+    KuwoUserInfo,
     LiveDirCategory,
     LiveDirRoomCard,
     LiveDirRoomListResult,
@@ -66,6 +77,9 @@ use chaos_proto::{
     MusicArtist,
     MusicArtistAlbumsParams,
     MusicAuthState,
+    MusicChart,
+    MusicChartTracksParams,
+    MusicDailyRecommendationsParams,
     MusicDownloadJobResult,
     MusicDownloadStartParams,
     MusicDownloadStartResult,
@@ -73,16 +87,26 @@ use chaos_proto::{
     MusicDownloadTarget,
     MusicDownloadTotals,
     MusicJobState,
+    MusicKuwoLoginTokenParams,
+    MusicListChartsParams,
     MusicLoginQr,
     MusicLoginQrPollResult,
     MusicLoginQrState,
     MusicLoginType,
+    MusicLyrics,
+    MusicNeteaseCookieResult,
+    MusicNeteaseRefreshCookieParams,
+    MusicPlaylist,
+    MusicPlaylistTracksParams,
     MusicProviderConfig,
     MusicSearchParams,
     MusicService,
     MusicTrack,
+    MusicTrackParams,
     MusicTrackPlayUrlParams,
     MusicTrackPlayUrlResult,
+    MusicUserPlaylistsParams,
+    NeteaseUserInfo,
     OkReply,
     QqMusicCookie,
     ReasoningMode,
@@ -96,9 +120,10 @@ use chaos_proto::{
     VoiceChatChunkNotif,
     VoiceChatStreamStartParams,
     VoiceChatStreamStartResult,
+    WordLyricsFormat,
 };
 
-const API_VERSION: u32 = 11;
+const API_VERSION: u32 = 19;
 
 fn ensure_rustls_provider() {
     static ONCE: OnceLock<()> = OnceLock::new();
@@ -376,13 +401,368 @@ pub extern "C" fn chaos_music_album_tracks_json(params_json_utf8: *const c_char)
     }
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_track_detail_json(params_json_utf8: *const c_char) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: MusicTrackParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+
+        let client = {
+            let st = music_state();
+            st.lock()
+                .map_err(|_| {
+                    set_last_error("music state poisoned", None);
+                })?
+                .client
+                .clone()
+        };
+
+        let out = runtime()
+            .block_on(client.track_detail(
+                map_music_service_to_core(params.service),
+                params.track_id.trim(),
+            ))
+            .map_err(|e| {
+                set_last_error("music trackDetail failed", Some(e.to_string()));
+            })?;
+        serde_json::to_string(&map_music_track_to_proto(out)).map_err(|e| {
+            set_last_error("failed to serialize track", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_track_detail_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_track_lyrics_json(params_json_utf8: *const c_char) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: MusicTrackParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+
+        let client = {
+            let st = music_state();
+            st.lock()
+                .map_err(|_| {
+                    set_last_error("music state poisoned", None);
+                })?
+                .client
+                .clone()
+        };
+
+        let out = runtime()
+            .block_on(client.track_lyrics(
+                map_music_service_to_core(params.service),
+                params.track_id.trim(),
+            ))
+            .map_err(|e| {
+                set_last_error("music trackLyrics failed", Some(e.to_string()));
+            })?;
+        serde_json::to_string(&map_music_lyrics_to_proto(out)).map_err(|e| {
+            set_last_error("failed to serialize lyrics", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_track_lyrics_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn chaos_music_artist_albums_json(params_json_utf8: *const c_char) -> *mut c_char {
     let res = std::panic::catch_unwind(|| -> Result<String, ()> {
         let json = require_cstr(params_json_utf8, "params_json_utf8")?;
-        let params: MusicArtistAlbumsParams = serde_json::from_str(json).map_err(|e| {
+        let params: MusicArtistAlbumsParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+
+        let client = {
+            let st = music_state();
+            st.lock()
+                .map_err(|_| {
+                    set_last_error("music state poisoned", None);
+                })?
+                .client
+                .clone()
+        };
+
+        let out = runtime()
+            .block_on(client.artist_albums(
+                map_music_service_to_core(params.service),
+                params.artist_id.trim(),
+            ))
+            .map_err(|e| {
+                set_last_error("music artistAlbums failed", Some(e.to_string()));
+            })?;
+        let mapped: Vec<MusicAlbum> = out.into_iter().map(map_music_album_to_proto).collect();
+        serde_json::to_string(&mapped).map_err(|e| {
+            set_last_error("failed to serialize albums", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_artist_albums_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
+fn parse_login_type(s: &str) -> Result<MusicLoginType, ()> {
+    let p = s.trim().to_ascii_lowercase();
+    match p.as_str() {
+        "qq" => Ok(MusicLoginType::Qq),
+        "wechat" | "wx" => Ok(MusicLoginType::Wechat),
+        _ => Err(()),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_search_playlists_json(
+    params_json_utf8: *const c_char,
+) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: MusicSearchParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+
+        let keyword = params.keyword.trim().to_string();
+        if keyword.is_empty() {
+            return serde_json::to_string::<Vec<MusicPlaylist>>(&vec![]).map_err(|e| {
+                set_last_error("failed to serialize playlists", Some(e.to_string()));
+            });
+        }
+
+        let client = {
+            let st = music_state();
+            st.lock()
+                .map_err(|_| {
+                    set_last_error("music state poisoned", None);
+                })?
+                .client
+                .clone()
+        };
+
+        let out = runtime()
+            .block_on(client.search_playlists(
+                map_music_service_to_core(params.service),
+                &keyword,
+                params.page.max(1),
+                params.page_size.clamp(1, 50).max(1),
+            ))
+            .map_err(|e| {
+                set_last_error("music search playlists failed", Some(e.to_string()));
+            })?;
+
+        let mapped: Vec<MusicPlaylist> = out.into_iter().map(map_music_playlist_to_proto).collect();
+        serde_json::to_string(&mapped).map_err(|e| {
+            set_last_error("failed to serialize playlists", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_search_playlists_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_playlist_tracks_json(params_json_utf8: *const c_char) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: MusicPlaylistTracksParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+        let auth = map_music_auth_to_core(params.auth);
+
+        let client = {
+            let st = music_state();
+            st.lock()
+                .map_err(|_| {
+                    set_last_error("music state poisoned", None);
+                })?
+                .client
+                .clone()
+        };
+
+        let out = runtime()
+            .block_on(client.playlist_tracks(
+                map_music_service_to_core(params.service),
+                params.playlist_id.trim(),
+                &auth,
+            ))
+            .map_err(|e| {
+                set_last_error("music playlistTracks failed", Some(e.to_string()));
+            })?;
+        let mapped: Vec<MusicTrack> = out.into_iter().map(map_music_track_to_proto).collect();
+        serde_json::to_string(&mapped).map_err(|e| {
+            set_last_error("failed to serialize tracks", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_playlist_tracks_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_user_playlists_json(params_json_utf8: *const c_char) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: MusicUserPlaylistsParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+        let auth = map_music_auth_to_core(params.auth);
+
+        let client = {
+            let st = music_state();
+            st.lock()
+                .map_err(|_| {
+                    set_last_error("music state poisoned", None);
+                })?
+                .client
+                .clone()
+        };
+
+        let out = runtime()
+            .block_on(client.user_playlists(map_music_service_to_core(params.service), &auth))
+            .map_err(|e| {
+                set_last_error("music userPlaylists failed", Some(e.to_string()));
+            })?;
+        let mapped: Vec<MusicPlaylist> = out.into_iter().map(map_music_playlist_to_proto).collect();
+        serde_json::to_string(&mapped).map_err(|e| {
+            set_last_error("failed to serialize playlists", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_user_playlists_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_list_charts_json(params_json_utf8: *const c_char) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: MusicListChartsParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+
+        let client = {
+            let st = music_state();
+            st.lock()
+                .map_err(|_| {
+                    set_last_error("music state poisoned", None);
+                })?
+                .client
+                .clone()
+        };
+
+        let out = runtime()
+            .block_on(client.list_charts(map_music_service_to_core(params.service)))
+            .map_err(|e| {
+                set_last_error("music listCharts failed", Some(e.to_string()));
+            })?;
+        let mapped: Vec<MusicChart> = out.into_iter().map(map_music_chart_to_proto).collect();
+        serde_json::to_string(&mapped).map_err(|e| {
+            set_last_error("failed to serialize charts", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_list_charts_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_chart_tracks_json(params_json_utf8: *const c_char) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: MusicChartTracksParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+
+        let client = {
+            let st = music_state();
+            st.lock()
+                .map_err(|_| {
+                    set_last_error("music state poisoned", None);
+                })?
+                .client
+                .clone()
+        };
+
+        let out = runtime()
+            .block_on(client.chart_tracks(
+                map_music_service_to_core(params.service),
+                params.chart_id.trim(),
+            ))
+            .map_err(|e| {
+                set_last_error("music chartTracks failed", Some(e.to_string()));
+            })?;
+        let mapped: Vec<MusicTrack> = out.into_iter().map(map_music_track_to_proto).collect();
+        serde_json::to_string(&mapped).map_err(|e| {
+            set_last_error("failed to serialize tracks", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_chart_tracks_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_daily_recommendations_json(
+    params_json_utf8: *const c_char,
+) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: MusicDailyRecommendationsParams = serde_json::from_str(json).map_err(|e| {
             set_last_error("invalid params_json_utf8", Some(e.to_string()));
         })?;
+        let auth = map_music_auth_to_core(params.auth);
 
         let client = {
             let st = music_state();
@@ -395,16 +775,15 @@ pub extern "C" fn chaos_music_artist_albums_json(params_json_utf8: *const c_char
         };
 
         let out = runtime()
-            .block_on(client.artist_albums(
-                map_music_service_to_core(params.service),
-                params.artist_id.trim(),
-            ))
+            .block_on(
+                client.daily_recommendations(map_music_service_to_core(params.service), &auth),
+            )
             .map_err(|e| {
-                set_last_error("music artistAlbums failed", Some(e.to_string()));
+                set_last_error("music dailyRecommendations failed", Some(e.to_string()));
             })?;
-        let mapped: Vec<MusicAlbum> = out.into_iter().map(map_music_album_to_proto).collect();
+        let mapped: Vec<MusicTrack> = out.into_iter().map(map_music_track_to_proto).collect();
         serde_json::to_string(&mapped).map_err(|e| {
-            set_last_error("failed to serialize albums", Some(e.to_string()));
+            set_last_error("failed to serialize tracks", Some(e.to_string()));
         })
     });
 
@@ -412,21 +791,12 @@ pub extern "C" fn chaos_music_artist_albums_json(params_json_utf8: *const c_char
         Ok(Ok(s)) => ok_json(s),
         Ok(Err(())) => ptr::null_mut(),
         Err(_) => {
-            set_last_error("panic in chaos_music_artist_albums_json", None);
+            set_last_error("panic in chaos_music_daily_recommendations_json", None);
             ptr::null_mut()
         }
     }
 }
 
-fn parse_login_type(s: &str) -> Result<MusicLoginType, ()> {
-    let p = s.trim().to_ascii_lowercase();
-    match p.as_str() {
-        "qq" => Ok(MusicLoginType::Qq),
-        "wechat" | "wx" => Ok(MusicLoginType::Wechat),
-        _ => Err(()),
-    }
-}
-
 #[unsafe(no_mangle)]
 pub extern "C" fn chaos_music_track_play_url_json(params_json_utf8: *const c_char) -> *mut c_char {
     let res = std::panic::catch_unwind(|| -> Result<String, ()> {
@@ -465,7 +835,7 @@ pub extern "C" fn chaos_music_track_play_url_json(params_json_utf8: *const c_cha
         let (url, ext) = runtime()
             .block_on(async move {
                 client
-                    .track_download_url(svc, &track_id, &quality_id, &auth)
+                    .track_play_url(svc, &track_id, &quality_id, &auth)
                     .await
                     .map_err(|e| e.to_string())
             })
@@ -596,6 +966,8 @@ pub extern "C" fn chaos_music_qq_login_qr_poll_json(session_id_utf8: *const c_ch
                 message: Some("login session timeout".to_string()),
                 cookie: None,
                 kugou_user: None,
+                netease_cookie: None,
+                netease_user: None,
             };
             return serde_json::to_string(&out).map_err(|e| {
                 set_last_error("failed to serialize poll result", Some(e.to_string()));
@@ -632,6 +1004,8 @@ pub extern "C" fn chaos_music_qq_login_qr_poll_json(session_id_utf8: *const c_ch
                 message: msg,
                 cookie: None,
                 kugou_user: None,
+                netease_cookie: None,
+                netease_user: None,
             };
             return serde_json::to_string(&out).map_err(|e| {
                 set_last_error("failed to serialize poll result", Some(e.to_string()));
@@ -695,6 +1069,8 @@ pub extern "C" fn chaos_music_qq_login_qr_poll_json(session_id_utf8: *const c_ch
             message: None,
             cookie: Some(cookie),
             kugou_user: None,
+            netease_cookie: None,
+            netease_user: None,
         };
         serde_json::to_string(&out).map_err(|e| {
             set_last_error("failed to serialize poll result", Some(e.to_string()));
@@ -880,6 +1256,8 @@ pub extern "C" fn chaos_music_kugou_login_qr_poll_json(
                 message: Some("login session timeout".to_string()),
                 cookie: None,
                 kugou_user: None,
+                netease_cookie: None,
+                netease_user: None,
             };
             return serde_json::to_string(&out).map_err(|e| {
                 set_last_error("failed to serialize poll result", Some(e.to_string()));
@@ -933,6 +1311,8 @@ pub extern "C" fn chaos_music_kugou_login_qr_poll_json(
                     token: u.token,
                     userid: u.userid,
                 }),
+                netease_cookie: None,
+                netease_user: None,
             };
             return serde_json::to_string(&out).map_err(|e| {
                 set_last_error("failed to serialize poll result", Some(e.to_string()));
@@ -945,6 +1325,8 @@ pub extern "C" fn chaos_music_kugou_login_qr_poll_json(
             message: None,
             cookie: None,
             kugou_user: None,
+            netease_cookie: None,
+            netease_user: None,
         };
         serde_json::to_string(&out).map_err(|e| {
             set_last_error("failed to serialize poll result", Some(e.to_string()));
@@ -961,398 +1343,334 @@ pub extern "C" fn chaos_music_kugou_login_qr_poll_json(
     }
 }
 
-async fn try_download_lyrics_for_track(
-    http: &reqwest::Client,
-    track: &MusicTrack,
-    audio_path: &std::path::Path,
-    overwrite: bool,
-) -> Result<(), String> {
-    // Best-effort: lyrics download should not fail the audio download job.
-    let title = (track.title.as_str()).trim();
-    if title.is_empty() {
-        return Ok(());
-    }
-
-    let artist = track
-        .artists
-        .iter()
-        .filter(|s| !s.trim().is_empty())
-        .cloned()
-        .collect::<Vec<_>>()
-        .join(" / ");
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_netease_login_qr_create_json(
+    login_type_utf8: *const c_char,
+) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let lt = require_cstr(login_type_utf8, "login_type_utf8")?;
+        let login_type = parse_login_type(lt).map_err(|_| {
+            set_last_error("invalid login_type_utf8 (expected: qq|wechat)", None);
+        })?;
 
-    let term = if artist.trim().is_empty() {
-        lyrics::model::LyricsSearchTerm::Keyword {
-            keyword: title.to_string(),
-        }
-    } else {
-        lyrics::model::LyricsSearchTerm::Info {
-            title: title.to_string(),
-            artist,
-            album: track.album.clone().filter(|s| !s.trim().is_empty()),
-        }
-    };
+        let (client, cfg) = {
+            let st = music_state();
+            let locked = st.lock().map_err(|_| {
+                set_last_error("music state poisoned", None);
+            })?;
+            (locked.client.clone(), locked.cfg.clone())
+        };
 
-    let mut req = lyrics::model::LyricsSearchRequest::new(term);
-    req.duration_ms = track.duration_ms;
-    req.limit = 1;
-
-    let opt = lyrics::model::LyricsSearchOptions {
-        timeout_ms: 8000,
-        strict_match: false,
-        services: vec![
-            lyrics::model::LyricsService::QQMusic,
-            lyrics::model::LyricsService::Netease,
-            lyrics::model::LyricsService::LrcLib,
-        ],
-    };
+        let qr = runtime()
+            .block_on(music::providers::netease::netease_qr_create(
+                &client.http,
+                &cfg,
+                client.timeout,
+            ))
+            .map_err(|e| {
+                set_last_error("netease qr create failed", Some(e.to_string()));
+            })?;
 
-    let mut items = lyrics::core::search_with_http(http, &req, opt)
-        .await
-        .map_err(|e| e.to_string())?;
-    let Some(best) = items.pop() else {
-        return Ok(());
-    };
-    if best.lyrics_original.trim().is_empty() {
-        return Ok(());
-    }
+        let session_id = gen_session_id("neteaselogin");
+        let created_at_unix_ms = now_unix_ms();
+        {
+            let st = music_state();
+            let mut locked = st.lock().map_err(|_| {
+                set_last_error("music state poisoned", None);
+            })?;
+            locked.netease_sessions.insert(
+                session_id.clone(),
+                NeteaseLoginSession {
+                    created_at_ms: created_at_unix_ms,
+                    key: qr.key.clone(),
+                },
+            );
+        }
 
-    let lrc_path = audio_path.with_extension("lrc");
-    if !overwrite && lrc_path.exists() {
-        return Ok(());
-    }
+        // 网易云只支持 App 扫码；login_type 仅原样回显。
+        let qr = MusicLoginQr {
+            session_id,
+            login_type,
+            mime: "image/png".to_string(),
+            base64: qr.image_base64,
+            identifier: qr.key,
+            created_at_unix_ms,
+        };
+        serde_json::to_string(&qr).map_err(|e| {
+            set_last_error("failed to serialize login qr", Some(e.to_string()));
+        })
+    });
 
-    let mut content = best.lyrics_original;
-    if let Some(t) = best.lyrics_translation {
-        if !t.trim().is_empty() {
-            content.push_str("\n\n");
-            content.push_str(&t);
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_netease_login_qr_create_json", None);
+            ptr::null_mut()
         }
     }
-    tokio::fs::write(&lrc_path, content)
-        .await
-        .map_err(|e| e.to_string())?;
-    Ok(())
 }
 
 #[unsafe(no_mangle)]
-pub extern "C" fn chaos_music_download_start_json(
-    start_params_json_utf8: *const c_char,
+pub extern "C" fn chaos_music_netease_login_qr_poll_json(
+    session_id_utf8: *const c_char,
 ) -> *mut c_char {
-    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
-        let json = require_cstr(start_params_json_utf8, "start_params_json_utf8")?;
-        let params: MusicDownloadStartParams = serde_json::from_str(json).map_err(|e| {
-            set_last_error("invalid start_params_json_utf8", Some(e.to_string()));
-        })?;
+    use music::providers::netease::{self, NeteaseQrStatus};
 
-        let MusicDownloadStartParams {
-            config,
-            auth,
-            target,
-            options,
-        } = params;
-
-        let out_dir = options.out_dir.trim().to_string();
-        if out_dir.is_empty() {
-            set_last_error("options.outDir is empty", None);
-            return Err(());
-        }
-        let quality_id = options.quality_id.trim().to_string();
-        if quality_id.is_empty() {
-            set_last_error("options.qualityId is empty", None);
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let sid = require_cstr(session_id_utf8, "session_id_utf8")?
+            .trim()
+            .to_string();
+        if sid.is_empty() {
+            set_last_error("session_id_utf8 is empty", None);
             return Err(());
         }
 
-        let cfg_core = map_music_provider_config_to_core(config);
-        let client = {
+        let (key, created_at_ms, client, cfg) = {
+            let st = music_state();
+            let locked = st.lock().map_err(|_| {
+                set_last_error("music state poisoned", None);
+            })?;
+            let Some(s) = locked.netease_sessions.get(&sid) else {
+                set_last_error("session not found", None);
+                return Err(());
+            };
+            (
+                s.key.clone(),
+                s.created_at_ms,
+                locked.client.clone(),
+                locked.cfg.clone(),
+            )
+        };
+        let remove_session = || -> Result<(), ()> {
             let st = music_state();
             let mut locked = st.lock().map_err(|_| {
                 set_last_error("music state poisoned", None);
             })?;
-            locked.cfg = cfg_core.clone();
-            locked.client.set_config(cfg_core.clone());
-            locked.client.clone()
+            locked.netease_sessions.remove(&sid);
+            Ok(())
         };
 
-        let session_id = gen_session_id("musicdl");
-        let sid = session_id.clone();
+        let mut out = MusicLoginQrPollResult {
+            session_id: sid.clone(),
+            state: MusicLoginQrState::Scan,
+            message: None,
+            cookie: None,
+            kugou_user: None,
+            netease_cookie: None,
+            netease_user: None,
+        };
+        if now_unix_ms().saturating_sub(created_at_ms) > 5 * 60 * 1000 {
+            remove_session()?;
+            out.state = MusicLoginQrState::Timeout;
+            out.message = Some("login session timeout".to_string());
+        } else {
+            let status = runtime()
+                .block_on(netease::netease_qr_poll(
+                    &client.http,
+                    &cfg,
+                    &key,
+                    client.timeout,
+                ))
+                .map_err(|e| {
+                    set_last_error("netease qr poll failed", Some(e.to_string()));
+                })?;
+            match status {
+                NeteaseQrStatus::Waiting => {}
+                NeteaseQrStatus::Scanned { nickname } => {
+                    out.state = MusicLoginQrState::Confirm;
+                    out.message = nickname;
+                }
+                NeteaseQrStatus::Expired => {
+                    remove_session()?;
+                    out.state = MusicLoginQrState::Timeout;
+                    out.message = Some("qrcode expired".to_string());
+                }
+                NeteaseQrStatus::Done { cookie } => {
+                    remove_session()?;
+                    out.state = MusicLoginQrState::Done;
+                    // Best-effort：账号信息查询失败不影响登录结果。
+                    out.netease_user = runtime()
+                        .block_on(netease::account_info(
+                            &client.http,
+                            &cfg,
+                            &cookie,
+                            client.timeout,
+                        ))
+                        .ok()
+                        .map(map_music_netease_user_to_proto);
+                    out.netease_cookie = Some(cookie);
+                }
+                NeteaseQrStatus::Other { code, message } => {
+                    out.state = MusicLoginQrState::Other;
+                    out.message = Some(message.unwrap_or_else(|| format!("code={code}")));
+                }
+            }
+        }
+        serde_json::to_string(&out).map_err(|e| {
+            set_last_error("failed to serialize poll result", Some(e.to_string()));
+        })
+    });
 
-        runtime()
-            .block_on(async move {
-                let opts = options;
-                let out_dir = PathBuf::from(out_dir);
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_netease_login_qr_poll_json", None);
+            ptr::null_mut()
+        }
+    }
+}
 
-                let mut auth = map_music_auth_to_core(auth);
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_netease_refresh_cookie_json(
+    params_json_utf8: *const c_char,
+) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: MusicNeteaseRefreshCookieParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
 
-                let target_service = match &target {
-                    MusicDownloadTarget::Track { track } => track.service,
-                    MusicDownloadTarget::Album { service, .. } => *service,
-                    MusicDownloadTarget::ArtistAll { service, .. } => *service,
-                };
-                if matches!(target_service, MusicService::Netease) && auth.netease_cookie.is_none()
-                {
-                    if let Ok(c) = music::providers::netease::fetch_anonymous_cookie(
-                        &client.http,
-                        &cfg_core,
-                        client.timeout,
-                    )
-                    .await
-                    {
-                        auth.netease_cookie = Some(c);
-                    }
-                }
+        let (client, cfg) = {
+            let st = music_state();
+            let locked = st.lock().map_err(|_| {
+                set_last_error("music state poisoned", None);
+            })?;
+            (locked.client.clone(), locked.cfg.clone())
+        };
 
-                let mut items: Vec<(MusicTrack, Option<u32>)> = Vec::new();
-                match target {
-                    MusicDownloadTarget::Track { track } => items.push((track, None)),
-                    MusicDownloadTarget::Album { service, album_id } => {
-                        let tracks = client
-                            .album_tracks(map_music_service_to_core(service), &album_id)
-                            .await
-                            .map_err(|e| e.to_string())?;
-                        for (idx, t) in tracks.into_iter().enumerate() {
-                            items.push((map_music_track_to_proto(t), Some((idx as u32) + 1)));
-                        }
-                    }
-                    MusicDownloadTarget::ArtistAll { service, artist_id } => {
-                        let albums = client
-                            .artist_albums(map_music_service_to_core(service), &artist_id)
-                            .await
-                            .map_err(|e| e.to_string())?;
-                        let mut seen = std::collections::HashSet::<String>::new();
-                        for alb in albums {
-                            let album_title = alb.title.clone();
-                            let tracks = client
-                                .album_tracks(map_music_service_to_core(service), &alb.id)
-                                .await
-                                .unwrap_or_default();
-                            for (idx, mut t) in tracks.into_iter().enumerate() {
-                                if !seen.insert(t.id.clone()) {
-                                    continue;
-                                }
-                                if t.album.is_none() {
-                                    t.album = Some(album_title.clone());
-                                }
-                                items.push((map_music_track_to_proto(t), Some((idx as u32) + 1)));
-                            }
-                        }
-                    }
-                }
+        let out = runtime()
+            .block_on(async {
+                let cookie = music::providers::netease::refresh_cookie(
+                    &client.http,
+                    &cfg,
+                    &params.cookie,
+                    client.timeout,
+                )
+                .await?;
+                let user = music::providers::netease::account_info(
+                    &client.http,
+                    &cfg,
+                    &cookie,
+                    client.timeout,
+                )
+                .await?;
+                Ok::<_, music::error::MusicError>(MusicNeteaseCookieResult {
+                    cookie,
+                    user: map_music_netease_user_to_proto(user),
+                })
+            })
+            .map_err(|e| {
+                set_last_error("netease refresh cookie failed", Some(e.to_string()));
+            })?;
 
-                let total = u32::try_from(items.len()).unwrap_or(u32::MAX);
-                let status = MusicDownloadStatus {
-                    done: total == 0,
-                    totals: MusicDownloadTotals {
-                        total,
-                        done: 0,
-                        failed: 0,
-                        skipped: 0,
-                        canceled: 0,
-                    },
-                    jobs: items
-                        .iter()
-                        .enumerate()
-                        .map(|(i, (t, _))| MusicDownloadJobResult {
-                            index: i as u32,
-                            track_id: Some(t.id.clone()),
-                            state: MusicJobState::Pending,
-                            path: None,
-                            bytes: None,
-                            error: None,
-                        })
-                        .collect(),
-                };
+        serde_json::to_string(&out).map_err(|e| {
+            set_last_error("failed to serialize cookie", Some(e.to_string()));
+        })
+    });
 
-                let status = Arc::new(tokio::sync::Mutex::new(status));
-                let cancel = Arc::new(AtomicBool::new(false));
-
-                let concurrency = opts.concurrency.max(1).min(16) as usize;
-                let retries = opts.retries.min(10);
-                let overwrite = opts.overwrite;
-                let path_template = opts
-                    .path_template
-                    .as_deref()
-                    .map(|s| s.trim())
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.to_string());
-
-                let st = Arc::clone(&status);
-                let cancel_flag = Arc::clone(&cancel);
-                let req_quality = quality_id;
-
-                let handle = tokio::spawn(async move {
-                    if items.is_empty() {
-                        let mut s = st.lock().await;
-                        s.done = true;
-                        return;
-                    }
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_netease_refresh_cookie_json", None);
+            ptr::null_mut()
+        }
+    }
+}
 
-                    let (tx, rx) = tokio::sync::mpsc::channel::<(u32, MusicTrack, Option<u32>)>(
-                        items.len().max(1),
-                    );
-                    for (idx, (t, no)) in items.into_iter().enumerate() {
-                        let _ = tx.send((idx as u32, t, no)).await;
-                    }
-                    drop(tx);
-                    let rx = Arc::new(tokio::sync::Mutex::new(rx));
-
-                    let mut joinset = tokio::task::JoinSet::new();
-                    for _ in 0..concurrency {
-                        let rx = Arc::clone(&rx);
-                        let st = Arc::clone(&st);
-                        let cancel = Arc::clone(&cancel_flag);
-                        let client = client.clone();
-                        let auth = auth.clone();
-                        let out_dir = out_dir.clone();
-                        let req_quality = req_quality.clone();
-                        let path_template = path_template.clone();
-                        joinset.spawn(async move {
-                            loop {
-                                if cancel.load(Ordering::Relaxed) {
-                                    return;
-                                }
-                                let next = {
-                                    let mut locked = rx.lock().await;
-                                    locked.recv().await
-                                };
-                                let Some((index, track, track_no)) = next else {
-                                    return;
-                                };
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_kuwo_login_token_json(
+    params_json_utf8: *const c_char,
+) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: MusicKuwoLoginTokenParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
 
-                                {
-                                    let mut s = st.lock().await;
-                                    if let Some(job) = s.jobs.get_mut(index as usize) {
-                                        job.state = MusicJobState::Running;
-                                    }
-                                }
+        let client = {
+            let st = music_state();
+            let locked = st.lock().map_err(|_| {
+                set_last_error("music state poisoned", None);
+            })?;
+            locked.client.clone()
+        };
+
+        let user = runtime()
+            .block_on(music::providers::kuwo::login_with_token(
+                &client.http,
+                &params.uid,
+                &params.sid,
+                client.timeout,
+            ))
+            .map_err(|e| {
+                set_last_error("kuwo login failed", Some(e.to_string()));
+            })?;
+
+        serde_json::to_string(&map_music_kuwo_user_to_proto(user)).map_err(|e| {
+            set_last_error("failed to serialize kuwo user", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_kuwo_login_token_json", None);
+            ptr::null_mut()
+        }
+    }
+}
 
-                                let core_svc = map_music_service_to_core(track.service);
-                                let chosen_quality = choose_quality_id(&track, &req_quality)
-                                    .unwrap_or_else(|| req_quality.clone());
-
-                                let res: Result<(PathBuf, Option<u64>, Option<String>), String> =
-                                    async {
-                                        let (url, ext) = client
-                                            .track_download_url(
-                                                core_svc,
-                                                &track.id,
-                                                &chosen_quality,
-                                                &auth,
-                                            )
-                                            .await
-                                            .map_err(|e| e.to_string())?;
-
-                                        let path = if let Some(tpl) = path_template.as_deref() {
-                                            music::util::build_track_path_by_template(
-                                                &out_dir,
-                                                tpl,
-                                                &track.artists,
-                                                track.album.as_deref(),
-                                                track_no,
-                                                &track.title,
-                                                &ext,
-                                            )
-                                        } else {
-                                            music::util::build_track_path(
-                                                &out_dir,
-                                                &track.artists,
-                                                track.album.as_deref(),
-                                                track_no,
-                                                &track.title,
-                                                &ext,
-                                            )
-                                        };
-
-                                        if path.exists() && !overwrite {
-                                            return Ok((
-                                                path,
-                                                None,
-                                                Some("skipped: target exists".to_string()),
-                                            ));
-                                        }
-                                        let bytes = music::download::download_url_to_file(
-                                            &client.http,
-                                            &url,
-                                            &path,
-                                            client.timeout,
-                                            retries,
-                                            overwrite,
-                                        )
-                                        .await
-                                        .map_err(|e| e.to_string())?;
-                                        Ok((path, Some(bytes), None))
-                                    }
-                                    .await;
-
-                                match res {
-                                    Ok((path, bytes, skipped_msg)) => {
-                                        if skipped_msg.is_none() && bytes.is_some() {
-                                            let _ = try_download_lyrics_for_track(
-                                                &client.http,
-                                                &track,
-                                                &path,
-                                                overwrite,
-                                            )
-                                            .await;
-                                        }
-                                        let mut s = st.lock().await;
-                                        let mut inc_skipped: u32 = 0;
-                                        let mut inc_done: u32 = 0;
-                                        if let Some(job) = s.jobs.get_mut(index as usize) {
-                                            job.path = Some(path.to_string_lossy().to_string());
-                                            job.bytes = bytes;
-                                            job.error = skipped_msg;
-                                            if job.error.is_some() {
-                                                job.state = MusicJobState::Skipped;
-                                                inc_skipped = 1;
-                                            } else {
-                                                job.state = MusicJobState::Done;
-                                                inc_done = 1;
-                                            }
-                                        }
-                                        s.totals.skipped =
-                                            s.totals.skipped.saturating_add(inc_skipped);
-                                        s.totals.done = s.totals.done.saturating_add(inc_done);
-                                    }
-                                    Err(e) => {
-                                        let mut s = st.lock().await;
-                                        let mut inc_failed: u32 = 0;
-                                        if let Some(job) = s.jobs.get_mut(index as usize) {
-                                            job.state = MusicJobState::Failed;
-                                            job.error = Some(e);
-                                            inc_failed = 1;
-                                        }
-                                        s.totals.failed =
-                                            s.totals.failed.saturating_add(inc_failed);
-                                    }
-                                }
-                            }
-                        });
-                    }
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_download_start_json(
+    start_params_json_utf8: *const c_char,
+) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(start_params_json_utf8, "start_params_json_utf8")?;
+        let params: MusicDownloadStartParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid start_params_json_utf8", Some(e.to_string()));
+        })?;
 
-                    while joinset.join_next().await.is_some() {}
-                    let mut s = st.lock().await;
-                    s.done = true;
-                });
+        let MusicDownloadStartParams {
+            config,
+            auth,
+            target,
+            options,
+        } = params;
 
-                {
-                    let st = music_state();
-                    let mut locked = st.lock().map_err(|_| "music state poisoned".to_string())?;
-                    locked.downloads.insert(
-                        sid.clone(),
-                        MusicDownloadSession {
-                            status,
-                            cancel,
-                            handle,
-                        },
-                    );
-                }
+        let cfg_core = map_music_provider_config_to_core(config);
+        let client = {
+            let st = music_state();
+            let mut locked = st.lock().map_err(|_| {
+                set_last_error("music state poisoned", None);
+            })?;
+            locked.cfg = cfg_core.clone();
+            locked.client.set_config(cfg_core);
+            locked.client.clone()
+        };
 
-                Ok::<(), String>(())
-            })
+        let job = runtime()
+            .block_on(music::batch::DownloadJob::start(
+                client,
+                map_music_download_target_to_core(target),
+                map_music_auth_to_core(auth),
+                music::batch::DownloadJobOptions::new(map_music_download_options_to_core(options)),
+            ))
             .map_err(|e| {
-                set_last_error("music download start failed", Some(e));
+                set_last_error("music download start failed", Some(e.to_string()));
+            })?;
+
+        let session_id = gen_session_id("musicdl");
+        {
+            let st = music_state();
+            let mut locked = st.lock().map_err(|_| {
+                set_last_error("music state poisoned", None);
             })?;
+            locked.downloads.insert(session_id.clone(), job);
+        }
 
         let out = MusicDownloadStartResult { session_id };
         serde_json::to_string(&out).map_err(|e| {
@@ -1384,19 +1702,17 @@ pub extern "C" fn chaos_music_download_status_json(session_id_utf8: *const c_cha
             return Err(());
         }
 
-        let status = {
+        let out = {
             let st = music_state();
             let locked = st.lock().map_err(|_| {
                 set_last_error("music state poisoned", None);
             })?;
-            let Some(sess) = locked.downloads.get(&sid) else {
+            let Some(job) = locked.downloads.get(&sid) else {
                 set_last_error("download session not found", None);
                 return Err(());
             };
-            Arc::clone(&sess.status)
+            map_music_download_status_to_proto(job.status())
         };
-
-        let out = runtime().block_on(async move { status.lock().await.clone() });
         serde_json::to_string(&out).map_err(|e| {
             set_last_error("failed to serialize download status", Some(e.to_string()));
         })
@@ -1423,35 +1739,17 @@ pub extern "C" fn chaos_music_download_cancel_json(session_id_utf8: *const c_cha
             return Err(());
         }
 
-        let (status, cancel) = {
+        {
             let st = music_state();
-            let mut locked = st.lock().map_err(|_| {
+            let locked = st.lock().map_err(|_| {
                 set_last_error("music state poisoned", None);
             })?;
-            let Some(sess) = locked.downloads.get_mut(&sid) else {
+            let Some(job) = locked.downloads.get(&sid) else {
                 set_last_error("download session not found", None);
                 return Err(());
             };
-            sess.handle.abort();
-            (Arc::clone(&sess.status), Arc::clone(&sess.cancel))
-        };
-
-        cancel.store(true, Ordering::Relaxed);
-
-        runtime().block_on(async move {
-            let mut st = status.lock().await;
-            if !st.done {
-                let mut canceled: u32 = 0;
-                for job in st.jobs.iter_mut() {
-                    if matches!(job.state, MusicJobState::Pending | MusicJobState::Running) {
-                        job.state = MusicJobState::Canceled;
-                        canceled = canceled.saturating_add(1);
-                    }
-                }
-                st.totals.canceled = st.totals.canceled.saturating_add(canceled);
-                st.done = true;
-            }
-        });
+            job.cancel();
+        }
 
         let out = OkReply { ok: true };
         serde_json::to_string(&out).map_err(|e| {
@@ -1469,10 +1767,182 @@ pub extern "C" fn chaos_music_download_cancel_json(session_id_utf8: *const c_cha
     }
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_download_pause_json(session_id_utf8: *const c_char) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let sid = require_cstr(session_id_utf8, "session_id_utf8")?
+            .trim()
+            .to_string();
+        if sid.is_empty() {
+            set_last_error("session_id_utf8 is empty", None);
+            return Err(());
+        }
+
+        {
+            let st = music_state();
+            let locked = st.lock().map_err(|_| {
+                set_last_error("music state poisoned", None);
+            })?;
+            let Some(job) = locked.downloads.get(&sid) else {
+                set_last_error("download session not found", None);
+                return Err(());
+            };
+            job.pause();
+        }
+
+        let out = OkReply { ok: true };
+        serde_json::to_string(&out).map_err(|e| {
+            set_last_error("failed to serialize pause reply", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_download_pause_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_music_download_resume_json(session_id_utf8: *const c_char) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let sid = require_cstr(session_id_utf8, "session_id_utf8")?
+            .trim()
+            .to_string();
+        if sid.is_empty() {
+            set_last_error("session_id_utf8 is empty", None);
+            return Err(());
+        }
+
+        {
+            let st = music_state();
+            let locked = st.lock().map_err(|_| {
+                set_last_error("music state poisoned", None);
+            })?;
+            let Some(job) = locked.downloads.get(&sid) else {
+                set_last_error("download session not found", None);
+                return Err(());
+            };
+            job.resume();
+        }
+
+        let out = OkReply { ok: true };
+        serde_json::to_string(&out).map_err(|e| {
+            set_last_error("failed to serialize resume reply", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_music_download_resume_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_download_scheduler_set_json(
+    params_json_utf8: *const c_char,
+) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: DownloadSchedulerSetParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+
+        let sched = DownloadScheduler::global();
+        if let Some(n) = params.max_connections {
+            sched.set_max_connections(n);
+        }
+        if let Some(bps) = params.rate_limit_bps {
+            sched.set_rate_limit(Some(bps));
+        }
+        match params.paused {
+            Some(true) => sched.pause(),
+            Some(false) => sched.resume(),
+            None => {}
+        }
+        let s = sched.stats();
+        let out = DownloadSchedulerState {
+            max_connections: s.max_connections,
+            rate_limit_bps: s.rate_limit_bps,
+            paused: s.paused,
+            active: s.active,
+            queued: s.queued,
+            tasks: s.tasks,
+        };
+        serde_json::to_string(&out).map_err(|e| {
+            set_last_error("failed to serialize scheduler state", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_download_scheduler_set_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
 // -----------------------------
 // Bili Video (FFI JSON)
 // -----------------------------
 
+fn map_bili_subtitle_options(o: &BiliDownloadOptions) -> bili_video::subtitle::SubtitleOptions {
+    use bili_video::subtitle::{SubtitleFormat, SubtitleOptions};
+    let format = match o.subtitle_format {
+        BiliSubtitleFormat::Srt => SubtitleFormat::Srt,
+        BiliSubtitleFormat::Ass => SubtitleFormat::Ass,
+        BiliSubtitleFormat::Vtt => SubtitleFormat::Vtt,
+        BiliSubtitleFormat::Json => SubtitleFormat::Json,
+    };
+    SubtitleOptions::from_lists(
+        format,
+        &o.subtitle_langs,
+        o.subtitle_include_ai,
+        o.subtitle_bilingual.as_deref(),
+    )
+}
+
+fn map_bili_embed_options(o: &BiliDownloadOptions) -> bili_video::mux::EmbedOptions {
+    bili_video::mux::EmbedOptions {
+        metadata: o.embed_metadata,
+        chapters: o.embed_chapters,
+        cover: o.embed_cover,
+    }
+}
+
+fn map_bili_view(view: bili_video::parse::ViewInfo) -> BiliParsedVideo {
+    BiliParsedVideo {
+        aid: view.aid,
+        bvid: view.bvid,
+        title: view.title,
+        desc: view.desc,
+        pic: view.pic,
+        owner_name: view.owner_name,
+        owner_mid: view.owner_mid,
+        pub_time_unix_s: view.pub_time_unix_s,
+        pages: view
+            .pages
+            .into_iter()
+            .map(|p| BiliPage {
+                page_number: p.page_number,
+                cid: p.cid,
+                page_title: p.page_title,
+                duration_s: p.duration_s,
+                dimension: p.dimension,
+            })
+            .collect(),
+    }
+}
+
 fn map_bili_auth_to_core(a: BiliAuthState) -> bili_video::auth::AuthState {
     bili_video::auth::AuthState {
         cookie: a.cookie.and_then(|s| (!s.trim().is_empty()).then_some(s)),
@@ -2075,29 +2545,8 @@ pub extern "C" fn chaos_bili_parse_json(params_json_utf8: *const c_char) -> *mut
                     bili_video::parse::ParsedInput::Video(vid) => {
                         let view =
                             bili_video::parse::fetch_view_info(&client, &vid, cookie).await?;
-                        let pages = view
-                            .pages
-                            .into_iter()
-                            .map(|p| BiliPage {
-                                page_number: p.page_number,
-                                cid: p.cid,
-                                page_title: p.page_title,
-                                duration_s: p.duration_s,
-                                dimension: p.dimension,
-                            })
-                            .collect::<Vec<_>>();
                         Ok(BiliParseResult {
-                            videos: vec![BiliParsedVideo {
-                                aid: view.aid,
-                                bvid: view.bvid,
-                                title: view.title,
-                                desc: view.desc,
-                                pic: view.pic,
-                                owner_name: view.owner_name,
-                                owner_mid: view.owner_mid,
-                                pub_time_unix_s: view.pub_time_unix_s,
-                                pages,
-                            }],
+                            videos: vec![map_bili_view(view)],
                         })
                     }
                     bili_video::parse::ParsedInput::BangumiEpisode { ep_id } => {
@@ -2179,6 +2628,21 @@ pub extern "C" fn chaos_bili_parse_json(params_json_utf8: *const c_char) -> *mut
                             }],
                         })
                     }
+                    bili_video::parse::ParsedInput::Audio { sid } => {
+                        let info =
+                            bili_video::audio::fetch_song_info(&client, &sid, cookie).await?;
+                        Ok(BiliParseResult {
+                            videos: vec![map_bili_view(info.into_view_info())],
+                        })
+                    }
+                    bili_video::parse::ParsedInput::LiveReplay { rid } => {
+                        let info =
+                            bili_video::live_replay::fetch_replay_info(&client, &rid, cookie)
+                                .await?;
+                        Ok(BiliParseResult {
+                            videos: vec![map_bili_view(info.into_view_info())],
+                        })
+                    }
                 }
             })
             .map_err(|e: bili_video::BiliError| {
@@ -2200,6 +2664,68 @@ pub extern "C" fn chaos_bili_parse_json(params_json_utf8: *const c_char) -> *mut
     }
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_bili_live_replay_list_json(params_json_utf8: *const c_char) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: BiliLiveReplayListParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+
+        let auth = params.auth.unwrap_or_default();
+        let cookie = auth.cookie.as_deref();
+
+        let client = {
+            let st = bili_state();
+            let locked = st
+                .lock()
+                .map_err(|_| set_last_error("bili state poisoned", None))?;
+            locked.client.clone()
+        };
+
+        let page = runtime()
+            .block_on(bili_video::live_replay::list_replays(
+                &client,
+                &params.room_id,
+                params.page,
+                params.page_size,
+                cookie,
+            ))
+            .map_err(|e| {
+                set_last_error("bili live replay list failed", Some(e.to_string()));
+            })?;
+        let out = BiliLiveReplayListResult {
+            items: page
+                .items
+                .into_iter()
+                .map(|r| BiliLiveReplay {
+                    rid: r.rid,
+                    room_id: r.room_id,
+                    title: r.title,
+                    cover: r.cover,
+                    start_unix_s: r.start_unix_s,
+                    end_unix_s: r.end_unix_s,
+                    duration_ms: r.duration_ms,
+                })
+                .collect(),
+            total: page.total,
+        };
+
+        serde_json::to_string(&out).map_err(|e| {
+            set_last_error("failed to serialize replay list", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_bili_live_replay_list_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn chaos_bili_download_start_json(params_json_utf8: *const c_char) -> *mut c_char {
     let res = std::panic::catch_unwind(|| -> Result<String, ()> {
@@ -2238,6 +2764,8 @@ pub extern "C" fn chaos_bili_download_start_json(params_json_utf8: *const c_char
         let status2 = Arc::clone(&status);
         let cancel2 = Arc::clone(&cancel);
         let params2 = params.clone();
+        let dl_task = DownloadScheduler::global().task(params.options.priority);
+        let sched_task = dl_task.clone();
         let handle = runtime().spawn(async move {
             fn recompute_totals(st: &mut BiliDownloadStatus) {
                 let mut done: u32 = 0;
@@ -2412,7 +2940,7 @@ pub extern "C" fn chaos_bili_download_start_json(params_json_utf8: *const c_char
                     &vars,
                     "mp4",
                 );
-                if out_mp4.exists() {
+                if out_mp4.exists() && !params2.options.overwrite {
                     let mut st = status2.lock().await;
                     if let Some(j) = st.jobs.get_mut(job_idx) {
                         j.state = BiliJobState::Skipped;
@@ -2450,7 +2978,7 @@ pub extern "C" fn chaos_bili_download_start_json(params_json_utf8: *const c_char
                     })
                 };
                 let mut tick = tokio::time::interval(std::time::Duration::from_millis(260));
-                let dl = bili_video::download::download_to_file_ranged(&client.http, &v.base_url, &headers, &video_tmp, params2.options.concurrency, params2.options.retries, true, Some(&cancel2), Some(cb));
+                let dl = bili_video::download::download_to_file_ranged(&client.http, &v.base_url, &headers, &video_tmp, bili_video::download::DownloadOptions { concurrency: params2.options.concurrency, retries: params2.options.retries, overwrite: true }, bili_video::download::DownloadCtx { cancel: Some(cancel2.clone()), progress: Some(cb), task: Some(dl_task.clone()) });
                 tokio::pin!(dl);
                 let video_res = loop {
                     tokio::select! {
@@ -2491,7 +3019,7 @@ pub extern "C" fn chaos_bili_download_start_json(params_json_utf8: *const c_char
                     })
                 };
                 let mut tick = tokio::time::interval(std::time::Duration::from_millis(260));
-                let dl = bili_video::download::download_to_file_ranged(&client.http, &a.base_url, &headers, &audio_tmp, params2.options.concurrency, params2.options.retries, true, Some(&cancel2), Some(cb));
+                let dl = bili_video::download::download_to_file_ranged(&client.http, &a.base_url, &headers, &audio_tmp, bili_video::download::DownloadOptions { concurrency: params2.options.concurrency, retries: params2.options.retries, overwrite: true }, bili_video::download::DownloadCtx { cancel: Some(cancel2.clone()), progress: Some(cb), task: Some(dl_task.clone()) });
                 tokio::pin!(dl);
                 let audio_res = loop {
                     tokio::select! {
@@ -2518,16 +3046,21 @@ pub extern "C" fn chaos_bili_download_start_json(params_json_utf8: *const c_char
                         let mut st = status2.lock().await;
                         if let Some(j) = st.jobs.get_mut(job_idx) { j.phase = BiliJobPhase::Subtitle; }
                     };
-                    if let Ok(subs) = bili_video::subtitle::fetch_subtitles(&client, &view.bvid, &page.cid, cookie).await {
-                        for s in subs {
-                            if cancel2.load(Ordering::Relaxed) { break; }
-                            if let Ok(srt) = bili_video::subtitle::download_subtitle_srt(&client, &s.url, cookie).await {
-                                let lang = music::util::sanitize_component(&s.lang);
-                                let path = out_mp4.with_extension(format!("{lang}.srt"));
-                                let _ = tokio::fs::write(&path, srt).await;
-                                sub_paths.push(path);
-                            }
-                        }
+                    if let Ok(paths) = bili_video::subtitle::save_subtitles(
+                        &client,
+                        bili_video::subtitle::SubtitleVideo {
+                            aid: &view.aid,
+                            bvid: &view.bvid,
+                            cid: &page.cid,
+                        },
+                        cookie,
+                        &map_bili_subtitle_options(&params2.options),
+                        &out_mp4,
+                        Some(&cancel2),
+                    )
+                    .await
+                    {
+                        sub_paths = paths;
                     }
                 }
 
@@ -2549,9 +3082,32 @@ pub extern "C" fn chaos_bili_download_start_json(params_json_utf8: *const c_char
                     let mut st = status2.lock().await;
                     if let Some(j) = st.jobs.get_mut(job_idx) { j.state = BiliJobState::Muxing; j.phase = BiliJobPhase::Mux; j.bytes_downloaded = 0; j.bytes_total = None; }
                 }
-                let mux_res = bili_video::mux::mux_ffmpeg(&params2.options.ffmpeg_path, &video_tmp, &audio_tmp, &sub_paths, &out_mp4, true, Some(&cancel2)).await;
+                let opts = &params2.options;
+                let cover_tmp = out_mp4.with_extension("cover.jpg");
+                let meta = bili_video::mux::build_metadata(
+                    &client,
+                    bili_video::mux::MetadataSource::from_view(&view, page),
+                    map_bili_embed_options(opts),
+                    &cover_tmp,
+                    cookie,
+                )
+                .await;
+                let mux_res = bili_video::mux::mux_ffmpeg(
+                    &opts.ffmpeg_path,
+                    bili_video::mux::MuxInputs {
+                        video: &video_tmp,
+                        audio: &audio_tmp,
+                        subtitles: &sub_paths,
+                        metadata: meta.as_ref(),
+                    },
+                    &out_mp4,
+                    true,
+                    Some(&cancel2),
+                )
+                .await;
                 let _ = tokio::fs::remove_file(&video_tmp).await;
                 let _ = tokio::fs::remove_file(&audio_tmp).await;
+                let _ = tokio::fs::remove_file(&cover_tmp).await;
 
                 match mux_res {
                     Ok(()) => {
@@ -2592,6 +3148,7 @@ pub extern "C" fn chaos_bili_download_start_json(params_json_utf8: *const c_char
                     api: params.api,
                     status,
                     cancel,
+                    task: sched_task,
                     handle,
                 },
             );
@@ -2795,6 +3352,8 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
         let cancel2 = Arc::clone(&cancel);
         let api = params.api;
         let options = params.options.clone();
+        let dl_task = DownloadScheduler::global().task(options.priority);
+        let sched_task = dl_task.clone();
         let out_dir = params.options.out_dir.trim().to_string();
 
         let handle = runtime().spawn(async move {
@@ -2843,6 +3402,9 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
                 video_title: String,
                 owner_name: String,
                 owner_mid: String,
+                desc: String,
+                cover_url: Option<String>,
+                pub_time_unix_s: Option<i64>,
             }
 
             let (all_count, jobs): (usize, Vec<JobInput>) =
@@ -2894,6 +3456,9 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
                                     video_title: view.title.clone(),
                                     owner_name: view.owner_name.clone().unwrap_or_default(),
                                     owner_mid: view.owner_mid.clone().unwrap_or_default(),
+                                    desc: view.desc.clone().unwrap_or_default(),
+                                    cover_url: view.pic.clone(),
+                                    pub_time_unix_s: view.pub_time_unix_s,
                                 }
                             })
                             .collect::<Vec<_>>();
@@ -2952,6 +3517,9 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
                                 },
                                 owner_name: "".to_string(),
                                 owner_mid: "".to_string(),
+                                desc: String::new(),
+                                cover_url: e.cover.clone().or_else(|| season.cover.clone()),
+                                pub_time_unix_s: None,
                             })
                             .collect::<Vec<_>>();
                         (season.episodes.len(), jobs)
@@ -3008,9 +3576,173 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
                                 },
                                 owner_name: "".to_string(),
                                 owner_mid: "".to_string(),
+                                desc: String::new(),
+                                cover_url: e.cover.clone().or_else(|| season.cover.clone()),
+                                pub_time_unix_s: None,
+                            })
+                            .collect::<Vec<_>>();
+                        (season.episodes.len(), jobs)
+                    }
+                    Ok(bili_video::parse::ParsedInput::Audio { sid }) => {
+                        // 音频区：单文件直下（无 mux），路径沿用音乐下载模板。
+                        {
+                            let mut st = status2.lock().await;
+                            st.jobs = vec![BiliDownloadJobStatus {
+                                index: 0,
+                                page_number: Some(1),
+                                cid: Some(sid.clone()),
+                                title: format!("au{sid}"),
+                                state: BiliJobState::Running,
+                                phase: BiliJobPhase::Audio,
+                                bytes_downloaded: 0,
+                                bytes_total: None,
+                                speed_bps: None,
+                                path: None,
+                                error: None,
+                            }];
+                            st.totals.total = 1;
+                        }
+                        let prog_downloaded = Arc::new(AtomicU64::new(0));
+                        let prog_total = Arc::new(AtomicU64::new(0));
+                        let prog_has_total = Arc::new(AtomicBool::new(false));
+                        let cb: bili_video::download::ProgressCb = {
+                            let prog_downloaded = prog_downloaded.clone();
+                            let prog_total = prog_total.clone();
+                            let prog_has_total = prog_has_total.clone();
+                            Arc::new(move |d, t| {
+                                prog_downloaded.store(d, Ordering::Relaxed);
+                                if let Some(tt) = t {
+                                    prog_total.store(tt, Ordering::Relaxed);
+                                    prog_has_total.store(true, Ordering::Relaxed);
+                                }
+                            })
+                        };
+                        let opt = bili_video::audio::AudioDownloadOptions {
+                            out_dir: std::path::PathBuf::from(options.out_dir.trim()),
+                            path_template: options.audio_path_template.clone(),
+                            overwrite: options.overwrite,
+                            retries: options.retries,
+                            download_lyrics: true,
+                            task: Some(dl_task.clone()),
+                        };
+                        let mut tick = tokio::time::interval(std::time::Duration::from_millis(260));
+                        let dl = bili_video::audio::download_audio(&client, &sid, cookie, &opt, Some(&cancel2), Some(cb));
+                        tokio::pin!(dl);
+                        let res = loop {
+                            tokio::select! {
+                                r = &mut dl => break r,
+                                _ = tick.tick() => {
+                                    let d = prog_downloaded.load(Ordering::Relaxed);
+                                    let t = prog_has_total.load(Ordering::Relaxed).then(|| prog_total.load(Ordering::Relaxed));
+                                    let mut st = status2.lock().await;
+                                    if let Some(j) = st.jobs.get_mut(0) { j.bytes_downloaded = d; j.bytes_total = t; }
+                                }
+                            }
+                        };
+                        let mut st = status2.lock().await;
+                        if let Some(j) = st.jobs.get_mut(0) {
+                            match res {
+                                Ok(r) => {
+                                    j.state = if r.skipped { BiliJobState::Skipped } else { BiliJobState::Done };
+                                    j.title = r.info.title;
+                                    j.path = Some(r.path.to_string_lossy().to_string());
+                                    if r.skipped {
+                                        j.error = Some("target exists".to_string());
+                                    }
+                                }
+                                Err(e) => {
+                                    j.state = if cancel2.load(Ordering::Relaxed) { BiliJobState::Canceled } else { BiliJobState::Failed };
+                                    j.error = Some(e.to_string());
+                                }
+                            }
+                        }
+                        recompute_totals(&mut st);
+                        st.done = true;
+                        return;
+                    }
+                    Ok(bili_video::parse::ParsedInput::LiveReplay { rid }) => {
+                        // 直播回放：分段下载后由 ffmpeg 合并（skipMux 时保留 FLV 分段），文件名沿用 filePattern（cid 为 rid）。
+                        {
+                            let mut st = status2.lock().await;
+                            st.jobs = vec![BiliDownloadJobStatus {
+                                index: 0,
+                                page_number: Some(1),
+                                cid: Some(rid.clone()),
+                                title: rid.clone(),
+                                state: BiliJobState::Running,
+                                phase: BiliJobPhase::Video,
+                                bytes_downloaded: 0,
+                                bytes_total: None,
+                                speed_bps: None,
+                                path: None,
+                                error: None,
+                            }];
+                            st.totals.total = 1;
+                        }
+                        let prog_downloaded = Arc::new(std::sync::atomic::AtomicU64::new(0));
+                        let prog_total = Arc::new(std::sync::atomic::AtomicU64::new(0));
+                        let prog_has_total = Arc::new(AtomicBool::new(false));
+                        let cb: bili_video::download::ProgressCb = {
+                            let prog_downloaded = prog_downloaded.clone();
+                            let prog_total = prog_total.clone();
+                            let prog_has_total = prog_has_total.clone();
+                            Arc::new(move |d, t| {
+                                prog_downloaded.store(d, Ordering::Relaxed);
+                                if let Some(tt) = t {
+                                    prog_total.store(tt, Ordering::Relaxed);
+                                    prog_has_total.store(true, Ordering::Relaxed);
+                                }
                             })
-                            .collect::<Vec<_>>();
-                        (season.episodes.len(), jobs)
+                        };
+                        let opt = bili_video::live_replay::ReplayDownloadOptions {
+                            out_dir: std::path::PathBuf::from(&out_dir),
+                            file_pattern: options.file_pattern.clone(),
+                            multi_file_pattern: options.multi_file_pattern.clone(),
+                            ffmpeg_path: options.ffmpeg_path.clone(),
+                            concurrency: options.concurrency,
+                            retries: options.retries,
+                            overwrite: options.overwrite,
+                            skip_mux: options.skip_mux,
+                            task: Some(dl_task.clone()),
+                        };
+                        let mut tick = tokio::time::interval(std::time::Duration::from_millis(260));
+                        let dl = bili_video::live_replay::download_replay(&client, &rid, cookie, &opt, Some(&cancel2), Some(cb));
+                        tokio::pin!(dl);
+                        let res = loop {
+                            tokio::select! {
+                                r = &mut dl => break r,
+                                _ = tick.tick() => {
+                                    let d = prog_downloaded.load(Ordering::Relaxed);
+                                    let t = prog_has_total.load(Ordering::Relaxed).then(|| prog_total.load(Ordering::Relaxed));
+                                    let mut st = status2.lock().await;
+                                    if let Some(j) = st.jobs.get_mut(0) { j.bytes_downloaded = d; j.bytes_total = t; }
+                                }
+                            }
+                        };
+                        let mut st = status2.lock().await;
+                        if let Some(j) = st.jobs.get_mut(0) {
+                            match res {
+                                Ok(r) => {
+                                    j.title = r.info.title;
+                                    j.path = Some(r.path.to_string_lossy().to_string());
+                                    if r.skipped {
+                                        j.state = BiliJobState::Skipped;
+                                        j.error = Some("target exists".to_string());
+                                    } else {
+                                        j.state = BiliJobState::Done;
+                                        j.phase = BiliJobPhase::Mux;
+                                        j.bytes_downloaded = r.bytes;
+                                    }
+                                }
+                                Err(e) => {
+                                    j.state = if cancel2.load(Ordering::Relaxed) { BiliJobState::Canceled } else { BiliJobState::Failed };
+                                    j.error = Some(e.to_string());
+                                }
+                            }
+                        }
+                        recompute_totals(&mut st);
+                        st.done = true;
+                        return;
                     }
                     Err(e) => {
                         let mut st = status2.lock().await;
@@ -3268,7 +4000,7 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
                     "mp4",
                 );
 
-                if out_mp4.exists() {
+                if out_mp4.exists() && !options.overwrite {
                     let mut st = status2.lock().await;
                     if let Some(j) = st.jobs.get_mut(job_idx) {
                         j.state = BiliJobState::Skipped;
@@ -3342,11 +4074,16 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
                     &v.base_url,
                     &headers,
                     &video_tmp,
-                    options.concurrency,
-                    options.retries,
-                    true,
-                    Some(&cancel2),
-                    Some(video_prog),
+                    bili_video::download::DownloadOptions {
+                        concurrency: options.concurrency,
+                        retries: options.retries,
+                        overwrite: true,
+                    },
+                    bili_video::download::DownloadCtx {
+                        cancel: Some(cancel2.clone()),
+                        progress: Some(video_prog),
+                        task: Some(dl_task.clone()),
+                    },
                 )
                 .await;
                 if let Err(e) = video_res {
@@ -3391,11 +4128,16 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
                     &a.base_url,
                     &headers,
                     &audio_tmp,
-                    options.concurrency,
-                    options.retries,
-                    true,
-                    Some(&cancel2),
-                    Some(audio_prog),
+                    bili_video::download::DownloadOptions {
+                        concurrency: options.concurrency,
+                        retries: options.retries,
+                        overwrite: true,
+                    },
+                    bili_video::download::DownloadCtx {
+                        cancel: Some(cancel2.clone()),
+                        progress: Some(audio_prog),
+                        task: Some(dl_task.clone()),
+                    },
                 )
                 .await;
                 if let Err(e) = audio_res {
@@ -3425,26 +4167,21 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
                         }
                     };
                     if !job.bvid.trim().is_empty() {
-                        if let Ok(subs) = bili_video::subtitle::fetch_subtitles(
-                            &client, &job.bvid, &job.cid, cookie,
+                        if let Ok(paths) = bili_video::subtitle::save_subtitles(
+                            &client,
+                            bili_video::subtitle::SubtitleVideo {
+                                aid: &job.aid,
+                                bvid: &job.bvid,
+                                cid: &job.cid,
+                            },
+                            cookie,
+                            &map_bili_subtitle_options(&options),
+                            &out_mp4,
+                            Some(&cancel2),
                         )
                         .await
                         {
-                            for s in subs {
-                                if cancel2.load(Ordering::Relaxed) {
-                                    break;
-                                }
-                                if let Ok(srt) = bili_video::subtitle::download_subtitle_srt(
-                                    &client, &s.url, cookie,
-                                )
-                                .await
-                                {
-                                    let lang = music::util::sanitize_component(&s.lang);
-                                    let path = out_mp4.with_extension(format!("{lang}.srt"));
-                                    let _ = tokio::fs::write(&path, srt).await;
-                                    sub_paths.push(path);
-                                }
-                            }
+                            sub_paths = paths;
                         }
                     }
                 }
@@ -3485,11 +4222,32 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
                         j.bytes_total = None;
                     }
                 }
+                let cover_tmp = out_mp4.with_extension("cover.jpg");
+                let meta = bili_video::mux::build_metadata(
+                    &client,
+                    bili_video::mux::MetadataSource {
+                        title: &job.video_title,
+                        page_title: (all_count > 1).then_some(job.title.as_str()),
+                        owner_name: &job.owner_name,
+                        bvid: &job.bvid,
+                        cid: &job.cid,
+                        description: &job.desc,
+                        pub_time_unix_s: job.pub_time_unix_s,
+                        cover_url: job.cover_url.as_deref(),
+                    },
+                    map_bili_embed_options(&options),
+                    &cover_tmp,
+                    cookie,
+                )
+                .await;
                 let mux_res = bili_video::mux::mux_ffmpeg(
                     &options.ffmpeg_path,
-                    &video_tmp,
-                    &audio_tmp,
-                    &sub_paths,
+                    bili_video::mux::MuxInputs {
+                        video: &video_tmp,
+                        audio: &audio_tmp,
+                        subtitles: &sub_paths,
+                        metadata: meta.as_ref(),
+                    },
                     &out_mp4,
                     true,
                     Some(&cancel2),
@@ -3497,6 +4255,7 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
                 .await;
                 let _ = tokio::fs::remove_file(&video_tmp).await;
                 let _ = tokio::fs::remove_file(&audio_tmp).await;
+                let _ = tokio::fs::remove_file(&cover_tmp).await;
 
                 match mux_res {
                     Ok(()) => {
@@ -3557,6 +4316,7 @@ pub extern "C" fn chaos_bili_task_add_json(params_json_utf8: *const c_char) -> *
                     api,
                     status,
                     cancel,
+                    task: sched_task,
                     handle,
                 },
             );
@@ -3783,6 +4543,88 @@ pub extern "C" fn chaos_bili_task_cancel_json(params_json_utf8: *const c_char) -
     }
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_bili_task_pause_json(params_json_utf8: *const c_char) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: BiliTaskPauseParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+
+        let sid = params.task_id.trim().to_string();
+        if sid.is_empty() {
+            set_last_error("taskId is empty", None);
+            return Err(());
+        }
+
+        {
+            let st = bili_state();
+            let locked = st.lock().map_err(|_| {
+                set_last_error("bili state poisoned", None);
+            })?;
+            let Some(sess) = locked.downloads.get(&sid) else {
+                set_last_error("task not found", None);
+                return Err(());
+            };
+            sess.task.pause();
+        }
+
+        serde_json::to_string(&OkReply { ok: true }).map_err(|e| {
+            set_last_error("failed to serialize pause reply", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_bili_task_pause_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn chaos_bili_task_resume_json(params_json_utf8: *const c_char) -> *mut c_char {
+    let res = std::panic::catch_unwind(|| -> Result<String, ()> {
+        let json = require_cstr(params_json_utf8, "params_json_utf8")?;
+        let params: BiliTaskResumeParams = serde_json::from_str(json).map_err(|e| {
+            set_last_error("invalid params_json_utf8", Some(e.to_string()));
+        })?;
+
+        let sid = params.task_id.trim().to_string();
+        if sid.is_empty() {
+            set_last_error("taskId is empty", None);
+            return Err(());
+        }
+
+        {
+            let st = bili_state();
+            let locked = st.lock().map_err(|_| {
+                set_last_error("bili state poisoned", None);
+            })?;
+            let Some(sess) = locked.downloads.get(&sid) else {
+                set_last_error("task not found", None);
+                return Err(());
+            };
+            sess.task.resume();
+        }
+
+        serde_json::to_string(&OkReply { ok: true }).map_err(|e| {
+            set_last_error("failed to serialize resume reply", Some(e.to_string()));
+        })
+    });
+
+    match res {
+        Ok(Ok(s)) => ok_json(s),
+        Ok(Err(())) => ptr::null_mut(),
+        Err(_) => {
+            set_last_error("panic in chaos_bili_task_resume_json", None);
+            ptr::null_mut()
+        }
+    }
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn chaos_bili_tasks_remove_finished_json(
     params_json_utf8: *const c_char,
@@ -3876,195 +4718,26 @@ pub extern "C" fn chaos_music_download_blocking_json(
                 set_last_error("music state poisoned", None);
             })?;
             locked.cfg = cfg_core.clone();
-            locked.client.set_config(cfg_core.clone());
+            locked.client.set_config(cfg_core);
             locked.client.clone()
         };
 
         let out = runtime()
             .block_on(async move {
-                let out_dir = options.out_dir.trim().to_string();
-                if out_dir.is_empty() {
-                    return Err("options.outDir is empty".to_string());
-                }
-                let requested_quality = options.quality_id.trim().to_string();
-                if requested_quality.is_empty() {
-                    return Err("options.qualityId is empty".to_string());
-                }
-
-                let mut auth = map_music_auth_to_core(auth);
-
-                let target_service = match &target {
-                    MusicDownloadTarget::Track { track } => track.service,
-                    MusicDownloadTarget::Album { service, .. } => *service,
-                    MusicDownloadTarget::ArtistAll { service, .. } => *service,
-                };
-                if matches!(target_service, MusicService::Netease) && auth.netease_cookie.is_none()
-                {
-                    if let Ok(c) = music::providers::netease::fetch_anonymous_cookie(
-                        &client.http,
-                        &cfg_core,
-                        client.timeout,
-                    )
-                    .await
-                    {
-                        auth.netease_cookie = Some(c);
-                    }
-                }
-
-                let mut tracks: Vec<(MusicTrack, Option<u32>)> = Vec::new();
-                match target {
-                    MusicDownloadTarget::Track { track } => tracks.push((track, None)),
-                    MusicDownloadTarget::Album { service, album_id } => {
-                        let list = client
-                            .album_tracks(map_music_service_to_core(service), &album_id)
-                            .await
-                            .map_err(|e| e.to_string())?;
-                        for (idx, t) in list.into_iter().enumerate() {
-                            tracks.push((map_music_track_to_proto(t), Some((idx as u32) + 1)));
-                        }
-                    }
-                    MusicDownloadTarget::ArtistAll { service, artist_id } => {
-                        let albums = client
-                            .artist_albums(map_music_service_to_core(service), &artist_id)
-                            .await
-                            .map_err(|e| e.to_string())?;
-                        let mut seen = std::collections::HashSet::<String>::new();
-                        for alb in albums {
-                            let album_title = alb.title.clone();
-                            let list = client
-                                .album_tracks(map_music_service_to_core(service), &alb.id)
-                                .await
-                                .unwrap_or_default();
-                            for (idx, mut t) in list.into_iter().enumerate() {
-                                if !seen.insert(t.id.clone()) {
-                                    continue;
-                                }
-                                if t.album.is_none() {
-                                    t.album = Some(album_title.clone());
-                                }
-                                tracks.push((map_music_track_to_proto(t), Some((idx as u32) + 1)));
-                            }
-                        }
-                    }
-                }
-
-                let out_dir = PathBuf::from(out_dir);
-                let overwrite = options.overwrite;
-                let retries = options.retries.min(10);
-
-                let total = u32::try_from(tracks.len()).unwrap_or(u32::MAX);
-                let mut status = MusicDownloadStatus {
-                    done: total == 0,
-                    totals: MusicDownloadTotals {
-                        total,
-                        done: 0,
-                        failed: 0,
-                        skipped: 0,
-                        canceled: 0,
-                    },
-                    jobs: tracks
-                        .iter()
-                        .enumerate()
-                        .map(|(i, (t, _))| MusicDownloadJobResult {
-                            index: i as u32,
-                            track_id: Some(t.id.clone()),
-                            state: MusicJobState::Pending,
-                            path: None,
-                            bytes: None,
-                            error: None,
-                        })
-                        .collect(),
-                };
-
-                let path_template = options
-                    .path_template
-                    .as_deref()
-                    .map(|s| s.trim())
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.to_string());
-
-                for (idx, (track, track_no)) in tracks.into_iter().enumerate() {
-                    if let Some(job) = status.jobs.get_mut(idx) {
-                        job.state = MusicJobState::Running;
-                    }
-
-                    let chosen_quality = choose_quality_id(&track, &requested_quality)
-                        .unwrap_or_else(|| requested_quality.clone());
-                    let core_svc = map_music_service_to_core(track.service);
-
-                    let (url, ext) = client
-                        .track_download_url(core_svc, &track.id, &chosen_quality, &auth)
-                        .await
-                        .map_err(|e| e.to_string())?;
-                    let path = if let Some(tpl) = path_template.as_deref() {
-                        music::util::build_track_path_by_template(
-                            &out_dir,
-                            tpl,
-                            &track.artists,
-                            track.album.as_deref(),
-                            track_no,
-                            &track.title,
-                            &ext,
-                        )
-                    } else {
-                        music::util::build_track_path(
-                            &out_dir,
-                            &track.artists,
-                            track.album.as_deref(),
-                            track_no,
-                            &track.title,
-                            &ext,
-                        )
-                    };
-                    if path.exists() && !overwrite {
-                        if let Some(job) = status.jobs.get_mut(idx) {
-                            job.state = MusicJobState::Skipped;
-                            job.path = Some(path.to_string_lossy().to_string());
-                            job.error = Some("skipped: target exists".to_string());
-                        }
-                        status.totals.skipped = status.totals.skipped.saturating_add(1);
-                        continue;
-                    }
-                    match music::download::download_url_to_file(
-                        &client.http,
-                        &url,
-                        &path,
-                        client.timeout,
-                        retries,
-                        overwrite,
-                    )
-                    .await
-                    {
-                        Ok(bytes) => {
-                            let _ = try_download_lyrics_for_track(
-                                &client.http,
-                                &track,
-                                &path,
-                                overwrite,
-                            )
-                            .await;
-                            if let Some(job) = status.jobs.get_mut(idx) {
-                                job.state = MusicJobState::Done;
-                                job.path = Some(path.to_string_lossy().to_string());
-                                job.bytes = Some(bytes);
-                            }
-                            status.totals.done = status.totals.done.saturating_add(1);
-                        }
-                        Err(e) => {
-                            if let Some(job) = status.jobs.get_mut(idx) {
-                                job.state = MusicJobState::Failed;
-                                job.error = Some(e.to_string());
-                            }
-                            status.totals.failed = status.totals.failed.saturating_add(1);
-                        }
-                    }
-                }
-
-                status.done = true;
-                Ok(status)
+                let job = music::batch::DownloadJob::start(
+                    client,
+                    map_music_download_target_to_core(target),
+                    map_music_auth_to_core(auth),
+                    music::batch::DownloadJobOptions::new(map_music_download_options_to_core(
+                        options,
+                    )),
+                )
+                .await?;
+                Ok::<_, music::error::MusicError>(job.wait().await)
             })
+            .map(map_music_download_status_to_proto)
             .map_err(|e| {
-                set_last_error("music download blocking failed", Some(e));
+                set_last_error("music download blocking failed", Some(e.to_string()));
             })?;
 
         serde_json::to_string(&out).map_err(|e| {
@@ -4212,6 +4885,7 @@ fn map_music_track_to_proto(t: music::model::MusicTrack) -> MusicTrack {
         album_id: t.album_id,
         duration_ms: t.duration_ms,
         cover_url: t.cover_url,
+        isrc: t.isrc,
         qualities: t
             .qualities
             .into_iter()
@@ -4226,6 +4900,141 @@ fn map_music_track_to_proto(t: music::model::MusicTrack) -> MusicTrack {
     }
 }
 
+fn map_music_quality_to_core(q: chaos_proto::MusicQuality) -> music::model::MusicQuality {
+    music::model::MusicQuality {
+        id: q.id,
+        label: q.label,
+        format: q.format,
+        bitrate_kbps: q.bitrate_kbps,
+        lossless: q.lossless,
+    }
+}
+
+fn map_music_track_to_core(t: MusicTrack) -> music::model::MusicTrack {
+    music::model::MusicTrack {
+        service: map_music_service_to_core(t.service),
+        id: t.id,
+        title: t.title,
+        artists: t.artists,
+        artist_ids: t.artist_ids,
+        album: t.album,
+        album_id: t.album_id,
+        duration_ms: t.duration_ms,
+        cover_url: t.cover_url,
+        isrc: t.isrc,
+        qualities: t
+            .qualities
+            .into_iter()
+            .map(map_music_quality_to_core)
+            .collect(),
+    }
+}
+
+fn map_music_download_target_to_core(t: MusicDownloadTarget) -> music::model::MusicDownloadTarget {
+    match t {
+        MusicDownloadTarget::Track { track } => music::model::MusicDownloadTarget::Track {
+            track: Box::new(map_music_track_to_core(*track)),
+        },
+        MusicDownloadTarget::Album { service, album_id } => {
+            music::model::MusicDownloadTarget::Album {
+                service: map_music_service_to_core(service),
+                album_id,
+            }
+        }
+        MusicDownloadTarget::ArtistAll { service, artist_id } => {
+            music::model::MusicDownloadTarget::ArtistAll {
+                service: map_music_service_to_core(service),
+                artist_id,
+            }
+        }
+        MusicDownloadTarget::Playlist {
+            service,
+            playlist_id,
+        } => music::model::MusicDownloadTarget::Playlist {
+            service: map_music_service_to_core(service),
+            playlist_id,
+        },
+    }
+}
+
+fn map_music_download_options_to_core(
+    o: chaos_proto::MusicDownloadOptions,
+) -> music::model::MusicDownloadOptions {
+    music::model::MusicDownloadOptions {
+        quality_id: o.quality_id,
+        out_dir: o.out_dir,
+        path_template: o.path_template,
+        overwrite: o.overwrite,
+        concurrency: o.concurrency,
+        retries: o.retries,
+        priority: o.priority,
+        fallback_services: o
+            .fallback_services
+            .into_iter()
+            .map(map_music_service_to_core)
+            .collect(),
+        fallback_min_score: o.fallback_min_score,
+        skip_owned: o.skip_owned,
+        post_process: o
+            .post_process
+            .map(|pp| music::model::MusicPostProcessOptions {
+                ffmpeg_path: pp.ffmpeg_path,
+                transcode: pp.transcode.map(|t| music::model::MusicTranscodeOptions {
+                    codec: match t.codec {
+                        chaos_proto::MusicTranscodeCodec::Mp3 => {
+                            music::model::MusicTranscodeCodec::Mp3
+                        }
+                        chaos_proto::MusicTranscodeCodec::Aac => {
+                            music::model::MusicTranscodeCodec::Aac
+                        }
+                        chaos_proto::MusicTranscodeCodec::Flac => {
+                            music::model::MusicTranscodeCodec::Flac
+                        }
+                    },
+                    bitrate_kbps: t.bitrate_kbps,
+                    source_formats: t.source_formats,
+                    keep_source: t.keep_source,
+                }),
+                replay_gain: pp.replay_gain,
+            }),
+    }
+}
+
+fn map_music_download_status_to_proto(s: music::model::MusicDownloadStatus) -> MusicDownloadStatus {
+    MusicDownloadStatus {
+        done: s.done,
+        totals: MusicDownloadTotals {
+            total: s.totals.total,
+            done: s.totals.done,
+            failed: s.totals.failed,
+            skipped: s.totals.skipped,
+            canceled: s.totals.canceled,
+        },
+        jobs: s
+            .jobs
+            .into_iter()
+            .map(|j| MusicDownloadJobResult {
+                index: j.index,
+                track_id: j.track_id,
+                state: match j.state {
+                    music::model::MusicJobState::Pending => MusicJobState::Pending,
+                    music::model::MusicJobState::Running => MusicJobState::Running,
+                    music::model::MusicJobState::Done => MusicJobState::Done,
+                    music::model::MusicJobState::Failed => MusicJobState::Failed,
+                    music::model::MusicJobState::Skipped => MusicJobState::Skipped,
+                    music::model::MusicJobState::Canceled => MusicJobState::Canceled,
+                },
+                path: j.path,
+                bytes: j.bytes,
+                error: j.error,
+                source_service: j.source_service.map(map_music_service_to_proto),
+                source_track_id: j.source_track_id,
+                upgraded_from: j.upgraded_from,
+            })
+            .collect(),
+    }
+}
+
 fn map_music_album_to_proto(a: music::model::MusicAlbum) -> MusicAlbum {
     MusicAlbum {
         service: map_music_service_to_proto(a.service),
@@ -4239,6 +5048,30 @@ fn map_music_album_to_proto(a: music::model::MusicAlbum) -> MusicAlbum {
     }
 }
 
+fn map_music_playlist_to_proto(p: music::model::MusicPlaylist) -> MusicPlaylist {
+    MusicPlaylist {
+        service: map_music_service_to_proto(p.service),
+        id: p.id,
+        title: p.title,
+        creator: p.creator,
+        creator_id: p.creator_id,
+        cover_url: p.cover_url,
+        track_count: p.track_count,
+        description: p.description,
+    }
+}
+
+fn map_music_chart_to_proto(c: music::model::MusicChart) -> MusicChart {
+    MusicChart {
+        service: map_music_service_to_proto(c.service),
+        id: c.id,
+        title: c.title,
+        cover_url: c.cover_url,
+        description: c.description,
+        update_frequency: c.update_frequency,
+    }
+}
+
 fn map_music_artist_to_proto(a: music::model::MusicArtist) -> MusicArtist {
     MusicArtist {
         service: map_music_service_to_proto(a.service),
@@ -4270,6 +5103,31 @@ fn map_music_provider_config_to_core(cfg: MusicProviderConfig) -> music::model::
     }
 }
 
+fn map_music_netease_user_to_proto(u: music::model::NeteaseUserInfo) -> NeteaseUserInfo {
+    NeteaseUserInfo {
+        user_id: u.user_id,
+        nickname: u.nickname,
+        avatar_url: u.avatar_url,
+        vip_type: u.vip_type,
+    }
+}
+
+fn map_music_lyrics_to_proto(l: music::model::MusicLyrics) -> MusicLyrics {
+    MusicLyrics {
+        service: map_music_service_to_proto(l.service),
+        track_id: l.track_id,
+        original: l.original,
+        translation: l.translation,
+        romanization: l.romanization,
+        word_lyrics: l.word_lyrics,
+        word_format: l.word_format.map(|f| match f {
+            music::model::WordLyricsFormat::Qrc => WordLyricsFormat::Qrc,
+            music::model::WordLyricsFormat::Krc => WordLyricsFormat::Krc,
+            music::model::WordLyricsFormat::Yrc => WordLyricsFormat::Yrc,
+        }),
+    }
+}
+
 fn map_music_auth_to_core(auth: MusicAuthState) -> music::model::AuthState {
     music::model::AuthState {
         qq: auth.qq.map(|c| music::model::QqMusicCookie {
@@ -4293,6 +5151,25 @@ fn map_music_auth_to_core(auth: MusicAuthState) -> music::model::AuthState {
             userid: u.userid,
         }),
         netease_cookie: auth.netease_cookie,
+        kuwo: auth.kuwo.map(map_music_kuwo_user_to_core),
+    }
+}
+
+fn map_music_kuwo_user_to_core(u: KuwoUserInfo) -> music::model::KuwoUserInfo {
+    music::model::KuwoUserInfo {
+        uid: u.uid,
+        sid: u.sid,
+        nickname: u.nickname,
+        vip_type: u.vip_type,
+    }
+}
+
+fn map_music_kuwo_user_to_proto(u: music::model::KuwoUserInfo) -> KuwoUserInfo {
+    KuwoUserInfo {
+        uid: u.uid,
+        sid: u.sid,
+        nickname: u.nickname,
+        vip_type: u.vip_type,
     }
 }
 
@@ -4334,22 +5211,6 @@ fn map_music_qq_cookie_to_proto(c: music::model::QqMusicCookie) -> QqMusicCookie
     }
 }
 
-fn choose_quality_id(track: &MusicTrack, requested: &str) -> Option<String> {
-    let req = requested.trim();
-    if req.is_empty() {
-        return None;
-    }
-    if track.qualities.iter().any(|q| q.id == req) {
-        return Some(req.to_string());
-    }
-    for q in music::util::quality_fallback_order() {
-        if track.qualities.iter().any(|x| x.id == q) {
-            return Some(q.to_string());
-        }
-    }
-    None
-}
-
 #[derive(Debug)]
 struct QqLoginSession {
     created_at_ms: i64,
@@ -4366,10 +5227,9 @@ struct KugouLoginSession {
 }
 
 #[derive(Debug)]
-struct MusicDownloadSession {
-    status: Arc<tokio::sync::Mutex<MusicDownloadStatus>>,
-    cancel: Arc<AtomicBool>,
-    handle: tokio::task::JoinHandle<()>,
+struct NeteaseLoginSession {
+    created_at_ms: i64,
+    key: String,
 }
 
 #[derive(Debug)]
@@ -4378,7 +5238,8 @@ struct MusicFfiState {
     cfg: music::model::ProviderConfig,
     qq_sessions: HashMap<String, QqLoginSession>,
     kugou_sessions: HashMap<String, KugouLoginSession>,
-    downloads: HashMap<String, MusicDownloadSession>,
+    netease_sessions: HashMap<String, NeteaseLoginSession>,
+    downloads: HashMap<String, music::batch::DownloadJob>,
 }
 
 fn music_state() -> &'static Mutex<MusicFfiState> {
@@ -4391,6 +5252,7 @@ fn music_state() -> &'static Mutex<MusicFfiState> {
             cfg,
             qq_sessions: HashMap::new(),
             kugou_sessions: HashMap::new(),
+            netease_sessions: HashMap::new(),
             downloads: HashMap::new(),
         })
     })
@@ -4419,6 +5281,7 @@ struct BiliDownloadSession {
     api: BiliApiType,
     status: Arc<tokio::sync::Mutex<BiliDownloadStatus>>,
     cancel: Arc<AtomicBool>,
+    task: DownloadTask,
     handle: tokio::task::JoinHandle<()>,
 }
 
@@ -4612,6 +5475,7 @@ pub extern "C" fn chaos_lyrics_search_json(
     strict_match: u8,
     services_csv_utf8_or_null: *const c_char,
     timeout_ms: u32,
+    isrc_utf8_or_null: *const c_char,
 ) -> *mut c_char {
     let res = std::panic::catch_unwind(|| -> Result<String, ()> {
         let title = require_cstr(title_utf8, "title_utf8")?.trim().to_string();
@@ -4644,6 +5508,9 @@ pub extern "C" fn chaos_lyrics_search_json(
         } else {
             Some(duration_ms_or_0 as u64)
         };
+        req.isrc = optional_cstr(isrc_utf8_or_null, "isrc_utf8_or_null")?
+            .map(|s| s.trim().to_uppercase())
+            .filter(|s| !s.is_empty());
 
         let mut opt = lyrics::model::LyricsSearchOptions::default();
         opt.timeout_ms = timeout_ms.max(1) as u64;
@@ -11,6 +11,16 @@ pub const METHOD_DANMAKU_CONNECT: &str = "danmaku.connect";
 pub const METHOD_DANMAKU_DISCONNECT: &str = "danmaku.disconnect";
 pub const METHOD_NOW_PLAYING_SNAPSHOT: &str = "nowPlaying.snapshot";
 pub const METHOD_LYRICS_SEARCH: &str = "lyrics.search";
+pub const METHOD_LYRICS_SEARCH_STREAM: &str = "lyrics.searchStream";
+pub const METHOD_LYRICS_PIN: &str = "lyrics.pin";
+pub const METHOD_LYRICS_SET_OFFSET: &str = "lyrics.setOffset";
+pub const NOTIF_LYRICS_SEARCH_RESULT: &str = "lyrics.searchResult";
+pub const NOTIF_LYRICS_SEARCH_DONE: &str = "lyrics.searchDone";
+pub const METHOD_LYRICS_SYNC_START: &str = "lyrics.sync.start";
+pub const METHOD_LYRICS_SYNC_UPDATE: &str = "lyrics.sync.update";
+pub const METHOD_LYRICS_SYNC_STOP: &str = "lyrics.sync.stop";
+pub const NOTIF_LYRICS_LINE_CHANGED: &str = "lyrics.lineChanged";
+pub const NOTIF_LYRICS_WORD_PROGRESS: &str = "lyrics.wordProgress";
 
 // Music (search + login + download)
 pub const METHOD_MUSIC_CONFIG_SET: &str = "music.config.set";
@@ -19,7 +29,15 @@ pub const METHOD_MUSIC_SEARCH_ALBUMS: &str = "music.searchAlbums";
 pub const METHOD_MUSIC_SEARCH_ARTISTS: &str = "music.searchArtists";
 pub const METHOD_MUSIC_ALBUM_TRACKS: &str = "music.albumTracks";
 pub const METHOD_MUSIC_ARTIST_ALBUMS: &str = "music.artistAlbums";
+pub const METHOD_MUSIC_SEARCH_PLAYLISTS: &str = "music.searchPlaylists";
+pub const METHOD_MUSIC_PLAYLIST_TRACKS: &str = "music.playlistTracks";
+pub const METHOD_MUSIC_USER_PLAYLISTS: &str = "music.userPlaylists";
+pub const METHOD_MUSIC_TRACK_DETAIL: &str = "music.trackDetail";
+pub const METHOD_MUSIC_TRACK_LYRICS: &str = "music.trackLyrics";
 pub const METHOD_MUSIC_TRACK_PLAY_URL: &str = "music.trackPlayUrl";
+pub const METHOD_MUSIC_LIST_CHARTS: &str = "music.listCharts";
+pub const METHOD_MUSIC_CHART_TRACKS: &str = "music.chartTracks";
+pub const METHOD_MUSIC_DAILY_RECOMMENDATIONS: &str = "music.dailyRecommendations";
 
 pub const METHOD_MUSIC_QQ_LOGIN_QR_CREATE: &str = "music.qq.loginQrCreate";
 pub const METHOD_MUSIC_QQ_LOGIN_QR_POLL: &str = "music.qq.loginQrPoll";
@@ -27,10 +45,19 @@ pub const METHOD_MUSIC_QQ_REFRESH_COOKIE: &str = "music.qq.refreshCookie";
 
 pub const METHOD_MUSIC_KUGOU_LOGIN_QR_CREATE: &str = "music.kugou.loginQrCreate";
 pub const METHOD_MUSIC_KUGOU_LOGIN_QR_POLL: &str = "music.kugou.loginQrPoll";
+pub const METHOD_MUSIC_NETEASE_LOGIN_QR_CREATE: &str = "music.netease.loginQrCreate";
+pub const METHOD_MUSIC_NETEASE_LOGIN_QR_POLL: &str = "music.netease.loginQrPoll";
+pub const METHOD_MUSIC_NETEASE_REFRESH_COOKIE: &str = "music.netease.refreshCookie";
+pub const METHOD_MUSIC_KUWO_LOGIN_TOKEN: &str = "music.kuwo.loginToken";
 
 pub const METHOD_MUSIC_DOWNLOAD_START: &str = "music.download.start";
 pub const METHOD_MUSIC_DOWNLOAD_STATUS: &str = "music.download.status";
 pub const METHOD_MUSIC_DOWNLOAD_CANCEL: &str = "music.download.cancel";
+pub const METHOD_MUSIC_DOWNLOAD_PAUSE: &str = "music.download.pause";
+pub const METHOD_MUSIC_DOWNLOAD_RESUME: &str = "music.download.resume";
+
+// Shared download scheduler (music + bili)
+pub const METHOD_DOWNLOAD_SCHEDULER_SET: &str = "download.scheduler.set";
 
 // TTS (CosyVoice SFT)
 pub const METHOD_TTS_SFT_START: &str = "tts.sft.start";
@@ -52,6 +79,7 @@ pub const METHOD_BILI_LOGIN_QR_CREATE: &str = "bili.loginQrCreate";
 pub const METHOD_BILI_LOGIN_QR_POLL: &str = "bili.loginQrPoll";
 pub const METHOD_BILI_REFRESH_COOKIE: &str = "bili.refreshCookie";
 pub const METHOD_BILI_PARSE: &str = "bili.parse";
+pub const METHOD_BILI_LIVE_REPLAY_LIST: &str = "bili.live.replayList";
 pub const METHOD_BILI_DOWNLOAD_START: &str = "bili.download.start";
 pub const METHOD_BILI_DOWNLOAD_STATUS: &str = "bili.download.status";
 pub const METHOD_BILI_DOWNLOAD_CANCEL: &str = "bili.download.cancel";
@@ -64,6 +92,8 @@ pub const METHOD_BILI_TASK_ADD: &str = "bili.task.add";
 pub const METHOD_BILI_TASKS_GET: &str = "bili.tasks.get";
 pub const METHOD_BILI_TASK_GET: &str = "bili.task.get";
 pub const METHOD_BILI_TASK_CANCEL: &str = "bili.task.cancel";
+pub const METHOD_BILI_TASK_PAUSE: &str = "bili.task.pause";
+pub const METHOD_BILI_TASK_RESUME: &str = "bili.task.resume";
 pub const METHOD_BILI_TASKS_REMOVE_FINISHED: &str = "bili.tasks.removeFinished";
 
 pub const METHOD_LIVE_DIR_CATEGORIES: &str = "liveDir.categories";
@@ -540,9 +570,37 @@ pub struct MusicTrack {
     pub duration_ms: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cover_url: Option<String>,
+    /// 录音 ISRC（服务返回时填写）；歌词搜索时用于精确查找。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isrc: Option<String>,
     pub qualities: Vec<MusicQuality>,
 }
 
+/// 逐字歌词格式：QQ 音乐 QRC / 酷狗 KRC / 网易云 YRC。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WordLyricsFormat {
+    Qrc,
+    Krc,
+    Yrc,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicLyrics {
+    pub service: MusicService,
+    pub track_id: String,
+    pub original: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub romanization: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word_lyrics: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word_format: Option<WordLyricsFormat>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct MusicAlbum {
@@ -573,6 +631,38 @@ pub struct MusicArtist {
     pub album_count: Option<u32>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicPlaylist {
+    pub service: MusicService,
+    pub id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicChart {
+    pub service: MusicService,
+    pub id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_frequency: Option<String>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct MusicProviderConfig {
@@ -602,6 +692,14 @@ pub struct MusicAlbumTracksParams {
     pub album_id: String,
 }
 
+/// `music.trackDetail` / `music.trackLyrics`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicTrackParams {
+    pub service: MusicService,
+    pub track_id: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct MusicArtistAlbumsParams {
@@ -609,6 +707,47 @@ pub struct MusicArtistAlbumsParams {
     pub artist_id: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicPlaylistTracksParams {
+    pub service: MusicService,
+    pub playlist_id: String,
+    /// 私密歌单需要对应服务的登录态。
+    #[serde(default)]
+    pub auth: MusicAuthState,
+}
+
+/// `music.userPlaylists`：仅 qq / kugou / netease，需要对应服务的登录态；酷我不支持。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicUserPlaylistsParams {
+    pub service: MusicService,
+    #[serde(default)]
+    pub auth: MusicAuthState,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicListChartsParams {
+    pub service: MusicService,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicChartTracksParams {
+    pub service: MusicService,
+    pub chart_id: String,
+}
+
+/// `music.dailyRecommendations`：仅 qq / netease，需要对应服务的登录态。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicDailyRecommendationsParams {
+    pub service: MusicService,
+    #[serde(default)]
+    pub auth: MusicAuthState,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct MusicTrackPlayUrlParams {
@@ -670,6 +809,29 @@ pub struct KugouUserInfo {
     pub userid: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NeteaseUserInfo {
+    pub user_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub vip_type: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KuwoUserInfo {
+    pub uid: String,
+    pub sid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(default)]
+    pub vip_type: i64,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct MusicAuthState {
@@ -679,6 +841,8 @@ pub struct MusicAuthState {
     pub kugou: Option<KugouUserInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub netease_cookie: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kuwo: Option<KuwoUserInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -689,7 +853,7 @@ pub struct MusicAuthState {
 )]
 pub enum MusicDownloadTarget {
     Track {
-        track: MusicTrack,
+        track: Box<MusicTrack>,
     },
     Album {
         service: MusicService,
@@ -701,6 +865,11 @@ pub enum MusicDownloadTarget {
         #[serde(alias = "artist_id")]
         artist_id: String,
     },
+    Playlist {
+        service: MusicService,
+        #[serde(alias = "playlist_id")]
+        playlist_id: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -716,6 +885,63 @@ pub struct MusicDownloadOptions {
     pub concurrency: u32,
     #[serde(default = "default_music_retries")]
     pub retries: u32,
+    /// 调度优先级（越大越先分配连接），默认 0。
+    #[serde(default)]
+    pub priority: i32,
+    /// 当前源无法下载时，按顺序在这些源上查找同一首歌重试；为空表示不回退。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_services: Vec<MusicService>,
+    /// 回退匹配的最低分（0-100），省略时为 80。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_min_score: Option<u8>,
+    /// 下载前扫描 `out_dir` 建立曲库索引：已拥有同一首歌（文件名/格式不同也算）时跳过，
+    /// 除非本次下载的音质更高。
+    #[serde(default)]
+    pub skip_owned: bool,
+    /// 下载完成后的转码 / 响度标准化（需要 ffmpeg）；省略时不做后处理。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_process: Option<MusicPostProcessOptions>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MusicTranscodeCodec {
+    Mp3,
+    /// AAC（m4a 容器）。
+    Aac,
+    Flac,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicTranscodeOptions {
+    pub codec: MusicTranscodeCodec,
+    /// 有损编码的目标码率（kbps），省略时 mp3 为 320、aac 为 256；flac 忽略。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitrate_kbps: Option<u32>,
+    /// 只转码这些扩展名的源文件（如 `["ogg"]`）；为空表示扩展名与目标不同的都转码。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_formats: Vec<String>,
+    /// 转码后保留源文件。
+    #[serde(default)]
+    pub keep_source: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicPostProcessOptions {
+    /// ffmpeg 可执行文件，默认 `ffmpeg`（从 PATH 查找）。
+    #[serde(default = "default_ffmpeg_path")]
+    pub ffmpeg_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcode: Option<MusicTranscodeOptions>,
+    /// EBU R128 响度扫描，写入 ReplayGain 标签（`REPLAYGAIN_TRACK_GAIN/PEAK`）。
+    #[serde(default)]
+    pub replay_gain: bool,
+}
+
+fn default_ffmpeg_path() -> String {
+    "ffmpeg".to_string()
 }
 
 const fn default_music_concurrency() -> u32 {
@@ -726,6 +952,32 @@ const fn default_music_retries() -> u32 {
     2
 }
 
+/// 全部字段可省略：省略的字段保持不变（空对象即查询当前状态）。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadSchedulerSetParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_connections: Option<u32>,
+    /// 全局限速（字节/秒），`0` 表示不限速。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_bps: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadSchedulerState {
+    pub max_connections: u32,
+    /// `None` 表示不限速。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_bps: Option<u64>,
+    pub paused: bool,
+    pub active: u32,
+    pub queued: u32,
+    pub tasks: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct MusicDownloadStartParams {
@@ -776,6 +1028,14 @@ pub struct MusicDownloadJobResult {
     pub bytes: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// 通过回退源下载时的实际来源服务与曲目 id。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_service: Option<MusicService>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_track_id: Option<String>,
+    /// 曲库中已有的较低音质文件（`skipOwned` 时）；本次下载为升级，成功后该文件被替换。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upgraded_from: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -798,6 +1058,18 @@ pub struct MusicDownloadCancelParams {
     pub session_id: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicDownloadPauseParams {
+    pub session_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicDownloadResumeParams {
+    pub session_id: String,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum MusicLoginType {
@@ -1014,6 +1286,51 @@ pub struct BiliParseResult {
     pub videos: Vec<BiliParsedVideo>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BiliLiveReplayListParams {
+    /// 直播间号（支持短号）。
+    pub room_id: String,
+    #[serde(default = "default_page")]
+    pub page: u32,
+    #[serde(default = "default_replay_page_size")]
+    pub page_size: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<BiliAuthState>,
+}
+
+const fn default_page() -> u32 {
+    1
+}
+
+const fn default_replay_page_size() -> u32 {
+    20
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BiliLiveReplay {
+    /// 回放 id（`R...`）；`https://live.bilibili.com/record/<rid>` 可作为 `bili.task.add` 的 input。
+    pub rid: String,
+    pub room_id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_unix_s: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_unix_s: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BiliLiveReplayListResult {
+    pub items: Vec<BiliLiveReplay>,
+    pub total: u32,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum BiliApiType {
@@ -1024,6 +1341,17 @@ pub enum BiliApiType {
     Intl,
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BiliSubtitleFormat {
+    #[default]
+    Srt,
+    Ass,
+    Vtt,
+    /// 原始 BCC JSON（不参与 mux）。
+    Json,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct BiliDownloadOptions {
@@ -1034,10 +1362,44 @@ pub struct BiliDownloadOptions {
     pub file_pattern: String,
     pub multi_file_pattern: String,
     pub download_subtitle: bool,
+    #[serde(default)]
+    pub subtitle_format: BiliSubtitleFormat,
+    /// 字幕语言过滤（逗号分隔，如 `zh-CN,en-US,ai-zh`）；空表示全部。
+    #[serde(default)]
+    pub subtitle_langs: String,
+    /// 包含 AI 字幕（需要登录）。
+    #[serde(default)]
+    pub subtitle_include_ai: bool,
+    /// 双语合并：`"主语言,副语言"`（如 `zh-CN,en-US`），额外输出一个双语 ASS。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle_bilingual: Option<String>,
+    /// 音频区（au 号）输出路径模板，语法同 `MusicDownloadOptions.pathTemplate`（如 `{{artist}}/{{title}}.{{ext}}`）。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_path_template: Option<String>,
+    /// 目标文件已存在时覆盖（默认跳过）。
+    #[serde(default)]
+    pub overwrite: bool,
+    /// 不调用 ffmpeg 合并：视频保留音视频分轨，直播回放保留 FLV 分段。
     pub skip_mux: bool,
     pub concurrency: u32,
     pub retries: u32,
     pub ffmpeg_path: String,
+    /// 调度优先级（越大越先分配连接），默认 0。
+    #[serde(default)]
+    pub priority: i32,
+    /// 封装时把视频封面嵌入为 attached_pic。
+    #[serde(default = "default_true")]
+    pub embed_cover: bool,
+    /// 封装时写入标题/UP 主/BV 号/发布日期/简介/标签。
+    #[serde(default = "default_true")]
+    pub embed_metadata: bool,
+    /// 封装时把视频看点（view_points）写为章节。
+    #[serde(default = "default_true")]
+    pub embed_chapters: bool,
+}
+
+const fn default_true() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -1187,6 +1549,18 @@ pub struct BiliTaskCancelParams {
     pub task_id: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BiliTaskPauseParams {
+    pub task_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BiliTaskResumeParams {
+    pub task_id: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BiliTasksRemoveFinishedParams {
@@ -1207,6 +1581,11 @@ pub struct MusicLoginQrPollResult {
     pub cookie: Option<QqMusicCookie>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub kugou_user: Option<KugouUserInfo>,
+    /// 网易云登录成功时的 cookie（可直接作为 `auth.neteaseCookie`）。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub netease_cookie: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub netease_user: Option<NeteaseUserInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -1215,6 +1594,27 @@ pub struct MusicRefreshCookieParams {
     pub cookie: QqMusicCookie,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicNeteaseRefreshCookieParams {
+    pub cookie: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicNeteaseCookieResult {
+    pub cookie: String,
+    pub user: NeteaseUserInfo,
+}
+
+/// 酷我 token 导入：网页端 cookie 中的 `userid` + `websid`（或 App 端 uid/sid）。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicKuwoLoginTokenParams {
+    pub uid: String,
+    pub sid: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct DanmakuConnectParams {
@@ -1309,6 +1709,21 @@ pub struct LyricsSearchParams {
     pub services: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout_ms: Option<u64>,
+    /// 跳过本地歌词缓存，强制重新搜索（结果仍会写回缓存）。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bypass_cache: Option<bool>,
+    /// 本地音频文件路径：读取同目录 `.lrc` 与内嵌歌词（排在最前），并用文件标签补全标题/歌手。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    /// 为 `true` 时返回 `{ results, report }`（含各歌词源状态/耗时），而不是结果数组。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<bool>,
+    /// 录音 ISRC；支持 ISRC 精确查找的歌词源会优先使用。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isrc: Option<String>,
+    /// 为 `true` 时只用各歌词源的精确查找（ISRC / 标题+歌手+专辑+时长），不回退到模糊搜索。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exact_only: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -1332,6 +1747,191 @@ pub struct LyricsSearchResult {
     pub lyrics_original: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lyrics_translation: Option<String>,
+    /// 用户为这首歌手动设置的时间偏移（同 LRC `[offset:]`，正数提前）。
+    #[serde(default)]
+    pub offset_ms: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub debug: Option<serde_json::Value>,
 }
+
+/// `lyrics.search` 中单个歌词源的诊断信息。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsProviderReport {
+    pub service: String,
+    /// `ok` / `timeout` / `http` / `parse` / `unsupported` / `not_registered` / `error`
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 候选来自精确查找（而非模糊搜索）。
+    #[serde(default)]
+    pub exact: bool,
+    pub latency_ms: u64,
+    pub candidates: u32,
+    pub fetched: u32,
+    pub fetch_failed: u32,
+    pub matched: u32,
+    pub filtered: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsSearchReport {
+    pub cache_hit: bool,
+    pub elapsed_ms: u64,
+    pub returned: u32,
+    pub providers: Vec<LyricsProviderReport>,
+}
+
+/// `lyrics.search`（`diagnostics=true`）的返回值。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsSearchDiagnosticsResult {
+    pub results: Vec<LyricsSearchResult>,
+    pub report: LyricsSearchReport,
+}
+
+/// `lyrics.searchStream` 的返回值；之后的推送用 `searchId` 关联。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsSearchStreamStartResult {
+    pub search_id: String,
+}
+
+/// 流式歌词搜索的部分结果：某个歌词源完成（已打分、未与其它源合并排序），或命中缓存（`cached=true`）。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsSearchResultNotif {
+    pub search_id: String,
+    #[serde(default)]
+    pub cached: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    pub results: Vec<LyricsSearchResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<LyricsProviderReport>,
+}
+
+/// 流式歌词搜索结束：最终排序后的结果（与 `lyrics.search` 相同）。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsSearchDoneNotif {
+    pub search_id: String,
+    pub results: Vec<LyricsSearchResult>,
+    #[serde(default)]
+    pub report: LyricsSearchReport,
+}
+
+/// `lyrics.pin`：为一首歌固定歌词（之后的 `lyrics.search` 总把它排在第一位）；`result` 为空时取消固定。
+///
+/// 歌曲按 `title` / `artist` / `durationMs` 定位，与 `lyrics.search` 的缓存 key 相同。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsPinParams {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<LyricsSearchResult>,
+}
+
+/// `lyrics.setOffset`：设置一首歌的手动时间偏移（毫秒，正数提前；`0` 清除）。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsSetOffsetParams {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    pub offset_ms: i64,
+}
+
+/// 播放时钟：位置、倍速（缺省 1.0）与是否暂停。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsPlaybackClock {
+    pub position_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<f64>,
+    #[serde(default)]
+    pub paused: bool,
+}
+
+/// `lyrics.sync.start`：订阅歌词时间轴（Dock / Float 各自订阅，收到相同的事件）。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsSyncStartParams {
+    /// LRC 原文（可带逐字时间戳）；为空时先不推送，之后用 `lyrics.sync.update` 设置。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lyrics_original: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lyrics_translation: Option<String>,
+    /// 跟随系统 Now Playing 校正时钟（默认 `true`）；为 `false` 时只用 `lyrics.sync.update` 传入的时钟。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub follow_now_playing: Option<bool>,
+    /// 推送检查间隔（毫秒，默认 50）。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsSyncStartResult {
+    pub sync_id: String,
+}
+
+/// `lyrics.sync.update`：换歌词（`lyricsOriginal` 为空字符串时清空）或传入播放时钟。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsSyncUpdateParams {
+    pub sync_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lyrics_original: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lyrics_translation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock: Option<LyricsPlaybackClock>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsSyncStopParams {
+    pub sync_id: String,
+}
+
+/// 歌词时间轴推送：当前行变化（含 seek 回退）；`lineIndex` 为空表示第一行之前。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsLineChangedNotif {
+    /// 对应的 `lyrics.sync.start` 订阅。
+    #[serde(default)]
+    pub sync_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_index: Option<u32>,
+    pub position_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+}
+
+/// 歌词时间轴推送：逐字进度（仅逐字歌词）；`progress` 为当前字内 0..=1。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsWordProgressNotif {
+    #[serde(default)]
+    pub sync_id: String,
+    pub line_index: u32,
+    pub word_index: u32,
+    pub position_ms: u64,
+    pub progress: f64,
+}